@@ -15,6 +15,11 @@ fn main() {
     let mut arch = target_bits.next().unwrap();
     let _vendor = target_bits.next();
     let platform = target_bits.next().unwrap();
+    let libc = match target_bits.next() {
+        Some(env) if env.starts_with("musl") => "musl",
+        Some(env) if env.starts_with("gnu") => "gnu",
+        _ => "",
+    };
 
     if platform == "darwin" && arch == "aarch64" {
         arch = "arm64"; // enforce Darwin naming conventions
@@ -24,6 +29,8 @@ fn main() {
     writeln!(f, "pub const PLATFORM: &str = \"{platform}\";").ok();
     writeln!(f, "/// The CPU architecture identifier").ok();
     writeln!(f, "pub const ARCH: &str = \"{arch}\";").ok();
+    writeln!(f, "/// The C library identifier, empty if not applicable").ok();
+    writeln!(f, "pub const LIBC: &str = \"{libc}\";").ok();
     writeln!(f, "/// The user agent for sentry events").ok();
     writeln!(f, "pub const USER_AGENT: &str = \"sentry-cli/{arch}\";").ok();
     println!("cargo:rerun-if-changed=build.rs\n");
@@ -1,5 +1,10 @@
 use std::convert::Infallible;
 
+/// Hands out curl handles for the connection pool.
+///
+/// Handles are never discarded as broken, so that the connections they hold
+/// stay alive and are not set up again for every request.  Only handles that
+/// were idle for longer than the idle timeout of the pool are closed.
 pub(super) struct CurlConnectionManager;
 
 impl r2d2::ManageConnection for CurlConnectionManager {
@@ -12,6 +12,10 @@ pub enum ChunkedFileState {
     Assembling,
     #[serde(rename = "ok")]
     Ok,
+    /// A state added in a newer version of Sentry, treated as pending until
+    /// the server reports a known state or the upload times out.
+    #[serde(other)]
+    Unknown,
 }
 
 impl ChunkedFileState {
@@ -27,3 +31,16 @@ impl ChunkedFileState {
         self == ChunkedFileState::Error || self == ChunkedFileState::NotFound
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_state_is_pending() {
+        let state: ChunkedFileState = serde_json::from_str(r#""queued""#).unwrap();
+        assert_eq!(state, ChunkedFileState::Unknown);
+        assert!(state.is_pending());
+        assert!(!state.is_err());
+    }
+}
@@ -3,9 +3,20 @@ use super::{
     Api, ApiResponse, Method,
 };
 use crate::{api::errors::ApiError, constants::USER_AGENT};
+use anyhow::{Context, Result};
+use chrono::Utc;
 use log::debug;
 use sentry::{types::Dsn, Envelope};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::utils::http;
+use crate::utils::lockfile::write_atomic;
+
+/// The file extension of envelopes stored in a queue directory.
+pub const QUEUED_ENVELOPE_EXT: &str = "envelope";
 
 pub struct EnvelopesApi {
     api: Arc<Api>,
@@ -22,11 +33,64 @@ impl EnvelopesApi {
     }
 
     pub fn send_envelope(&self, envelope: impl Into<Envelope>) -> ApiResult<ApiResponse> {
-        let mut body = vec![];
-        envelope
-            .into()
-            .to_writer(&mut body)
-            .map_err(|e| ApiError::with_source(ApiErrorKind::CannotSerializeEnvelope, e))?;
+        self.send_body(serialize_envelope(envelope)?, 0)?
+            .into_result()
+    }
+
+    /// Sends the envelope, or writes it to `queue_dir` if Sentry cannot be
+    /// reached.  Returns the path of the queued envelope if it was queued.
+    pub fn send_or_queue_envelope(
+        &self,
+        envelope: impl Into<Envelope>,
+        queue_dir: &Path,
+    ) -> Result<Option<PathBuf>> {
+        let body = serialize_envelope(envelope)?;
+        if self.try_send_body(body.clone(), 0)? {
+            return Ok(None);
+        }
+
+        fs::create_dir_all(queue_dir)
+            .with_context(|| format!("Could not create queue directory {}", queue_dir.display()))?;
+        let path = queue_dir.join(format!(
+            "{}-{}.{QUEUED_ENVELOPE_EXT}",
+            Utc::now().timestamp_millis(),
+            Uuid::new_v4().simple()
+        ));
+        // Write atomically, so that a concurrent flush never sends a partial envelope.
+        write_atomic(&path, &body)
+            .with_context(|| format!("Could not write queued envelope {}", path.display()))?;
+        Ok(Some(path))
+    }
+
+    /// Sends an envelope previously stored by `send_or_queue_envelope`,
+    /// retrying with backoff while the server is unavailable.  Returns `false`
+    /// if Sentry cannot be reached.
+    pub fn send_queued_envelope(&self, path: &Path) -> Result<bool> {
+        let body = fs::read(path)
+            .with_context(|| format!("Could not read queued envelope {}", path.display()))?;
+        Ok(self.try_send_body(body, self.api.config.get_max_retry_count()?)?)
+    }
+
+    /// Sends the serialized envelope and returns `false` if Sentry cannot be
+    /// reached or is unavailable.
+    fn try_send_body(&self, body: Vec<u8>, max_retries: u32) -> ApiResult<bool> {
+        let response = match self.send_body(body, max_retries) {
+            Ok(response) => response,
+            Err(err) if err.kind() == ApiErrorKind::RequestFailed => {
+                debug!("could not reach Sentry: {err}");
+                return Ok(false);
+            }
+            Err(err) => return Err(err),
+        };
+
+        if response.status() >= 500 {
+            debug!("Sentry is unavailable: {}", response.status());
+            return Ok(false);
+        }
+        response.into_result().map(|_| true)
+    }
+
+    fn send_body(&self, body: Vec<u8>, max_retries: u32) -> ApiResult<ApiResponse> {
         let url = self.dsn.envelope_api_url();
         let auth = self.dsn.to_auth(Some(USER_AGENT));
         debug!("Sending envelope:\n{}", String::from_utf8_lossy(&body));
@@ -34,7 +98,24 @@ impl EnvelopesApi {
             .request(Method::Post, url.as_str(), None)?
             .with_header("X-Sentry-Auth", &auth.to_string())?
             .with_body(body)?
-            .send()?
-            .into_result()
+            .with_idempotency_key()?
+            .with_retry(
+                max_retries,
+                &[
+                    http::HTTP_STATUS_502_BAD_GATEWAY,
+                    http::HTTP_STATUS_503_SERVICE_UNAVAILABLE,
+                    http::HTTP_STATUS_504_GATEWAY_TIMEOUT,
+                ],
+            )?
+            .send()
     }
 }
+
+fn serialize_envelope(envelope: impl Into<Envelope>) -> ApiResult<Vec<u8>> {
+    let mut body = vec![];
+    envelope
+        .into()
+        .to_writer(&mut body)
+        .map_err(|e| ApiError::with_source(ApiErrorKind::CannotSerializeEnvelope, e))?;
+    Ok(body)
+}
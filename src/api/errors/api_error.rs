@@ -1,5 +1,9 @@
 use std::fmt;
 
+use crate::utils::exit_code::ExitCode;
+
+use super::SentryError;
+
 #[derive(Debug, thiserror::Error)]
 pub struct ApiError {
     inner: ApiErrorKind,
@@ -28,6 +32,16 @@ pub(in crate::api) enum ApiErrorKind {
     ProjectNotFound,
     #[error("Release not found. Ensure that you configured the correct release, project, and organization.")]
     ReleaseNotFound,
+    #[error("Team not found. Ensure that you configured the correct team and organization.")]
+    TeamNotFound,
+    #[error("Monitor not found. Ensure that you configured the correct monitor and organization.")]
+    MonitorNotFound,
+    #[error("Environment not found. Ensure that you configured the correct environment, project, and organization.")]
+    EnvironmentNotFound,
+    #[error("Issue not found. Ensure that you configured the correct issue and organization.")]
+    IssueNotFound,
+    #[error("request was rate limited by Sentry")]
+    RateLimited,
     #[error("chunk upload endpoint not supported by sentry server")]
     ChunkUploadNotSupported,
     #[error("API request failed")]
@@ -46,6 +60,10 @@ pub(in crate::api) enum ApiErrorKind {
     DsnMissing,
     #[error("Error preparing request")]
     ErrorPreparingRequest,
+    #[error("could not sign request")]
+    SigningFailed,
+    #[error("The command needs the response of a request that is not sent in read-only mode.")]
+    SkippedInReadOnlyMode,
 }
 
 impl fmt::Display for ApiError {
@@ -69,6 +87,40 @@ impl ApiError {
         self.inner
     }
 
+    /// Classifies the error for the exit code of the process.
+    pub(in crate::api) fn exit_code(&self) -> ExitCode {
+        match self.inner {
+            ApiErrorKind::AuthMissing => ExitCode::Auth,
+            ApiErrorKind::OrganizationNotFound
+            | ApiErrorKind::ResourceNotFound
+            | ApiErrorKind::ProjectNotFound
+            | ApiErrorKind::ReleaseNotFound
+            | ApiErrorKind::TeamNotFound
+            | ApiErrorKind::MonitorNotFound
+            | ApiErrorKind::EnvironmentNotFound
+            | ApiErrorKind::IssueNotFound => ExitCode::NotFound,
+            ApiErrorKind::RateLimited => ExitCode::Network,
+            ApiErrorKind::BadApiUrl
+            | ApiErrorKind::InvalidRegionRequest
+            | ApiErrorKind::DsnMissing => ExitCode::Validation,
+            ApiErrorKind::RequestFailed => match &self.source {
+                Some(source) => match source.downcast_ref::<SentryError>() {
+                    Some(err) => match err.status {
+                        401 | 403 => ExitCode::Auth,
+                        404 => ExitCode::NotFound,
+                        400 | 409 | 413 | 422 => ExitCode::Validation,
+                        429 | 502..=504 => ExitCode::Network,
+                        _ => ExitCode::Failure,
+                    },
+                    // Requests that did not get a response failed in curl.
+                    None => ExitCode::Network,
+                },
+                None => ExitCode::Failure,
+            },
+            _ => ExitCode::Failure,
+        }
+    }
+
     fn set_source<E: Into<anyhow::Error>>(mut self, source: E) -> ApiError {
         self.source = Some(source.into());
         self
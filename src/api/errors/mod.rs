@@ -1,6 +1,8 @@
 mod api_error;
 mod sentry_error;
 
+use crate::utils::exit_code::ExitCode;
+
 pub(super) use api_error::{ApiError, ApiErrorKind};
 pub(super) use sentry_error::SentryError;
 
@@ -10,3 +12,29 @@ pub(super) struct ProjectRenamedError(pub(super) String);
 
 /// Shortcut alias for results of this module.
 pub(super) type ApiResult<T> = Result<T, ApiError>;
+
+/// Returns the exit code for a failure caused by an API request, or `None` if
+/// the error was not caused by one.
+pub fn exit_code_for_error(err: &anyhow::Error) -> Option<ExitCode> {
+    err.chain().find_map(|cause| {
+        if let Some(api_error) = cause.downcast_ref::<ApiError>() {
+            Some(api_error.exit_code())
+        } else if cause.is::<ProjectRenamedError>() {
+            Some(ExitCode::NotFound)
+        } else if cause.is::<curl::Error>() {
+            Some(ExitCode::Network)
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns whether the error was caused by a response that could not be
+/// parsed.
+pub fn is_bad_json_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<ApiError>()
+            .is_some_and(|api_error| api_error.kind() == ApiErrorKind::BadJson)
+    })
+}
@@ -10,17 +10,18 @@ mod data_types;
 mod encoding;
 mod errors;
 mod pagination;
+mod rate_limits;
+mod signing;
 
 use std::borrow::Cow;
-use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fmt;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, Read, Write};
 use std::path::Path;
-use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::{Context, Result};
 use backoff::backoff::Backoff;
@@ -46,20 +47,27 @@ use uuid::Uuid;
 
 use crate::api::errors::ProjectRenamedError;
 use crate::config::{Auth, Config};
-use crate::constants::{ARCH, DEFAULT_URL, EXT, PLATFORM, RELEASE_REGISTRY_LATEST_URL, VERSION};
+use crate::constants::{
+    DEFAULT_MAX_CONNECTIONS, DEFAULT_POOL_IDLE_TIMEOUT, DEFAULT_RETRIES, DEFAULT_URL,
+    RELEASE_REGISTRY_LATEST_URL, VERSION,
+};
 use crate::utils::file_upload::UploadContext;
 use crate::utils::http::{self, is_absolute_url};
 use crate::utils::progress::{ProgressBar, ProgressBarMode};
 use crate::utils::retry::{get_default_backoff, DurationAsMilliseconds};
 use crate::utils::sourcemaps::get_sourcemap_reference_from_headers;
-use crate::utils::ui::{capitalize_string, make_byte_progress_bar};
+use crate::utils::support_bundle;
+use crate::utils::ui::make_byte_progress_bar;
 
 use self::pagination::Pagination;
+use self::signing::SigningCommand;
 use connection_manager::CurlConnectionManager;
 use encoding::{PathArg, QueryArg};
 use errors::{ApiError, ApiErrorKind, ApiResult, SentryError};
 
 pub use self::data_types::*;
+pub use self::errors::{exit_code_for_error, is_bad_json_error};
+pub use self::rate_limits::RateLimitBehavior;
 
 lazy_static! {
     static ref API: Mutex<Option<Arc<Api>>> = Mutex::new(None);
@@ -68,11 +76,25 @@ lazy_static! {
 /// Helper for the API access.
 /// Implements the low-level API access methods, and provides high-level implementations for interacting
 /// with portions of the API that do not require authentication via an auth token.
+///
+/// `Api` is `Send + Sync` and can be shared between threads, e.g. through the `Arc` returned by
+/// [`Api::current`]. Every request checks out its own curl handle from the pool, so requests may be
+/// sent concurrently from any number of threads. Once the pool is exhausted, further requests block
+/// until a handle is returned. An `ApiRequest` itself is `Send` but not `Sync`: it may be moved to
+/// another thread, but is only ever used by one thread at a time.
 pub struct Api {
     config: Arc<Config>,
     pool: r2d2::Pool<CurlConnectionManager>,
 }
 
+// `Api` is shared between upload threads, so make sure it stays thread-safe.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    const fn assert_send<T: Send>() {}
+    assert_send_sync::<Api>();
+    assert_send::<ApiRequest>();
+};
+
 /// Wrapper for Api that ensures Auth is provided. AuthenticatedApi provides implementations of high-level
 /// functions that make API requests requiring authentication via auth token.
 pub struct AuthenticatedApi<'a> {
@@ -94,6 +116,13 @@ pub enum Method {
     Delete,
 }
 
+impl Method {
+    /// Returns whether requests with this method may modify data on the server.
+    fn is_mutating(&self) -> bool {
+        *self != Method::Get
+    }
+}
+
 impl fmt::Display for Method {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
@@ -115,6 +144,14 @@ pub struct ApiRequest {
     progress_bar_mode: ProgressBarMode,
     max_retries: u32,
     retry_on_statuses: &'static [u32],
+    rate_limit_behavior: RateLimitBehavior,
+    /// The host the request is sent to, which rate limits are tracked for.
+    host: String,
+    /// Set for mutating requests in read-only mode.  These requests are
+    /// described instead of sent.
+    read_only_description: Option<String>,
+    /// Computes additional headers right before the request is sent.
+    signing: Option<SigningCommand>,
 }
 
 /// Represents an API response.
@@ -123,6 +160,7 @@ pub struct ApiResponse {
     status: u32,
     headers: Vec<String>,
     body: Option<Vec<u8>>,
+    skipped: bool,
 }
 
 impl<'a> TryFrom<&'a Api> for AuthenticatedApi<'a> {
@@ -130,8 +168,9 @@ impl<'a> TryFrom<&'a Api> for AuthenticatedApi<'a> {
 
     fn try_from(api: &'a Api) -> ApiResult<AuthenticatedApi<'a>> {
         match api.config.get_auth() {
-            Some(_) => Ok(AuthenticatedApi { api }),
-            None => Err(ApiErrorKind::AuthMissing.into()),
+            Ok(Some(_)) => Ok(AuthenticatedApi { api }),
+            Ok(None) => Err(ApiErrorKind::AuthMissing.into()),
+            Err(err) => Err(ApiError::with_source(ApiErrorKind::AuthMissing, err)),
         }
     }
 }
@@ -151,15 +190,48 @@ impl Api {
 
     /// Similar to `new` but uses a specific config.
     pub fn with_config(config: Arc<Config>) -> Api {
+        let max_connections = config.get_max_connections().unwrap_or_else(|err| {
+            warn!("Invalid maximum number of connections, using {DEFAULT_MAX_CONNECTIONS}: {err}");
+            DEFAULT_MAX_CONNECTIONS
+        });
+        let idle_timeout = config.get_pool_idle_timeout().unwrap_or_else(|err| {
+            warn!("Invalid pool idle timeout, using the default: {err}");
+            Some(DEFAULT_POOL_IDLE_TIMEOUT)
+        });
+
         Api {
             config,
             pool: r2d2::Pool::builder()
-                .max_size(16)
+                .max_size(max_connections)
+                // Connections are opened on demand and closed once idle for too long.
+                .min_idle(Some(0))
+                .idle_timeout(idle_timeout)
                 .build(CurlConnectionManager)
                 .unwrap(),
         }
     }
 
+    /// Takes a handle from the connection pool, waiting for one to be
+    /// returned if all of them are in use.
+    fn get_handle(&self) -> r2d2::PooledConnection<CurlConnectionManager> {
+        let state = self.pool.state();
+        if state.idle_connections > 0 || state.connections < self.pool.max_size() {
+            return self.pool.get().unwrap();
+        }
+
+        debug!(
+            "connection pool exhausted ({} connections, {} idle), waiting for a connection",
+            state.connections, state.idle_connections
+        );
+        let started = Instant::now();
+        let handle = self.pool.get().unwrap();
+        debug!(
+            "waited {}ms for a connection",
+            started.elapsed().as_millis()
+        );
+        handle
+    }
+
     /// Utility method that unbinds the current api.
     pub fn dispose_pool() {
         *API.lock() = None;
@@ -167,7 +239,7 @@ impl Api {
 
     /// Creates an AuthenticatedApi referencing this Api instance if an auth token is available.
     /// If an auth token is not available, returns an error.
-    pub fn authenticated(&self) -> ApiResult<AuthenticatedApi> {
+    pub fn authenticated(&self) -> ApiResult<AuthenticatedApi<'_>> {
         self.try_into()
     }
 
@@ -182,15 +254,25 @@ impl Api {
         url: &str,
         region_url: Option<&str>,
     ) -> ApiResult<ApiRequest> {
+        // Absolute URLs point outside of Sentry, e.g. to webhooks, so only
+        // requests to the Sentry API are signed.
+        let signing_command = self
+            .config
+            .get_signing_command()
+            .filter(|_| !is_absolute_url(url));
         let (url, auth) = self.resolve_base_url_and_auth(url, region_url)?;
-        self.construct_api_request(method, &url, auth)
+        let signing =
+            signing_command.map(|command| SigningCommand::new(command, &method.to_string(), &url));
+        let mut request = self.construct_api_request(method, &url, auth.as_ref())?;
+        request.signing = signing;
+        Ok(request)
     }
 
     fn resolve_base_url_and_auth(
         &self,
         url: &str,
         region_url: Option<&str>,
-    ) -> ApiResult<(String, Option<&Auth>)> {
+    ) -> ApiResult<(String, Option<Auth>)> {
         if is_absolute_url(url) && region_url.is_some() {
             return Err(ApiErrorKind::InvalidRegionRequest.into());
         }
@@ -203,7 +285,9 @@ impl Api {
                     Ok(rv) => rv,
                     Err(err) => return Err(ApiError::with_source(ApiErrorKind::BadApiUrl, err)),
                 }),
-                self.config.get_auth(),
+                self.config
+                    .get_auth()
+                    .map_err(|err| ApiError::with_source(ApiErrorKind::AuthMissing, err))?,
             )
         };
 
@@ -216,11 +300,23 @@ impl Api {
         url: &str,
         auth: Option<&Auth>,
     ) -> ApiResult<ApiRequest> {
-        let mut handle = self.pool.get().unwrap();
+        let mut handle = self.get_handle();
         handle.reset();
-        if !self.config.allow_keepalive() {
+        if self.config.allow_keepalive() {
+            // Connection reuse comes from the connection cache of the handle,
+            // which survives the reset above.  Keepalive probes only prevent
+            // idle cached connections from being dropped by firewalls and NATs
+            // in between.  Requests are not multiplexed over one connection,
+            // as that would need curl's multi interface.
+            handle.tcp_keepalive(true)?;
+        } else {
             handle.forbid_reuse(true).ok();
         }
+        handle.http_version(if self.config.allow_http2() {
+            curl::easy::HttpVersion::V2TLS
+        } else {
+            curl::easy::HttpVersion::V11
+        })?;
         let mut ssl_opts = curl::easy::SslOpt::new();
         if self.config.disable_ssl_revocation_check() {
             ssl_opts.no_revoke(true);
@@ -236,6 +332,15 @@ impl Api {
         if let Some(proxy_password) = self.config.get_proxy_password() {
             handle.proxy_password(proxy_password)?;
         }
+        if let Some(client_cert) = self.config.get_client_cert() {
+            handle.ssl_cert(client_cert)?;
+        }
+        if let Some(client_key) = self.config.get_client_key() {
+            handle.ssl_key(client_key)?;
+        }
+        if let Some(ca_file) = self.config.get_ca_file() {
+            handle.cainfo(ca_file)?;
+        }
         handle.ssl_verify_host(self.config.should_verify_ssl())?;
         handle.ssl_verify_peer(self.config.should_verify_ssl())?;
 
@@ -244,8 +349,12 @@ impl Api {
 
         let env = self.config.get_pipeline_env();
         let headers = self.config.get_headers();
+        let read_only = self.config.is_read_only() && method.is_mutating();
 
-        ApiRequest::create(handle, &method, url, auth, env, headers)
+        let mut request = ApiRequest::create(handle, &method, url, auth, env, headers, read_only)?;
+        // Only rate limited requests are retried unless `with_retry` is used.
+        request.max_retries = self.config.get_max_retry_count().unwrap_or(DEFAULT_RETRIES);
+        request.rate_limit_behavior(self.config.get_rate_limit_behavior())
     }
 
     /// Convenience method that performs a `GET` request.
@@ -272,6 +381,16 @@ impl Api {
             .send()
     }
 
+    /// Posts a JSON payload to a webhook outside of Sentry.  The request is
+    /// not authenticated.
+    pub fn post_webhook<S: Serialize>(&self, url: &str, body: &S) -> ApiResult<()> {
+        self.request(Method::Post, url, None)?
+            .with_json_body(body)?
+            .send()?
+            .into_result()
+            .map(|_| ())
+    }
+
     /// Convenience method that downloads a file into the given file object.
     pub fn download(&self, url: &str, dst: &mut File) -> ApiResult<ApiResponse> {
         self.request(Method::Get, url, None)?
@@ -312,24 +431,25 @@ impl Api {
 
     // High Level Methods
 
-    /// Finds the latest release for sentry-cli on GitHub.
-    pub fn get_latest_sentrycli_release(&self) -> ApiResult<Option<SentryCliRelease>> {
+    /// Finds the latest release for sentry-cli in the release registry.
+    ///
+    /// The release file is the first of the given file names that is part of
+    /// the release, so pass the best match first.
+    pub fn get_latest_sentrycli_release(
+        &self,
+        file_names: &[String],
+    ) -> ApiResult<Option<SentryCliRelease>> {
         let resp = self.get(RELEASE_REGISTRY_LATEST_URL)?;
-
-        // Prefer universal binary on macOS
-        let arch = match PLATFORM {
-            "darwin" => "universal",
-            _ => ARCH,
-        };
-
-        let ref_name = format!("sentry-cli-{}-{}{}", capitalize_string(PLATFORM), arch, EXT);
-        info!("Looking for file named: {}", ref_name);
+        info!("Looking for files named: {}", file_names.join(", "));
 
         if resp.status() == 200 {
-            let info: RegistryRelease = resp.convert()?;
-            for (filename, _download_url) in info.file_urls {
+            let mut info: RegistryRelease = resp.convert()?;
+            for filename in info.file_urls.keys() {
                 info!("Found asset {}", filename);
-                if filename == ref_name {
+            }
+            for filename in file_names {
+                if let Some(_download_url) = info.file_urls.remove(filename) {
+                    info!("Using asset {}", filename);
                     return Ok(Some(SentryCliRelease {
                         version: info.version,
                         #[cfg(not(feature = "managed"))]
@@ -337,7 +457,10 @@ impl Api {
                     }));
                 }
             }
-            warn!("Unable to find release file");
+            warn!(
+                "Unable to find release file, looked for {}",
+                file_names.join(", ")
+            );
             Ok(None)
         } else {
             info!("Release registry returned {}", resp.status());
@@ -410,10 +533,13 @@ impl Api {
         // not add the authorization header, by default. Since the URL is guaranteed
         // to be a Sentry-compatible endpoint, we force the Authorization header at
         // this point.
-        let request = match Config::current().get_auth() {
+        let auth = Config::current()
+            .get_auth()
+            .map_err(|err| ApiError::with_source(ApiErrorKind::AuthMissing, err))?;
+        let request = match auth {
             // Make sure that we don't authenticate a request
             // that has been already authenticated
-            Some(auth) if !request.is_authenticated => request.with_auth(auth)?,
+            Some(ref auth) if !request.is_authenticated => request.with_auth(auth)?,
             _ => request,
         };
 
@@ -447,6 +573,24 @@ impl<'a> AuthenticatedApi<'a> {
         self.api.post(path, body)
     }
 
+    /// Performs a `POST` request with JSON data that is retried while the
+    /// server is unavailable.  The request carries an idempotency key, so
+    /// that a retry does not create the resource twice.
+    fn post_idempotent<S: Serialize>(&self, path: &str, body: &S) -> ApiResult<ApiResponse> {
+        self.request(Method::Post, path)?
+            .with_json_body(body)?
+            .with_idempotency_key()?
+            .with_retry(
+                self.api.config.get_max_retry_count().unwrap(),
+                &[
+                    http::HTTP_STATUS_502_BAD_GATEWAY,
+                    http::HTTP_STATUS_503_SERVICE_UNAVAILABLE,
+                    http::HTTP_STATUS_504_GATEWAY_TIMEOUT,
+                ],
+            )?
+            .send()
+    }
+
     /// Convenience method to call self.api.put.
     fn put<S: Serialize>(&self, path: &str, body: &S) -> ApiResult<ApiResponse> {
         self.api.put(path, body)
@@ -663,7 +807,8 @@ impl<'a> AuthenticatedApi<'a> {
     }
 
     /// Creates a new release.
-    pub fn new_release(&self, org: &str, release: &NewRelease) -> ApiResult<ReleaseInfo> {
+    /// Returns `None` in read-only mode.
+    pub fn new_release(&self, org: &str, release: &NewRelease) -> ApiResult<Option<ReleaseInfo>> {
         // for single project releases use the legacy endpoint that is project bound.
         // This means we can support both old and new servers.
         if release.projects.len() == 1 {
@@ -672,22 +817,22 @@ impl<'a> AuthenticatedApi<'a> {
                 PathArg(org),
                 PathArg(&release.projects[0])
             );
-            self.post(&path, release)?
-                .convert_rnf(ApiErrorKind::ProjectNotFound)
+            self.post_idempotent(&path, release)?
+                .convert_sent(ApiErrorKind::ProjectNotFound)
         } else {
             let path = format!("/organizations/{}/releases/", PathArg(org));
-            self.post(&path, release)?
-                .convert_rnf(ApiErrorKind::OrganizationNotFound)
+            self.post_idempotent(&path, release)?
+                .convert_sent(ApiErrorKind::OrganizationNotFound)
         }
     }
 
-    /// Updates a release.
+    /// Updates a release.  Returns `None` in read-only mode.
     pub fn update_release(
         &self,
         org: &str,
         version: &str,
         release: &UpdatedRelease,
-    ) -> ApiResult<ReleaseInfo> {
+    ) -> ApiResult<Option<ReleaseInfo>> {
         if_chain! {
             if let Some(ref projects) = release.projects;
             if projects.len() == 1;
@@ -697,29 +842,29 @@ impl<'a> AuthenticatedApi<'a> {
                     PathArg(&projects[0]),
                     PathArg(version)
                 );
-                self.put(&path, release)?.convert_rnf(ApiErrorKind::ReleaseNotFound)
+                self.put(&path, release)?.convert_sent(ApiErrorKind::ReleaseNotFound)
             } else {
                 if release.version.is_some() {
                     let path = format!("/organizations/{}/releases/",
                                     PathArg(org));
-                    return self.post(&path, release)?.convert_rnf(ApiErrorKind::ReleaseNotFound)
+                    return self.post(&path, release)?.convert_sent(ApiErrorKind::ReleaseNotFound)
                 }
 
                 let path = format!("/organizations/{}/releases/{}/",
                                 PathArg(org),
                                 PathArg(version));
-                self.put(&path, release)?.convert_rnf(ApiErrorKind::ReleaseNotFound)
+                self.put(&path, release)?.convert_sent(ApiErrorKind::ReleaseNotFound)
             }
         }
     }
 
-    /// Sets release commits
+    /// Sets release commits.  Returns `None` in read-only mode.
     pub fn set_release_refs(
         &self,
         org: &str,
         version: &str,
         refs: Vec<Ref>,
-    ) -> ApiResult<ReleaseInfo> {
+    ) -> ApiResult<Option<ReleaseInfo>> {
         let update = UpdatedRelease {
             refs: Some(refs),
             ..Default::default()
@@ -730,7 +875,7 @@ impl<'a> AuthenticatedApi<'a> {
             PathArg(version)
         );
         self.put(&path, &update)?
-            .convert_rnf(ApiErrorKind::ReleaseNotFound)
+            .convert_sent(ApiErrorKind::ReleaseNotFound)
     }
 
     /// Deletes an already existing release.  Returns `true` if it was deleted
@@ -807,8 +952,47 @@ impl<'a> AuthenticatedApi<'a> {
         }
     }
 
-    /// Looks up a release commits and returns it.  If it does not exist `None`
-    /// will be returned.
+    /// Lists all releases of an organization or project, following all pages.
+    pub fn list_all_releases(
+        &self,
+        org: &str,
+        project: Option<&str>,
+    ) -> ApiResult<Vec<ReleaseInfo>> {
+        let (path, not_found) = if let Some(project) = project {
+            (
+                format!("/projects/{}/{}/releases/", PathArg(org), PathArg(project)),
+                ApiErrorKind::ProjectNotFound,
+            )
+        } else {
+            (
+                format!("/organizations/{}/releases/", PathArg(org)),
+                ApiErrorKind::OrganizationNotFound,
+            )
+        };
+        let mut rv = vec![];
+        let mut cursor = "".to_string();
+        loop {
+            let resp = self.get(&format!("{path}?cursor={}", QueryArg(&cursor)))?;
+            if resp.status() == 404 || (resp.status() == 400 && !cursor.is_empty()) {
+                if rv.is_empty() {
+                    return Err(not_found.into());
+                } else {
+                    break;
+                }
+            }
+            let pagination = resp.pagination();
+            rv.extend(resp.convert::<Vec<ReleaseInfo>>()?);
+            if let Some(next) = pagination.into_next_cursor() {
+                cursor = next;
+            } else {
+                break;
+            }
+        }
+        Ok(rv)
+    }
+
+    /// Looks up the commits of a release, following all pages.  If the release
+    /// does not exist `None` will be returned.
     pub fn get_release_commits(
         &self,
         org: &str,
@@ -829,12 +1013,26 @@ impl<'a> AuthenticatedApi<'a> {
                 PathArg(version)
             )
         };
-        let resp = self.get(&path)?;
-        if resp.status() == 404 {
-            Ok(None)
-        } else {
-            resp.convert()
+        let mut rv = vec![];
+        let mut cursor = "".to_string();
+        loop {
+            let resp = self.get(&format!("{path}?cursor={}", QueryArg(&cursor)))?;
+            if resp.status() == 404 {
+                if rv.is_empty() {
+                    return Ok(None);
+                } else {
+                    break;
+                }
+            }
+            let pagination = resp.pagination();
+            rv.extend(resp.convert::<Vec<ReleaseCommit>>()?);
+            if let Some(next) = pagination.into_next_cursor() {
+                cursor = next;
+            } else {
+                break;
+            }
         }
+        Ok(Some(rv))
     }
 
     // Finds the most recent release with commits and returns it.
@@ -857,20 +1055,25 @@ impl<'a> AuthenticatedApi<'a> {
         }
     }
 
-    /// Creates a new deploy for a release.
-    pub fn create_deploy(&self, org: &str, version: &str, deploy: &Deploy) -> ApiResult<Deploy> {
+    /// Creates a new deploy for a release.  Returns `None` in read-only mode.
+    pub fn create_deploy(
+        &self,
+        org: &str,
+        version: &str,
+        deploy: &Deploy,
+    ) -> ApiResult<Option<Deploy<'_>>> {
         let path = format!(
             "/organizations/{}/releases/{}/deploys/",
             PathArg(org),
             PathArg(version)
         );
 
-        self.post(&path, deploy)?
-            .convert_rnf(ApiErrorKind::ReleaseNotFound)
+        self.post_idempotent(&path, deploy)?
+            .convert_sent(ApiErrorKind::ReleaseNotFound)
     }
 
     /// Lists all deploys for a release
-    pub fn list_deploys(&self, org: &str, version: &str) -> ApiResult<Vec<Deploy>> {
+    pub fn list_deploys(&self, org: &str, version: &str) -> ApiResult<Vec<Deploy<'_>>> {
         let path = format!(
             "/organizations/{}/releases/{}/deploys/",
             PathArg(org),
@@ -907,6 +1110,104 @@ impl<'a> AuthenticatedApi<'a> {
         .map(|_| true)
     }
 
+    /// Deletes the issues within a project that match a provided filter,
+    /// along with all of their events.
+    pub fn bulk_delete_issues(
+        &self,
+        org: &str,
+        project: &str,
+        filter: &IssueFilter,
+    ) -> ApiResult<bool> {
+        let qs = match filter.get_query_string() {
+            None => {
+                return Ok(false);
+            }
+            Some(qs) => qs,
+        };
+        self.delete(&format!(
+            "/projects/{}/{}/issues/?{}",
+            PathArg(org),
+            PathArg(project),
+            qs
+        ))?
+        .into_result()
+        .map(|_| true)
+    }
+
+    /// Assigns an issue to the user with the given username or email.
+    pub fn assign_issue(&self, org: &str, issue: &str, assignee: &str) -> ApiResult<()> {
+        let path = format!("/organizations/{}/issues/{}/", PathArg(org), PathArg(issue));
+        let resp = self.put(
+            &path,
+            &IssueAssignment {
+                assigned_to: assignee,
+            },
+        )?;
+        if resp.status() == 404 {
+            Err(ApiErrorKind::IssueNotFound.into())
+        } else {
+            resp.into_result().map(|_| ())
+        }
+    }
+
+    /// Adds a comment to the activity of an issue.
+    pub fn create_issue_comment(&self, org: &str, issue: &str, text: &str) -> ApiResult<()> {
+        let path = format!(
+            "/organizations/{}/issues/{}/comments/",
+            PathArg(org),
+            PathArg(issue)
+        );
+        let resp = self.post(&path, &NewIssueComment { text })?;
+        if resp.status() == 404 {
+            Err(ApiErrorKind::IssueNotFound.into())
+        } else {
+            resp.into_result().map(|_| ())
+        }
+    }
+
+    /// Lists the debug information files with the given debug identifier
+    /// that were uploaded to a project.
+    pub fn list_dsyms(
+        &self,
+        org: &str,
+        project: &str,
+        debug_id: DebugId,
+    ) -> ApiResult<Vec<DebugInfoFile>> {
+        let url = format!(
+            "/projects/{}/{}/files/dsyms/?debug_id={}",
+            PathArg(org),
+            PathArg(project),
+            QueryArg(&debug_id.to_string())
+        );
+        self.get(&url)?.convert()
+    }
+
+    /// Downloads an uploaded debug information file into the given file.
+    pub fn download_dsym(
+        &self,
+        org: &str,
+        project: &str,
+        file_id: &str,
+        dst: &mut File,
+    ) -> ApiResult<()> {
+        let url = format!(
+            "/projects/{}/{}/files/dsyms/?id={}",
+            PathArg(org),
+            PathArg(project),
+            QueryArg(file_id)
+        );
+        #[cfg(not(feature = "managed"))]
+        let progress_bar_mode = ProgressBarMode::Response;
+        #[cfg(feature = "managed")]
+        let progress_bar_mode = ProgressBarMode::Disabled;
+        self.request(Method::Get, &url)?
+            .follow_location(true)?
+            .progress_bar_mode(progress_bar_mode)?
+            .send_into(dst)?
+            .into_result()
+            .map(|_| ())
+    }
+
     /// Given a list of checksums for DIFs, this returns a list of those
     /// that do not exist for the project yet.
     pub fn find_missing_dif_checksums<I>(
@@ -953,7 +1254,8 @@ impl<'a> AuthenticatedApi<'a> {
         }
     }
 
-    /// Request DIF assembling and processing from chunks.
+    /// Request DIF assembling and processing from chunks.  In read-only mode
+    /// the response is empty.
     pub fn assemble_difs(
         &self,
         org: &str,
@@ -977,16 +1279,18 @@ impl<'a> AuthenticatedApi<'a> {
                 ],
             )?
             .send()?
-            .convert_rnf(ApiErrorKind::ProjectNotFound)
+            .convert_sent(ApiErrorKind::ProjectNotFound)
+            .map(Option::unwrap_or_default)
     }
 
+    /// Returns `None` in read-only mode.
     pub fn assemble_release_artifacts(
         &self,
         org: &str,
         release: &str,
         checksum: Digest,
         chunks: &[Digest],
-    ) -> ApiResult<AssembleArtifactsResponse> {
+    ) -> ApiResult<Option<AssembleArtifactsResponse>> {
         let url = format!(
             "/organizations/{}/releases/{}/assemble/",
             PathArg(org),
@@ -1010,9 +1314,10 @@ impl<'a> AuthenticatedApi<'a> {
                 ],
             )?
             .send()?
-            .convert_rnf(ApiErrorKind::ReleaseNotFound)
+            .convert_sent(ApiErrorKind::ReleaseNotFound)
     }
 
+    /// Returns `None` in read-only mode.
     pub fn assemble_artifact_bundle(
         &self,
         org: &str,
@@ -1021,7 +1326,7 @@ impl<'a> AuthenticatedApi<'a> {
         chunks: &[Digest],
         version: Option<&str>,
         dist: Option<&str>,
-    ) -> ApiResult<AssembleArtifactsResponse> {
+    ) -> ApiResult<Option<AssembleArtifactsResponse>> {
         let url = format!("/organizations/{}/artifactbundle/assemble/", PathArg(org));
 
         self.request(Method::Post, &url)?
@@ -1041,7 +1346,7 @@ impl<'a> AuthenticatedApi<'a> {
                 ],
             )?
             .send()?
-            .convert_rnf(ApiErrorKind::ReleaseNotFound)
+            .convert_sent(ApiErrorKind::ReleaseNotFound)
     }
 
     pub fn associate_proguard_mappings(
@@ -1059,7 +1364,7 @@ impl<'a> AuthenticatedApi<'a> {
             .request(Method::Post, &path)?
             .with_json_body(data)?
             .send()?;
-        if resp.status() == 201 {
+        if resp.status() == 201 || resp.skipped {
             Ok(())
         } else if resp.status() == 409 {
             info!(
@@ -1068,7 +1373,7 @@ impl<'a> AuthenticatedApi<'a> {
             );
             Ok(())
         } else if resp.status() == 404 {
-            return Err(ApiErrorKind::ResourceNotFound.into());
+            Err(ApiErrorKind::ResourceNotFound.into())
         } else {
             resp.convert()
         }
@@ -1152,6 +1457,54 @@ impl<'a> AuthenticatedApi<'a> {
         Ok(rv)
     }
 
+    /// Looks up a monitor by its slug.
+    pub fn get_monitor(&self, org: &str, monitor: &str) -> ApiResult<Monitor> {
+        let path = format!(
+            "/organizations/{}/monitors/{}/",
+            PathArg(org),
+            PathArg(monitor)
+        );
+        self.get(&path)?.convert_rnf(ApiErrorKind::MonitorNotFound)
+    }
+
+    /// Creates a new monitor.  Returns `None` in read-only mode.
+    pub fn create_monitor(&self, org: &str, monitor: &NewMonitor) -> ApiResult<Option<Monitor>> {
+        let path = format!("/organizations/{}/monitors/", PathArg(org));
+        self.post(&path, monitor)?
+            .convert_sent(ApiErrorKind::OrganizationNotFound)
+    }
+
+    /// Updates an existing monitor.  Returns `None` in read-only mode.
+    pub fn update_monitor(
+        &self,
+        org: &str,
+        monitor: &str,
+        changes: &UpdatedMonitor,
+    ) -> ApiResult<Option<Monitor>> {
+        let path = format!(
+            "/organizations/{}/monitors/{}/",
+            PathArg(org),
+            PathArg(monitor)
+        );
+        self.put(&path, changes)?
+            .convert_sent(ApiErrorKind::MonitorNotFound)
+    }
+
+    /// Deletes an existing monitor.  Returns `true` if it was deleted or
+    /// `false` if it does not exist.
+    pub fn delete_monitor(&self, org: &str, monitor: &str) -> ApiResult<bool> {
+        let resp = self.delete(&format!(
+            "/organizations/{}/monitors/{}/",
+            PathArg(org),
+            PathArg(monitor)
+        ))?;
+        if resp.status() == 404 {
+            Ok(false)
+        } else {
+            resp.into_result().map(|_| true)
+        }
+    }
+
     /// List all projects associated with an organization
     pub fn list_organization_projects(&self, org: &str) -> ApiResult<Vec<Project>> {
         let mut rv = vec![];
@@ -1180,6 +1533,78 @@ impl<'a> AuthenticatedApi<'a> {
         Ok(rv)
     }
 
+    /// Creates a new project owned by the given team.  Returns `None` in
+    /// read-only mode.
+    pub fn create_project(
+        &self,
+        org: &str,
+        team: &str,
+        project: &NewProject,
+    ) -> ApiResult<Option<Project>> {
+        let path = format!("/teams/{}/{}/projects/", PathArg(org), PathArg(team));
+        self.post(&path, project)?
+            .convert_sent(ApiErrorKind::TeamNotFound)
+    }
+
+    /// Updates an existing project.  Returns `None` in read-only mode.
+    pub fn update_project(
+        &self,
+        org: &str,
+        project: &str,
+        changes: &UpdatedProject,
+    ) -> ApiResult<Option<Project>> {
+        let path = format!("/projects/{}/{}/", PathArg(org), PathArg(project));
+        self.put(&path, changes)?
+            .convert_sent(ApiErrorKind::ProjectNotFound)
+    }
+
+    /// Deletes an existing project.  Returns `true` if it was deleted or
+    /// `false` if it does not exist.
+    pub fn delete_project(&self, org: &str, project: &str) -> ApiResult<bool> {
+        let resp = self.delete(&format!("/projects/{}/{}/", PathArg(org), PathArg(project)))?;
+        if resp.status() == 404 {
+            Ok(false)
+        } else {
+            resp.into_result().map(|_| true)
+        }
+    }
+
+    /// Lists the environments of a project.  `visibility` is one of `all`,
+    /// `visible` or `hidden`.
+    pub fn list_project_environments(
+        &self,
+        org: &str,
+        project: &str,
+        visibility: &str,
+    ) -> ApiResult<Vec<Environment>> {
+        let path = format!(
+            "/projects/{}/{}/environments/?visibility={}",
+            PathArg(org),
+            PathArg(project),
+            QueryArg(visibility)
+        );
+        self.get(&path)?.convert_rnf(ApiErrorKind::ProjectNotFound)
+    }
+
+    /// Hides or shows an environment of a project.  Returns `None` in
+    /// read-only mode.
+    pub fn update_project_environment(
+        &self,
+        org: &str,
+        project: &str,
+        environment: &str,
+        changes: &UpdatedEnvironment,
+    ) -> ApiResult<Option<Environment>> {
+        let path = format!(
+            "/projects/{}/{}/environments/{}/",
+            PathArg(org),
+            PathArg(project),
+            PathArg(environment)
+        );
+        self.put(&path, changes)?
+            .convert_sent(ApiErrorKind::EnvironmentNotFound)
+    }
+
     /// List all events associated with an organization and a project
     pub fn list_organization_project_events(
         &self,
@@ -1279,6 +1704,81 @@ impl<'a> AuthenticatedApi<'a> {
         Ok(rv)
     }
 
+    /// Returns the number of issues of a project matching the given search
+    /// query.
+    pub fn count_project_issues(&self, org: &str, project: &str, query: &str) -> ApiResult<u64> {
+        let path = format!(
+            "/projects/{}/{}/issues/?query={}",
+            PathArg(org),
+            PathArg(project),
+            QueryArg(query)
+        );
+        let resp = self.get(&path)?;
+        let hits = resp
+            .get_header("x-hits")
+            .and_then(|hits| hits.trim().parse().ok());
+        let issues: Vec<Issue> = resp.convert_rnf(ApiErrorKind::ProjectNotFound)?;
+        // Fall back to counting the first page if the server does not report
+        // the total number of hits.
+        Ok(hits.unwrap_or(issues.len() as u64))
+    }
+
+    /// Returns the crash free rate of the sessions of a release across all
+    /// projects within the given stats period, or `None` if there were no
+    /// sessions.
+    pub fn get_release_crash_free_rate(
+        &self,
+        org: &str,
+        version: &str,
+        stats_period: &str,
+    ) -> ApiResult<Option<f64>> {
+        let path = format!(
+            "/organizations/{}/sessions/?project=-1&field=crash_free_rate(session)&query={}&statsPeriod={}",
+            PathArg(org),
+            QueryArg(&format!("release:\"{version}\"")),
+            QueryArg(stats_period)
+        );
+        let sessions: Sessions = self
+            .get(&path)?
+            .convert_rnf(ApiErrorKind::OrganizationNotFound)?;
+        Ok(sessions
+            .groups
+            .into_iter()
+            .next()
+            .and_then(|group| group.totals.crash_free_rate))
+    }
+
+    /// Returns the number of sessions of a release in an environment across
+    /// all projects within the given stats period.
+    pub fn get_release_session_counts(
+        &self,
+        org: &str,
+        version: &str,
+        environment: &str,
+        stats_period: &str,
+    ) -> ApiResult<SessionCounts> {
+        let path = format!(
+            "/organizations/{}/sessions/?project=-1&field=sum(session)&groupBy=session.status&query={}&environment={}&statsPeriod={}",
+            PathArg(org),
+            QueryArg(&format!("release:\"{version}\"")),
+            QueryArg(environment),
+            QueryArg(stats_period)
+        );
+        let sessions: Sessions = self
+            .get(&path)?
+            .convert_rnf(ApiErrorKind::OrganizationNotFound)?;
+
+        let mut counts = SessionCounts::default();
+        for group in sessions.groups {
+            let count = group.totals.sessions.unwrap_or(0);
+            counts.total += count;
+            if group.by.status.as_deref() != Some("healthy") {
+                counts.errored += count;
+            }
+        }
+        Ok(counts)
+    }
+
     /// List all repos associated with an organization
     pub fn list_organization_repos(&self, org: &str) -> ApiResult<Vec<Repo>> {
         let mut rv = vec![];
@@ -1305,6 +1805,82 @@ impl<'a> AuthenticatedApi<'a> {
         Ok(rv)
     }
 
+    /// Creates a new repository in an organization.  Returns `None` in
+    /// read-only mode.
+    pub fn create_repo(&self, org: &str, repo: &NewRepo) -> ApiResult<Option<Repo>> {
+        let path = format!("/organizations/{}/repos/", PathArg(org));
+        self.post(&path, repo)?
+            .convert_sent(ApiErrorKind::OrganizationNotFound)
+    }
+
+    /// Deletes a repository by its ID.  Returns `true` if it was deleted or
+    /// `false` if it does not exist.
+    pub fn delete_repo(&self, org: &str, repo_id: &str) -> ApiResult<bool> {
+        let resp = self.delete(&format!(
+            "/organizations/{}/repos/{}/",
+            PathArg(org),
+            PathArg(repo_id)
+        ))?;
+        if resp.status() == 404 {
+            Ok(false)
+        } else {
+            resp.into_result().map(|_| true)
+        }
+    }
+
+    /// Lists the org auth tokens of an organization.
+    pub fn list_org_auth_tokens(&self, org: &str) -> ApiResult<Vec<OrgAuthTokenInfo>> {
+        self.get(&format!("/organizations/{}/org-auth-tokens/", PathArg(org)))?
+            .convert_rnf(ApiErrorKind::OrganizationNotFound)
+    }
+
+    /// Creates a new org auth token.  The returned info contains the token,
+    /// which cannot be retrieved again later.  Returns `None` in read-only
+    /// mode.
+    pub fn create_org_auth_token(
+        &self,
+        org: &str,
+        name: &str,
+    ) -> ApiResult<Option<OrgAuthTokenInfo>> {
+        self.post(
+            &format!("/organizations/{}/org-auth-tokens/", PathArg(org)),
+            &NewOrgAuthToken { name },
+        )?
+        .convert_sent(ApiErrorKind::OrganizationNotFound)
+    }
+
+    /// Revokes an org auth token by its ID.  Returns `true` if it was revoked
+    /// or `false` if it does not exist.
+    pub fn delete_org_auth_token(&self, org: &str, token_id: &str) -> ApiResult<bool> {
+        let resp = self.delete(&format!(
+            "/organizations/{}/org-auth-tokens/{}/",
+            PathArg(org),
+            PathArg(token_id)
+        ))?;
+        if resp.status() == 404 {
+            Ok(false)
+        } else {
+            resp.into_result().map(|_| true)
+        }
+    }
+
+    /// Checks how the frames of an event resolve with the source maps that
+    /// are currently uploaded.
+    pub fn get_sourcemap_debug(
+        &self,
+        org: &str,
+        project: &str,
+        event_id: &str,
+    ) -> ApiResult<SourceMapDebug> {
+        let path = format!(
+            "/projects/{}/{}/events/{}/source-map-debug-blue-thunder-edition/",
+            PathArg(org),
+            PathArg(project),
+            PathArg(event_id)
+        );
+        self.get(&path)?.convert_rnf(ApiErrorKind::ResourceNotFound)
+    }
+
     /// Looks up an event, which was already processed by Sentry and returns it.
     /// If it does not exist `None` will be returned.
     pub fn get_event(
@@ -1355,13 +1931,8 @@ impl<'a> AuthenticatedApi<'a> {
             };
         }
 
-        let region_url = match self
-            .api
-            .config
-            .get_auth()
-            .expect("auth should not be None for authenticated API!")
-        {
-            Auth::Token(token) => match token.payload() {
+        let region_url = match self.api.config.get_auth() {
+            Ok(Some(Auth::Token(token))) => match token.payload() {
                 Some(payload) => Some(payload.region_url.clone().into()),
                 None => {
                     let region_url = self.get_region_url(org);
@@ -1373,13 +1944,15 @@ impl<'a> AuthenticatedApi<'a> {
                     region_url.ok().map(|url| url.into())
                 }
             },
-            Auth::Key(_) => {
+            Ok(Some(Auth::Key(_))) => {
                 log::warn!(
                     "Auth key is not supported for region-specific API. Falling back to default region."
                 );
 
                 None
             }
+            // A failing auth token command is reported by the request itself.
+            Ok(None) | Err(_) => None,
         };
 
         RegionSpecificApi {
@@ -1397,7 +1970,8 @@ impl RegionSpecificApi<'_> {
             .request(method, url, self.region_url.as_deref())
     }
 
-    /// Uploads a ZIP archive containing DIFs from the given path.
+    /// Uploads a ZIP archive containing DIFs from the given path.  In
+    /// read-only mode no DIFs are returned.
     pub fn upload_dif_archive(&self, project: &str, file: &Path) -> ApiResult<Vec<DebugInfoFile>> {
         let path = format!(
             "/projects/{}/{}/files/dsyms/",
@@ -1406,7 +1980,8 @@ impl RegionSpecificApi<'_> {
         );
         let mut form = curl::easy::Form::new();
         form.part("file").file(file).add()?;
-        self.request(Method::Post, &path)?
+        let resp = self
+            .request(Method::Post, &path)?
             .with_form_data(form)?
             .with_retry(
                 self.api.api.config.get_max_retry_count().map_err(|e| {
@@ -1418,8 +1993,11 @@ impl RegionSpecificApi<'_> {
                 &[http::HTTP_STATUS_507_INSUFFICIENT_STORAGE],
             )?
             .progress_bar_mode(ProgressBarMode::Request)?
-            .send()?
-            .convert()
+            .send()?;
+        if resp.skipped {
+            return Ok(vec![]);
+        }
+        resp.convert()
     }
 
     /// Uploads a new release file.  The file is loaded directly from the file
@@ -1525,7 +2103,7 @@ fn handle_req<W: Write>(
     handle.verbose(true)?;
 
     let mut headers = Vec::new();
-    let pb: Rc<RefCell<Option<ProgressBar>>> = Rc::new(RefCell::new(None));
+    let pb: Arc<Mutex<Option<ProgressBar>>> = Arc::new(Mutex::new(None));
     {
         let headers = &mut headers;
         let mut handle = handle.transfer();
@@ -1542,7 +2120,7 @@ fn handle_req<W: Write>(
             let pb_progress = pb.clone();
             handle.progress_function(move |a, b, c, d| {
                 let (down_len, down_pos, up_len, up_pos) = (a as u64, b as u64, c as u64, d as u64);
-                let mut pb = pb_progress.borrow_mut();
+                let mut pb = pb_progress.lock();
                 if up_len > 0 && progress_bar_mode.request() {
                     if up_pos < up_len {
                         if pb.is_none() {
@@ -1593,8 +2171,8 @@ fn handle_req<W: Write>(
         handle.perform()?;
     }
 
-    if pb.borrow().is_some() {
-        pb.borrow().as_ref().unwrap().finish_and_clear();
+    if let Some(pb) = pb.lock().as_ref() {
+        pb.finish_and_clear();
     }
 
     Ok((handle.response_code()?, headers))
@@ -1629,6 +2207,7 @@ impl ApiRequest {
         auth: Option<&Auth>,
         pipeline_env: Option<String>,
         global_headers: Option<Vec<String>>,
+        read_only: bool,
     ) -> ApiResult<Self> {
         debug!("request {} {}", method, url);
 
@@ -1672,6 +2251,13 @@ impl ApiRequest {
             progress_bar_mode: ProgressBarMode::Disabled,
             max_retries: 0,
             retry_on_statuses: &[],
+            rate_limit_behavior: RateLimitBehavior::default(),
+            host: url::Url::parse(url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_owned))
+                .unwrap_or_default(),
+            read_only_description: read_only.then(|| format!("{method} {url}")),
+            signing: None,
         };
 
         let request = match auth {
@@ -1708,6 +2294,15 @@ impl ApiRequest {
         Ok(self)
     }
 
+    /// adds an idempotency key to the request.  The key is generated once,
+    /// so all retries of the request carry the same key and the server
+    /// performs the request at most once.
+    pub fn with_idempotency_key(self) -> ApiResult<Self> {
+        let key = Uuid::new_v4();
+        debug!("idempotency key: {key}");
+        self.with_header("Idempotency-Key", &key.to_string())
+    }
+
     /// sets the JSON request body for the request.
     pub fn with_json_body<S: Serialize>(mut self, body: &S) -> ApiResult<Self> {
         let mut body_bytes: Vec<u8> = vec![];
@@ -1725,7 +2320,19 @@ impl ApiRequest {
     }
 
     /// attaches some form data to the request.
+    ///
+    /// Form bodies are encoded by curl while sending, so requests that need
+    /// to be signed cannot have one.
     pub fn with_form_data(mut self, form: curl::easy::Form) -> ApiResult<Self> {
+        if self.signing.is_some() {
+            return Err(ApiError::with_source(
+                ApiErrorKind::SigningFailed,
+                anyhow::anyhow!(
+                    "Uploads with a multipart form body cannot be signed. \
+                    Unset the signing command to upload files."
+                ),
+            ));
+        }
         debug!("sending form data");
         self.handle.httppost(form)?;
         self.body = None;
@@ -1755,6 +2362,12 @@ impl ApiRequest {
         Ok(self)
     }
 
+    /// sets what to do when the request is rate limited.
+    pub fn rate_limit_behavior(mut self, behavior: RateLimitBehavior) -> ApiResult<Self> {
+        self.rate_limit_behavior = behavior;
+        Ok(self)
+    }
+
     /// Get a copy of the header list
     fn get_headers(&self) -> curl::easy::List {
         let mut result = curl::easy::List::new();
@@ -1767,22 +2380,61 @@ impl ApiRequest {
 
     /// Sends the request and writes response data into the given file
     /// instead of the response object's in memory buffer.
+    ///
+    /// In read-only mode, mutating requests are not sent and fail with
+    /// `SkippedInReadOnlyMode`, since there is no response to write.
     pub fn send_into<W: Write>(&mut self, out: &mut W) -> ApiResult<ApiResponse> {
-        let headers = self.get_headers();
+        if let Some(ref description) = self.read_only_description {
+            eprintln!("Read-only mode, not sending {description}");
+            return Err(ApiErrorKind::SkippedInReadOnlyMode.into());
+        }
+
+        let mut headers = self.get_headers();
+        if let Some(ref signing) = self.signing {
+            // Signed again on every attempt, so retries carry fresh signatures.
+            let signed_headers = signing
+                .headers(self.body.as_deref())
+                .map_err(|err| ApiError::with_source(ApiErrorKind::SigningFailed, err))?;
+            for header in signed_headers {
+                headers.append(&header)?;
+            }
+        }
         self.handle.http_headers(headers)?;
         let body = self.body.as_deref();
         let (status, headers) =
             send_req(&mut self.handle, out, body, self.progress_bar_mode.clone())?;
         debug!("response status: {}", status);
+        support_bundle::record_request(&mut self.handle, status);
         Ok(ApiResponse {
             status,
             headers,
             body: None,
+            skipped: false,
         })
     }
 
     /// Sends the request and reads the response body into the response object.
+    ///
+    /// Rate limited requests are retried once the delay announced by the
+    /// server has passed, unless the rate limit behavior is `Fail`.  These
+    /// retries count against the maximum number of retries.
+    ///
+    /// In read-only mode, mutating requests are described instead of sent, and
+    /// the returned response is marked as skipped.
     pub fn send(mut self) -> ApiResult<ApiResponse> {
+        if let Some(ref description) = self.read_only_description {
+            eprintln!("Read-only mode, not sending {description}");
+            if let Some(ref body) = self.body {
+                eprintln!("{}", String::from_utf8_lossy(body));
+            }
+            return Ok(ApiResponse {
+                status: 204,
+                headers: vec![],
+                body: None,
+                skipped: true,
+            });
+        }
+
         let mut backoff = get_default_backoff();
         let mut retry_number = 0;
 
@@ -1793,7 +2445,49 @@ impl ApiRequest {
                 retry_number, self.max_retries,
             );
 
+            if self.rate_limit_behavior == RateLimitBehavior::Wait {
+                rate_limits::wait(&self.host);
+            }
+
             let mut rv = self.send_into(&mut out)?;
+            if rv.status == 429 {
+                let delay = rate_limits::parse_retry_after(
+                    rv.get_header("retry-after"),
+                    rv.get_header("x-sentry-rate-limits"),
+                );
+                rate_limits::block_for(&self.host, delay);
+
+                if self.rate_limit_behavior == RateLimitBehavior::Fail {
+                    return Err(ApiError::with_source(
+                        ApiErrorKind::RateLimited,
+                        anyhow::anyhow!(
+                            "Retry after {} seconds, or pass --rate-limit-behavior wait \
+                            to wait automatically.",
+                            delay.as_secs()
+                        ),
+                    ));
+                }
+                if delay > rate_limits::MAX_RETRY_AFTER {
+                    return Err(ApiError::with_source(
+                        ApiErrorKind::RateLimited,
+                        anyhow::anyhow!(
+                            "Sentry asked to retry after {} seconds, which is longer than \
+                            sentry-cli waits.",
+                            delay.as_secs()
+                        ),
+                    ));
+                }
+                if retry_number >= self.max_retries {
+                    return Err(ApiError::with_source(
+                        ApiErrorKind::RateLimited,
+                        anyhow::anyhow!("Still rate limited after {} retries.", self.max_retries),
+                    ));
+                }
+                info!("Rate limited, retrying in {} seconds", delay.as_secs());
+                retry_number += 1;
+                continue;
+            }
+
             if retry_number >= self.max_retries || !self.retry_on_statuses.contains(&rv.status) {
                 rv.body = Some(out);
                 return Ok(rv);
@@ -1839,7 +2533,7 @@ impl ApiResponse {
         if self.ok() {
             return Ok(self);
         }
-        if let Ok(err) = self.deserialize::<ErrorInfo>() {
+        if let Ok(err) = self.parse::<ErrorInfo>() {
             Err(ApiError::with_source(
                 ApiErrorKind::RequestFailed,
                 SentryError {
@@ -1851,7 +2545,7 @@ impl ApiResponse {
                     extra: None,
                 },
             ))
-        } else if let Ok(value) = self.deserialize::<serde_json::Value>() {
+        } else if let Ok(value) = self.parse::<serde_json::Value>() {
             Err(ApiError::with_source(
                 ApiErrorKind::RequestFailed,
                 SentryError {
@@ -1872,16 +2566,60 @@ impl ApiResponse {
         }
     }
 
-    /// Deserializes the response body into the given type
+    /// Deserializes the response body into the given type.
+    ///
+    /// If the body does not match the type, the error names the field that
+    /// failed, and the body is written to the `--dump-response` file.
     pub fn deserialize<T: DeserializeOwned>(&self) -> ApiResult<T> {
+        let rv = self.parse();
+        if let Err(ref err) = rv {
+            if err.kind() == ApiErrorKind::BadJson {
+                self.dump_body();
+            }
+        }
+        rv
+    }
+
+    /// Like `deserialize`, but does not dump the body if it does not match.
+    fn parse<T: DeserializeOwned>(&self) -> ApiResult<T> {
+        if self.skipped {
+            return Err(ApiErrorKind::SkippedInReadOnlyMode.into());
+        }
         if !self.is_json() {
             return Err(ApiErrorKind::NotJson.into());
         }
-        serde_json::from_reader(match self.body {
-            Some(ref body) => body,
-            None => &b""[..],
-        })
-        .map_err(|err| ApiError::with_source(ApiErrorKind::BadJson, err))
+        let body = self.body.as_deref().unwrap_or_default();
+        let mut deserializer = serde_json::Deserializer::from_slice(body);
+        let rv = serde_path_to_error::deserialize(&mut deserializer)
+            .map_err(|err| ApiError::with_source(ApiErrorKind::BadJson, err))?;
+        deserializer
+            .end()
+            .map_err(|err| ApiError::with_source(ApiErrorKind::BadJson, err))?;
+        Ok(rv)
+    }
+
+    /// Writes the sanitized body to the file given with `--dump-response`.
+    fn dump_body(&self) {
+        let Some(config) = Config::current_opt() else {
+            return;
+        };
+        let Some(path) = config.get_dump_response() else {
+            return;
+        };
+
+        let body = self.body.as_deref().unwrap_or_default();
+        let contents = match serde_json::from_slice(body) {
+            Ok(mut value) => {
+                support_bundle::sanitize_json(&mut value);
+                serde_json::to_string_pretty(&value).unwrap_or_default()
+            }
+            // Not valid JSON, so the body is dumped as text.
+            Err(_) => support_bundle::sanitize(&String::from_utf8_lossy(body)),
+        };
+        match fs::write(path, contents) {
+            Ok(()) => info!("Wrote the response to {}", path.display()),
+            Err(err) => warn!("Could not write the response to {}: {err}", path.display()),
+        }
     }
 
     /// Like `deserialize` but consumes the response and will convert
@@ -1890,6 +2628,15 @@ impl ApiResponse {
         self.into_result().and_then(|x| x.deserialize())
     }
 
+    /// Like `convert_rnf`, but returns `None` for requests that were not sent
+    /// in read-only mode.
+    fn convert_sent<T: DeserializeOwned>(self, res_err: ApiErrorKind) -> ApiResult<Option<T>> {
+        if self.skipped {
+            return Ok(None);
+        }
+        self.convert_rnf(res_err).map(Some)
+    }
+
     /// Like convert but produces resource not found errors.
     fn convert_rnf<T: DeserializeOwned>(self, res_err: ApiErrorKind) -> ApiResult<T> {
         match self.status() {
@@ -2005,7 +2752,7 @@ pub struct AuthInfo {
 }
 
 /// A release artifact
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 pub struct Artifact {
     pub id: String,
     pub sha1: String,
@@ -2077,7 +2824,6 @@ pub struct UpdatedRelease {
 #[derive(Debug, Deserialize)]
 pub struct ReleaseInfo {
     pub version: String,
-    #[expect(dead_code)]
     pub url: Option<String>,
     #[serde(rename = "dateCreated")]
     pub date_created: DateTime<Utc>,
@@ -2111,18 +2857,8 @@ pub struct NoneReleaseInfo {}
 #[derive(Debug, Deserialize)]
 pub struct ReleaseCommit {
     pub id: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct GitHubAsset {
-    browser_download_url: String,
-    name: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct GitHubRelease {
-    tag_name: String,
-    assets: Vec<GitHubAsset>,
+    #[serde(default)]
+    pub message: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -2151,6 +2887,9 @@ pub struct DebugInfoData {
 /// Can be dSYMs, ELF debug infos, Breakpad symbols, etc...
 #[derive(Debug, Deserialize)]
 pub struct DebugInfoFile {
+    /// The identifier of the stored file, used to download it.
+    #[serde(default, rename = "id")]
+    pub file_id: Option<String>,
     #[serde(rename = "uuid")]
     uuid: Option<DebugId>,
     #[serde(rename = "debugId")]
@@ -2159,8 +2898,9 @@ pub struct DebugInfoFile {
     pub object_name: String,
     #[serde(rename = "cpuName")]
     pub cpu_name: String,
+    #[serde(default, rename = "symbolType")]
+    pub symbol_type: Option<String>,
     #[serde(rename = "sha1")]
-    #[expect(dead_code)]
     pub checksum: String,
     #[serde(default)]
     pub data: DebugInfoData,
@@ -2203,6 +2943,63 @@ pub struct IssueChanges {
     pub snooze_duration: Option<i64>,
 }
 
+/// Aggregated session data of the sessions endpoint.
+#[derive(Deserialize)]
+struct Sessions {
+    groups: Vec<SessionsGroup>,
+}
+
+#[derive(Deserialize)]
+struct SessionsGroup {
+    #[serde(default)]
+    by: SessionsGroupBy,
+    totals: SessionsTotals,
+}
+
+#[derive(Default, Deserialize)]
+struct SessionsGroupBy {
+    #[serde(rename = "session.status")]
+    status: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SessionsTotals {
+    #[serde(rename = "crash_free_rate(session)")]
+    crash_free_rate: Option<f64>,
+    #[serde(rename = "sum(session)")]
+    sessions: Option<u64>,
+}
+
+/// The number of sessions of a release in an environment.
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct SessionCounts {
+    /// All sessions.
+    pub total: u64,
+    /// Sessions that errored, crashed or ended abnormally.
+    pub errored: u64,
+}
+
+impl SessionCounts {
+    /// Returns the percentage of sessions that errored, or `None` if there
+    /// were no sessions.
+    pub fn error_rate(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.errored as f64 / self.total as f64 * 100.0)
+    }
+}
+
+/// Assignment of a single issue.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueAssignment<'a> {
+    assigned_to: &'a str,
+}
+
+/// A comment added to an issue.
+#[derive(Serialize)]
+struct NewIssueComment<'a> {
+    text: &'a str,
+}
+
 /// Filters for issue bulk requests.
 pub enum IssueFilter {
     /// Match no issues
@@ -2213,6 +3010,8 @@ pub enum IssueFilter {
     ExplicitIds(Vec<u64>),
     /// Match on issues with the given status
     Status(String),
+    /// Match on issues matching the given search query
+    Query(String),
 }
 
 impl IssueFilter {
@@ -2234,6 +3033,9 @@ impl IssueFilter {
             IssueFilter::Status(ref status) => {
                 rv.push(format!("status={status}"));
             }
+            IssueFilter::Query(ref query) => {
+                rv.push(format!("query={}", QueryArg(query)));
+            }
         }
         Some(rv.join("&"))
     }
@@ -2294,6 +3096,42 @@ pub struct ProjectSlugAndName {
     pub name: String,
 }
 
+/// A project to create
+#[derive(Debug, Serialize)]
+pub struct NewProject {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+}
+
+/// Changes to a project
+#[derive(Debug, Serialize, Default)]
+pub struct UpdatedProject {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+}
+
+/// An environment of a project
+#[derive(Debug, Deserialize)]
+pub struct Environment {
+    pub name: String,
+    #[serde(rename = "isHidden")]
+    pub is_hidden: bool,
+}
+
+/// Changes to an environment of a project
+#[derive(Debug, Serialize)]
+pub struct UpdatedEnvironment {
+    #[serde(rename = "isHidden")]
+    pub is_hidden: bool,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Project {
     pub id: String,
@@ -2308,6 +3146,80 @@ pub struct Monitor {
     pub slug: String,
     pub name: String,
     pub status: String,
+    #[serde(default)]
+    pub config: Option<MonitorSettings>,
+}
+
+/// The schedule and thresholds of a cron monitor
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct MonitorSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule_type: Option<String>,
+    /// A crontab string, or an interval for interval schedules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkin_margin: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_runtime: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+/// A monitor to create
+#[derive(Debug, Serialize)]
+pub struct NewMonitor {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    pub project: String,
+    #[serde(rename = "type")]
+    pub ty: &'static str,
+    pub config: MonitorSettings,
+}
+
+/// Changes to a monitor
+#[derive(Debug, Serialize, Default)]
+pub struct UpdatedMonitor {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<MonitorSettings>,
+}
+
+/// An org auth token to create
+#[derive(Debug, Serialize)]
+struct NewOrgAuthToken<'a> {
+    name: &'a str,
+}
+
+/// An org auth token of an organization.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrgAuthTokenInfo {
+    pub id: String,
+    pub name: String,
+    /// The token itself, which is only returned when it is created.
+    pub token: Option<String>,
+    pub token_last_characters: Option<String>,
+    pub date_created: DateTime<Utc>,
+}
+
+/// A repository to create
+#[derive(Debug, Serialize)]
+pub struct NewRepo {
+    pub name: String,
+    pub provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The ID of the integration for repositories of integration providers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<String>,
+    /// The repository name as known to the integration provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -2367,7 +3279,6 @@ pub struct ProcessedEvent {
     #[serde(default)]
     pub title: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[expect(dead_code)]
     pub project: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub release: Option<String>,
@@ -2381,7 +3292,53 @@ pub struct ProcessedEvent {
     pub tags: Option<Vec<ProcessedEventTag>>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+/// How the frames of an event resolve with the uploaded source maps.
+#[derive(Debug, Deserialize)]
+pub struct SourceMapDebug {
+    #[serde(default)]
+    pub exceptions: Vec<SourceMapDebugException>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SourceMapDebugException {
+    #[serde(default)]
+    pub frames: Vec<SourceMapDebugFrame>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SourceMapDebugFrame {
+    #[serde(default)]
+    pub debug_id_process: SourceMapDebugIdProcess,
+    #[serde(default)]
+    pub release_process: Option<SourceMapReleaseProcess>,
+}
+
+impl SourceMapDebugFrame {
+    /// Returns whether a source map was found for the frame, either by its
+    /// debug ID or by the release.
+    pub fn resolves(&self) -> bool {
+        self.debug_id_process
+            .uploaded_source_map_with_correct_debug_id
+            || self
+                .release_process
+                .as_ref()
+                .is_some_and(|process| process.source_map_lookup_result == "found")
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SourceMapDebugIdProcess {
+    #[serde(default)]
+    pub uploaded_source_map_with_correct_debug_id: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SourceMapReleaseProcess {
+    #[serde(default)]
+    pub source_map_lookup_result: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProcessedEventUser {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -2415,7 +3372,7 @@ impl fmt::Display for ProcessedEventUser {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProcessedEventTag {
     pub key: String,
     pub value: String,
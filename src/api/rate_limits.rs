@@ -0,0 +1,137 @@
+//! Process wide handling of rate limits imposed by Sentry.
+//!
+//! When a request is rate limited, all further requests to the same host are
+//! held back until the delay announced by the server has passed, so that
+//! concurrent uploads do not keep running into the limit.
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use log::debug;
+use parking_lot::Mutex;
+
+/// The delay used if a rate limited response does not announce one.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// The longest delay sentry-cli waits for before giving up.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(10 * 60);
+
+lazy_static! {
+    static ref BLOCKED_UNTIL: Mutex<HashMap<String, Instant>> = Mutex::new(HashMap::new());
+}
+
+/// What to do when Sentry rate limits a request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RateLimitBehavior {
+    /// Wait until the rate limit is lifted and retry.
+    #[default]
+    Wait,
+    /// Fail the request.
+    Fail,
+}
+
+impl RateLimitBehavior {
+    /// Parses the behavior from its name, `wait` or `fail`.
+    pub fn from_name(name: &str) -> Option<RateLimitBehavior> {
+        match name {
+            "wait" => Some(RateLimitBehavior::Wait),
+            "fail" => Some(RateLimitBehavior::Fail),
+            _ => None,
+        }
+    }
+}
+
+/// Holds back all requests to the given host for the given delay.
+pub fn block_for(host: &str, delay: Duration) {
+    let until = Instant::now() + delay;
+    let mut blocked_until = BLOCKED_UNTIL.lock();
+    let blocked = blocked_until.entry(host.to_owned()).or_insert(until);
+    if *blocked < until {
+        *blocked = until;
+    }
+}
+
+/// Returns how long requests to the given host are still held back.
+fn remaining(host: &str) -> Duration {
+    BLOCKED_UNTIL
+        .lock()
+        .get(host)
+        .map(|until| until.saturating_duration_since(Instant::now()))
+        .unwrap_or_default()
+}
+
+/// Waits until requests to the given host are no longer held back.
+pub fn wait(host: &str) {
+    let delay = remaining(host);
+    if !delay.is_zero() {
+        debug!("rate limited, waiting {} s", delay.as_secs());
+        std::thread::sleep(delay);
+    }
+}
+
+/// Returns the delay announced in the `Retry-After` and
+/// `X-Sentry-Rate-Limits` headers of a rate limited response.
+pub fn parse_retry_after(retry_after: Option<&str>, rate_limits: Option<&str>) -> Duration {
+    let retry_after = retry_after.and_then(|value| {
+        let value = value.trim();
+        if let Ok(seconds) = value.parse::<f64>() {
+            Duration::try_from_secs_f64(seconds).ok()
+        } else {
+            let date = DateTime::parse_from_rfc2822(value).ok()?;
+            (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+        }
+    });
+
+    // Every limit is formatted as `retry_after:categories:scope:...`.
+    let rate_limits = rate_limits.and_then(|value| {
+        value
+            .split(',')
+            .filter_map(|limit| limit.trim().split(':').next()?.parse::<f64>().ok())
+            .filter_map(|seconds| Duration::try_from_secs_f64(seconds).ok())
+            .max()
+    });
+
+    retry_after
+        .into_iter()
+        .chain(rate_limits)
+        .max()
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after(None, None), DEFAULT_RETRY_AFTER);
+        assert_eq!(parse_retry_after(Some("30"), None), Duration::from_secs(30));
+        assert_eq!(
+            parse_retry_after(
+                None,
+                Some("60:transaction:key, 2700:default;error:organization")
+            ),
+            Duration::from_secs(2700)
+        );
+        assert_eq!(
+            parse_retry_after(Some("120"), Some("60::organization")),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            parse_retry_after(Some("not a date"), None),
+            DEFAULT_RETRY_AFTER
+        );
+    }
+
+    #[test]
+    fn test_block_for_host() {
+        block_for("rate-limited.example.com", Duration::from_secs(60));
+        assert!(remaining("rate-limited.example.com") > Duration::from_secs(50));
+        assert!(remaining("other.example.com").is_zero());
+
+        // A shorter delay does not lift an existing block.
+        block_for("rate-limited.example.com", Duration::from_secs(1));
+        assert!(remaining("rate-limited.example.com") > Duration::from_secs(50));
+    }
+}
@@ -0,0 +1,155 @@
+//! Signing of requests for proxies that only let signed requests through.
+
+use std::io::{self, Write};
+use std::process::{ChildStdin, Stdio};
+use std::thread;
+
+use anyhow::{bail, Context, Result};
+use log::debug;
+
+use crate::utils::system::shell_command;
+
+/// A command that computes the signature headers of a request.
+///
+/// The command runs in a shell right before the request is sent.  It gets the
+/// method and URL of the request in the `SENTRY_SIGNING_METHOD` and
+/// `SENTRY_SIGNING_URL` environment variables, and the body of the request on
+/// stdin.  Every line it prints to stdout is added to the request as a header,
+/// e.g. `X-Signature: ...`.
+///
+/// Only requests to the Sentry API are signed, and they cannot have a
+/// multipart form body.
+#[derive(Debug, Clone)]
+pub struct SigningCommand {
+    command: String,
+    method: String,
+    url: String,
+}
+
+impl SigningCommand {
+    pub fn new(command: String, method: &str, url: &str) -> Self {
+        SigningCommand {
+            command,
+            method: method.to_owned(),
+            url: url.to_owned(),
+        }
+    }
+
+    /// Runs the command and returns the headers to add to the request.
+    pub fn headers(&self, body: Option<&[u8]>) -> Result<Vec<String>> {
+        debug!("signing {} {}", self.method, self.url);
+
+        let mut child = shell_command(&self.command)
+            .env("SENTRY_SIGNING_METHOD", &self.method)
+            .env("SENTRY_SIGNING_URL", &self.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Could not run signing command `{}`", self.command))?;
+
+        // The body is written from another thread while stdout is read, so
+        // that a command printing a lot before it reads the body cannot block.
+        let stdin = child.stdin.take();
+        let output = thread::scope(|scope| -> Result<_> {
+            let writer = scope.spawn(move || write_body(stdin, body.unwrap_or_default()));
+            let output = child.wait_with_output()?;
+            writer.join().expect("writing the body should not panic")?;
+            Ok(output)
+        })?;
+        if !output.status.success() {
+            bail!(
+                "Signing command `{}` failed with {}",
+                self.command,
+                output.status
+            );
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("Signing command did not print UTF-8")?;
+        stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                if !line.contains(':') {
+                    bail!("Signing command printed `{line}`, expected a `Name: value` header");
+                }
+                Ok(line.to_owned())
+            })
+            .collect()
+    }
+}
+
+/// Writes the body to the stdin of the command.  Dropping stdin closes it, so
+/// that the command sees the end of the body.
+///
+/// Commands do not need to read the body, so a closed pipe is not an error.
+fn write_body(stdin: Option<ChildStdin>, body: &[u8]) -> io::Result<()> {
+    let Some(mut stdin) = stdin else {
+        return Ok(());
+    };
+    match stdin.write_all(body) {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        rv => rv,
+    }
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headers() {
+        let command = SigningCommand::new(
+            "echo \"X-Method: $SENTRY_SIGNING_METHOD\"; \
+            echo \"X-Url: $SENTRY_SIGNING_URL\"; \
+            echo \"X-Body: $(cat)\""
+                .into(),
+            "POST",
+            "https://sentry.io/api/0/projects/",
+        );
+        assert_eq!(
+            command.headers(Some(b"{}")).unwrap(),
+            [
+                "X-Method: POST",
+                "X-Url: https://sentry.io/api/0/projects/",
+                "X-Body: {}",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_headers_large_body() {
+        let body = vec![b'a'; 1024 * 1024];
+
+        // The command ignores the body and exits before it is written.
+        let command = SigningCommand::new(
+            "echo 'X-Signed: yes'".into(),
+            "POST",
+            "https://sentry.io/api/0/",
+        );
+        assert_eq!(command.headers(Some(&body)).unwrap(), ["X-Signed: yes"]);
+
+        // The command fills the stdout pipe before it reads the body.
+        let command = SigningCommand::new(
+            "for i in $(seq 1 5000); do echo \"X-Padding-$i: padding\"; done; \
+            echo \"X-Length: $(wc -c | tr -d ' ')\""
+                .into(),
+            "POST",
+            "https://sentry.io/api/0/",
+        );
+        let headers = command.headers(Some(&body)).unwrap();
+        assert_eq!(headers.len(), 5001);
+        assert_eq!(headers.last().unwrap(), "X-Length: 1048576");
+    }
+
+    #[test]
+    fn test_headers_invalid_output() {
+        let command = SigningCommand::new("echo signed".into(), "GET", "https://sentry.io/api/0/");
+        assert!(command.headers(None).is_err());
+
+        let command = SigningCommand::new("exit 1".into(), "GET", "https://sentry.io/api/0/");
+        assert!(command.headers(None).is_err());
+    }
+}
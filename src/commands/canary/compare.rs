@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use chrono::Duration;
+use clap::{Arg, ArgMatches, Command};
+use serde::Serialize;
+
+use crate::api::{Api, AuthenticatedApi, Issue, SessionCounts};
+use crate::config::Config;
+use crate::utils::system::QuietExit;
+use crate::utils::value_parsers::{age_parser, percentage_parser};
+
+/// The number of pages of new issues fetched per project and environment.
+const ISSUE_PAGES: usize = 5;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Compare the health of a release in a canary and a baseline environment.")
+        .long_about(
+            "Compare the health of a release in a canary and a baseline environment.{n}{n}\
+            The error rates of the sessions of the release within --window and the issues \
+            first seen in the release are compared between the environments. The canary fails \
+            if its error rate exceeds the one of the baseline by more than \
+            --max-error-rate-increase, or if more than --max-new-issues of its new issues do \
+            not occur in the baseline. In this case, the command exits with a non-zero exit \
+            code, so it can be used by progressive rollout controllers.",
+        )
+        .arg(
+            Arg::new("baseline_env")
+                .long("baseline-env")
+                .value_name("ENVIRONMENT")
+                .required(true)
+                .help("The environment that runs the release for most users, e.g. production."),
+        )
+        .arg(
+            Arg::new("canary_env")
+                .long("canary-env")
+                .value_name("ENVIRONMENT")
+                .required(true)
+                .help("The environment that runs the release for a few users first."),
+        )
+        .arg(
+            Arg::new("window")
+                .long("window")
+                .value_name("DURATION")
+                .value_parser(age_parser)
+                .default_value("30m")
+                .help(
+                    "The duration over which error rates are computed, e.g. 30m. Supported \
+                    units are s, m, h, d and w.",
+                ),
+        )
+        .arg(
+            Arg::new("max_error_rate_increase")
+                .long("max-error-rate-increase")
+                .value_name("PERCENT")
+                .value_parser(percentage_parser)
+                .default_value("1%")
+                .help(
+                    "The maximum number of percentage points by which the rate of errored \
+                    sessions in the canary may exceed the one of the baseline.",
+                ),
+        )
+        .arg(
+            Arg::new("max_new_issues")
+                .long("max-new-issues")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("0")
+                .help("The maximum number of new issues that only occur in the canary."),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .help("The output format. With `json`, the verdict and all details are printed."),
+        )
+}
+
+/// The health of the release in one environment.
+#[derive(Serialize)]
+struct EnvironmentHealth {
+    environment: String,
+    sessions: SessionCounts,
+    error_rate: Option<f64>,
+    new_issues: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Verdict {
+    Pass,
+    Fail,
+}
+
+/// An issue that only occurs in the canary.
+#[derive(Serialize)]
+struct CanaryIssue {
+    id: String,
+    short_id: String,
+    title: String,
+}
+
+#[derive(Serialize)]
+struct Comparison {
+    release: String,
+    verdict: Verdict,
+    reasons: Vec<String>,
+    baseline: EnvironmentHealth,
+    canary: EnvironmentHealth,
+    canary_only_issues: Vec<CanaryIssue>,
+}
+
+/// Fetches the sessions and new issues of the release in an environment.
+fn fetch_health(
+    api: &AuthenticatedApi,
+    org: &str,
+    projects: &[String],
+    release: &str,
+    environment: &str,
+    stats_period: &str,
+) -> Result<(EnvironmentHealth, BTreeMap<String, Issue>)> {
+    let sessions = api.get_release_session_counts(org, release, environment, stats_period)?;
+
+    let query = format!("first-release:\"{release}\" environment:\"{environment}\"");
+    let mut issues = BTreeMap::new();
+    for project in projects {
+        for issue in
+            api.list_organization_project_issues(org, project, ISSUE_PAGES, Some(query.clone()))?
+        {
+            issues.insert(issue.id.clone(), issue);
+        }
+    }
+
+    let health = EnvironmentHealth {
+        environment: environment.to_owned(),
+        sessions,
+        error_rate: sessions.error_rate(),
+        new_issues: issues
+            .values()
+            .map(|issue| issue.short_id.clone())
+            .collect(),
+    };
+    Ok((health, issues))
+}
+
+fn print_health(title: &str, health: &EnvironmentHealth) {
+    let error_rate = match health.error_rate {
+        Some(error_rate) => format!(
+            "{error_rate:.2}% errored sessions ({} of {})",
+            health.sessions.errored, health.sessions.total
+        ),
+        None => "no sessions".to_owned(),
+    };
+    println!(
+        "{title} ({}): {error_rate}, {} new issues",
+        health.environment,
+        health.new_issues.len()
+    );
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let org = config.get_org(matches)?;
+    let projects = config.get_projects(matches)?;
+    let release = config.get_release(matches)?;
+    let baseline_env = matches.get_one::<String>("baseline_env").unwrap();
+    let canary_env = matches.get_one::<String>("canary_env").unwrap();
+    let window = matches.get_one::<Duration>("window").unwrap();
+    let max_error_rate_increase = *matches.get_one::<f64>("max_error_rate_increase").unwrap();
+    let max_new_issues = *matches.get_one::<usize>("max_new_issues").unwrap();
+    let json_output = matches.get_one::<String>("format").map(String::as_str) == Some("json");
+
+    let stats_period = format!("{}s", window.num_seconds().max(1));
+    let (baseline, baseline_issues) = fetch_health(
+        &authenticated_api,
+        &org,
+        &projects,
+        &release,
+        baseline_env,
+        &stats_period,
+    )?;
+    let (canary, canary_issues) = fetch_health(
+        &authenticated_api,
+        &org,
+        &projects,
+        &release,
+        canary_env,
+        &stats_period,
+    )?;
+
+    let canary_only_issues: Vec<_> = canary_issues
+        .into_values()
+        .filter(|issue| !baseline_issues.contains_key(&issue.id))
+        .map(|issue| CanaryIssue {
+            id: issue.id,
+            short_id: issue.short_id,
+            title: issue.title,
+        })
+        .collect();
+
+    let mut reasons = vec![];
+    if let (Some(baseline_rate), Some(canary_rate)) = (baseline.error_rate, canary.error_rate) {
+        if canary_rate - baseline_rate > max_error_rate_increase {
+            reasons.push(format!(
+                "the error rate of {canary_rate:.2}% exceeds the baseline of \
+                {baseline_rate:.2}% by more than {max_error_rate_increase}%"
+            ));
+        }
+    }
+    if canary_only_issues.len() > max_new_issues {
+        reasons.push(format!(
+            "{} new issues only in the canary exceed the maximum of {max_new_issues}",
+            canary_only_issues.len()
+        ));
+    }
+
+    let comparison = Comparison {
+        release,
+        verdict: if reasons.is_empty() {
+            Verdict::Pass
+        } else {
+            Verdict::Fail
+        },
+        reasons,
+        baseline,
+        canary,
+        canary_only_issues,
+    };
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&comparison)?);
+        return match comparison.verdict {
+            Verdict::Pass => Ok(()),
+            Verdict::Fail => Err(QuietExit(1).into()),
+        };
+    }
+
+    print_health("Baseline", &comparison.baseline);
+    print_health("Canary", &comparison.canary);
+    if !comparison.canary_only_issues.is_empty() {
+        println!("Issues only in the canary:");
+        for issue in &comparison.canary_only_issues {
+            println!("  {} {}", issue.short_id, issue.title);
+        }
+    }
+
+    if !comparison.reasons.is_empty() {
+        bail!(
+            "Canary {canary_env} of release {} failed: {}",
+            comparison.release,
+            comparison.reasons.join(", ")
+        );
+    }
+    println!(
+        "Canary {canary_env} of release {} passed.",
+        comparison.release
+    );
+    Ok(())
+}
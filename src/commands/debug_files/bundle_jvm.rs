@@ -3,16 +3,22 @@ use crate::config::Config;
 use crate::constants::DEFAULT_MAX_WAIT;
 use crate::utils::args::ArgExt;
 use crate::utils::file_search::ReleaseFileSearch;
-use crate::utils::file_upload::{FileUpload, SourceFile, UploadContext};
+use crate::utils::file_upload::{FileUpload, SourceFile, SourceFiles, UploadContext};
 use crate::utils::fs::path_as_url;
+use crate::utils::lockfile::write_atomic;
 use anyhow::{bail, Context, Result};
-use clap::{Arg, ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use console::style;
 use sentry::types::DebugId;
+use serde::{Deserialize, Serialize};
+use sha1_smol::Sha1;
 use std::collections::BTreeMap;
-use std::fs;
-use std::path::PathBuf;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use symbolic::debuginfo::sourcebundle::SourceFileType;
+use zip::ZipArchive;
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -27,7 +33,10 @@ pub fn make_command(command: Command) -> Command {
                 .value_name("PATH")
                 .required(true)
                 .value_parser(clap::builder::PathBufValueParser::new())
-                .help("The directory containing source files to bundle."),
+                .help(
+                    "The directory or sources JAR (e.g. app-sources.jar) containing source \
+                    files to bundle.",
+                ),
         )
         .arg(
             Arg::new("output")
@@ -45,6 +54,110 @@ pub fn make_command(command: Command) -> Command {
                 .value_parser(DebugId::from_str)
                 .help("Debug ID (UUID) to use for the source bundle."),
         )
+        .arg(
+            Arg::new("no_vcs_ignore")
+                .long("no-vcs-ignore")
+                .action(ArgAction::SetTrue)
+                .help("Do not skip files ignored by .gitignore or .ignore files."),
+        )
+        .arg(
+            Arg::new("incremental")
+                .long("incremental")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Keep a manifest of source hashes next to the bundle and only rebuild \
+                    the bundle if sources changed.",
+                ),
+        )
+}
+
+/// The hashes of the sources in a bundle, keyed by their url.
+///
+/// With `--incremental`, this is stored next to the bundle to skip
+/// rebuilding it when no source changed.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct BundleManifest {
+    files: BTreeMap<String, String>,
+}
+
+impl BundleManifest {
+    fn load(path: &Path) -> Option<Self> {
+        let contents = fs::read(path).ok()?;
+        serde_json::from_slice(&contents).ok()
+    }
+}
+
+/// A source file to bundle, with its path relative to the source root.
+struct JvmSource {
+    local_path: PathBuf,
+    path: PathBuf,
+    contents: Vec<u8>,
+}
+
+fn is_jar(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+}
+
+/// Collects the sources in a directory.
+fn collect_dir_sources(path: &Path, respect_vcs_ignore: bool) -> Result<Vec<JvmSource>> {
+    let sources = ReleaseFileSearch::new(path.to_path_buf())
+        .respect_vcs_ignore(respect_vcs_ignore)
+        .collect_files()?;
+    Ok(sources
+        .into_iter()
+        .map(|source| JvmSource {
+            local_path: source.path.strip_prefix(&source.base_path).unwrap().into(),
+            path: source.path,
+            contents: source.contents,
+        })
+        .collect())
+}
+
+/// Collects the sources in a sources JAR, as published to Maven repositories.
+///
+/// Entries are relative to the package root already.  Directories and
+/// metadata in `META-INF` are skipped, as are entries whose path would
+/// escape the archive.
+fn collect_jar_sources(path: &Path) -> Result<Vec<JvmSource>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut archive = ZipArchive::new(BufReader::new(file))
+        .with_context(|| format!("Invalid JAR archive: {}", path.display()))?;
+
+    let mut sources = vec![];
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(local_path) = entry.enclosed_name().map(Path::to_path_buf) else {
+            continue;
+        };
+        if local_path.starts_with("META-INF") {
+            continue;
+        }
+        let mut contents = Vec::with_capacity(entry.size() as usize);
+        entry
+            .read_to_end(&mut contents)
+            .with_context(|| format!("Failed to read {} from JAR", local_path.display()))?;
+        sources.push(JvmSource {
+            path: path.join(&local_path),
+            local_path,
+            contents,
+        });
+    }
+    println!(
+        "{} Found {} {}",
+        style(">").dim(),
+        style(sources.len()).yellow(),
+        match sources.len() {
+            1 => "file",
+            _ => "files",
+        }
+    );
+    Ok(sources)
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -63,6 +176,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         wait: true,
         max_wait: DEFAULT_MAX_WAIT,
         dedupe: false,
+        provenance: None,
         chunk_upload_options: chunk_upload_options.as_ref(),
     };
     let path = matches.get_one::<PathBuf>("path").unwrap();
@@ -74,7 +188,8 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         bail!("Given path does not exist: {}", path.display())
     }
 
-    if !path.is_dir() {
+    let jar = is_jar(path);
+    if !jar && !path.is_dir() {
         bail!("Given path is not a directory: {}", path.display())
     }
 
@@ -85,19 +200,22 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         ))?;
     }
 
-    let sources = ReleaseFileSearch::new(path.to_path_buf()).collect_files()?;
-    let files = sources
-        .iter()
+    let sources = if jar {
+        collect_jar_sources(path)?
+    } else {
+        collect_dir_sources(path, !matches.get_flag("no_vcs_ignore"))?
+    };
+    let files: SourceFiles = sources
+        .into_iter()
         .map(|source| {
-            let local_path = source.path.strip_prefix(&source.base_path).unwrap();
-            let local_path_jvm_ext = local_path.with_extension("jvm");
+            let local_path_jvm_ext = source.local_path.with_extension("jvm");
             let url = format!("~/{}", path_as_url(&local_path_jvm_ext));
             (
                 url.to_string(),
                 SourceFile {
                     url,
-                    path: source.path.clone(),
-                    contents: source.contents.clone(),
+                    path: source.path,
+                    contents: source.contents,
                     ty: SourceFileType::Source,
                     headers: BTreeMap::new(),
                     messages: vec![],
@@ -107,12 +225,37 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         })
         .collect();
 
+    let manifest = BundleManifest {
+        files: files
+            .values()
+            .map(|file| {
+                (
+                    file.url.clone(),
+                    Sha1::from(&file.contents).digest().to_string(),
+                )
+            })
+            .collect(),
+    };
+    let manifest_path = output_path.join(format!("{debug_id}.manifest.json"));
+    let incremental = matches.get_flag("incremental");
+    if incremental
+        && out.is_file()
+        && BundleManifest::load(&manifest_path).as_ref() == Some(&manifest)
+    {
+        println!("Sources unchanged, reusing {}", out.display());
+        return Ok(());
+    }
+
     let tempfile = FileUpload::new(context)
         .files(&files)
         .build_jvm_bundle(Some(*debug_id))
         .context("Unable to create source bundle")?;
 
     fs::copy(tempfile.path(), &out).context("Unable to write source bundle")?;
+    if incremental {
+        write_atomic(&manifest_path, &serde_json::to_vec(&manifest)?)
+            .context("Unable to write bundle manifest")?;
+    }
     println!("Created {}", out.display());
 
     Ok(())
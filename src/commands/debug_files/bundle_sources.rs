@@ -6,8 +6,11 @@ use clap::{Arg, ArgAction, ArgMatches, Command};
 use log::{info, warn};
 use symbolic::debuginfo::sourcebundle::SourceBundleWriter;
 
+use crate::config::Config;
+use crate::utils::args::ArgExt;
 use crate::utils::dif::DifFile;
-use crate::utils::dif_upload::filter_bad_sources;
+use crate::utils::dif_upload::{filter_bad_sources, rewrite_source_bundle};
+use crate::utils::source_root::SourceRoot;
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -29,10 +32,11 @@ pub fn make_command(command: Command) -> Command {
                      file is placed next to the input file.",
                 ),
         )
+        .source_root_arg()
 }
 
 fn is_dsym(path: &Path) -> bool {
-    path.extension().map_or(false, |e| e == "dSYM")
+    path.extension().is_some_and(|e| e == "dSYM")
 }
 
 fn get_sane_parent(path: &Path) -> &Path {
@@ -69,6 +73,8 @@ fn get_canonical_path<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
     let output_path = matches.get_one::<String>("output").map(Path::new);
+    let source_root = SourceRoot::from_matches(matches)?;
+    let scrubber = Config::current().get_scrubber()?;
 
     for orig_path in matches.get_many::<String>("paths").unwrap() {
         let canonical_path = get_canonical_path(orig_path)?;
@@ -112,9 +118,10 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                 eprintln!("skipped {orig_path} (no files found)");
                 fs::remove_file(&out)?;
                 continue;
-            } else {
-                println!("{}", out.display());
             }
+
+            rewrite_source_bundle(&out, source_root.as_ref(), scrubber.as_ref())?;
+            println!("{}", out.display());
         }
     }
 
@@ -1,25 +1,35 @@
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{builder::PossibleValuesParser, Arg, ArgAction, ArgMatches, Command};
 use console::style;
+use glob::{glob_with, MatchOptions};
+use itertools::Itertools;
+use log::debug;
+use serde::Serialize;
+use walkdir::WalkDir;
 
+use crate::utils::args::ArgExt;
 use crate::utils::dif::{DifFile, DifType};
+use crate::utils::formatting::{OutputFormat, Table};
 use crate::utils::logging::is_quiet_mode;
 use crate::utils::system::QuietExit;
 
 pub fn make_command(command: Command) -> Command {
     command
-        .about("Check the debug info file at a given path.")
+        .about("Check the debug info files at the given paths.")
         // Legacy name, left hidden for backward compatibility
         .alias("id")
         // Legacy name, left hidden for backward compatibility
         .alias("uuid")
         .arg(
-            Arg::new("path")
+            Arg::new("paths")
+                .value_name("PATH")
                 .required(true)
-                .help("The path to the debug info file."),
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .help("The paths to debug info files, directories or glob patterns."),
         )
         .arg(
             Arg::new("type")
@@ -36,33 +46,94 @@ pub fn make_command(command: Command) -> Command {
             Arg::new("json")
                 .long("json")
                 .action(ArgAction::SetTrue)
+                .conflicts_with_all(["format", "template"])
                 .help("Format outputs as JSON."),
         )
+        .format_arg()
 }
 
-pub fn execute(matches: &ArgMatches) -> Result<()> {
-    let path = Path::new(matches.get_one::<String>("path").unwrap());
-
-    if path.is_dir() {
-        anyhow::bail!(
-            "This command can only check individual debug files, but the provided \
-            path leads to a directory. Please a pass a path to an individual file, \
-            instead!"
-        );
+/// A debug info file checked in batch mode.
+#[derive(Serialize)]
+struct CheckedFile {
+    path: String,
+    #[serde(flatten)]
+    dif: DifFile<'static>,
+    missing_features: String,
+}
+
+/// Expands the given paths into the files to check, and returns whether more
+/// than a single file was requested.
+fn collect_paths<'a, I>(paths: I) -> (Vec<PathBuf>, bool)
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let mut batch = false;
+    let mut files = vec![];
+    for path in paths {
+        let matched: Vec<_> = if Path::new(path).exists() {
+            vec![PathBuf::from(path)]
+        } else {
+            glob_with(path, MatchOptions::new())
+                .map(|paths| paths.flatten().collect())
+                .unwrap_or_default()
+        };
+
+        if matched.is_empty() {
+            // Fails to open with a helpful error later.
+            files.push(PathBuf::from(path));
+            continue;
+        }
+
+        batch |= matched.len() > 1;
+        for path in matched {
+            if path.is_dir() {
+                batch = true;
+                files.extend(
+                    WalkDir::new(&path)
+                        .sort_by_file_name()
+                        .into_iter()
+                        .filter_map(Result::ok)
+                        .filter(|entry| entry.file_type().is_file())
+                        .map(|entry| entry.into_path()),
+                );
+            } else {
+                files.push(path);
+            }
+        }
     }
 
+    batch |= files.len() > 1;
+    (files, batch)
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
     // which types should we consider?
     let ty = matches
         .get_one::<String>("type")
         .map(|t| t.parse().unwrap());
-    let dif = DifFile::open_path(path, ty)?;
+    let format = if matches.get_flag("json") {
+        OutputFormat::Json
+    } else {
+        OutputFormat::from_matches(matches)
+    };
+
+    let (paths, batch) = collect_paths(matches.get_many::<String>("paths").unwrap());
+    if batch {
+        return check_batch(&paths, ty, format);
+    }
+
+    let dif = DifFile::open_path(&paths[0], ty)?;
 
-    if matches.get_flag("json") {
-        serde_json::to_writer_pretty(&mut io::stdout(), &dif)?;
-        println!();
+    match format {
+        OutputFormat::Table => (),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut io::stdout(), &dif)?;
+            println!();
+        }
+        ref format => format.clone().print_items(&[&dif])?,
     }
 
-    if matches.get_flag("json") || is_quiet_mode() {
+    if format != OutputFormat::Table || is_quiet_mode() {
         return if dif.is_usable() {
             Ok(())
         } else {
@@ -106,3 +177,59 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         Ok(())
     }
 }
+
+/// Checks all debug info files in the given paths and prints one row per file.
+fn check_batch(paths: &[PathBuf], ty: Option<DifType>, format: OutputFormat) -> Result<()> {
+    let mut checked = vec![];
+    for path in paths {
+        match DifFile::open_path(path, ty) {
+            Ok(dif) => checked.push(CheckedFile {
+                path: path.display().to_string(),
+                missing_features: dif.features().missing().to_string(),
+                dif,
+            }),
+            Err(err) => debug!("Skipping {}: {err:#}", path.display()),
+        }
+    }
+
+    if checked.is_empty() {
+        bail!("No debug info files found");
+    }
+
+    if is_quiet_mode() {
+        // Only the exit code is relevant.
+    } else if format == OutputFormat::Table {
+        let mut table = Table::new();
+        table
+            .title_row()
+            .add("Path")
+            .add("Type")
+            .add("Debug ID")
+            .add("Features")
+            .add("Missing")
+            .add("Usable");
+        for file in &checked {
+            let usable = match file.dif.get_problem() {
+                Some(problem) => format!("{} ({problem})", style("no").red()),
+                None => style("yes").green().to_string(),
+            };
+            table
+                .add_row()
+                .add(&file.path)
+                .add(file.dif.ty())
+                .add(file.dif.variants().iter().map(|v| v.debug_id).join(", "))
+                .add(file.dif.features())
+                .add(&file.missing_features)
+                .add(usable);
+        }
+        table.print();
+    } else {
+        format.print_items(&checked)?;
+    }
+
+    if checked.iter().all(|file| file.dif.is_usable()) {
+        Ok(())
+    } else {
+        Err(QuietExit(1).into())
+    }
+}
@@ -0,0 +1,125 @@
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use log::warn;
+use symbolic::debuginfo::FileFormat;
+
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::breakpad::write_breakpad_symbols;
+use crate::utils::dif::DifFile;
+use crate::utils::dif_upload::{DifFormat, DifUpload};
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Convert debug information files to Breakpad symbols.")
+        .long_about(
+            "Convert debug information files to Breakpad symbols.{n}{n}\
+            For every object in the given files, a Breakpad symbol file with the module, file, \
+            function, line and public symbol records is written. Stack unwinding records are \
+            not written. With --upload, the symbol files are uploaded to the project as well.",
+        )
+        .org_arg()
+        .project_arg(false)
+        .arg(
+            Arg::new("paths")
+                .value_name("PATH")
+                .required(true)
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .help("The paths to the debug information files to convert."),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(["breakpad"])
+                .default_value("breakpad")
+                .help("The format to convert to."),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("PATH")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help(
+                    "The path to the output folder. If not provided, the symbol files are \
+                    placed next to the input files.",
+                ),
+        )
+        .arg(
+            Arg::new("upload")
+                .long("upload")
+                .action(ArgAction::SetTrue)
+                .help("Upload the symbol files after converting them."),
+        )
+}
+
+/// Converts all objects in a file and returns the paths of the symbol files.
+fn convert_file(path: &Path, output_path: Option<&Path>) -> Result<Vec<PathBuf>> {
+    let archive = match DifFile::open_path(path, None)? {
+        DifFile::Archive(archive) => archive,
+        _ => {
+            warn!("Cannot convert {}", path.display());
+            return Ok(vec![]);
+        }
+    };
+
+    let file_name = path.file_name().unwrap().to_string_lossy();
+    let out_dir = output_path.unwrap_or_else(|| path.parent().unwrap_or(Path::new(".")));
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+
+    let archive = archive.get();
+    let mut written = vec![];
+    for object in archive.objects() {
+        let object = object?;
+        if object.file_format() == FileFormat::Breakpad {
+            warn!("{} already contains Breakpad symbols", path.display());
+            continue;
+        }
+
+        // Fat Mach-O files contain one object per architecture.
+        let out = if archive.object_count() > 1 {
+            out_dir.join(format!("{file_name}.{}.sym", object.arch().name()))
+        } else {
+            out_dir.join(format!("{file_name}.sym"))
+        };
+
+        let mut writer = BufWriter::new(
+            File::create(&out).with_context(|| format!("Failed to create {}", out.display()))?,
+        );
+        write_breakpad_symbols(&object, &file_name, &mut writer)
+            .with_context(|| format!("Failed to convert {}", path.display()))?;
+        writer.into_inner()?;
+
+        println!("{}", out.display());
+        written.push(out);
+    }
+    Ok(written)
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let output_path = matches.get_one::<PathBuf>("output").map(PathBuf::as_path);
+
+    let mut written = vec![];
+    for path in matches.get_many::<String>("paths").unwrap() {
+        written.extend(convert_file(Path::new(path), output_path)?);
+    }
+
+    if !matches.get_flag("upload") || written.is_empty() {
+        return Ok(());
+    }
+
+    let config = Config::current();
+    let (org, project) = config.get_org_and_project(matches)?;
+    let mut upload = DifUpload::new(&org, &project);
+    upload
+        .search_paths(written)
+        .filter_format(DifFormat::Object(FileFormat::Breakpad));
+    upload.upload()?;
+    Ok(())
+}
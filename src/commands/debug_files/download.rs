@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use console::style;
+use log::warn;
+use symbolic::common::DebugId;
+
+use crate::api::{Api, DebugInfoFile};
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::system::QuietExit;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Download uploaded debug information files from Sentry.")
+        .long_about(
+            "Download uploaded debug information files from Sentry.{n}{n}\
+            All files with the given debug identifiers are downloaded, such as the \
+            executable, its debug companion and source bundle. Every file is stored in a \
+            folder named after its debug identifier, to reproduce symbolication issues \
+            locally.",
+        )
+        .arg(
+            Arg::new("ids")
+                .long("id")
+                .value_name("DEBUG_ID")
+                .required(true)
+                .action(ArgAction::Append)
+                .value_parser(DebugId::from_str)
+                .help("The debug identifier of the files to download."),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("PATH")
+                .default_value(".")
+                .help("The directory to download the files to."),
+        )
+        .org_arg()
+        .project_arg(false)
+}
+
+/// Returns the file name of an object name, which may be a path on any
+/// platform.
+fn file_name(object_name: &str) -> &str {
+    object_name
+        .rsplit(['/', '\\'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("debug-file")
+}
+
+/// Returns the paths to download the files of a single debug identifier
+/// to.  Files with the same name are told apart by their symbol type.
+fn download_paths(dir: &Path, files: &[DebugInfoFile]) -> Vec<PathBuf> {
+    let mut counts = HashMap::new();
+    for file in files {
+        *counts.entry(file_name(&file.object_name)).or_insert(0) += 1;
+    }
+
+    files
+        .iter()
+        .map(|file| {
+            let name = file_name(&file.object_name);
+            if counts[name] == 1 {
+                return dir.join(name);
+            }
+            let suffix = file
+                .symbol_type
+                .as_deref()
+                .or(file.file_id.as_deref())
+                .unwrap_or_default()
+                .to_lowercase();
+            dir.join(format!("{name}.{suffix}"))
+        })
+        .collect()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let (org, project) = Config::current().get_org_and_project(matches)?;
+    let output = Path::new(matches.get_one::<String>("output").unwrap());
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+
+    let mut missing = vec![];
+    for id in matches.get_many::<DebugId>("ids").unwrap() {
+        let files = authenticated_api.list_dsyms(&org, &project, *id)?;
+        if files.is_empty() {
+            missing.push(id);
+            continue;
+        }
+
+        let dir = output.join(id.to_string());
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Could not create directory {}", dir.display()))?;
+        for (file, path) in files.iter().zip(download_paths(&dir, &files)) {
+            let Some(ref file_id) = file.file_id else {
+                warn!(
+                    "Cannot download {}: the server did not return its id",
+                    path.display()
+                );
+                continue;
+            };
+            let mut dst = File::create(&path)
+                .with_context(|| format!("Could not create {}", path.display()))?;
+            authenticated_api.download_dsym(&org, &project, file_id, &mut dst)?;
+            println!(
+                "{} {} [{}, {}]",
+                style(file.id()).dim(),
+                path.display(),
+                style(file.symbol_type.as_deref().unwrap_or("unknown")).yellow(),
+                file.cpu_name
+            );
+        }
+    }
+
+    if !missing.is_empty() {
+        eprintln!();
+        eprintln!("missing debug information files:");
+        for id in missing {
+            eprintln!("  {id}");
+        }
+        return Err(QuietExit(1).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name("/usr/lib/libc.so.6"), "libc.so.6");
+        assert_eq!(file_name("C:\\build\\app.pdb"), "app.pdb");
+        assert_eq!(file_name("app"), "app");
+        assert_eq!(file_name("/usr/lib/"), "debug-file");
+    }
+}
@@ -1,40 +1,111 @@
 use std::collections::HashSet;
 use std::env;
 use std::ffi::OsStr;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
+use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use clap::{builder::PossibleValuesParser, Arg, ArgAction, ArgMatches, Command};
 use console::style;
+use glob::{MatchOptions, Pattern};
 use if_chain::if_chain;
+use itertools::Itertools;
+use log::{debug, warn};
 use proguard::ProguardMapping;
 use serde::Serialize;
-use symbolic::common::{ByteView, DebugId};
+use symbolic::common::{Arch, ByteView, DebugId};
 use uuid::{Uuid, Version as UuidVersion};
 use walkdir::{DirEntry, WalkDir};
 
+use crate::api::Api;
+use crate::config::{is_stateless, Config};
+use crate::constants::APP_NAME;
+use crate::utils::args::ArgExt;
 use crate::utils::dif::{DifFile, DifType};
+use crate::utils::dif_upload::DifUpload;
+use crate::utils::lockfile::AtomicFile;
 use crate::utils::progress::{ProgressBar, ProgressStyle};
+use crate::utils::symbol_server;
 use crate::utils::system::QuietExit;
 
 // text files larger than 32 megabytes are not considered to be
 // valid mapping files when scanning
 const MAX_MAPPING_FILE: u64 = 32 * 1024 * 1024;
 
+/// The directories that Linux distributions install debug files into.
+const SYSTEM_DEBUG_DIRS: &[&str] = &["/usr/lib/debug", "/usr/local/lib/debug"];
+
+/// The environment variable listing debuginfod servers.
+const DEBUGINFOD_URLS_ENV: &str = "DEBUGINFOD_URLS";
+
+/// Where a debug information file was found.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Location {
+    /// A path passed with `--path`.
+    Path,
+    WorkingDirectory,
+    DerivedData,
+    /// The Spotlight index on macOS.
+    Spotlight,
+    /// Files downloaded from symbol servers or debuginfod.
+    SymbolCache,
+    /// The debug directories of the system.
+    System,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Location::Path => "search path",
+            Location::WorkingDirectory => "working directory",
+            Location::DerivedData => "Xcode DerivedData",
+            Location::Spotlight => "Spotlight",
+            Location::SymbolCache => "symbol cache",
+            Location::System => "system",
+        };
+        write!(f, "{name}")
+    }
+}
+
 #[derive(Serialize, Debug)]
 struct DifMatch {
     #[serde(rename = "type")]
     pub ty: DifType,
     pub id: DebugId,
     pub path: PathBuf,
+    pub location: Location,
+}
+
+/// The debug information files to look for.
+struct Wanted<'a> {
+    /// The debug identifiers that have not been found yet, or `None` to
+    /// accept files with any identifier.
+    ids: Option<&'a HashSet<DebugId>>,
+    arch: Option<Arch>,
+}
+
+impl Wanted<'_> {
+    /// Returns the wanted identifiers in the given file.
+    fn extract(&self, dif: &DifFile, ty: DifType) -> Vec<(DebugId, DifType)> {
+        dif.variants()
+            .into_iter()
+            .filter(|variant| {
+                self.arch
+                    .map_or(true, |arch| variant.arch.as_deref() == Some(arch.name()))
+            })
+            .filter(|variant| self.ids.map_or(true, |ids| ids.contains(&variant.debug_id)))
+            .map(|variant| (variant.debug_id, ty))
+            .collect()
+    }
 }
 
 pub fn make_command(command: Command) -> Command {
     command
-        .about("Locate debug information files for given debug identifiers.")
+        .about("Locate debug information files by debug identifier, name or architecture.")
         .arg(
             Arg::new("ids")
                 .value_name("ID")
@@ -43,6 +114,28 @@ pub fn make_command(command: Command) -> Command {
                 .num_args(1..)
                 .action(ArgAction::Append),
         )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .value_name("PATTERN")
+                .value_parser(parse_name_pattern)
+                .help(
+                    "Only consider files whose name matches the given pattern, ignoring \
+                     case.  Patterns without wildcards match any part of the name.  Without \
+                     debug identifiers, all matching files are listed.",
+                ),
+        )
+        .arg(
+            Arg::new("arch")
+                .long("arch")
+                .value_name("ARCH")
+                .value_parser(Arch::from_str)
+                .help(
+                    "Only consider debug information files for the given CPU \
+                     architecture, such as x86_64 or arm64.  Without debug identifiers, \
+                     all matching files are listed.",
+                ),
+        )
         .arg(
             Arg::new("types")
                 .long("type")
@@ -81,6 +174,183 @@ pub fn make_command(command: Command) -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Format outputs as JSON."),
         )
+        .arg(
+            Arg::new("build_ids")
+                .long("build-id")
+                .value_name("BUILD_ID")
+                .action(ArgAction::Append)
+                .value_parser(parse_build_id)
+                .help(
+                    "The GNU build ID of an ELF file to search for.  Build IDs \
+                     are required to look up files with --system.",
+                ),
+        )
+        .arg(
+            Arg::new("system")
+                .long("system")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Look up the given build IDs in the debug directories of the \
+                     system, such as /usr/lib/debug/.build-id, and on the \
+                     debuginfod servers in the DEBUGINFOD_URLS environment variable.",
+                ),
+        )
+        .arg(
+            Arg::new("upload")
+                .long("upload")
+                .action(ArgAction::SetTrue)
+                .requires("system")
+                .help("Upload the debug files found with --system to Sentry."),
+        )
+        .org_arg()
+        .project_arg(false)
+        // `-p` is taken by `--path`.
+        .mut_arg("project", |arg| arg.short(None))
+}
+
+/// Parses a file name pattern.  Names without wildcards match any part of
+/// the file name.
+fn parse_name_pattern(value: &str) -> Result<Pattern> {
+    let pattern = if value.contains(['*', '?', '[']) {
+        value.to_owned()
+    } else {
+        format!("*{}*", Pattern::escape(value))
+    };
+    Ok(Pattern::new(&pattern)?)
+}
+
+fn matches_name(pattern: &Pattern, dirent: &DirEntry) -> bool {
+    let options = MatchOptions {
+        case_sensitive: false,
+        ..MatchOptions::new()
+    };
+    dirent
+        .file_name()
+        .to_str()
+        .is_some_and(|name| pattern.matches_with(name, options))
+}
+
+/// Parses a GNU build ID into its lowercase hex representation.
+fn parse_build_id(value: &str) -> Result<String> {
+    let build_id = value.to_ascii_lowercase();
+    match data_encoding::HEXLOWER.decode(build_id.as_bytes()) {
+        Ok(bytes) if !bytes.is_empty() => Ok(build_id),
+        _ => Err(format_err!("Invalid build ID: {value}")),
+    }
+}
+
+/// Computes the debug identifier of an ELF file from its build ID, the way
+/// Sentry does for little-endian files.
+fn build_id_to_debug_id(build_id: &str) -> Option<DebugId> {
+    let mut bytes = data_encoding::HEXLOWER.decode(build_id.as_bytes()).ok()?;
+    bytes.resize(16, 0);
+    DebugId::from_guid_age(&bytes[..16], 0).ok()
+}
+
+/// Looks up the debug file with the given build ID in the debug directories
+/// of the system, and otherwise downloads it from a debuginfod server.
+fn find_system_debug_file(build_id: &str) -> Result<Option<PathBuf>> {
+    let (prefix, rest) = build_id.split_at(2);
+    let local = SYSTEM_DEBUG_DIRS
+        .iter()
+        .map(|dir| Path::new(dir).join(format!(".build-id/{prefix}/{rest}.debug")))
+        .find(|path| path.is_file());
+    if local.is_some() {
+        return Ok(local);
+    }
+
+    let Ok(urls) = env::var(DEBUGINFOD_URLS_ENV) else {
+        return Ok(None);
+    };
+    if is_stateless() {
+        warn!("Not downloading debug files from debuginfod servers in stateless mode");
+        return Ok(None);
+    }
+    let cache_dir =
+        debuginfod_cache_dir().ok_or_else(|| format_err!("Could not get cache folder"))?;
+    let path = cache_dir.join(format!("{build_id}.debug"));
+    if path.is_file() {
+        return Ok(Some(path));
+    }
+
+    fs::create_dir_all(&cache_dir)?;
+    for url in urls.split_whitespace() {
+        let url = format!("{}/buildid/{build_id}/debuginfo", url.trim_end_matches('/'));
+        // Other processes must not pick up partial downloads from the cache.
+        let mut file = AtomicFile::create(&path)?;
+        match Api::current().download(&url, file.file_mut()) {
+            Ok(resp) if resp.ok() => {
+                file.commit()?;
+                return Ok(Some(path));
+            }
+            Ok(resp) => debug!("{url} returned status {}", resp.status()),
+            Err(err) => debug!("could not download {url}: {err}"),
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the folder in the user's cache directory that holds debug files
+/// downloaded from debuginfod servers.
+fn debuginfod_cache_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join(APP_NAME).join("debuginfod"))
+}
+
+/// Returns the paths of files in the Spotlight index that may match the
+/// search: dSYMs with the given UUIDs and files with matching names.
+#[cfg(target_os = "macos")]
+fn spotlight_paths(ids: &HashSet<DebugId>, name: Option<&Pattern>) -> Vec<PathBuf> {
+    let mut queries: Vec<_> = ids
+        .iter()
+        .map(|id| {
+            let uuid = id.uuid().hyphenated().to_string().to_uppercase();
+            format!("com_apple_xcode_dsym_uuids == {uuid}")
+        })
+        .collect();
+    if let Some(name) = name {
+        // Spotlight only supports the `*` wildcard, so `?` matches more
+        // here and files are filtered by the actual pattern later.
+        let name = name.as_str().replace('?', "*").replace('"', "\\\"");
+        queries.push(format!("kMDItemFSName == \"{name}\"c"));
+    }
+
+    let mut paths = vec![];
+    for query in queries {
+        match std::process::Command::new("mdfind").arg(&query).output() {
+            Ok(output) if output.status.success() => paths.extend(
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .map(PathBuf::from),
+            ),
+            Ok(output) => debug!("mdfind {query} failed: {}", output.status),
+            Err(err) => debug!("could not run mdfind: {err}"),
+        }
+    }
+    paths
+}
+
+#[cfg(not(target_os = "macos"))]
+fn spotlight_paths(_ids: &HashSet<DebugId>, _name: Option<&Pattern>) -> Vec<PathBuf> {
+    vec![]
+}
+
+/// Looks up the given build IDs on the system and returns the found files.
+fn find_system_files(build_ids: &[String]) -> Result<Vec<DifMatch>> {
+    let mut found = vec![];
+    for build_id in build_ids {
+        let Some(id) = build_id_to_debug_id(build_id) else {
+            continue;
+        };
+        if let Some(path) = find_system_debug_file(build_id)? {
+            found.push(DifMatch {
+                ty: DifType::Elf,
+                id,
+                path,
+                location: Location::System,
+            });
+        }
+    }
+    Ok(found)
 }
 
 fn id_hint(id: &DebugId) -> &'static str {
@@ -96,27 +366,42 @@ fn id_hint(id: &DebugId) -> &'static str {
     }
 }
 
+/// Searches the given paths for debug information files with the given
+/// identifiers.  Without identifiers, all files that match the name and
+/// architecture are found.  Returns whether all identifiers were found.
 fn find_ids(
-    paths: &HashSet<PathBuf>,
+    paths: &[(PathBuf, Location)],
     types: &HashSet<DifType>,
     ids: &HashSet<DebugId>,
+    name: Option<&Pattern>,
+    arch: Option<Arch>,
+    mut found_files: Vec<DifMatch>,
     as_json: bool,
 ) -> Result<bool> {
+    let find_all = ids.is_empty();
     let mut remaining = ids.clone();
+    for found in &found_files {
+        remaining.remove(&found.id);
+    }
     let mut breakpad_found = HashSet::new();
     let mut proguard_uuids: HashSet<_> = ids
         .iter()
         .map(DebugId::uuid)
         .filter(|&x| x.get_version() == Some(UuidVersion::Sha1))
         .collect();
+    let no_uuids = HashSet::new();
 
     let iter = paths
         .iter()
-        .flat_map(WalkDir::new)
-        .filter_map(Result::ok)
-        .filter(|e| e.file_type().is_file());
+        .flat_map(|(path, location)| {
+            WalkDir::new(path)
+                .into_iter()
+                .filter_map(Result::ok)
+                .map(move |dirent| (dirent, *location))
+        })
+        .filter(|(e, _)| e.file_type().is_file())
+        .filter(|(e, _)| name.map_or(true, |name| matches_name(name, e)));
 
-    let mut found_files = vec![];
     let pb = ProgressBar::new_spinner();
     pb.set_style(
         ProgressStyle::default_spinner()
@@ -127,8 +412,8 @@ fn find_ids(
             ),
     );
 
-    for dirent in iter {
-        if remaining.is_empty() {
+    for (dirent, location) in iter {
+        if !find_all && remaining.is_empty() {
             break;
         }
 
@@ -138,26 +423,43 @@ fn find_ids(
         pb.tick();
         pb.set_prefix(&format!("{}", found_files.len()));
 
+        let wanted = Wanted {
+            ids: (!find_all).then_some(&remaining),
+            arch,
+        };
+        // Proguard mappings have no architecture.
+        let wanted_proguard = match (find_all, arch) {
+            (_, Some(_)) => Some(&no_uuids),
+            (true, None) => None,
+            (false, None) => Some(&proguard_uuids),
+        };
         let found: Vec<_> = types
             .iter()
             .filter_map(|t| match t {
-                DifType::Dsym => find_ids_for_dsym(&dirent, &remaining),
-                DifType::Elf => find_ids_for_elf(&dirent, &remaining),
-                DifType::Pe => find_ids_for_pe(&dirent, &remaining),
-                DifType::Pdb => find_ids_for_pdb(&dirent, &remaining),
-                DifType::PortablePdb => find_ids_for_portablepdb(&dirent, &remaining),
-                DifType::SourceBundle => find_ids_for_sourcebundle(&dirent, &remaining),
-                DifType::Breakpad => find_ids_for_breakpad(&dirent, &remaining),
-                DifType::Proguard => find_ids_for_proguard(&dirent, &proguard_uuids),
-                DifType::Jvm => find_ids_for_sourcebundle(&dirent, &remaining),
+                DifType::Dsym => find_ids_for_dsym(&dirent, &wanted),
+                DifType::Elf => find_ids_for_elf(&dirent, &wanted),
+                DifType::Pe => find_ids_for_pe(&dirent, &wanted),
+                DifType::Pdb => find_ids_for_pdb(&dirent, &wanted),
+                DifType::PortablePdb => find_ids_for_portablepdb(&dirent, &wanted),
+                DifType::SourceBundle => find_ids_for_sourcebundle(&dirent, &wanted),
+                DifType::Breakpad => find_ids_for_breakpad(&dirent, &wanted),
+                DifType::Proguard => find_ids_for_proguard(&dirent, wanted_proguard),
+                DifType::Jvm => find_ids_for_sourcebundle(&dirent, &wanted),
                 DifType::Wasm => None,
             })
             .flatten()
+            // Source bundles are found for both the JVM and source bundle types.
+            .unique()
             .collect();
 
         for (id, ty) in found {
             let path = dirent.path().to_path_buf();
-            found_files.push(DifMatch { ty, id, path });
+            found_files.push(DifMatch {
+                ty,
+                id,
+                path,
+                location,
+            });
             if ty == DifType::Breakpad {
                 breakpad_found.insert(id);
             } else {
@@ -173,12 +475,13 @@ fn find_ids(
         serde_json::to_writer_pretty(&mut io::stdout(), &found_files)?;
         println!();
     } else {
-        for m in found_files {
+        for m in &found_files {
             println!(
-                "{} {} [{}]",
+                "{} {} [{}] ({})",
                 style(m.id).dim(),
                 m.path.display(),
-                style(m.ty).yellow()
+                style(m.ty).yellow(),
+                m.location
             );
         }
         remaining.extend(breakpad_found);
@@ -191,22 +494,28 @@ fn find_ids(
         }
     }
 
-    Ok(remaining.is_empty())
+    if find_all {
+        Ok(!found_files.is_empty())
+    } else {
+        Ok(remaining.is_empty())
+    }
 }
 
+/// Finds the Proguard mapping with one of the given UUIDs, or any UUID if
+/// `proguard_uuids` is `None`.
 fn find_ids_for_proguard(
     dirent: &DirEntry,
-    proguard_uuids: &HashSet<Uuid>,
+    proguard_uuids: Option<&HashSet<Uuid>>,
 ) -> Option<Vec<(DebugId, DifType)>> {
     if_chain! {
-        if !proguard_uuids.is_empty();
+        if proguard_uuids.map_or(true, |uuids| !uuids.is_empty());
         if dirent.path().extension() == Some(OsStr::new("txt"));
         if let Ok(md) = dirent.metadata();
         if md.len() < MAX_MAPPING_FILE;
         if let Ok(byteview) = ByteView::open(dirent.path());
         let mapping = ProguardMapping::new(&byteview);
         if mapping.is_valid();
-        if proguard_uuids.contains(&mapping.uuid());
+        if proguard_uuids.map_or(true, |uuids| uuids.contains(&mapping.uuid()));
         then {
             return Some(vec![(mapping.uuid().into(), DifType::Proguard)]);
         }
@@ -214,73 +523,58 @@ fn find_ids_for_proguard(
     None
 }
 
-fn find_ids_for_dsym(
-    dirent: &DirEntry,
-    remaining: &HashSet<DebugId>,
-) -> Option<Vec<(DebugId, DifType)>> {
+fn find_ids_for_dsym(dirent: &DirEntry, wanted: &Wanted) -> Option<Vec<(DebugId, DifType)>> {
     if_chain! {
         // we regularly match on .class files but the will never be
         // dsyms, so we can quickly skip them here
         if dirent.path().extension() != Some(OsStr::new("class"));
         if let Ok(dif) = DifFile::open_path(dirent.path(), Some(DifType::Dsym));
         then {
-            return Some(extract_remaining_ids(&dif.ids(), remaining, DifType::Dsym))
+            return Some(wanted.extract(&dif, DifType::Dsym))
         }
     }
     None
 }
 
-fn find_ids_for_elf(
-    dirent: &DirEntry,
-    remaining: &HashSet<DebugId>,
-) -> Option<Vec<(DebugId, DifType)>> {
+fn find_ids_for_elf(dirent: &DirEntry, wanted: &Wanted) -> Option<Vec<(DebugId, DifType)>> {
     if_chain! {
         if let Ok(dif) = DifFile::open_path(dirent.path(), Some(DifType::Elf));
         then {
-            return Some(extract_remaining_ids(&dif.ids(), remaining, DifType::Elf))
+            return Some(wanted.extract(&dif, DifType::Elf))
         }
     }
     None
 }
 
-fn find_ids_for_pe(
-    dirent: &DirEntry,
-    remaining: &HashSet<DebugId>,
-) -> Option<Vec<(DebugId, DifType)>> {
+fn find_ids_for_pe(dirent: &DirEntry, wanted: &Wanted) -> Option<Vec<(DebugId, DifType)>> {
     if_chain! {
         if dirent.path().extension() == Some(OsStr::new("exe")) ||
         dirent.path().extension() == Some(OsStr::new("dll"));
         if let Ok(dif) = DifFile::open_path(dirent.path(), Some(DifType::Pe));
         then {
-            return Some(extract_remaining_ids(&dif.ids(), remaining, DifType::Pe))
+            return Some(wanted.extract(&dif, DifType::Pe))
         }
     }
     None
 }
 
-fn find_ids_for_pdb(
-    dirent: &DirEntry,
-    remaining: &HashSet<DebugId>,
-) -> Option<Vec<(DebugId, DifType)>> {
+fn find_ids_for_pdb(dirent: &DirEntry, wanted: &Wanted) -> Option<Vec<(DebugId, DifType)>> {
     if_chain! {
         if dirent.path().extension() == Some(OsStr::new("pdb"));
         if let Ok(dif) = DifFile::open_path(dirent.path(), Some(DifType::Pdb));
         then {
-            return Some(extract_remaining_ids(&dif.ids(), remaining, DifType::Pdb))
+            return Some(wanted.extract(&dif, DifType::Pdb))
         }
     }
     None
 }
 
-fn find_ids_for_portablepdb(
-    dirent: &DirEntry,
-    remaining: &HashSet<DebugId>,
-) -> Option<Vec<(DebugId, DifType)>> {
+fn find_ids_for_portablepdb(dirent: &DirEntry, wanted: &Wanted) -> Option<Vec<(DebugId, DifType)>> {
     if_chain! {
         if dirent.path().extension() == Some(OsStr::new("pdb"));
         if let Ok(dif) = DifFile::open_path(dirent.path(), Some(DifType::PortablePdb));
         then {
-            return Some(extract_remaining_ids(&dif.ids(), remaining, DifType::PortablePdb))
+            return Some(wanted.extract(&dif, DifType::PortablePdb))
         }
     }
     None
@@ -288,49 +582,31 @@ fn find_ids_for_portablepdb(
 
 fn find_ids_for_sourcebundle(
     dirent: &DirEntry,
-    remaining: &HashSet<DebugId>,
+    wanted: &Wanted,
 ) -> Option<Vec<(DebugId, DifType)>> {
     if_chain! {
         if dirent.path().extension() == Some(OsStr::new("zip"));
         if let Ok(dif) = DifFile::open_path(dirent.path(), Some(DifType::SourceBundle));
         then {
-            return Some(extract_remaining_ids(&dif.ids(), remaining, DifType::SourceBundle))
+            return Some(wanted.extract(&dif, DifType::SourceBundle))
         }
     }
     None
 }
 
-fn find_ids_for_breakpad(
-    dirent: &DirEntry,
-    remaining: &HashSet<DebugId>,
-) -> Option<Vec<(DebugId, DifType)>> {
+fn find_ids_for_breakpad(dirent: &DirEntry, wanted: &Wanted) -> Option<Vec<(DebugId, DifType)>> {
     if_chain! {
         if dirent.path().extension() == Some(OsStr::new("sym"));
         if let Ok(dif) = DifFile::open_path(dirent.path(), Some(DifType::Breakpad));
         then {
-            return Some(extract_remaining_ids(&dif.ids(), remaining, DifType::Breakpad))
+            return Some(wanted.extract(&dif, DifType::Breakpad))
         }
     }
     None
 }
 
-fn extract_remaining_ids(
-    ids: &[DebugId],
-    remaining: &HashSet<DebugId>,
-    t: DifType,
-) -> Vec<(DebugId, DifType)> {
-    ids.iter()
-        .filter_map(|id| {
-            if remaining.contains(id) {
-                return Some((id.to_owned(), t));
-            }
-            None
-        })
-        .collect()
-}
-
 pub fn execute(matches: &ArgMatches) -> Result<()> {
-    let mut paths = HashSet::new();
+    let mut paths = vec![];
     let mut types = HashSet::new();
     let mut ids = HashSet::new();
 
@@ -353,7 +629,17 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         if let Some(path) = dirs::home_dir().map(|x| x.join("Library/Developer/Xcode/DerivedData"));
         if path.is_dir();
         then {
-            paths.insert(path);
+            paths.push((path, Location::DerivedData));
+        }
+    }
+
+    // files downloaded by `--symbol-server` and `--system`
+    if with_well_known && !is_stateless() {
+        let cache_dirs = [symbol_server::default_cache_dir(), debuginfod_cache_dir()];
+        for path in cache_dirs.into_iter().flatten() {
+            if path.is_dir() {
+                paths.push((path, Location::SymbolCache));
+            }
         }
     }
 
@@ -362,14 +648,14 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         if with_cwd;
         if let Ok(path) = env::current_dir();
         then {
-            paths.insert(path);
+            paths.push((path, Location::WorkingDirectory));
         }
     }
 
     // extra paths
     if let Some(p) = matches.get_many::<String>("paths") {
         for path in p {
-            paths.insert(PathBuf::from(path));
+            paths.push((PathBuf::from(path), Location::Path));
         }
     }
 
@@ -378,13 +664,71 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         for id in i {
             ids.insert(*id);
         }
-    } else {
+    }
+    let build_ids: Vec<String> = matches
+        .get_many::<String>("build_ids")
+        .map(|build_ids| build_ids.cloned().collect())
+        .unwrap_or_default();
+    ids.extend(build_ids.iter().filter_map(|id| build_id_to_debug_id(id)));
+    let name = matches.get_one::<Pattern>("name");
+    let arch = matches.get_one::<Arch>("arch").copied();
+    if ids.is_empty() && name.is_none() && arch.is_none() {
         return Ok(());
     }
 
-    if !find_ids(&paths, &types, &ids, matches.get_flag("json"))? {
+    if with_well_known {
+        paths.extend(
+            spotlight_paths(&ids, name)
+                .into_iter()
+                .map(|path| (path, Location::Spotlight)),
+        );
+    }
+    let mut seen = HashSet::new();
+    paths.retain(|(path, _)| seen.insert(path.clone()));
+
+    // debug files of distro-provided libraries
+    let system_files = if matches.get_flag("system") {
+        find_system_files(&build_ids)?
+    } else {
+        vec![]
+    };
+    let upload_paths: Vec<_> = system_files.iter().map(|m| m.path.clone()).collect();
+
+    let all_found = find_ids(
+        &paths,
+        &types,
+        &ids,
+        name,
+        arch,
+        system_files,
+        matches.get_flag("json"),
+    )?;
+
+    if matches.get_flag("upload") && !upload_paths.is_empty() {
+        let (org, project) = Config::current().get_org_and_project(matches)?;
+        let mut upload = DifUpload::new(&org, &project);
+        upload.search_paths(upload_paths).filter_ids(ids);
+        upload.upload()?;
+    }
+
+    if !all_found {
         return Err(QuietExit(1).into());
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_id_to_debug_id() {
+        assert_eq!(
+            build_id_to_debug_id("f1c3bcc0279865fe3058404b2831d9e64135386c"),
+            Some("c0bcc3f1-9827-fe65-3058-404b2831d9e6".parse().unwrap())
+        );
+        assert!(parse_build_id("f1c3bcc0279865fe").is_ok());
+        assert!(parse_build_id("not-a-build-id").is_err());
+    }
+}
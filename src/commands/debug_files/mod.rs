@@ -4,6 +4,8 @@ use clap::{ArgMatches, Command};
 pub mod bundle_jvm;
 pub mod bundle_sources;
 pub mod check;
+pub mod convert;
+pub mod download;
 pub mod find;
 pub mod print_sources;
 pub mod upload;
@@ -12,6 +14,8 @@ macro_rules! each_subcommand {
     ($mac:ident) => {
         $mac!(bundle_sources);
         $mac!(check);
+        $mac!(convert);
+        $mac!(download);
         $mac!(bundle_jvm);
         $mac!(find);
         $mac!(print_sources);
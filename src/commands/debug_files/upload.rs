@@ -1,12 +1,14 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 use std::str::{self, FromStr};
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 use anyhow::{bail, format_err, Result};
 use clap::{builder::PossibleValuesParser, Arg, ArgAction, ArgMatches, Command};
 use console::style;
 use itertools::Itertools;
-use log::info;
+use log::{info, warn};
 use symbolic::common::DebugId;
 use symbolic::debuginfo::FileFormat;
 
@@ -15,11 +17,19 @@ use crate::constants::DEFAULT_MAX_WAIT;
 use crate::utils::args::ArgExt;
 use crate::utils::dif::{DifType, ObjectDifFeatures};
 use crate::utils::dif_upload::{DifFormat, DifUpload};
+use crate::utils::exit_code::ExitCode;
+use crate::utils::file_search;
+use crate::utils::provenance::Provenance;
+use crate::utils::source_root::SourceRoot;
+use crate::utils::symbol_server::SymbolServer;
 use crate::utils::system::QuietExit;
 use crate::utils::xcode::InfoPlist;
 
 static DERIVED_DATA_FOLDER: &str = "Library/Developer/Xcode/DerivedData";
 
+/// How often `--watch` checks the search paths for changes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
 pub fn make_command(command: Command) -> Command {
     let types = DifType::all_names()
         .iter()
@@ -162,10 +172,15 @@ pub fn make_command(command: Command) -> Command {
         )
         .arg(
             Arg::new("include_sources")
+                .value_name("URL")
                 .long("include-sources")
-                .action(ArgAction::SetTrue)
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("")
                 .help(
-                    "Include sources from the local file system and upload them as source bundles.",
+                    "Include sources from the local file system and upload them as source bundles. \
+                    With a URL, the sources are read from the ZIP archive at the URL instead, \
+                    such as one created by `git archive --format=zip`.",
                 ),
         )
         .arg(
@@ -205,6 +220,59 @@ pub fn make_command(command: Command) -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Compute il2cpp line mappings and upload them along with sources."),
         )
+        .arg(
+            Arg::new("full_scan")
+                .long("full-scan")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Hash all files, instead of reusing checksums of files that \
+                    did not change since a previous upload.",
+                ),
+        )
+        .arg(
+            Arg::new("no_cache")
+                .long("no-cache")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Ignore the local cache of uploaded files, and hash and check all \
+                    files with the server again.",
+                ),
+        )
+        .max_concurrency_arg()
+        .provenance_arg()
+        .mut_arg("provenance", |arg| arg.requires("include_sources"))
+        .source_root_arg()
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("require_all")
+                .help(
+                    "Keep running after the upload, and upload the debug files in the \
+                    given paths again whenever they change.",
+                ),
+        )
+        .arg(
+            Arg::new("symbol_server")
+                .long("symbol-server")
+                .value_name("URL")
+                .help(
+                    "Fetch the PDBs of the PE binaries in the given paths from this symbol \
+                    server (symsrv layout) and upload them along with the binaries, e.g. \
+                    https://msdl.microsoft.com/download/symbols.",
+                ),
+        )
+        .arg(
+            Arg::new("symbol_cache")
+                .long("symbol-cache")
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf))
+                .requires("symbol_server")
+                .help(
+                    "The directory to cache PDBs fetched from the symbol server in. \
+                    Defaults to the user's cache directory.",
+                ),
+        )
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -232,6 +300,11 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         .max_wait(max_wait)
         .search_paths(matches.get_many::<String>("paths").unwrap_or_default())
         .allow_zips(!matches.get_flag("no_zips"))
+        .full_scan(matches.get_flag("full_scan") || matches.get_flag("no_cache"))
+        .no_cache(matches.get_flag("no_cache"))
+        .max_concurrency(matches.get_one::<u8>("max_concurrency").copied())
+        .provenance(Provenance::from_matches(matches))
+        .source_root(SourceRoot::from_matches(matches)?)
         .filter_ids(ids);
 
     // Restrict symbol types, if specified by the user
@@ -271,7 +344,10 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         sources: !matches.get_flag("no_sources"),
     });
 
-    upload.include_sources(matches.get_flag("include_sources"));
+    let include_sources = matches.get_one::<String>("include_sources");
+    upload
+        .include_sources(include_sources.is_some())
+        .sources_url(include_sources.filter(|url| !url.is_empty()).cloned());
     upload.il2cpp_mapping(matches.get_flag("il2cpp_mapping"));
 
     // Configure BCSymbolMap resolution, if possible
@@ -298,11 +374,26 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         None => InfoPlist::discover_from_env()?,
     };
 
+    // Fetch the PDBs of PE binaries, so that they are found like any other file
+    if let Some(url) = matches.get_one::<String>("symbol_server") {
+        let server = SymbolServer::new(url, matches.get_one::<PathBuf>("symbol_cache").cloned())?;
+        for pdb in server.fetch_pdbs(matches.get_many::<String>("paths").unwrap_or_default())? {
+            upload.search_path(pdb);
+        }
+    }
+
     if matches.get_flag("no_upload") {
         println!("{} skipping upload.", style(">").dim());
         return Ok(());
     }
 
+    if matches.get_flag("watch") {
+        return watch(
+            upload,
+            matches.get_many::<String>("paths").unwrap_or_default(),
+        );
+    }
+
     // Execute the upload
     let (uploaded, has_processing_errors) = upload.upload()?;
 
@@ -333,8 +424,54 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     if has_processing_errors {
         eprintln!();
         eprintln!("{}", style("Error: some symbols did not process correctly"));
-        return Err(QuietExit(1).into());
+        return Err(QuietExit(ExitCode::PartialUpload.code()).into());
     }
 
     Ok(())
 }
+
+/// Uploads the debug files, and uploads them again whenever a file in the
+/// search paths changes.  Runs until the process is interrupted.
+fn watch<I>(upload: DifUpload, paths: I) -> Result<()>
+where
+    I: IntoIterator,
+    I::Item: AsRef<Path>,
+{
+    let paths: Vec<_> = paths.into_iter().map(|p| p.as_ref().to_owned()).collect();
+    let mut uploaded = None;
+    let mut previous = scan_files(&paths);
+
+    loop {
+        // Only upload once the files stopped changing between two scans, so
+        // that we do not pick up files the build is still writing.
+        let current = scan_files(&paths);
+        if current == previous && uploaded.as_ref() != Some(&current) {
+            // Failed uploads are retried with the next scan.
+            match upload.clone().upload() {
+                Ok(_) => uploaded = Some(current.clone()),
+                Err(err) => warn!("Upload failed: {err:#}"),
+            }
+            println!("{} Watching for changes...", style(">").dim());
+        }
+
+        previous = current;
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// Returns the modification time and size of all files in the given paths,
+/// skipping files matched by `.gitignore` and `.ignore` files.
+fn scan_files(paths: &[PathBuf]) -> BTreeMap<PathBuf, (Option<SystemTime>, u64)> {
+    paths
+        .iter()
+        .flat_map(|path| file_search::walk_builder(path, true).build())
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some((
+                entry.into_path(),
+                (metadata.modified().ok(), metadata.len()),
+            ))
+        })
+        .collect()
+}
@@ -4,13 +4,15 @@ use clap::{Arg, ArgMatches, Command};
 
 use crate::api::Api;
 use crate::config::Config;
-use crate::utils::formatting::{HumanDuration, Table};
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{HumanDuration, OutputFormat, Table};
 
 pub fn make_command(command: Command) -> Command {
     command
         .about("List all deployments of a release.")
         // Backward compatibility with `releases deploys <VERSION>` commands.
         .arg(Arg::new("version").long("version").hide(true))
+        .format_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -32,15 +34,17 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             .add_row()
             .add(&deploy.env)
             .add(deploy.name())
-            .add(HumanDuration(
-                Utc::now().signed_duration_since(deploy.finished.unwrap()),
-            ));
+            .add_typed(
+                HumanDuration(Utc::now().signed_duration_since(deploy.finished.unwrap())),
+                deploy.finished,
+            );
     }
 
-    if table.is_empty() {
+    let format = OutputFormat::from_matches(matches);
+    if table.is_empty() && format == OutputFormat::Table {
         println!("No deploys found");
     } else {
-        table.print();
+        table.print_as(format)?;
     }
 
     Ok(())
@@ -1,14 +1,21 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use clap::{Arg, ArgMatches, Command};
 
 use crate::api::{Api, Deploy};
 use crate::config::Config;
 use crate::utils::args::get_timestamp;
+use crate::utils::notify::notify;
+use crate::utils::value_parsers::absolute_url_parser;
 
 pub fn make_command(command: Command) -> Command {
     command
         .about("Creates a new release deployment.")
+        .long_about(
+            "Creates a new release deployment.{n}{n}\
+            Pass --project once per deployed project to associate the deployment with only \
+            these projects. Otherwise, the deployment applies to all projects of the release.",
+        )
         // Backward compatibility with `releases deploys <VERSION>` commands.
         .arg(Arg::new("version").long("version").hide(true))
         .arg(
@@ -61,6 +68,16 @@ pub fn make_command(command: Command) -> Command {
                             This can be specified alternatively to `--started` and `--finished`.",
                 ),
         )
+        .arg(
+            Arg::new("notify_webhook")
+                .long("notify-webhook")
+                .value_name("URL")
+                .value_parser(absolute_url_parser)
+                .help(
+                    "Post a summary of the deploy to this webhook, such as a Slack incoming \
+                    webhook, once the deploy was created.",
+                ),
+        )
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -71,7 +88,9 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         env: matches.get_one::<String>("env").unwrap().into(),
         name: matches.get_one::<String>("name").map(|n| n.into()),
         url: matches.get_one::<String>("url").map(|u| u.into()),
-        projects: matches.get_many::<String>("project").map(|x| x.into_iter().map(|x| x.into()).collect()),
+        projects: matches
+            .get_many::<String>("project")
+            .map(|projects| projects.map(|project| project.into()).collect()),
         ..Default::default()
     };
 
@@ -93,8 +112,9 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let org = config.get_org(matches)?;
     let authenticated_api = api.authenticated()?;
 
-    let created_deploy = authenticated_api
-        .create_deploy(&org, &version, &deploy)?;
+    let Some(created_deploy) = authenticated_api.create_deploy(&org, &version, &deploy)? else {
+        return Ok(());
+    };
 
     println!(
         "Created new deploy {} for '{}'",
@@ -102,5 +122,10 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         created_deploy.env
     );
 
+    if let Some(webhook) = matches.get_one::<String>("notify_webhook") {
+        notify(webhook, &org, &version, Some(&created_deploy.env), None)
+            .context("Could not notify the webhook")?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,62 @@
+use std::iter;
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::utils::args::ArgExt;
+use crate::utils::deprecations::{find_deprecated_usages, Deprecation, DEPRECATIONS};
+use crate::utils::formatting::{OutputFormat, Table};
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("List deprecated commands and options.")
+        .long_about(
+            "List deprecated commands and options.{n}{n}\
+            By default, all deprecations are listed. Pass a command line after `--` to \
+            only list the deprecated usages in that invocation.{n}{n}\
+            Set SENTRY_DEPRECATION_WARNINGS=off to suppress deprecation warnings, or \
+            SENTRY_DEPRECATION_WARNINGS=json to print them as JSON.",
+        )
+        .format_arg()
+        .arg(
+            Arg::new("args")
+                .value_name("ARGS")
+                .num_args(1..)
+                .last(true)
+                .help("The command line to check for deprecated usages."),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let deprecations: Vec<&Deprecation> = match matches.get_many::<String>("args") {
+        Some(args) => {
+            let args = iter::once("sentry-cli").chain(args.map(String::as_str));
+            find_deprecated_usages(&super::add_commands(super::app()).try_get_matches_from(args)?)
+        }
+        None => DEPRECATIONS.iter().collect(),
+    };
+
+    let mut table = Table::new();
+    table
+        .title_row()
+        .add("Usage")
+        .add("Removal Version")
+        .add("Message");
+
+    for deprecation in deprecations {
+        table
+            .add_row()
+            .add(deprecation.usage)
+            .add(deprecation.removal_version)
+            .add(deprecation.message);
+    }
+
+    let format = OutputFormat::from_matches(matches);
+    if table.is_empty() && format == OutputFormat::Table {
+        println!("No deprecated usages found");
+    } else {
+        table.print_as(format)?;
+    }
+
+    Ok(())
+}
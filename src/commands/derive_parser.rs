@@ -1,6 +1,6 @@
 use crate::utils::auth_token::AuthToken;
 use crate::utils::value_parsers::{auth_token_parser, kv_parser};
-use clap::{command, ArgAction::SetTrue, Parser, Subcommand};
+use clap::{ArgAction::SetTrue, Parser, Subcommand};
 
 use super::send_metric::SendMetricArgs;
 
@@ -0,0 +1,14 @@
+use anyhow::Result;
+use clap::{ArgMatches, Command};
+
+pub fn make_command(command: Command) -> Command {
+    super::environments_arg(
+        command.about(
+            "Hide environments of a project, e.g. ones named after deleted feature branches.",
+        ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    super::set_hidden(matches, true)
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{OutputFormat, Table};
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("List the environments of a project.")
+        .arg(
+            Arg::new("visibility")
+                .long("visibility")
+                .value_name("VISIBILITY")
+                .value_parser(["all", "visible", "hidden"])
+                .default_value("all")
+                .help("Only list environments with the given visibility."),
+        )
+        .format_arg()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let org = config.get_org(matches)?;
+    let project = config.get_project(matches)?;
+    let visibility = matches.get_one::<String>("visibility").unwrap();
+    let environments = Api::current()
+        .authenticated()?
+        .list_project_environments(&org, &project, visibility)?;
+
+    let mut table = Table::new();
+    table.title_row().add("Name").add("Hidden");
+
+    for environment in &environments {
+        table.add_row().add(&environment.name).add_typed(
+            if environment.is_hidden { "yes" } else { "no" },
+            environment.is_hidden,
+        );
+    }
+
+    let format = OutputFormat::from_matches(matches);
+    if table.is_empty() && format == OutputFormat::Table {
+        println!("No environments found");
+    } else {
+        table.print_as(format)?;
+    }
+
+    Ok(())
+}
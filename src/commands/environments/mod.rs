@@ -0,0 +1,87 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::{Api, UpdatedEnvironment};
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+
+pub mod hide;
+pub mod list;
+pub mod show;
+
+macro_rules! each_subcommand {
+    ($mac:ident) => {
+        $mac!(hide);
+        $mac!(list);
+        $mac!(show);
+    };
+}
+
+pub fn make_command(mut command: Command) -> Command {
+    macro_rules! add_subcommand {
+        ($name:ident) => {{
+            command = command.subcommand(crate::commands::environments::$name::make_command(
+                Command::new(stringify!($name).replace('_', "-")),
+            ));
+        }};
+    }
+
+    command = command
+        .about("Manage environments of a project.")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .org_arg()
+        .project_arg(false);
+    each_subcommand!(add_subcommand);
+    command
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    macro_rules! execute_subcommand {
+        ($name:ident) => {{
+            if let Some(sub_matches) =
+                matches.subcommand_matches(&stringify!($name).replace('_', "-"))
+            {
+                return crate::commands::environments::$name::execute(&sub_matches);
+            }
+        }};
+    }
+    each_subcommand!(execute_subcommand);
+    unreachable!();
+}
+
+/// Adds the positional argument taking the names of the environments.
+fn environments_arg(command: Command) -> Command {
+    command.arg(
+        Arg::new("environments")
+            .value_name("ENVIRONMENT")
+            .required(true)
+            .num_args(1..)
+            .help("The names of the environments."),
+    )
+}
+
+/// Hides or shows the environments passed to `environments_arg`.
+fn set_hidden(matches: &ArgMatches, is_hidden: bool) -> Result<()> {
+    let config = Config::current();
+    let org = config.get_org(matches)?;
+    let project = config.get_project(matches)?;
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let changes = UpdatedEnvironment { is_hidden };
+
+    for environment in matches.get_many::<String>("environments").unwrap() {
+        let updated =
+            authenticated_api.update_project_environment(&org, &project, environment, &changes)?;
+        if updated.is_none() {
+            continue;
+        }
+        if is_hidden {
+            println!("Hid environment {environment}");
+        } else {
+            println!("Unhid environment {environment}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use anyhow::Result;
+use clap::{ArgMatches, Command};
+
+pub fn make_command(command: Command) -> Command {
+    super::environments_arg(command.about("Show previously hidden environments of a project."))
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    super::set_hidden(matches, false)
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
+
+use crate::api::{Api, IssueFilter};
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::ui::confirm_destructive;
+use crate::utils::value_parsers::search_query_parser;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Delete events of a project.")
+        .long_about(
+            "Delete events of a project.{n}{n}\
+            Sentry does not delete single events. Select issues with --issue or --query \
+            instead: all events of the selected issues are deleted along with them.",
+        )
+        .arg(
+            Arg::new("issue")
+                .long("issue")
+                .value_name("ISSUE_ID")
+                .action(ArgAction::Append)
+                .value_parser(clap::value_parser!(u64))
+                .help("Delete the issue with the given ID and all of its events."),
+        )
+        .arg(
+            Arg::new("query")
+                .long("query")
+                .value_name("QUERY")
+                .value_parser(search_query_parser)
+                .help(
+                    "Delete the issues matching the given search query, e.g. \
+                    `is:unresolved release:1.0`, and all of their events.",
+                ),
+        )
+        .group(
+            ArgGroup::new("target")
+                .args(["issue", "query"])
+                .required(true),
+        )
+        .yes_arg()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let (org, project) = config.get_org_and_project(matches)?;
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+
+    let (filter, action) = match matches.get_many::<u64>("issue") {
+        Some(ids) => {
+            let ids: Vec<_> = ids.copied().collect();
+            let action = format!("delete {} issues and all of their events", ids.len());
+            (IssueFilter::ExplicitIds(ids), action)
+        }
+        None => {
+            let query = matches.get_one::<String>("query").unwrap();
+            let action = format!("delete all issues matching `{query}` and their events");
+            (IssueFilter::Query(query.to_owned()), action)
+        }
+    };
+
+    if !confirm_destructive(matches, &action)? {
+        println!("Aborted!");
+        return Ok(());
+    }
+
+    authenticated_api.bulk_delete_issues(&org, &project, &filter)?;
+    println!("Deleted the selected issues and their events.");
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde_json::{Map, Value};
+
+use crate::api::{Api, ProcessedEvent};
+use crate::config::Config;
+
+/// The fields that can be exported, besides `tags.<KEY>` for single tags.
+const FIELDS: &[&str] = &[
+    "event_id",
+    "date_created",
+    "title",
+    "project",
+    "release",
+    "dist",
+    "user.id",
+    "user.username",
+    "user.email",
+    "user.ip_address",
+    "tags",
+];
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Export all events of a project for analysis in other tools.")
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(["csv", "ndjson"])
+                .default_value("csv")
+                .help("The export format."),
+        )
+        .arg(
+            Arg::new("fields")
+                .long("fields")
+                .value_name("FIELDS")
+                .value_delimiter(',')
+                .action(ArgAction::Append)
+                .value_parser(field_parser)
+                .default_values(["event_id", "date_created", "title", "release"])
+                .help(
+                    "Comma separated list of fields to export. Supported fields are \
+                    event_id, date_created, title, project, release, dist, user.id, \
+                    user.username, user.email, user.ip_address, tags and tags.<KEY> \
+                    for the value of a single tag.",
+                ),
+        )
+        .arg(
+            Arg::new("pages")
+                .long("pages")
+                .value_name("PAGES")
+                .value_parser(clap::value_parser!(usize))
+                .help(
+                    "Maximum number of pages to fetch (100 events/page). \
+                    Defaults to fetching all events.",
+                ),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .short('O')
+                .value_name("PATH")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write the export to the given file instead of stdout."),
+        )
+}
+
+fn field_parser(s: &str) -> Result<String> {
+    if FIELDS.contains(&s) || s.strip_prefix("tags.").is_some_and(|key| !key.is_empty()) {
+        Ok(s.to_owned())
+    } else {
+        Err(anyhow!("`{s}` is not a supported field"))
+    }
+}
+
+/// Returns the value of an export field of an event, or an empty string if
+/// the event does not have it.
+fn field_value(event: &ProcessedEvent, field: &str) -> String {
+    let user = event.user.as_ref();
+    let value = match field {
+        "event_id" => Some(event.event_id.to_string()),
+        "date_created" => Some(event.date_created.clone()),
+        "title" => Some(event.title.clone()),
+        "project" => event.project.map(|project| project.to_string()),
+        "release" => event.release.clone(),
+        "dist" => event.dist.clone(),
+        "user.id" => user.and_then(|user| user.id.clone()),
+        "user.username" => user.and_then(|user| user.username.clone()),
+        "user.email" => user.and_then(|user| user.email.clone()),
+        "user.ip_address" => user.and_then(|user| user.ip_address.clone()),
+        "tags" => event.tags.as_ref().map(|tags| {
+            tags.iter()
+                .map(|tag| tag.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }),
+        field => field.strip_prefix("tags.").and_then(|key| {
+            event
+                .tags
+                .as_ref()?
+                .iter()
+                .find(|tag| tag.key == key)
+                .map(|tag| tag.value.clone())
+        }),
+    };
+    value.unwrap_or_default()
+}
+
+/// Quotes a CSV field if needed, as described in RFC 4180.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let org = config.get_org(matches)?;
+    let project = config.get_project(matches)?;
+    let pages = matches
+        .get_one::<usize>("pages")
+        .copied()
+        .unwrap_or(usize::MAX);
+    let fields: Vec<_> = matches
+        .get_many::<String>("fields")
+        .unwrap()
+        .map(String::as_str)
+        .collect();
+
+    let events = Api::current()
+        .authenticated()?
+        .list_organization_project_events(&org, &project, pages)?;
+
+    let mut out: Box<dyn Write> = match matches.get_one::<PathBuf>("output") {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    if matches.get_one::<String>("format").unwrap() == "ndjson" {
+        for event in &events {
+            let object: Map<_, _> = fields
+                .iter()
+                .map(|field| (field.to_string(), Value::from(field_value(event, field))))
+                .collect();
+            writeln!(out, "{}", Value::Object(object))?;
+        }
+    } else {
+        writeln!(out, "{}", fields.join(","))?;
+        for event in &events {
+            let row: Vec<_> = fields
+                .iter()
+                .map(|field| csv_field(&field_value(event, field)))
+                .collect();
+            writeln!(out, "{}", row.join(","))?;
+        }
+    }
+    out.flush()?;
+
+    if matches.contains_id("output") {
+        eprintln!("Exported {} events", events.len());
+    }
+
+    Ok(())
+}
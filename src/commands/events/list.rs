@@ -3,7 +3,8 @@ use clap::{Arg, ArgAction, ArgMatches, Command};
 
 use crate::api::Api;
 use crate::config::Config;
-use crate::utils::formatting::Table;
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{OutputFormat, Table};
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -37,6 +38,7 @@ pub fn make_command(command: Command) -> Command {
                 .value_parser(clap::value_parser!(usize))
                 .help("Maximum number of pages to fetch (100 events/page)."),
         )
+        .format_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -75,31 +77,33 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
 
             if matches.get_flag("show_user") {
                 if let Some(user) = &event.user {
-                    row.add(user);
+                    row.add_typed(user, user);
                 } else {
-                    row.add("-");
+                    row.add_typed("-", ());
                 }
             }
 
             if matches.get_flag("show_tags") {
                 if let Some(tags) = &event.tags {
-                    row.add(
+                    row.add_typed(
                         tags.iter()
                             .map(|t| t.to_string())
                             .collect::<Vec<_>>()
                             .join("\n"),
+                        tags,
                     );
                 } else {
-                    row.add("-");
+                    row.add_typed("-", ());
                 }
             }
         }
     }
 
-    if table.is_empty() {
+    let format = OutputFormat::from_matches(matches);
+    if table.is_empty() && format == OutputFormat::Table {
         println!("No events found");
     } else {
-        table.print();
+        table.print_as(format)?;
     }
 
     Ok(())
@@ -3,10 +3,14 @@ use clap::{ArgMatches, Command};
 
 use crate::utils::args::ArgExt;
 
+pub mod delete;
+pub mod export;
 pub mod list;
 
 macro_rules! each_subcommand {
     ($mac:ident) => {
+        $mac!(delete);
+        $mac!(export);
         $mac!(list);
     };
 }
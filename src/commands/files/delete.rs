@@ -5,6 +5,8 @@ use clap::{Arg, ArgAction, ArgMatches, Command};
 
 use crate::api::Api;
 use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::ui::confirm_destructive;
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -25,6 +27,7 @@ pub fn make_command(command: Command) -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Delete all files."),
         )
+        .yes_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -36,6 +39,10 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let authenticated_api = api.authenticated()?;
 
     if matches.get_flag("all") {
+        if !confirm_destructive(matches, &format!("delete all files of release {release}"))? {
+            println!("Aborted!");
+            return Ok(());
+        }
         authenticated_api.delete_release_files(&org, project.as_deref(), &release)?;
         println!("All files deleted.");
         return Ok(());
@@ -0,0 +1,98 @@
+use std::io;
+
+use anyhow::{bail, Result};
+use clap::{Arg, ArgMatches, Command};
+use indicatif::HumanBytes;
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::{validate_distribution, ArgExt};
+use crate::utils::formatting::{OutputFormat, Table};
+
+use super::list::format_headers;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Print information about a release file.")
+        // Backward compatibility with `releases files <VERSION>` commands.
+        .arg(Arg::new("version").long("version").hide(true))
+        .arg(
+            Arg::new("name")
+                .value_name("NAME")
+                .required(true)
+                .help("The name of the file on the server."),
+        )
+        .arg(
+            Arg::new("dist")
+                .long("dist")
+                .short('d')
+                .value_name("DISTRIBUTION")
+                .value_parser(validate_distribution)
+                .help("Only consider the file of the given distribution."),
+        )
+        .format_arg()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let release = config.get_release_with_legacy_fallback(matches)?;
+    let org = config.get_org(matches)?;
+    let project = config.get_project(matches).ok();
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+
+    let name = matches.get_one::<String>("name").unwrap();
+    let dist = matches.get_one::<String>("dist");
+
+    let file = authenticated_api
+        .list_release_files(&org, project.as_deref(), &release)?
+        .into_iter()
+        .find(|file| &file.name == name && (dist.is_none() || file.dist.as_ref() == dist));
+
+    let Some(file) = file else {
+        bail!("File {name} not found in release {release}.");
+    };
+
+    let Some(artifact) = authenticated_api.get_release_file_metadata(
+        &org,
+        project.as_deref(),
+        &release,
+        &file.id,
+    )?
+    else {
+        bail!("File {name} not found in release {release}.");
+    };
+
+    match OutputFormat::from_matches(matches) {
+        OutputFormat::Table => (),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut io::stdout(), &artifact)?;
+            println!();
+            return Ok(());
+        }
+        OutputFormat::Ndjson => {
+            println!("{}", serde_json::to_string(&artifact)?);
+            return Ok(());
+        }
+        format @ OutputFormat::Template(_) => return format.print_items(&[artifact]),
+    }
+
+    let mut table = Table::new();
+    table
+        .title_row()
+        .add("Name")
+        .add("Distribution")
+        .add("Size")
+        .add("SHA1")
+        .add("Headers");
+    table
+        .add_row()
+        .add(&artifact.name)
+        .add(artifact.dist.as_deref().unwrap_or(""))
+        .add(HumanBytes(artifact.size))
+        .add(&artifact.sha1)
+        .add(format_headers(&artifact.headers));
+    table.print();
+
+    Ok(())
+}
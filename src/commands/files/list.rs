@@ -1,14 +1,30 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
 use clap::{Arg, ArgMatches, Command};
 use indicatif::HumanBytes;
 
-use crate::{api::Api, config::Config, utils::formatting::Table};
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{OutputFormat, Table};
 
 pub fn make_command(command: Command) -> Command {
     command
         .about("List all release files.")
         // Backward compatibility with `releases files <VERSION>` commands.
         .arg(Arg::new("version").long("version").hide(true))
+        .format_arg()
+}
+
+/// Formats artifact headers as sorted `key: value` pairs, one per line.
+pub(super) fn format_headers(headers: &HashMap<String, String>) -> String {
+    let mut headers = headers
+        .iter()
+        .map(|(key, value)| format!("{key}: {value}"))
+        .collect::<Vec<_>>();
+    headers.sort();
+    headers.join("\n")
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -18,18 +34,26 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let project = config.get_project(matches).ok();
     let api = Api::current();
 
+    let artifacts = api
+        .authenticated()?
+        .list_release_files(&org, project.as_deref(), &release)?;
+
+    let format = OutputFormat::from_matches(matches);
+    if format != OutputFormat::Table {
+        return format.print_items(&artifacts);
+    }
+
     let mut table = Table::new();
     table
         .title_row()
         .add("Name")
         .add("Distribution")
         .add("Source Map")
-        .add("Size");
+        .add("Size")
+        .add("SHA1")
+        .add("Headers");
 
-    for artifact in api
-        .authenticated()?
-        .list_release_files(&org, project.as_deref(), &release)?
-    {
+    for artifact in artifacts {
         let row = table.add_row();
         row.add(&artifact.name);
         if let Some(ref dist) = artifact.dist {
@@ -43,6 +67,8 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             row.add("");
         }
         row.add(HumanBytes(artifact.size));
+        row.add(&artifact.sha1);
+        row.add(format_headers(&artifact.headers));
     }
 
     table.print();
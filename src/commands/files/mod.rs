@@ -4,12 +4,14 @@ use clap::{ArgMatches, Command};
 use crate::utils::args::ArgExt;
 
 pub mod delete;
+pub mod info;
 pub mod list;
 pub mod upload;
 
 macro_rules! each_subcommand {
     ($mac:ident) => {
         $mac!(delete);
+        $mac!(info);
         $mac!(list);
         $mac!(upload);
     };
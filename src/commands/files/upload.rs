@@ -5,15 +5,17 @@ use std::io::Read;
 use std::path::Path;
 use std::time::Duration;
 
-use anyhow::{bail, format_err, Result};
+use anyhow::{bail, format_err, Context, Result};
 use clap::{Arg, ArgAction, ArgMatches, Command};
+use glob::Pattern;
 use log::warn;
 use symbolic::debuginfo::sourcebundle::SourceFileType;
 
 use crate::api::Api;
 use crate::config::Config;
 use crate::constants::DEFAULT_MAX_WAIT;
-use crate::utils::args::validate_distribution;
+use crate::utils::args::{validate_distribution, ArgExt};
+use crate::utils::content_type::detect_content_type;
 use crate::utils::file_search::ReleaseFileSearch;
 use crate::utils::file_upload::{
     initialize_legacy_release_upload, FileUpload, SourceFile, UploadContext,
@@ -77,6 +79,26 @@ pub fn make_command(command: Command) -> Command {
                 .action(ArgAction::Append)
                 .help("Store a header with this file."),
         )
+        .arg(
+            Arg::new("file_headers_for")
+                .long("file-header-for")
+                .value_names(["GLOB", "KEY:VALUE"])
+                .num_args(2)
+                .action(ArgAction::Append)
+                .help(
+                    "Store a header with all files whose path matches the given glob. \
+                    This takes precedence over headers set with --file-header.",
+                ),
+        )
+        .arg(
+            Arg::new("no_content_type")
+                .long("no-content-type")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Do not detect the Content-Type of files.  By default, it is derived \
+                    from the file extension or contents unless set with a header.",
+                ),
+        )
         .arg(
             Arg::new("url_prefix")
                 .short('u')
@@ -108,6 +130,12 @@ pub fn make_command(command: Command) -> Command {
                     ignore file, e.g. .gitignore.",
                 ),
         )
+        .arg(
+            Arg::new("no_vcs_ignore")
+                .long("no-vcs-ignore")
+                .action(ArgAction::SetTrue)
+                .help("Do not skip files ignored by .gitignore or .ignore files."),
+        )
         .arg(
             Arg::new("extensions")
                 .long("ext")
@@ -120,6 +148,7 @@ pub fn make_command(command: Command) -> Command {
                     extensions must be repeated. Specify once per extension.",
                 ),
         )
+        .max_concurrency_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -129,20 +158,42 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let project = config.get_project(matches).ok();
     let api = Api::current();
     let authenticated_api = api.authenticated()?;
-    let chunk_upload_options = authenticated_api.get_chunk_upload_options(&org)?;
+    let mut chunk_upload_options = authenticated_api.get_chunk_upload_options(&org)?;
+    if let (Some(options), Some(max_concurrency)) = (
+        chunk_upload_options.as_mut(),
+        matches.get_one::<u8>("max_concurrency"),
+    ) {
+        options.concurrency = *max_concurrency;
+    }
 
     let dist = matches.get_one::<String>("dist").map(String::as_str);
     let mut headers = BTreeMap::new();
     if let Some(header_list) = matches.get_many::<String>("file-headers") {
         for header in header_list {
-            if !header.contains(':') {
-                bail!("Invalid header. Needs to be in key:value format");
-            }
-            let (key, value) = header.split_once(':').unwrap();
-            headers.insert(key.trim().to_string(), value.trim().to_string());
+            let (key, value) = parse_header(header)?;
+            headers.insert(key, value);
         }
     };
 
+    let mut header_overrides = Vec::new();
+    if let Some(occurrences) = matches.get_occurrences::<String>("file_headers_for") {
+        for mut occurrence in occurrences {
+            let (Some(glob), Some(header)) = (occurrence.next(), occurrence.next()) else {
+                continue;
+            };
+            let pattern = Pattern::new(glob)
+                .with_context(|| format!("Invalid glob for --file-header-for: {glob}"))?;
+            let (key, value) = parse_header(header)?;
+            header_overrides.push((pattern, key, value));
+        }
+    }
+
+    let file_headers = FileHeaders {
+        headers,
+        overrides: header_overrides,
+        detect_content_type: !matches.get_flag("no_content_type"),
+    };
+
     let wait_for_secs = matches.get_one::<u64>("wait_for").copied();
     let wait = matches.get_flag("wait") || wait_for_secs.is_some();
     let max_wait = wait_for_secs.map_or(DEFAULT_MAX_WAIT, Duration::from_secs);
@@ -156,6 +207,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         wait,
         max_wait,
         dedupe: false,
+        provenance: None,
         chunk_upload_options: chunk_upload_options.as_ref(),
     };
 
@@ -180,6 +232,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             .ignores(ignores)
             .extensions(extensions)
             .decompress(matches.get_flag("decompress"))
+            .respect_vcs_ignore(!matches.get_flag("no_vcs_ignore"))
             .collect_files()?;
 
         let url_suffix = matches
@@ -207,7 +260,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                         path: source.path.clone(),
                         contents: source.contents.clone(),
                         ty: SourceFileType::Source,
-                        headers: headers.clone(),
+                        headers: file_headers.for_file(local_path, &source.contents),
                         messages: vec![],
                         already_uploaded: false,
                     },
@@ -240,6 +293,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             });
         }
 
+        let headers = file_headers.for_file(Path::new(name), &contents);
         if let Some(artifact) = authenticated_api
             .region_specific(context.org)
             .upload_release_file(
@@ -263,3 +317,45 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         Ok(())
     }
 }
+
+/// Parses a header in `key:value` format.
+fn parse_header(header: &str) -> Result<(String, String)> {
+    let Some((key, value)) = header.split_once(':') else {
+        bail!("Invalid header. Needs to be in key:value format");
+    };
+    Ok((key.trim().to_string(), value.trim().to_string()))
+}
+
+/// Headers to store with uploaded files.
+struct FileHeaders {
+    /// Headers stored with every file.
+    headers: BTreeMap<String, String>,
+    /// Headers stored with files matching a glob. Later matches take precedence.
+    overrides: Vec<(Pattern, String, String)>,
+    /// Whether a `Content-Type` header is added if none was given.
+    detect_content_type: bool,
+}
+
+impl FileHeaders {
+    /// Returns the headers for the file at the given path relative to the upload root.
+    fn for_file(&self, path: &Path, contents: &[u8]) -> BTreeMap<String, String> {
+        let mut headers = self.headers.clone();
+        for (pattern, key, value) in &self.overrides {
+            if pattern.matches_path(path) {
+                headers.insert(key.clone(), value.clone());
+            }
+        }
+
+        let has_content_type = headers
+            .keys()
+            .any(|key| key.eq_ignore_ascii_case("content-type"));
+        if self.detect_content_type && !has_content_type {
+            headers.insert(
+                "Content-Type".to_string(),
+                detect_content_type(path, contents),
+            );
+        }
+
+        headers
+    }
+}
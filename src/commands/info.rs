@@ -1,10 +1,10 @@
 use std::io;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use serde::Serialize;
 
-use crate::api::Api;
+use crate::api::{Api, AuthInfo};
 use crate::config::{Auth, Config};
 use crate::utils::logging::is_quiet_mode;
 use crate::utils::system::QuietExit;
@@ -30,6 +30,26 @@ pub struct Status {
     have_dsn: bool,
 }
 
+#[derive(Serialize)]
+struct AuthInfoJson<'a> {
+    #[serde(rename = "type")]
+    auth_type: Option<&'a str>,
+    /// Whether the auth token belongs to a user or an organization.
+    token_type: Option<&'a str>,
+    successful: bool,
+    user: Option<&'a str>,
+    scopes: &'a [String],
+}
+
+#[derive(Serialize)]
+struct InfoJson<'a> {
+    url: Option<&'a str>,
+    org: Option<&'a str>,
+    project: Option<&'a str>,
+    auth: AuthInfoJson<'a>,
+    missing_scopes: &'a [&'a str],
+}
+
 pub fn make_command(command: Command) -> Command {
     command
         .about("Print information about the configuration and verify authentication.")
@@ -53,6 +73,22 @@ pub fn make_command(command: Command) -> Command {
                     without the need for setting other defaults.",
                 ),
         )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .help("Print the information as JSON."),
+        )
+        .arg(
+            Arg::new("check_scopes")
+                .long("check-scope")
+                .value_name("SCOPE")
+                .action(ArgAction::Append)
+                .help(
+                    "Fail unless the auth token has the given scope. \
+                    Can be passed multiple times.",
+                ),
+        )
 }
 
 fn describe_auth(auth: Option<&Auth>) -> &str {
@@ -63,6 +99,48 @@ fn describe_auth(auth: Option<&Auth>) -> &str {
     }
 }
 
+fn describe_token_type(auth: Option<&Auth>) -> Option<&'static str> {
+    match auth {
+        Some(Auth::Token(token)) if token.payload().is_some() => Some("org"),
+        Some(Auth::Token(_)) => Some("user"),
+        _ => None,
+    }
+}
+
+/// Returns whether the granted scopes include the required scope.  Scopes
+/// with a higher access level, such as `project:write` for `project:read`,
+/// include those with a lower level.
+fn has_scope(scopes: &[String], required: &str) -> bool {
+    fn level(scope: &str) -> Option<(&str, usize)> {
+        const LEVELS: [&str; 3] = ["read", "write", "admin"];
+        let (resource, access) = scope.split_once(':')?;
+        Some((resource, LEVELS.iter().position(|level| *level == access)?))
+    }
+
+    scopes.iter().any(|scope| {
+        scope == required
+            || matches!(
+                (level(scope), level(required)),
+                (Some((resource, granted)), Some((required_resource, needed)))
+                    if resource == required_resource && granted >= needed
+            )
+    })
+}
+
+/// Returns the required scopes that the auth token lacks.
+fn missing_scopes<'a>(matches: &'a ArgMatches, info: Option<&AuthInfo>) -> Vec<&'a str> {
+    let scopes = info
+        .and_then(|info| info.auth.as_ref())
+        .map(|auth| auth.scopes.as_slice())
+        .unwrap_or_default();
+    matches
+        .get_many::<String>("check_scopes")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .filter(|scope| !has_scope(scopes, scope))
+        .collect()
+}
+
 fn get_config_status_json() -> Result<()> {
     let config = Config::current();
     let mut rv = Status::default();
@@ -72,12 +150,13 @@ fn get_config_status_json() -> Result<()> {
     rv.config.project = project;
     rv.config.url = Some(config.get_base_url()?.to_string());
 
-    rv.auth.auth_type = config.get_auth().map(|val| match val {
+    let auth = config.get_auth()?;
+    rv.auth.auth_type = auth.as_ref().map(|val| match val {
         Auth::Token(_) => "token".into(),
         Auth::Key(_) => "api_key".into(),
     });
     rv.auth.successful =
-        config.get_auth().is_some() && Api::current().authenticated()?.get_auth_info().is_ok();
+        auth.is_some() && Api::current().authenticated()?.get_auth_info().is_ok();
     rv.have_dsn = config.get_dsn().is_ok();
 
     serde_json::to_writer_pretty(&mut io::stdout(), &rv)?;
@@ -94,16 +173,51 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let (org, project) = config.get_org_and_project_defaults();
     let org = org.filter(|s| !s.is_empty());
     let project = project.filter(|s| !s.is_empty());
+    let auth = config.get_auth()?;
     let info_rv = Api::current()
         .authenticated()
         .and_then(|api| api.get_auth_info());
-    let mut errors = config.get_auth().is_none() || info_rv.is_err();
+    let missing_scopes = missing_scopes(matches, info_rv.as_ref().ok());
+    let mut errors = auth.is_none() || info_rv.is_err() || !missing_scopes.is_empty();
 
     // If `no-defaults` is present, only authentication should be verified.
     if !matches.get_flag("no_defaults") {
         errors = errors || project.is_none() || org.is_none();
     }
 
+    if matches.get_flag("json") {
+        let info = info_rv.as_ref().ok();
+        let json = InfoJson {
+            url: config.get_base_url().ok(),
+            org: org.as_deref(),
+            project: project.as_deref(),
+            auth: AuthInfoJson {
+                auth_type: auth.as_ref().map(|auth| match auth {
+                    Auth::Token(_) => "token",
+                    Auth::Key(_) => "api_key",
+                }),
+                token_type: describe_token_type(auth.as_ref()),
+                successful: auth.is_some() && info.is_some(),
+                user: info
+                    .and_then(|info| info.user.as_ref())
+                    .map(|user| user.email.as_str()),
+                scopes: info
+                    .and_then(|info| info.auth.as_ref())
+                    .map(|auth| auth.scopes.as_slice())
+                    .unwrap_or_default(),
+            },
+            missing_scopes: &missing_scopes,
+        };
+        serde_json::to_writer_pretty(&mut io::stdout(), &json)?;
+        println!();
+
+        return if errors {
+            Err(QuietExit(1).into())
+        } else {
+            Ok(())
+        };
+    }
+
     if is_quiet_mode() {
         return if errors {
             Err(QuietExit(1).into())
@@ -124,7 +238,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
 
     println!();
     println!("Authentication Info:");
-    println!("  Method: {}", describe_auth(config.get_auth()));
+    println!("  Method: {}", describe_auth(auth.as_ref()));
 
     let info = info_rv?;
 
@@ -139,5 +253,12 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         }
     }
 
+    if !missing_scopes.is_empty() {
+        bail!(
+            "The auth token is missing the required scopes: {}",
+            missing_scopes.join(", ")
+        );
+    }
+
     Ok(())
 }
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::Api;
+use crate::config::Config;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Assign the issue selected with --id to a user.")
+        .arg(
+            Arg::new("user")
+                .long("user")
+                .value_name("EMAIL")
+                .required(true)
+                .help("The email or username of the user to assign the issue to."),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let org = Config::current().get_org(matches)?;
+    let issue = super::get_single_issue(matches)?;
+    let user = matches.get_one::<String>("user").unwrap();
+
+    Api::current()
+        .authenticated()?
+        .assign_issue(&org, issue, user)?;
+    println!("Assigned issue {issue} to {user}.");
+    Ok(())
+}
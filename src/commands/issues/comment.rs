@@ -0,0 +1,30 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::Api;
+use crate::config::Config;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Add a comment to the issue selected with --id.")
+        .arg(
+            Arg::new("message")
+                .long("message")
+                .short('m')
+                .value_name("MESSAGE")
+                .required(true)
+                .help("The text of the comment."),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let org = Config::current().get_org(matches)?;
+    let issue = super::get_single_issue(matches)?;
+    let message = matches.get_one::<String>("message").unwrap();
+
+    Api::current()
+        .authenticated()?
+        .create_issue_comment(&org, issue, message)?;
+    println!("Added comment to issue {issue}.");
+    Ok(())
+}
@@ -3,7 +3,8 @@ use clap::{Arg, ArgMatches, Command};
 
 use crate::api::Api;
 use crate::config::Config;
-use crate::utils::formatting::Table;
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{OutputFormat, Table};
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -18,6 +19,7 @@ pub fn make_command(command: Command) -> Command {
         .arg(
             Arg::new("pages")
                 .long("pages")
+                .visible_alias("max-pages")
                 .value_name("PAGES")
                 .default_value("5")
                 .value_parser(clap::value_parser!(usize))
@@ -30,6 +32,7 @@ pub fn make_command(command: Command) -> Command {
                 .default_value("")
                 .help("Query to pass at the request. An example is \"is:unresolved\""),
         )
+        .format_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -37,7 +40,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let org = config.get_org(matches)?;
     let project = config.get_project(matches)?;
     let pages = *matches.get_one("pages").unwrap();
-    let query = matches.get_one::<String>("query").cloned();
+    let query = get_query(matches);
     let api = Api::current();
 
     let issues = api
@@ -71,11 +74,28 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         }
     }
 
-    if table.is_empty() {
+    let format = OutputFormat::from_matches(matches);
+    if table.is_empty() && format == OutputFormat::Table {
         println!("No issues found");
     } else {
-        table.print();
+        table.print_as(format)?;
     }
 
     Ok(())
 }
+
+/// Returns the search query, including the issue status passed with `--status`.
+fn get_query(matches: &ArgMatches) -> Option<String> {
+    let query = matches.get_one::<String>("query")?;
+    let Some(status) = matches.get_one::<String>("status") else {
+        return Some(query.to_owned());
+    };
+
+    // Muted issues are called ignored in the search syntax.
+    let status = if status == "muted" {
+        "ignored"
+    } else {
+        status.as_str()
+    };
+    Some(format!("{query} is:{status}").trim_start().to_owned())
+}
@@ -1,19 +1,27 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 
 use crate::utils::args::ArgExt;
 
+pub mod assign;
+pub mod comment;
 pub mod list;
 pub mod mute;
 pub mod resolve;
+pub mod summary;
 pub mod unresolve;
+pub mod watch;
 
 macro_rules! each_subcommand {
     ($mac:ident) => {
+        $mac!(assign);
+        $mac!(comment);
         $mac!(list);
         $mac!(mute);
         $mac!(resolve);
+        $mac!(summary);
         $mac!(unresolve);
+        $mac!(watch);
     };
 }
 
@@ -75,3 +83,25 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     each_subcommand!(execute_subcommand);
     unreachable!();
 }
+
+/// Returns the single issue selected with `--id`, for subcommands that act on
+/// one issue.
+fn get_single_issue(matches: &ArgMatches) -> Result<&str> {
+    if matches.get_flag("all") || matches.contains_id("status") {
+        bail!("This command cannot select issues with --all or --status. Use --id instead.");
+    }
+    let mut ids = matches.get_many::<String>("id").unwrap_or_default();
+    match (ids.next(), ids.next()) {
+        (Some(id), None) => Ok(id),
+        _ => bail!("Select exactly one issue with --id."),
+    }
+}
+
+/// Fails if any issues were selected, for subcommands that do not act on
+/// selected issues.
+fn ensure_no_issues_selected(matches: &ArgMatches) -> Result<()> {
+    if matches.get_flag("all") || matches.contains_id("status") || matches.contains_id("id") {
+        bail!("This command does not accept --all, --status or --id.");
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use serde::Serialize;
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{OutputFormat, Table};
+
+/// The number of new, regressed and unresolved issues of a release.
+#[derive(Serialize)]
+struct IssueSummary<'a> {
+    release: &'a str,
+    new: u64,
+    regressed: u64,
+    unresolved: u64,
+}
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Print the number of new, regressed and unresolved issues of a release.")
+        .arg(
+            Arg::new("release")
+                .long("release")
+                .short('r')
+                .value_name("RELEASE")
+                .required(true)
+                .help("The version of the release."),
+        )
+        .format_arg()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    super::ensure_no_issues_selected(matches)?;
+    let config = Config::current();
+    let org = config.get_org(matches)?;
+    let project = config.get_project(matches)?;
+    let release = matches.get_one::<String>("release").unwrap();
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+
+    let count = |query: &str| {
+        authenticated_api.count_project_issues(&org, &project, &format!("{query}\"{release}\""))
+    };
+    let summary = IssueSummary {
+        release,
+        new: count("first-release:")?,
+        regressed: count("is:regressed release:")?,
+        unresolved: count("is:unresolved release:")?,
+    };
+
+    let format = OutputFormat::from_matches(matches);
+    if format != OutputFormat::Table {
+        return format.print_items(&[summary]);
+    }
+
+    let mut table = Table::new();
+    table
+        .title_row()
+        .add("Release")
+        .add("New")
+        .add("Regressed")
+        .add("Unresolved");
+    table
+        .add_row()
+        .add(summary.release)
+        .add(summary.new)
+        .add(summary.regressed)
+        .add(summary.unresolved);
+    table.print();
+
+    Ok(())
+}
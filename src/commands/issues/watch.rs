@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+use std::thread;
+
+use anyhow::{bail, Result};
+use chrono::{Duration, Utc};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::api::{Api, Issue};
+use crate::config::Config;
+use crate::utils::value_parsers::age_parser;
+
+/// The number of pages of issues fetched on every poll. Issues are sorted by
+/// the time they were last seen, so new issues are on the first page.
+const WATCH_PAGES: usize = 1;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Print new issues matching a query as they come in.")
+        .long_about(
+            "Print new issues matching a query as they come in.{n}{n}\
+            The issues matching the query are fetched every --interval. Issues that already \
+            match when the command starts are not reported. This is useful to observe a \
+            release during a canary rollout, e.g. with --query 'first-release:1.0.0'.",
+        )
+        .arg(
+            Arg::new("query")
+                .long("query")
+                .value_name("QUERY")
+                .default_value("is:unresolved")
+                .help("The query that new issues must match."),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .value_name("DURATION")
+                .value_parser(age_parser)
+                .default_value("30s")
+                .help("How often to check for new issues."),
+        )
+        .arg(
+            Arg::new("window")
+                .long("window")
+                .value_name("DURATION")
+                .value_parser(age_parser)
+                .help(
+                    "Stop watching after the given duration, e.g. 1h. By default, the \
+                    command runs until it is interrupted.",
+                ),
+        )
+        .arg(
+            Arg::new("fail_on_new")
+                .long("fail-on-new")
+                .action(ArgAction::SetTrue)
+                .help("Exit with a non-zero exit code as soon as a new issue is found."),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let org = config.get_org(matches)?;
+    let project = config.get_project(matches)?;
+    let query = matches.get_one::<String>("query").unwrap();
+    let interval = matches.get_one::<Duration>("interval").unwrap().to_std()?;
+    let deadline = matches
+        .get_one::<Duration>("window")
+        .map(|window| Utc::now() + *window);
+    let fail_on_new = matches.get_flag("fail_on_new");
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+
+    let fetch_issues = || -> Result<Vec<Issue>> {
+        Ok(authenticated_api.list_organization_project_issues(
+            &org,
+            &project,
+            WATCH_PAGES,
+            Some(query.clone()),
+        )?)
+    };
+
+    let mut seen: HashSet<_> = fetch_issues()?.into_iter().map(|issue| issue.id).collect();
+    println!(
+        "Watching for new issues matching \"{query}\" ({} already matching)",
+        seen.len()
+    );
+
+    let mut new_issues = 0;
+    loop {
+        match deadline {
+            Some(deadline) => {
+                let remaining = deadline - Utc::now();
+                if remaining <= Duration::zero() {
+                    break;
+                }
+                thread::sleep(interval.min(remaining.to_std()?));
+            }
+            None => thread::sleep(interval),
+        }
+
+        for issue in fetch_issues()? {
+            if !seen.insert(issue.id.clone()) {
+                continue;
+            }
+            new_issues += 1;
+            println!(
+                "New issue {}: {} ({})",
+                issue.short_id, issue.title, issue.level
+            );
+        }
+
+        if fail_on_new && new_issues > 0 {
+            bail!("Found {new_issues} new issues matching \"{query}\"");
+        }
+    }
+
+    println!("Found {new_issues} new issues matching \"{query}\"");
+    Ok(())
+}
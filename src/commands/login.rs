@@ -6,6 +6,7 @@ use url::Url;
 
 use crate::api::Api;
 use crate::config::{Auth, Config};
+use crate::utils::auth_token::keyring::TokenStorage;
 use crate::utils::auth_token::AuthToken;
 use crate::utils::ui::{prompt, prompt_to_continue};
 
@@ -106,10 +107,13 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
 
     update_config(&config_to_update, token)?;
     println!();
-    println!(
-        "Stored token in {}",
-        config_to_update.get_filename().display()
-    );
+    match config_to_update.get_token_storage()? {
+        TokenStorage::Keyring => println!("Stored token in the system keyring"),
+        TokenStorage::File => println!(
+            "Stored token in {}",
+            config_to_update.get_filename().display()
+        ),
+    }
 
     Ok(())
 }
@@ -0,0 +1,41 @@
+use anyhow::Result;
+use clap::{ArgMatches, Command};
+
+pub mod send;
+
+macro_rules! each_subcommand {
+    ($mac:ident) => {
+        $mac!(send);
+    };
+}
+
+pub fn make_command(mut command: Command) -> Command {
+    macro_rules! add_subcommand {
+        ($name:ident) => {{
+            command = command.subcommand(crate::commands::logs::$name::make_command(Command::new(
+                stringify!($name).replace('_', "-"),
+            )));
+        }};
+    }
+
+    command = command
+        .about("Send logs to Sentry.")
+        .subcommand_required(true)
+        .arg_required_else_help(true);
+    each_subcommand!(add_subcommand);
+    command
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    macro_rules! execute_subcommand {
+        ($name:ident) => {{
+            if let Some(sub_matches) =
+                matches.subcommand_matches(&stringify!($name).replace('_', "-"))
+            {
+                return crate::commands::logs::$name::execute(&sub_matches);
+            }
+        }};
+    }
+    each_subcommand!(execute_subcommand);
+    unreachable!();
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use clap::{Arg, ArgMatches, Command};
+use log::{info, warn};
+
+use crate::api::envelopes_api::EnvelopesApi;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::log_parsing::{
+    detect_log_format, make_envelope, parse_line, LogEntry, LogFormat, LogLevel,
+};
+use crate::utils::trace::Trace;
+
+/// The maximum size of a serialized log envelope.  Batches exceeding it are
+/// split up.
+const MAX_ENVELOPE_SIZE: usize = 1024 * 1024;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Send a log entry, or all entries of a log file, to Sentry.")
+        .long_about(
+            "Send a log entry, or all entries of a log file, to Sentry.{n}{n}\
+            Log entries are sent to the project of the configured DSN. Lines of a file \
+            given with --file are parsed according to --format and sent in batches.",
+        )
+        .arg(
+            Arg::new("message")
+                .value_name("MESSAGE")
+                .required_unless_present("file")
+                .conflicts_with("file")
+                .help("The message of the log entry."),
+        )
+        .arg(
+            Arg::new("level")
+                .long("level")
+                .value_name("LEVEL")
+                .default_value("info")
+                .value_parser(["trace", "debug", "info", "warn", "error", "fatal"])
+                .conflicts_with("file")
+                .help("The level of the log entry."),
+        )
+        .arg(
+            Arg::new("file")
+                .long("file")
+                .value_name("PATH")
+                .help("Send all lines of the given log file."),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .default_value("auto")
+                .value_parser(["auto", "nginx", "apache", "plain", "json"])
+                .requires("file")
+                .help(
+                    "The format of the log file. `auto` detects files of JSON lines and \
+                    the format of every other line. Lines that do not match the format are \
+                    sent as plain lines.",
+                ),
+        )
+        .arg(
+            Arg::new("batch_size")
+                .long("batch-size")
+                .value_name("COUNT")
+                .default_value("100")
+                .value_parser(clap::value_parser!(u16).range(1..))
+                .help("The maximum number of log entries sent in one envelope."),
+        )
+        .trace_id_arg()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let mut entries: Vec<LogEntry> = match matches.get_one::<String>("file") {
+        Some(path) => {
+            let contents =
+                fs::read(path).with_context(|| format!("Could not read log file {path}"))?;
+            let lines: Vec<_> = String::from_utf8_lossy(&contents)
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_owned)
+                .collect();
+            let format = match matches.get_one::<String>("format").unwrap().as_str() {
+                "auto" => detect_log_format(&lines),
+                format => format.parse::<LogFormat>()?,
+            };
+            lines
+                .iter()
+                .map(|line| {
+                    let mut entry = parse_line(&format, line);
+                    entry
+                        .attributes
+                        .insert("log.file.path".into(), path.clone().into());
+                    entry
+                })
+                .collect()
+        }
+        None => vec![LogEntry {
+            timestamp: Utc::now(),
+            level: LogLevel::from_name(matches.get_one::<String>("level").unwrap())
+                .unwrap_or(LogLevel::Info),
+            body: matches.get_one::<String>("message").unwrap().clone(),
+            attributes: BTreeMap::new(),
+        }],
+    };
+
+    if let Some(scrubber) = Config::current().get_scrubber()? {
+        entries
+            .iter_mut()
+            .for_each(|entry| scrubber.scrub_log_entry(entry));
+    }
+
+    let api = EnvelopesApi::try_new()?;
+    let trace_id = Trace::from_matches(matches).trace_id.to_string();
+    let batch_size = usize::from(*matches.get_one::<u16>("batch_size").unwrap());
+    let mut sent = 0;
+    let mut failed = 0;
+    for batch in entries.chunks(batch_size) {
+        for batch in split_oversized(batch, &trace_id)? {
+            match api.send_envelope(make_envelope(batch, &trace_id)?) {
+                Ok(_) => {
+                    info!("Sent {} log entries", batch.len());
+                    sent += batch.len();
+                }
+                Err(err) => {
+                    warn!("Could not send {} log entries: {err}", batch.len());
+                    failed += batch.len();
+                }
+            }
+        }
+    }
+
+    println!("Sent {sent} log entries, {failed} failed.");
+    if failed > 0 {
+        bail!("Could not send {failed} log entries.");
+    }
+    Ok(())
+}
+
+/// Splits a batch into batches whose envelopes do not exceed the maximum
+/// envelope size.  Single entries exceeding the size are sent as they are.
+fn split_oversized<'a>(batch: &'a [LogEntry], trace_id: &str) -> Result<Vec<&'a [LogEntry]>> {
+    let mut body = vec![];
+    make_envelope(batch, trace_id)?.to_writer(&mut body)?;
+    if body.len() <= MAX_ENVELOPE_SIZE || batch.len() == 1 {
+        return Ok(vec![batch]);
+    }
+
+    let (left, right) = batch.split_at(batch.len() / 2);
+    let mut batches = split_oversized(left, trace_id)?;
+    batches.extend(split_oversized(right, trace_id)?);
+    Ok(batches)
+}
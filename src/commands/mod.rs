@@ -1,20 +1,25 @@
 //! This module implements the root command of the CLI tool.
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use clap::parser::ValueSource;
 use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
 use clap_complete::{generate, Generator, Shell};
-use log::{debug, info, set_logger, set_max_level, LevelFilter};
-use std::borrow::Cow;
-use std::io;
+use log::{debug, info, set_logger, set_max_level, warn, LevelFilter};
+use std::env;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 use std::process;
-use std::{env, iter};
 
-use crate::api::Api;
-use crate::config::{Auth, Config};
-use crate::constants::{ARCH, PLATFORM, VERSION};
-use crate::utils::auth_token::{redact_token_from_string, AuthToken};
-use crate::utils::logging::set_quiet_mode;
+use crate::api::{Api, RateLimitBehavior};
+use crate::config::{Auth, Config, PROFILE_ENV, STATELESS_ENV};
+use crate::constants::{ARCH, DEFAULT_MAX_CONNECTIONS, PLATFORM, VERSION};
+use crate::utils::auth_token::{redact_args, AuthToken};
+use crate::utils::deprecations::warn_deprecated_usages;
+use crate::utils::exit_code::ExitCode;
 use crate::utils::logging::Logger;
+use crate::utils::logging::{set_experimental_warnings, set_quiet_mode};
+use crate::utils::support_bundle;
 use crate::utils::system::{init_backtrace, load_dotenv, print_error, QuietExit};
 use crate::utils::update::run_sentrycli_update_nagger;
 use crate::utils::value_parsers::auth_token_parser;
@@ -24,23 +29,32 @@ mod derive_parser;
 macro_rules! each_subcommand {
     ($mac:ident) => {
         $mac!(bash_hook);
+        $mac!(canary);
         $mac!(debug_files);
+        $mac!(deprecations);
         $mac!(deploys);
+        $mac!(environments);
         $mac!(events);
         $mac!(files);
         $mac!(info);
         $mac!(issues);
         $mac!(login);
+        $mac!(logs);
         $mac!(monitors);
+        $mac!(notify);
         $mac!(organizations);
         $mac!(projects);
+        $mac!(queue);
         $mac!(react_native);
         $mac!(releases);
         $mac!(repos);
+        $mac!(run);
         $mac!(send_event);
         $mac!(send_envelope);
         $mac!(send_metric);
         $mac!(sourcemaps);
+        $mac!(tail);
+        $mac!(tokens);
         #[cfg(not(feature = "managed"))]
         $mac!(uninstall);
         #[cfg(not(feature = "managed"))]
@@ -48,6 +62,8 @@ macro_rules! each_subcommand {
         $mac!(upload_dif);
         $mac!(upload_dsym);
         $mac!(upload_proguard);
+        #[cfg(unix)]
+        $mac!(worker);
     };
 }
 
@@ -70,6 +86,7 @@ to learn more about them.";
 const UPDATE_NAGGER_CMDS: &[&str] = &[
     "debug-files",
     "deploys",
+    "environments",
     "events",
     "files",
     "info",
@@ -85,6 +102,30 @@ const UPDATE_NAGGER_CMDS: &[&str] = &[
 /// The long auth token argument (--auth-token).
 const AUTH_TOKEN_ARG: &str = "auth-token";
 
+/// Root arguments that configure the connection to Sentry.  The config and
+/// connection pool are shared by all commands of a process, so commands run
+/// by the worker or from a manifest cannot pass these.
+const STARTUP_ARGS: &[&str] = &[
+    "url",
+    "headers",
+    "auth_token",
+    "api_key",
+    "log_level",
+    "support_bundle",
+    "read_only",
+    "rate_limit_behavior",
+    "max_connections",
+    "client_cert",
+    "client_key",
+    "cacert",
+    "profile",
+    "legacy_exit_codes",
+    "no_state",
+    "auth_token_fd",
+    "auth_token_file",
+    "dump_response",
+];
+
 fn print_completions<G: Generator>(gen: G, cmd: &mut Command) {
     generate(gen, cmd, cmd.get_name().to_string(), &mut io::stdout());
 }
@@ -118,6 +159,18 @@ fn configure_args(config: &mut Config, matches: &ArgMatches) -> Result<()> {
         config.set_auth(Auth::Token(auth_token.to_owned()))?;
     }
 
+    if let Some(&fd) = matches.get_one::<i32>("auth_token_fd") {
+        let token = read_auth_token(open_fd(fd)?, &format!("file descriptor {fd}"))?;
+        config.set_auth(Auth::Token(token))?;
+    }
+
+    if let Some(path) = matches.get_one::<PathBuf>("auth_token_file") {
+        let file = File::open(path)
+            .with_context(|| format!("Could not open auth token file {}", path.display()))?;
+        let token = read_auth_token(file, &path.display().to_string())?;
+        config.set_auth(Auth::Token(token))?;
+    }
+
     if let Some(url) = matches.get_one::<String>("url") {
         config.set_base_url(url);
     }
@@ -127,9 +180,80 @@ fn configure_args(config: &mut Config, matches: &ArgMatches) -> Result<()> {
         config.set_headers(headers);
     }
 
+    if matches.get_flag("read_only") {
+        config.set_read_only(true);
+    }
+
+    if matches.get_flag("legacy_exit_codes") {
+        config.set_legacy_exit_codes(true);
+    }
+
+    if let Some(max_connections) = matches.get_one::<u32>("max_connections") {
+        config.set_max_connections(*max_connections);
+    }
+
+    if let Some(client_cert) = matches.get_one::<String>("client_cert") {
+        config.set_client_cert(client_cert);
+    }
+
+    if let Some(client_key) = matches.get_one::<String>("client_key") {
+        config.set_client_key(client_key);
+    }
+
+    if let Some(cacert) = matches.get_one::<PathBuf>("cacert") {
+        config.set_ca_file(cacert.clone());
+    }
+
+    if let Some(path) = matches.get_one::<PathBuf>("dump_response") {
+        config.set_dump_response(path.clone());
+    }
+
+    if let Some(behavior) = matches.get_one::<String>("rate_limit_behavior") {
+        config.set_rate_limit_behavior(
+            RateLimitBehavior::from_name(behavior).expect("value parser only allows known values"),
+        );
+    }
+
     Ok(())
 }
 
+/// Reads an auth token that was passed through a file or file descriptor.
+fn read_auth_token(mut reader: impl Read, source: &str) -> Result<AuthToken> {
+    let mut token = String::new();
+    reader
+        .read_to_string(&mut token)
+        .with_context(|| format!("Could not read auth token from {source}"))?;
+    let token = token.trim();
+    if token.is_empty() {
+        bail!("No auth token in {source}");
+    }
+    Ok(auth_token_parser(token)?)
+}
+
+#[cfg(unix)]
+fn open_fd(fd: i32) -> Result<File> {
+    use std::os::unix::io::FromRawFd;
+
+    // Taking ownership of a closed descriptor would be undefined behavior.
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        bail!("File descriptor {fd} is not open");
+    }
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+#[cfg(not(unix))]
+fn open_fd(_fd: i32) -> Result<File> {
+    bail!("--auth-token-fd is only supported on Unix");
+}
+
+/// Returns the first startup argument that was passed on the command line.
+fn find_startup_arg(matches: &ArgMatches) -> Option<&'static str> {
+    STARTUP_ARGS
+        .iter()
+        .copied()
+        .find(|id| matches.value_source(id) == Some(ValueSource::CommandLine))
+}
+
 pub fn get_log_level(matches: &ArgMatches) -> Result<Option<LevelFilter>> {
     match matches.get_one::<String>("log_level") {
         Some(log_level) => match log_level.parse() {
@@ -202,6 +326,143 @@ fn app() -> Command {
               .hide(true)
               .help("Always return 0 exit code."),
         )
+        .arg(
+            Arg::new("no_experimental_warning")
+                .long("no-experimental-warning")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Do not print warnings about experimental features."),
+        )
+        .arg(
+            Arg::new("support_bundle")
+                .value_name("PATH")
+                .long("support-bundle")
+                .help(
+                    "Write sanitized logs, configuration sources, HTTP timings and the outcome \
+                    of the command to the given zip file, to attach to a bug report.",
+                ),
+        )
+        .arg(
+            Arg::new("read_only")
+                .long("read-only")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Do not send requests that would modify data on Sentry. \
+                    Print what would be sent instead.",
+                ),
+        )
+        .arg(
+            Arg::new("rate_limit_behavior")
+                .long("rate-limit-behavior")
+                .value_name("BEHAVIOR")
+                .value_parser(["wait", "fail"])
+                .help(
+                    "What to do when Sentry rate limits a request: wait until the limit \
+                    is lifted and retry, or fail.",
+                ),
+        )
+        .arg(
+            Arg::new("max_connections")
+                .value_name("NUM")
+                .long("max-connections")
+                .value_parser(value_parser!(u32).range(1..))
+                .help(format!(
+                    "The maximum number of connections to Sentry kept open at the same time. \
+                    Can also be set with SENTRY_HTTP_MAX_CONNECTIONS. \
+                    [default: {DEFAULT_MAX_CONNECTIONS}]"
+                )),
+        )
+        .arg(
+            Arg::new("client_cert")
+                .value_name("PATH")
+                .long("client-cert")
+                .help(
+                    "Authenticate with the given client certificate (PEM) to Sentry servers \
+                    behind a mutual TLS gateway.",
+                ),
+        )
+        .arg(
+            Arg::new("client_key")
+                .value_name("PATH")
+                .long("client-key")
+                .help(
+                    "The private key of the client certificate, unless the certificate \
+                    file includes it.",
+                ),
+        )
+        .arg(
+            Arg::new("cacert")
+                .value_name("PATH")
+                .long("cacert")
+                .value_parser(value_parser!(PathBuf))
+                .help(
+                    "Verify the certificate of the Sentry server with the CA bundle at the \
+                    given path. Can also be set with SENTRY_CAFILE.",
+                ),
+        )
+        .arg(
+            Arg::new("profile")
+                .value_name("NAME")
+                .long("profile")
+                .help(
+                    "Use the settings of the given profile from the config file. \
+                    Can also be set with SENTRY_PROFILE.",
+                ),
+        )
+        .arg(
+            Arg::new("legacy_exit_codes")
+                .long("legacy-exit-codes")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Exit with 1 on all failures instead of a code that classifies the \
+                    failure. Can also be set with SENTRY_LEGACY_EXIT_CODES.",
+                ),
+        )
+        .arg(
+            Arg::new("no_state")
+                .long("no-state")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Read the configuration only from the environment and the command line, \
+                    and do not write config files, caches or update checks to disk. \
+                    Can also be set with SENTRY_CLI_STATELESS.",
+                ),
+        )
+        .arg(
+            Arg::new("auth_token_fd")
+                .value_name("FD")
+                .long("auth-token-fd")
+                .global(true)
+                // The standard streams would be closed when the token file is dropped.
+                .value_parser(value_parser!(i32).range(3..))
+                .conflicts_with_all(["auth_token", "auth_token_file"])
+                .help(
+                    "Read the Sentry auth token from the given file descriptor, so that it \
+                    does not show up in the process list or the environment.",
+                ),
+        )
+        .arg(
+            Arg::new("auth_token_file")
+                .value_name("PATH")
+                .long("auth-token-file")
+                .global(true)
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with("auth_token")
+                .help(
+                    "Read the Sentry auth token from the given file, so that it does not \
+                    show up in the process list or the environment.",
+                ),
+        )
+        .arg(
+            Arg::new("dump_response")
+                .value_name("PATH")
+                .long("dump-response")
+                .value_parser(value_parser!(PathBuf))
+                .help(
+                    "Write API responses that cannot be parsed to the given file, to attach \
+                    them to bug reports. Auth tokens and other secrets are removed.",
+                ),
+        )
         .subcommand(
             Command::new("completions")
             .about("Generate completions for the specified shell.")
@@ -227,6 +488,8 @@ fn add_commands(mut app: Command) -> Command {
 }
 
 fn run_command(matches: &ArgMatches) -> Result<()> {
+    Config::current().check_command_policy(&command_path(matches))?;
+
     macro_rules! execute_subcommand {
         ($name:ident) => {{
             let cmd = stringify!($name).replace("_", "-");
@@ -244,6 +507,16 @@ fn run_command(matches: &ArgMatches) -> Result<()> {
     unreachable!();
 }
 
+/// Returns the names of the invoked subcommands, e.g. `releases delete`.
+fn command_path(mut matches: &ArgMatches) -> String {
+    let mut names = vec![];
+    while let Some((name, sub_matches)) = matches.subcommand() {
+        names.push(name);
+        matches = sub_matches;
+    }
+    names.join(" ")
+}
+
 pub fn execute() -> Result<()> {
     // special case for the xcode integration for react native.  For more
     // information see commands/react_native_xcode.rs
@@ -258,9 +531,18 @@ pub fn execute() -> Result<()> {
     if let Some(log_level) = log_level {
         set_max_level(log_level);
     }
+    // The profile is needed to load the config, so it is passed on like
+    // the environment variable.
+    if let Some(profile) = matches.get_one::<String>("profile") {
+        env::set_var(PROFILE_ENV, profile);
+    }
+    if matches.get_flag("no_state") {
+        env::set_var(STATELESS_ENV, "1");
+    }
     let mut config = Config::from_cli_config()?;
     configure_args(&mut config, &matches)?;
     set_quiet_mode(matches.get_flag("quiet"));
+    set_experimental_warnings(!config.get_no_experimental_warning(&matches));
 
     if let Some(log_level) = log_level {
         config.set_log_level(log_level);
@@ -268,6 +550,9 @@ pub fn execute() -> Result<()> {
 
     // bind the config to the process and fetch an immutable reference to it
     config.bind_to_process();
+    if let Some(path) = matches.get_one::<String>("support_bundle") {
+        support_bundle::start(Path::new(path));
+    }
     if Config::current().get_filename().exists() {
         info!(
             "Loaded config from {}",
@@ -282,26 +567,9 @@ pub fn execute() -> Result<()> {
 
     info!(
         "sentry-cli was invoked with the following command line: {}",
-        env::args()
-            // Check whether the previous argument is "--auth-token"
-            .zip(
-                iter::once(false)
-                    .chain(env::args().map(|arg| arg == format!("--{AUTH_TOKEN_ARG}")))
-            )
-            .map(|(a, is_auth_token_arg)| {
-                let redact_replacement = "[REDACTED]";
-
-                // Redact anything that comes after --auth-token
-                let redacted = if is_auth_token_arg {
-                    Cow::Borrowed(redact_replacement)
-                } else if a.starts_with(&format!("--{AUTH_TOKEN_ARG}=")) {
-                    Cow::Owned(format!("--{AUTH_TOKEN_ARG}={redact_replacement}"))
-                } else {
-                    redact_token_from_string(&a, redact_replacement)
-                };
-
-                format!("\"{redacted}\"")
-            })
+        redact_args(env::args(), "[REDACTED]")
+            .iter()
+            .map(|arg| format!("\"{arg}\""))
             .collect::<Vec<_>>()
             .join(" ")
     );
@@ -316,7 +584,14 @@ pub fn execute() -> Result<()> {
         }
     }
 
-    match run_command(&matches) {
+    warn_deprecated_usages(&matches);
+
+    let result = run_command(&matches);
+    if let Err(err) = support_bundle::finish(&result) {
+        warn!("Could not write support bundle: {err}");
+    }
+
+    match result {
         Ok(()) => Ok(()),
         Err(e) => {
             if Config::current().get_allow_failure(&matches) {
@@ -359,7 +634,7 @@ pub fn main() -> ! {
                 code
             } else {
                 print_error(&err);
-                1
+                ExitCode::from_error(&err).code()
             };
 
             // if the user hit an error, it might be time to run the update
@@ -0,0 +1,52 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::{Api, NewMonitor};
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+
+pub fn make_command(command: Command) -> Command {
+    super::schedule_args(
+        command
+            .about("Create a new cron monitor.")
+            .org_arg()
+            .project_arg(false)
+            .arg(
+                Arg::new("name")
+                    .value_name("NAME")
+                    .required(true)
+                    .help("The name of the new monitor."),
+            )
+            .arg(
+                Arg::new("slug")
+                    .long("slug")
+                    .value_name("SLUG")
+                    .help("The slug of the new monitor. Defaults to a slug derived from the name."),
+            ),
+    )
+    .mut_arg("schedule", |arg| arg.required(true))
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let (org, project) = config.get_org_and_project(matches)?;
+
+    let Some(monitor) = api.authenticated()?.create_monitor(
+        &org,
+        &NewMonitor {
+            name: matches.get_one::<String>("name").unwrap().to_owned(),
+            slug: matches.get_one::<String>("slug").cloned(),
+            project,
+            ty: "cron_job",
+            config: super::settings_from_matches(matches).unwrap_or_default(),
+        },
+    )?
+    else {
+        return Ok(());
+    };
+
+    println!("Created monitor {} (ID {})", monitor.slug, monitor.id);
+
+    Ok(())
+}
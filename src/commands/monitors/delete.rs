@@ -0,0 +1,40 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::ui::confirm_destructive;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Delete a cron monitor.")
+        .org_arg()
+        .arg(
+            Arg::new("monitor")
+                .value_name("MONITOR")
+                .required(true)
+                .help("The slug of the monitor to delete."),
+        )
+        .yes_arg()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let org = config.get_org(matches)?;
+    let monitor = matches.get_one::<String>("monitor").unwrap();
+
+    if !confirm_destructive(matches, &format!("delete monitor {monitor}"))? {
+        println!("Aborted!");
+        return Ok(());
+    }
+
+    if api.authenticated()?.delete_monitor(&org, monitor)? {
+        println!("Deleted monitor {monitor}!");
+    } else {
+        println!("Did nothing. Monitor {monitor} does not exist.");
+    }
+
+    Ok(())
+}
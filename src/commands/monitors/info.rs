@@ -0,0 +1,61 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{OutputFormat, Table};
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Print information about a cron monitor.")
+        .org_arg()
+        .format_arg()
+        .arg(
+            Arg::new("monitor")
+                .value_name("MONITOR")
+                .required(true)
+                .help("The slug of the monitor."),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let org = config.get_org(matches)?;
+    let monitor = api
+        .authenticated()?
+        .get_monitor(&org, matches.get_one::<String>("monitor").unwrap())?;
+
+    let config = monitor.config.unwrap_or_default();
+    let schedule = match &config.schedule {
+        Some(serde_json::Value::String(schedule)) => schedule.clone(),
+        Some(schedule) => schedule.to_string(),
+        None => "-".to_owned(),
+    };
+    let minutes = |value: Option<u64>| value.map_or("-".to_owned(), |value| format!("{value}m"));
+
+    let mut table = Table::new();
+    table
+        .title_row()
+        .add("Slug")
+        .add("Name")
+        .add("Status")
+        .add("Schedule")
+        .add("Check-in Margin")
+        .add("Max Runtime")
+        .add("Timezone");
+    table
+        .add_row()
+        .add(&monitor.slug)
+        .add(&monitor.name)
+        .add(&monitor.status)
+        .add_typed(schedule, &config.schedule)
+        .add_typed(minutes(config.checkin_margin), config.checkin_margin)
+        .add_typed(minutes(config.max_runtime), config.max_runtime)
+        .add_typed(config.timezone.as_deref().unwrap_or("-"), &config.timezone);
+
+    table.print_as(OutputFormat::from_matches(matches))?;
+
+    Ok(())
+}
@@ -4,12 +4,13 @@ use clap::{ArgMatches, Command};
 use crate::api::Api;
 use crate::config::Config;
 use crate::utils::args::ArgExt;
-use crate::utils::formatting::Table;
+use crate::utils::formatting::{OutputFormat, Table};
 
 pub fn make_command(command: Command) -> Command {
     command
         .about("List all monitors for an organization.")
         .org_arg()
+        .format_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -17,7 +18,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let api = Api::current();
     let org = config.get_org(matches)?;
     let mut monitors = api.authenticated()?.list_organization_monitors(&org)?;
-    monitors.sort_by_key(|p| (p.name.clone()));
+    monitors.sort_by_key(|p| p.name.clone());
 
     let mut table = Table::new();
     table
@@ -36,7 +37,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             .add(&monitor.status);
     }
 
-    table.print();
+    table.print_as(OutputFormat::from_matches(matches))?;
 
     Ok(())
 }
@@ -1,13 +1,25 @@
 use anyhow::Result;
-use clap::{ArgMatches, Command};
+use chrono_tz::Tz;
+use clap::{Arg, ArgMatches, Command};
 
+use crate::api::MonitorSettings;
+use crate::utils::value_parsers::{crontab_parser, timezone_parser};
+
+pub mod create;
+pub mod delete;
+pub mod info;
 pub mod list;
 pub mod run;
+pub mod update;
 
 macro_rules! each_subcommand {
     ($mac:ident) => {
+        $mac!(create);
+        $mac!(delete);
+        $mac!(info);
         $mac!(list);
         $mac!(run);
+        $mac!(update);
     };
 }
 
@@ -22,6 +34,7 @@ pub fn make_command(mut command: Command) -> Command {
 
     command = command
         .about("Manage cron monitors on Sentry.")
+        .visible_alias("crons")
         .subcommand_required(true)
         .arg_required_else_help(true);
 
@@ -42,3 +55,72 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     each_subcommand!(execute_subcommand);
     unreachable!();
 }
+
+/// Adds the arguments configuring the schedule of a monitor.
+fn schedule_args(command: Command) -> Command {
+    command
+        .arg(
+            Arg::new("schedule")
+                .long("schedule")
+                .short('s')
+                .value_name("SCHEDULE")
+                .value_parser(crontab_parser)
+                .help(
+                    "The schedule of the monitor in crontab format. Enclose the schedule in \
+                    quotes to ensure your command line environment parses it correctly.",
+                ),
+        )
+        .arg(
+            Arg::new("checkin_margin")
+                .long("check-in-margin")
+                .value_name("MINUTES")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help(
+                    "The allowed margin of minutes after the expected check-in time that the \
+                    monitor will not be considered missed for.",
+                ),
+        )
+        .arg(
+            Arg::new("max_runtime")
+                .long("max-runtime")
+                .value_name("MINUTES")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help(
+                    "The allowed duration in minutes that the monitor may be in progress for \
+                    before being considered failed due to timeout.",
+                ),
+        )
+        .arg(
+            Arg::new("timezone")
+                .long("timezone")
+                .value_name("TIMEZONE")
+                .value_parser(timezone_parser)
+                .help(
+                    "A tz database string (e.g. \"Europe/Vienna\") representing the monitor's \
+                    execution schedule's timezone.",
+                ),
+        )
+}
+
+/// Returns the monitor settings passed to `schedule_args`, or `None` if no
+/// setting was given.
+fn settings_from_matches(matches: &ArgMatches) -> Option<MonitorSettings> {
+    let schedule = matches.get_one::<String>("schedule");
+    let settings = MonitorSettings {
+        schedule_type: schedule.map(|_| "crontab".to_owned()),
+        schedule: schedule.map(|schedule| schedule.as_str().into()),
+        checkin_margin: matches.get_one("checkin_margin").copied(),
+        max_runtime: matches.get_one("max_runtime").copied(),
+        timezone: matches.get_one("timezone").map(Tz::to_string),
+    };
+
+    if settings.schedule.is_none()
+        && settings.checkin_margin.is_none()
+        && settings.max_runtime.is_none()
+        && settings.timezone.is_none()
+    {
+        None
+    } else {
+        Some(settings)
+    }
+}
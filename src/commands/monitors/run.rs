@@ -3,15 +3,21 @@ use std::process;
 use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use clap::{Arg, ArgMatches, Command};
 use console::style;
 
-use sentry::protocol::{MonitorCheckIn, MonitorCheckInStatus, MonitorConfig, MonitorSchedule};
+use sentry::protocol::{
+    MonitorCheckIn, MonitorCheckInStatus, MonitorConfig, MonitorSchedule, SpanId,
+};
+use sentry::Envelope;
+use serde_json::json;
 
 use crate::api::envelopes_api::EnvelopesApi;
+use crate::utils::args::ArgExt;
 use crate::utils::system::QuietExit;
-use crate::utils::value_parsers::auth_token_parser;
+use crate::utils::trace::{Trace, SENTRY_TRACE_ENV};
+use crate::utils::value_parsers::{auth_token_parser, timezone_parser};
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -64,12 +70,7 @@ pub fn make_command(command: Command) -> Command {
         .arg(
             Arg::new("timezone")
                 .long("timezone")
-                .value_parser(|value: &str| {
-                    value.parse::<Tz>().map_err(|err| {
-                        err + "\n\tSee here for a list of valid timezone strings: \
-                            https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List"
-                    })
-                })
+                .value_parser(timezone_parser)
                 .requires("schedule")
                 .help(
                     "A tz database string (e.g. \"Europe/Vienna\") representing the monitor's \
@@ -103,13 +104,19 @@ pub fn make_command(command: Command) -> Command {
                 .value_parser(auth_token_parser)
                 .hide(true),
         )
+        .trace_id_arg()
 }
 
-fn run_program(args: Vec<&String>, monitor_slug: &str) -> (bool, Option<i32>, Duration) {
+fn run_program(
+    args: Vec<&String>,
+    monitor_slug: &str,
+    trace_header: &str,
+) -> (bool, Option<i32>, Duration) {
     let started = Instant::now();
     let mut p = process::Command::new(args[0]);
     p.args(&args[1..]);
     p.env("SENTRY_MONITOR_SLUG", monitor_slug);
+    p.env(SENTRY_TRACE_ENV, trace_header);
 
     let (success, code) = match p.status() {
         Ok(status) => (status.success(), status.code()),
@@ -128,11 +135,23 @@ fn run_program(args: Vec<&String>, monitor_slug: &str) -> (bool, Option<i32>, Du
     (success, code, elapsed)
 }
 
+/// Creates an envelope with the check-in, linked to the given trace.
+fn checkin_envelope(check_in: &MonitorCheckIn, trace: &Trace) -> Result<Envelope> {
+    let mut item = serde_json::to_value(check_in)?;
+    item["contexts"] = json!({ "trace": { "trace_id": trace.trace_id } });
+
+    let mut body = b"{}\n{\"type\":\"check_in\"}\n".to_vec();
+    serde_json::to_writer(&mut body, &item)?;
+    body.push(b'\n');
+    Envelope::from_bytes_raw(body).map_err(|err| format_err!("Invalid check-in envelope: {err}"))
+}
+
 fn execute_checkin(
     args: Vec<&String>,
     monitor_slug: &str,
     environment: &str,
     monitor_config: Option<MonitorConfig>,
+    trace: &Trace,
 ) -> Result<(bool, Option<i32>)> {
     let check_in_id = Uuid::new_v4();
 
@@ -147,12 +166,14 @@ fn execute_checkin(
 
     let envelopes_api = EnvelopesApi::try_new()?;
 
-    if let Err(e) = envelopes_api.send_envelope(open_checkin) {
+    if let Err(e) = envelopes_api.send_envelope(checkin_envelope(&open_checkin, trace)?) {
         log::error!("Failed to send in-progress check-in envelope: {e}");
         log::info!("Continuing to run program...");
     }
 
-    let (success, code, elapsed) = run_program(args, monitor_slug);
+    // The wrapped program continues the trace from the span of this run.
+    let trace_header = trace.header(SpanId::default());
+    let (success, code, elapsed) = run_program(args, monitor_slug, &trace_header);
 
     let status = if success {
         MonitorCheckInStatus::Ok
@@ -171,7 +192,7 @@ fn execute_checkin(
         monitor_config: None,
     };
 
-    if let Err(e) = envelopes_api.send_envelope(close_checkin) {
+    if let Err(e) = envelopes_api.send_envelope(checkin_envelope(&close_checkin, trace)?) {
         log::error!("Failed to send final check-in envelope: {e}");
         log::info!("Continuing to exit with program's exit code...");
     }
@@ -199,8 +220,9 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let monitor_slug = matches.get_one::<String>("monitor_slug").unwrap();
     let environment = matches.get_one::<String>("environment").unwrap();
     let monitor_config = parse_monitor_config_args(matches)?;
+    let trace = Trace::from_matches(matches);
 
-    let (success, code) = execute_checkin(args, monitor_slug, environment, monitor_config)?;
+    let (success, code) = execute_checkin(args, monitor_slug, environment, monitor_config, &trace)?;
 
     if !success {
         return Err(QuietExit(code.unwrap_or(1)).into());
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use clap::{Arg, ArgGroup, ArgMatches, Command};
+
+use crate::api::{Api, UpdatedMonitor};
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+
+pub fn make_command(command: Command) -> Command {
+    super::schedule_args(
+        command
+            .about("Update a cron monitor.")
+            .org_arg()
+            .arg(
+                Arg::new("monitor")
+                    .value_name("MONITOR")
+                    .required(true)
+                    .help("The slug of the monitor to update."),
+            )
+            .arg(
+                Arg::new("name")
+                    .long("name")
+                    .value_name("NAME")
+                    .help("Set the name of the monitor."),
+            )
+            .arg(
+                Arg::new("slug")
+                    .long("slug")
+                    .value_name("SLUG")
+                    .help("Set the slug of the monitor."),
+            ),
+    )
+    .group(
+        ArgGroup::new("changes")
+            .args([
+                "name",
+                "slug",
+                "schedule",
+                "checkin_margin",
+                "max_runtime",
+                "timezone",
+            ])
+            .multiple(true)
+            .required(true),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let org = config.get_org(matches)?;
+
+    let Some(monitor) = api.authenticated()?.update_monitor(
+        &org,
+        matches.get_one::<String>("monitor").unwrap(),
+        &UpdatedMonitor {
+            name: matches.get_one::<String>("name").cloned(),
+            slug: matches.get_one::<String>("slug").cloned(),
+            config: super::settings_from_matches(matches),
+        },
+    )?
+    else {
+        return Ok(());
+    };
+
+    println!("Updated monitor {}", monitor.slug);
+
+    Ok(())
+}
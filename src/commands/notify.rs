@@ -0,0 +1,59 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::notify::notify;
+use crate::utils::value_parsers::absolute_url_parser;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Post a summary of a release to a webhook.")
+        .long_about(
+            "Post a summary of a release to a webhook.{n}{n}\
+            The summary contains the release, the environment, the range of commits \
+            associated with the release and a link to the release on Sentry. It is sent \
+            as JSON with a `text` field, which is shown by Slack incoming webhooks and \
+            compatible tools, and a field for every detail.",
+        )
+        .org_arg()
+        .release_arg()
+        .arg(
+            Arg::new("webhook")
+                .long("webhook")
+                .value_name("URL")
+                .required(true)
+                .value_parser(absolute_url_parser)
+                .help("The URL of the webhook."),
+        )
+        .arg(
+            Arg::new("env")
+                .long("env")
+                .short('e')
+                .value_name("ENV")
+                .help("The environment the release was deployed to."),
+        )
+        .arg(
+            Arg::new("message")
+                .long("message")
+                .short('m')
+                .value_name("TEXT")
+                .help("An additional message to include in the summary."),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let org = config.get_org(matches)?;
+    let release = config.get_release(matches)?;
+
+    notify(
+        matches.get_one::<String>("webhook").unwrap(),
+        &org,
+        &release,
+        matches.get_one::<String>("env").map(String::as_str),
+        matches.get_one::<String>("message").map(String::as_str),
+    )?;
+    println!("Notified the webhook about release {release}.");
+    Ok(())
+}
@@ -3,13 +3,16 @@ use clap::{ArgMatches, Command};
 use log::debug;
 
 use crate::api::{Api, Organization};
-use crate::utils::formatting::Table;
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{OutputFormat, Table};
 
 pub fn make_command(command: Command) -> Command {
-    command.about("List all organizations available to the authenticated token.")
+    command
+        .about("List all organizations available to the authenticated token.")
+        .format_arg()
 }
 
-pub fn execute(_matches: &ArgMatches) -> Result<()> {
+pub fn execute(matches: &ArgMatches) -> Result<()> {
     let api = Api::current();
     let authenticated_api = api.authenticated()?;
 
@@ -47,12 +50,15 @@ pub fn execute(_matches: &ArgMatches) -> Result<()> {
             .add(&organization.id)
             .add(&organization.name)
             .add(&organization.slug)
-            .add(organization.date_created.format("%F"))
-            .add(organization.is_early_adopter)
-            .add(organization.require_2fa);
+            .add_typed(
+                organization.date_created.format("%F"),
+                organization.date_created,
+            )
+            .add_typed(organization.is_early_adopter, organization.is_early_adopter)
+            .add_typed(organization.require_2fa, organization.require_2fa);
     }
 
-    table.print();
+    table.print_as(OutputFormat::from_matches(matches))?;
 
     Ok(())
 }
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::{Api, NewProject};
+use crate::config::Config;
+use crate::utils::args::validate_project;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Create a new project.")
+        .arg(
+            Arg::new("name")
+                .value_name("NAME")
+                .required(true)
+                .help("The name of the new project."),
+        )
+        .arg(
+            Arg::new("team")
+                .long("team")
+                .short('t')
+                .value_name("TEAM")
+                .required(true)
+                .help("The slug of the team that owns the new project."),
+        )
+        .arg(
+            Arg::new("slug")
+                .long("slug")
+                .value_name("SLUG")
+                .value_parser(validate_project)
+                .help("The slug of the new project. Defaults to a slug derived from the name."),
+        )
+        .arg(
+            Arg::new("platform")
+                .long("platform")
+                .value_name("PLATFORM")
+                .help("The platform of the new project, e.g. javascript or python."),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let org = config.get_org(matches)?;
+    let team = matches.get_one::<String>("team").unwrap();
+
+    let Some(project) = api.authenticated()?.create_project(
+        &org,
+        team,
+        &NewProject {
+            name: matches.get_one::<String>("name").unwrap().to_owned(),
+            slug: matches.get_one::<String>("slug").cloned(),
+            platform: matches.get_one::<String>("platform").cloned(),
+        },
+    )?
+    else {
+        return Ok(());
+    };
+
+    println!("Created project {} (ID {})", project.slug, project.id);
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::{validate_project, ArgExt};
+use crate::utils::ui::confirm_destructive;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Delete a project.")
+        .arg(
+            Arg::new("project")
+                .value_name("PROJECT")
+                .value_parser(validate_project)
+                .required(true)
+                .help("The slug of the project to delete."),
+        )
+        .yes_arg()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let org = config.get_org(matches)?;
+    let project = matches.get_one::<String>("project").unwrap();
+
+    if !confirm_destructive(matches, &format!("delete project {project}"))? {
+        println!("Aborted!");
+        return Ok(());
+    }
+
+    if api.authenticated()?.delete_project(&org, project)? {
+        println!("Deleted project {project}!");
+    } else {
+        println!("Did nothing. Project {project} does not exist.");
+    }
+
+    Ok(())
+}
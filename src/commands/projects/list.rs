@@ -3,10 +3,13 @@ use clap::{ArgMatches, Command};
 
 use crate::api::Api;
 use crate::config::Config;
-use crate::utils::formatting::Table;
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{OutputFormat, Table};
 
 pub fn make_command(command: Command) -> Command {
-    command.about("List all projects for an organization.")
+    command
+        .about("List all projects for an organization.")
+        .format_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -30,20 +33,17 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         .add("Name");
 
     for project in &projects {
-        let team_name = &project
-            .team
-            .as_ref()
-            .map_or(String::from("-"), |t| t.name.clone());
+        let team_name = project.team.as_ref().map(|t| &t.name);
 
         table
             .add_row()
             .add(&project.id)
             .add(&project.slug)
-            .add(team_name)
+            .add_typed(team_name.map_or("-", String::as_str), team_name)
             .add(&project.name);
     }
 
-    table.print();
+    table.print_as(OutputFormat::from_matches(matches))?;
 
     Ok(())
 }
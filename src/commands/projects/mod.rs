@@ -3,11 +3,17 @@ use clap::{ArgMatches, Command};
 
 use crate::utils::args::ArgExt;
 
+pub mod create;
+pub mod delete;
 pub mod list;
+pub mod update;
 
 macro_rules! each_subcommand {
     ($mac:ident) => {
+        $mac!(create);
+        $mac!(delete);
         $mac!(list);
+        $mac!(update);
     };
 }
 
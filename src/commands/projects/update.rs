@@ -0,0 +1,66 @@
+use anyhow::Result;
+use clap::{Arg, ArgGroup, ArgMatches, Command};
+
+use crate::api::{Api, UpdatedProject};
+use crate::config::Config;
+use crate::utils::args::validate_project;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Update a project.")
+        .arg(
+            Arg::new("project")
+                .value_name("PROJECT")
+                .value_parser(validate_project)
+                .required(true)
+                .help("The slug of the project to update."),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .value_name("NAME")
+                .help("Set the name of the project."),
+        )
+        .arg(
+            Arg::new("slug")
+                .long("slug")
+                .value_name("SLUG")
+                .value_parser(validate_project)
+                .help("Set the slug of the project."),
+        )
+        .arg(
+            Arg::new("platform")
+                .long("platform")
+                .value_name("PLATFORM")
+                .help("Set the platform of the project."),
+        )
+        .group(
+            ArgGroup::new("changes")
+                .args(["name", "slug", "platform"])
+                .multiple(true)
+                .required(true),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let org = config.get_org(matches)?;
+
+    let Some(project) = api.authenticated()?.update_project(
+        &org,
+        matches.get_one::<String>("project").unwrap(),
+        &UpdatedProject {
+            name: matches.get_one::<String>("name").cloned(),
+            slug: matches.get_one::<String>("slug").cloned(),
+            platform: matches.get_one::<String>("platform").cloned(),
+        },
+    )?
+    else {
+        return Ok(());
+    };
+
+    println!("Updated project {}", project.slug);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{ArgMatches, Command};
+use log::warn;
+
+use crate::api::envelopes_api::{EnvelopesApi, QUEUED_ENVELOPE_EXT};
+use crate::utils::args::ArgExt;
+use crate::utils::lockfile::FileLock;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Send all envelopes stored in a queue directory.")
+        .long_about(
+            "Send all envelopes stored in a queue directory.{n}{n}\
+             Envelopes are sent in the order they were queued, and removed from the \
+             directory once Sentry has received them. If Sentry still cannot be reached, \
+             the remaining envelopes are kept for the next flush.",
+        )
+        .queue_dir_arg()
+        .mut_arg("queue_dir", |arg| {
+            arg.required(true)
+                .help("The directory that contains the queued envelopes.")
+        })
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let queue_dir = matches.get_one::<PathBuf>("queue_dir").unwrap();
+
+    // Parallel flushes of the same queue would send envelopes twice.
+    let _lock = FileLock::acquire(queue_dir)?;
+    let mut paths = fs::read_dir(queue_dir)
+        .with_context(|| format!("Could not read queue directory {}", queue_dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|path| {
+        path.extension()
+            .is_some_and(|ext| ext == QUEUED_ENVELOPE_EXT)
+    });
+    paths.sort();
+
+    let total = paths.len();
+    let api = EnvelopesApi::try_new()?;
+    let mut flushed = 0;
+    for path in &paths {
+        match api.send_queued_envelope(path) {
+            Ok(true) => {}
+            Ok(false) => bail!(
+                "Could not reach Sentry, {} queued envelopes remain.",
+                total - flushed
+            ),
+            Err(err) => {
+                warn!("Keeping queued envelope {}: {err:#}", path.display());
+                continue;
+            }
+        }
+
+        fs::remove_file(path)
+            .with_context(|| format!("Could not remove queued envelope {}", path.display()))?;
+        println!("Envelope {} dispatched", path.display());
+        flushed += 1;
+    }
+
+    println!("Flushed {flushed} of {total} queued envelopes.");
+    Ok(())
+}
@@ -1,22 +1,26 @@
+use std::collections::BTreeMap;
 use std::env;
 use std::ffi::OsStr;
 use std::fs;
+use std::str;
 use std::time::Duration;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use console::style;
 use if_chain::if_chain;
-use log::info;
+use log::{info, warn};
+use sourcemap::SourceMap;
+use symbolic::debuginfo::js::discover_sourcemap_embedded_debug_id;
 
-use crate::api::Api;
+use crate::api::{Api, ChunkUploadCapability};
 use crate::config::Config;
 use crate::constants::DEFAULT_MAX_WAIT;
 use crate::utils::appcenter::{get_appcenter_package, get_react_native_appcenter_release};
 use crate::utils::args::{validate_distribution, ArgExt};
 use crate::utils::file_search::ReleaseFileSearch;
 use crate::utils::file_upload::UploadContext;
-use crate::utils::sourcemaps::SourceMapProcessor;
+use crate::utils::sourcemaps::{compose_hermes_sourcemap, is_hermes_bytecode, SourceMapProcessor};
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -156,8 +160,8 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         style(">").dim()
     );
 
-    let mut processor = SourceMapProcessor::new();
-
+    let mut files = BTreeMap::new();
+    let mut hermes_sourcemaps = vec![];
     for path in matches.get_many::<String>("paths").unwrap() {
         let entries = fs::read_dir(path)
             .map_err(|e| anyhow!(e).context(format!("Failed processing path: \"{}\"", &path)))?;
@@ -170,13 +174,57 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                    ext == OsStr::new("map") ||
                    ext == OsStr::new("bundle");
                 then {
-                    let url = format!("~/{filename}");
-                    processor.add(&url, ReleaseFileSearch::collect_file(entry.path())?)?;
+                    // Source maps emitted by the Hermes compiler that were not
+                    // composed with the packager source map yet.
+                    if let Some(bundle) = filename.strip_suffix(".hbc.map") {
+                        hermes_sourcemaps.push((format!("{bundle}.map"), entry.path()));
+                    } else {
+                        files.insert(
+                            filename.to_owned(),
+                            ReleaseFileSearch::collect_file(entry.path())?,
+                        );
+                    }
                 }
             }
         }
     }
 
+    for (sourcemap_name, hermes_path) in hermes_sourcemaps {
+        let Some(file) = files.get_mut(&sourcemap_name) else {
+            continue;
+        };
+        println!(
+            "{} Composing {} with Hermes source map",
+            style(">").dim(),
+            sourcemap_name
+        );
+        let mut sourcemap = SourceMap::from_slice(&file.contents)
+            .with_context(|| format!("Invalid source map {sourcemap_name}"))?;
+        let hermes_sourcemap = SourceMap::from_slice(&fs::read(&hermes_path)?)
+            .with_context(|| format!("Invalid source map {}", hermes_path.display()))?;
+        compose_hermes_sourcemap(&mut sourcemap, &hermes_sourcemap);
+        file.contents.clear();
+        sourcemap.to_writer(&mut file.contents)?;
+    }
+
+    // Hermes bytecode bundles cannot be matched by their contents, so they are
+    // uploaded with the debug ids of their source maps if all of them have one.
+    let hermes_debug_ids: Vec<_> = files
+        .iter()
+        .filter(|(_, file)| is_hermes_bytecode(&file.contents))
+        .map(|(filename, _)| {
+            files
+                .get(&format!("{filename}.map"))
+                .and_then(|sourcemap| str::from_utf8(&sourcemap.contents).ok())
+                .and_then(discover_sourcemap_embedded_debug_id)
+        })
+        .collect();
+
+    let mut processor = SourceMapProcessor::new();
+    for (filename, file) in files {
+        processor.add(&format!("~/{filename}"), file)?;
+    }
+
     processor.rewrite(&[here_str])?;
     processor.add_sourcemap_references()?;
 
@@ -186,6 +234,35 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let wait = matches.get_flag("wait") || wait_for_secs.is_some();
     let max_wait = wait_for_secs.map_or(DEFAULT_MAX_WAIT, Duration::from_secs);
 
+    let supports_artifact_bundles = chunk_upload_options.as_ref().is_some_and(|options| {
+        options.supports(ChunkUploadCapability::ArtifactBundles)
+            || options.supports(ChunkUploadCapability::ArtifactBundlesV2)
+    });
+    if !hermes_debug_ids.is_empty() {
+        if hermes_debug_ids.iter().all(Option::is_some) && supports_artifact_bundles {
+            processor.add_debug_id_references()?;
+            println!("Uploading sourcemaps of Hermes bundles by debug id");
+
+            processor.upload(&UploadContext {
+                org: &org,
+                project: Some(&project),
+                release: None,
+                dist: None,
+                note: None,
+                wait,
+                max_wait,
+                dedupe: false,
+                provenance: None,
+                chunk_upload_options: chunk_upload_options.as_ref(),
+            })?;
+            return Ok(());
+        }
+        warn!(
+            "Hermes bundles found, but their source maps have no debug ids or the server does \
+            not support artifact bundles. Uploading sourcemaps as release files."
+        );
+    }
+
     match matches.get_many::<String>("dist") {
         None => {
             println!(
@@ -202,6 +279,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                 wait,
                 max_wait,
                 dedupe: false,
+                provenance: None,
                 chunk_upload_options: chunk_upload_options.as_ref(),
             })?;
         }
@@ -221,6 +299,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                     wait,
                     max_wait,
                     dedupe: false,
+                    provenance: None,
                     chunk_upload_options: chunk_upload_options.as_ref(),
                 })?;
             }
@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Arg, ArgMatches, Command};
+use log::info;
+use sourcemap::SourceMap;
+
+use crate::utils::lockfile::write_atomic;
+use crate::utils::sourcemaps::{compose_hermes_sourcemap, inject};
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Compose the packager and Hermes source maps of a React Native bundle.")
+        .long_about(
+            "Compose the packager and Hermes source maps of a React Native bundle.{n}{n}\
+            When Hermes is enabled, the JavaScript bundle created by the packager is compiled \
+            to bytecode. The resulting source map maps the bytecode to the bundle, so it has to \
+            be composed with the packager source map to resolve the original sources. The debug \
+            id of the packager source map is kept, or a new one is derived from the composed \
+            source map. Use this from Gradle or any other build system instead of the \
+            compose-source-maps.js script.",
+        )
+        .arg(
+            Arg::new("packager_sourcemap")
+                .long("packager-sourcemap")
+                .value_name("PATH")
+                .required(true)
+                .help("The path to the source map created by the React Native packager."),
+        )
+        .arg(
+            Arg::new("hermes_sourcemap")
+                .long("hermes-sourcemap")
+                .value_name("PATH")
+                .required(true)
+                .help("The path to the source map created by the Hermes compiler."),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("PATH")
+                .required(true)
+                .help("The path to write the composed source map to."),
+        )
+}
+
+fn read_sourcemap(path: &Path) -> Result<SourceMap> {
+    let contents = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    SourceMap::from_slice(&contents)
+        .with_context(|| format!("Invalid source map at {}", path.display()))
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let packager_path = PathBuf::from(matches.get_one::<String>("packager_sourcemap").unwrap());
+    let hermes_path = PathBuf::from(matches.get_one::<String>("hermes_sourcemap").unwrap());
+    let output_path = PathBuf::from(matches.get_one::<String>("output").unwrap());
+
+    info!("  packager source map path: {}", packager_path.display());
+    info!("  hermes source map path: {}", hermes_path.display());
+
+    let mut sourcemap = read_sourcemap(&packager_path)?;
+    let hermes_sourcemap = read_sourcemap(&hermes_path)?;
+
+    compose_hermes_sourcemap(&mut sourcemap, &hermes_sourcemap);
+
+    let mut contents = vec![];
+    sourcemap.to_writer(&mut contents)?;
+    let debug_id = sourcemap
+        .get_debug_id()
+        .unwrap_or_else(|| inject::debug_id_from_bytes_hashed(&contents));
+    sourcemap.set_debug_id(Some(debug_id));
+
+    contents.clear();
+    sourcemap.to_writer(&mut contents)?;
+    write_atomic(&output_path, &contents)
+        .with_context(|| format!("Failed to write {}", output_path.display()))?;
+
+    println!(
+        "Composed source map written to {} (debug id {debug_id})",
+        output_path.display()
+    );
+    Ok(())
+}
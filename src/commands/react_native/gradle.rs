@@ -135,6 +135,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                 wait,
                 max_wait,
                 dedupe: false,
+                provenance: None,
                 chunk_upload_options: chunk_upload_options.as_ref(),
             })?;
         }
@@ -149,6 +150,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             wait,
             max_wait,
             dedupe: false,
+            provenance: None,
             chunk_upload_options: chunk_upload_options.as_ref(),
         })?;
     }
@@ -2,6 +2,7 @@ use anyhow::Result;
 use clap::{ArgMatches, Command};
 
 pub mod appcenter;
+pub mod compose_sourcemaps;
 pub mod gradle;
 #[cfg(target_os = "macos")]
 pub mod xcode;
@@ -10,6 +11,7 @@ macro_rules! each_subcommand {
     ($mac:ident) => {
         $mac!(gradle);
         $mac!(appcenter);
+        $mac!(compose_sourcemaps);
         #[cfg(target_os = "macos")]
         $mac!(xcode);
     };
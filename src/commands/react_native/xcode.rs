@@ -352,6 +352,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             wait,
             max_wait,
             dedupe: false,
+            provenance: None,
             chunk_upload_options: chunk_upload_options.as_ref(),
         })?;
     } else {
@@ -388,6 +389,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                     wait,
                     max_wait,
                     dedupe: false,
+                    provenance: None,
                     chunk_upload_options: chunk_upload_options.as_ref(),
                 })?;
             }
@@ -402,6 +404,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                         wait,
                         max_wait,
                         dedupe: false,
+                        provenance: None,
                         chunk_upload_options: chunk_upload_options.as_ref(),
                     })?;
                 }
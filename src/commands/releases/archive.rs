@@ -17,7 +17,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let api = Api::current();
     let version = matches.get_one::<String>("version").unwrap();
 
-    let info_rv = api.authenticated()?.update_release(
+    let Some(info_rv) = api.authenticated()?.update_release(
         &config.get_org(matches)?,
         version,
         &UpdatedRelease {
@@ -26,7 +26,10 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             status: Some(ReleaseStatus::Archived),
             ..Default::default()
         },
-    )?;
+    )?
+    else {
+        return Ok(());
+    };
 
     println!("Archived release {}", info_rv.version);
     Ok(())
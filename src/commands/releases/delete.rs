@@ -1,15 +1,44 @@
 use anyhow::Result;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use log::warn;
 
 use crate::api::Api;
 use crate::config::Config;
 use crate::utils::args::ArgExt;
+use crate::utils::release_trash::{purge_expired, ReleaseTrash, RETENTION_DAYS};
+use crate::utils::ui::confirm_destructive_typed;
 
 pub fn make_command(command: Command) -> Command {
     command
         .about("Delete a release.")
+        .long_about(
+            "Delete a release.{n}{n}\
+            When running interactively, the version of the release must be typed to confirm \
+            the deletion. Otherwise, pass --confirm-release with the version, or --yes.",
+        )
         .allow_hyphen_values(true)
         .version_arg(false)
+        .yes_arg()
+        .arg(
+            Arg::new("confirm_release")
+                .long("confirm-release")
+                .value_name("VERSION")
+                .help(
+                    "Confirm the deletion by repeating the version of the release. \
+                    Fails if it does not match.",
+                ),
+        )
+        .arg(
+            Arg::new("archive_first")
+                .long("archive-first")
+                .action(ArgAction::SetTrue)
+                .help(format!(
+                    "Record the release and download its release files before deleting it, \
+                    so that it can be restored with `sentry-cli releases undelete` within \
+                    {RETENTION_DAYS} days. Artifact bundles, as uploaded for debug IDs, \
+                    are not archived."
+                )),
+        )
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -18,11 +47,32 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let version = matches.get_one::<String>("version").unwrap();
     let project = config.get_project(matches).ok();
 
-    if api.authenticated()?.delete_release(
-        &config.get_org(matches)?,
-        project.as_deref(),
-        version,
-    )? {
+    if !confirm_destructive_typed(matches, &format!("delete release {version}"), version)? {
+        println!("Aborted!");
+        return Ok(());
+    }
+
+    let org = config.get_org(matches)?;
+    let authenticated_api = api.authenticated()?;
+
+    if matches.get_flag("archive_first") {
+        purge_expired();
+        let trash = ReleaseTrash::new(&org, version)?;
+        if let Some(release) =
+            trash.archive(&authenticated_api, &org, project.as_deref(), version)?
+        {
+            println!(
+                "Archived release {version} with {} artifacts.",
+                release.artifacts.len()
+            );
+            warn!(
+                "Artifact bundles of release {version} are not archived and cannot be \
+                restored with `sentry-cli releases undelete`."
+            );
+        }
+    }
+
+    if authenticated_api.delete_release(&org, project.as_deref(), version)? {
         println!("Deleted release {version}!");
     } else {
         println!("Did nothing. Release with this version ({version}) does not exist.");
@@ -38,7 +38,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let api = Api::current();
     let version = matches.get_one::<String>("version").unwrap();
 
-    api.authenticated()?.update_release(
+    let finalized = api.authenticated()?.update_release(
         &config.get_org(matches)?,
         version,
         &UpdatedRelease {
@@ -54,6 +54,8 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         },
     )?;
 
-    println!("Finalized release {version}");
+    if finalized.is_some() {
+        println!("Finalized release {version}");
+    }
     Ok(())
 }
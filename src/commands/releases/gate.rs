@@ -0,0 +1,143 @@
+use std::thread;
+
+use anyhow::{bail, Result};
+use chrono::{Duration, Utc};
+use clap::{Arg, ArgGroup, ArgMatches, Command};
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::value_parsers::{age_parser, percentage_parser};
+
+/// The number of issues returned per page by the issues endpoint.
+const ISSUES_PER_PAGE: usize = 100;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Check the health of a release against thresholds.")
+        .long_about(
+            "Check the health of a release against thresholds.{n}{n}\
+            The new issues and the crash rate of the release are checked every --interval \
+            until --window has passed. The command fails as soon as a threshold is exceeded, \
+            so it can be used to decide whether to roll back a release in progressive \
+            delivery pipelines.",
+        )
+        .version_arg(false)
+        .arg(
+            Arg::new("max_new_issues")
+                .long("max-new-issues")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("The maximum number of issues first seen in the release."),
+        )
+        .arg(
+            Arg::new("max_crash_rate")
+                .long("max-crash-rate")
+                .value_name("PERCENT")
+                .value_parser(percentage_parser)
+                .help(
+                    "The maximum percentage of sessions of the release that crashed \
+                    within the window, e.g. 0.5%.",
+                ),
+        )
+        .arg(
+            Arg::new("window")
+                .long("window")
+                .value_name("DURATION")
+                .value_parser(age_parser)
+                .default_value("1h")
+                .help(
+                    "How long to observe the release, e.g. 1h. Crash rates are computed over \
+                    this duration. Supported units are s, m, h, d and w.",
+                ),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .value_name("DURATION")
+                .value_parser(age_parser)
+                .default_value("1m")
+                .help("How often to check the thresholds within the window."),
+        )
+        .group(
+            ArgGroup::new("thresholds")
+                .args(["max_new_issues", "max_crash_rate"])
+                .multiple(true)
+                .required(true),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let org = config.get_org(matches)?;
+    let version = matches.get_one::<String>("version").unwrap();
+    let max_new_issues = matches.get_one::<usize>("max_new_issues").copied();
+    let max_crash_rate = matches.get_one::<f64>("max_crash_rate").copied();
+    let window = *matches.get_one::<Duration>("window").unwrap();
+    let interval = matches.get_one::<Duration>("interval").unwrap().to_std()?;
+
+    // new issues are counted per project, so only require projects if needed.
+    let projects = if max_new_issues.is_some() {
+        config.get_projects(matches)?
+    } else {
+        vec![]
+    };
+    let query = format!("first-release:\"{version}\"");
+    let stats_period = format!("{}s", window.num_seconds().max(1));
+    let deadline = Utc::now() + window;
+
+    loop {
+        let mut violations = vec![];
+
+        if let Some(max_new_issues) = max_new_issues {
+            // fetch just enough pages to tell whether the maximum is exceeded.
+            let pages = max_new_issues / ISSUES_PER_PAGE + 1;
+            let mut new_issues = 0;
+            for project in &projects {
+                new_issues += authenticated_api
+                    .list_organization_project_issues(&org, project, pages, Some(query.clone()))?
+                    .len();
+            }
+            println!("New issues: {new_issues} (max {max_new_issues})");
+            if new_issues > max_new_issues {
+                violations.push(format!(
+                    "{new_issues} new issues exceed the maximum of {max_new_issues}"
+                ));
+            }
+        }
+
+        if let Some(max_crash_rate) = max_crash_rate {
+            match authenticated_api.get_release_crash_free_rate(&org, version, &stats_period)? {
+                Some(crash_free_rate) => {
+                    let crash_rate = (1.0 - crash_free_rate) * 100.0;
+                    println!("Crash rate: {crash_rate:.2}% (max {max_crash_rate}%)");
+                    if crash_rate > max_crash_rate {
+                        violations.push(format!(
+                            "a crash rate of {crash_rate:.2}% exceeds the maximum of \
+                            {max_crash_rate}%"
+                        ));
+                    }
+                }
+                None => println!("Crash rate: no sessions yet (max {max_crash_rate}%)"),
+            }
+        }
+
+        if !violations.is_empty() {
+            bail!(
+                "Release {version} failed the health check: {}",
+                violations.join(", ")
+            );
+        }
+
+        let remaining = deadline - Utc::now();
+        if remaining <= Duration::zero() {
+            break;
+        }
+        thread::sleep(interval.min(remaining.to_std()?));
+    }
+
+    println!("Release {version} passed the health check.");
+    Ok(())
+}
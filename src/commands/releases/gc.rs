@@ -0,0 +1,128 @@
+use std::cmp::Reverse;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
+use log::warn;
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::ui::confirm_destructive;
+use crate::utils::value_parsers::age_parser;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Delete old releases.")
+        .long_about(
+            "Delete old releases.{n}{n}\
+            Releases are deleted if they are older than --older-than and not among the \
+            --keep-last most recently created releases. If both options are given, \
+            releases must satisfy both conditions to be deleted.",
+        )
+        .arg(
+            Arg::new("keep_last")
+                .long("keep-last")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("Keep the given number of most recently created releases."),
+        )
+        .arg(
+            Arg::new("older_than")
+                .long("older-than")
+                .value_name("AGE")
+                .value_parser(age_parser)
+                .help(
+                    "Only delete releases created longer ago than the given age, \
+                    e.g. 90d. Supported units are s, m, h, d and w.",
+                ),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("Print the releases that would be deleted without deleting them."),
+        )
+        .yes_arg()
+        .group(
+            ArgGroup::new("retention")
+                .args(["keep_last", "older_than"])
+                .multiple(true)
+                .required(true),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let org = config.get_org(matches)?;
+    let project = config.get_project(matches).ok();
+    let dry_run = matches.get_flag("dry_run");
+
+    let mut releases = authenticated_api.list_all_releases(&org, project.as_deref())?;
+    releases.sort_by_key(|release| Reverse(release.date_created));
+
+    let keep_last = matches.get_one::<usize>("keep_last").copied().unwrap_or(0);
+    let cutoff = matches
+        .get_one::<Duration>("older_than")
+        .map(|age| Utc::now() - *age);
+
+    let candidates: Vec<_> = releases
+        .iter()
+        .skip(keep_last)
+        .filter(|release| !cutoff.is_some_and(|cutoff| release.date_created >= cutoff))
+        .collect();
+
+    if !dry_run
+        && !candidates.is_empty()
+        && !confirm_destructive(
+            matches,
+            &format!("delete {} of {} releases", candidates.len(), releases.len()),
+        )?
+    {
+        println!("Aborted!");
+        return Ok(());
+    }
+
+    let mut deleted = 0;
+    let mut skipped = 0;
+    for release in candidates {
+        if dry_run {
+            println!("Would delete release {}", release.version);
+            deleted += 1;
+            continue;
+        }
+        // Sentry refuses to delete releases that are still referenced, e.g.
+        // by active issues.  These are skipped, so the other releases are
+        // still collected.
+        match authenticated_api.delete_release(&org, project.as_deref(), &release.version) {
+            Ok(true) => {
+                println!("Deleted release {}", release.version);
+                deleted += 1;
+            }
+            Ok(false) => (),
+            Err(err) => {
+                warn!(
+                    "Could not delete release {}: {:#}",
+                    release.version,
+                    anyhow::Error::from(err)
+                );
+                skipped += 1;
+            }
+        }
+    }
+
+    if dry_run {
+        println!("Would delete {deleted} of {} releases.", releases.len());
+    } else if skipped > 0 {
+        println!(
+            "Deleted {deleted} of {} releases, skipped {skipped}.",
+            releases.len()
+        );
+    } else {
+        println!("Deleted {deleted} of {} releases.", releases.len());
+    }
+
+    Ok(())
+}
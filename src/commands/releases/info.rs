@@ -4,7 +4,7 @@ use clap::{Arg, ArgAction, ArgMatches, Command};
 use crate::api::Api;
 use crate::config::Config;
 use crate::utils::args::ArgExt;
-use crate::utils::formatting::Table;
+use crate::utils::formatting::{OutputFormat, Table};
 use crate::utils::logging::is_quiet_mode;
 use crate::utils::system::QuietExit;
 
@@ -13,6 +13,7 @@ pub fn make_command(command: Command) -> Command {
         .about("Print information about a release.")
         .allow_hyphen_values(true)
         .version_arg(false)
+        .format_arg()
         .arg(
             Arg::new("show_projects")
                 .short('P')
@@ -64,10 +65,10 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         let data_row = tbl
             .add_row()
             .add(&release.version)
-            .add(release.date_created);
+            .add_typed(release.date_created, release.date_created);
 
         if let Some(last_event) = release.last_event {
-            data_row.add(last_event);
+            data_row.add_typed(last_event, last_event);
         }
 
         if matches.get_flag("show_projects") {
@@ -77,9 +78,9 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                 .map(|p| p.slug)
                 .collect::<Vec<_>>();
             if !project_slugs.is_empty() {
-                data_row.add(project_slugs.join("\n"));
+                data_row.add_typed(project_slugs.join("\n"), &project_slugs);
             } else {
-                data_row.add("-");
+                data_row.add_typed("-", &project_slugs);
             }
         }
 
@@ -87,23 +88,18 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             if let Ok(Some(commits)) =
                 authenticated_api.get_release_commits(&org, project.as_deref(), version)
             {
-                if !commits.is_empty() {
-                    data_row.add(
-                        commits
-                            .into_iter()
-                            .map(|c| c.id)
-                            .collect::<Vec<String>>()
-                            .join("\n"),
-                    );
+                let commit_ids = commits.into_iter().map(|c| c.id).collect::<Vec<_>>();
+                if !commit_ids.is_empty() {
+                    data_row.add_typed(commit_ids.join("\n"), &commit_ids);
                 } else {
-                    data_row.add("-");
+                    data_row.add_typed("-", &commit_ids);
                 }
             } else {
-                data_row.add("-");
+                data_row.add_typed("-", ());
             }
         }
 
-        tbl.print();
+        tbl.print_as(OutputFormat::from_matches(matches))?;
     } else {
         return Err(QuietExit(1).into());
     }
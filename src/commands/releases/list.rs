@@ -4,7 +4,8 @@ use clap::{Arg, ArgAction, ArgMatches, Command};
 
 use crate::api::Api;
 use crate::config::Config;
-use crate::utils::formatting::{HumanDuration, Table};
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{HumanDuration, OutputFormat, Table};
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -38,6 +39,7 @@ pub fn make_command(command: Command) -> Command {
                 .action(ArgAction::SetTrue)
                 .hide(true),
         )
+        .format_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -74,12 +76,15 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     for release_info in releases {
         let row = table.add_row();
         if let Some(date) = release_info.date_released {
-            row.add(format!(
-                "{} ago",
-                HumanDuration(Utc::now().signed_duration_since(date))
-            ));
+            row.add_typed(
+                format!(
+                    "{} ago",
+                    HumanDuration(Utc::now().signed_duration_since(date))
+                ),
+                date,
+            );
         } else {
-            row.add("(unreleased)");
+            row.add_typed("(unreleased)", ());
         }
         row.add(&release_info.version);
         if matches.get_flag("show_projects") {
@@ -89,21 +94,24 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                 .map(|p| p.slug)
                 .collect::<Vec<_>>();
             if !project_slugs.is_empty() {
-                row.add(project_slugs.join("\n"));
+                row.add_typed(project_slugs.join("\n"), &project_slugs);
             } else {
-                row.add("-");
+                row.add_typed("-", &project_slugs);
             }
         }
-        row.add(release_info.new_groups);
+        row.add_typed(release_info.new_groups, release_info.new_groups);
         if let Some(date) = release_info.last_event {
-            row.add(format!(
-                "{} ago",
-                HumanDuration(Utc::now().signed_duration_since(date))
-            ));
+            row.add_typed(
+                format!(
+                    "{} ago",
+                    HumanDuration(Utc::now().signed_duration_since(date))
+                ),
+                date,
+            );
         } else {
-            row.add("-");
+            row.add_typed("-", ());
         }
     }
-    table.print();
+    table.print_as(OutputFormat::from_matches(matches))?;
     Ok(())
 }
@@ -6,24 +6,32 @@ use crate::utils::args::ArgExt;
 pub mod archive;
 pub mod delete;
 pub mod finalize;
+pub mod gate;
+pub mod gc;
 pub mod info;
 pub mod list;
 pub mod new;
+pub mod notes;
 pub mod propose_version;
 pub mod restore;
 pub mod set_commits;
+pub mod undelete;
 
 macro_rules! each_subcommand {
     ($mac:ident) => {
         $mac!(archive);
         $mac!(delete);
         $mac!(finalize);
+        $mac!(gate);
+        $mac!(gc);
         $mac!(info);
         $mac!(list);
         $mac!(new);
+        $mac!(notes);
         $mac!(propose_version);
         $mac!(restore);
         $mac!(set_commits);
+        $mac!(undelete);
     };
 }
 
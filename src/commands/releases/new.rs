@@ -32,7 +32,7 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let api = Api::current();
     let version = matches.get_one::<String>("version").unwrap();
 
-    api.authenticated()?.new_release(
+    let created = api.authenticated()?.new_release(
         &config.get_org(matches)?,
         &NewRelease {
             version: version.to_owned(),
@@ -47,6 +47,8 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         },
     )?;
 
-    println!("Created release {version}");
+    if created.is_some() {
+        println!("Created release {version}");
+    }
     Ok(())
 }
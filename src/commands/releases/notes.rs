@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use log::warn;
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::file_upload::UploadContext;
+use crate::utils::progress::ProgressBarMode;
+use crate::utils::release_notes::ReleaseNotes;
+use crate::utils::vcs::get_commit_range_messages;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Generate release notes from the commits of a release.")
+        .long_about(
+            "Generate release notes from the commits of a release.{n}{n}\
+            Commits are grouped into breaking changes, features, bug fixes, performance \
+            improvements and other changes by their conventional commit type, such as \
+            `feat(scope): Summary`. By default, the commits associated with the release \
+            on Sentry are used, see `sentry-cli releases set-commits`.",
+        )
+        .allow_hyphen_values(true)
+        .version_arg(false)
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .default_value("markdown")
+                .value_parser(["markdown", "json"])
+                .help("The output format."),
+        )
+        .arg(Arg::new("range").long("range").value_name("FROM..TO").help(
+            "Use the commits in this range of the local git repository instead of the \
+            commits associated with the release. TO defaults to HEAD.",
+        ))
+        .arg(
+            Arg::new("attach")
+                .long("attach")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Upload the release notes to the release as a release file named \
+                    RELEASE_NOTES.md, or RELEASE_NOTES.json with --format json.",
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let version = matches.get_one::<String>("version").unwrap();
+    let org = config.get_org(matches)?;
+    let project = config.get_project(matches).ok();
+
+    let commits = if let Some(range) = matches.get_one::<String>("range") {
+        get_commit_range_messages(range)?
+    } else {
+        let Some(commits) =
+            authenticated_api.get_release_commits(&org, project.as_deref(), version)?
+        else {
+            bail!("Release {version} does not exist.");
+        };
+        if commits.is_empty() {
+            warn!("Release {version} has no associated commits");
+        }
+        commits
+            .into_iter()
+            .map(|commit| (commit.id, commit.message.unwrap_or_default()))
+            .collect()
+    };
+
+    let notes = ReleaseNotes::from_commits(
+        version,
+        commits
+            .iter()
+            .map(|(id, message)| (id.as_str(), message.as_str())),
+    );
+    let (contents, name) = match matches.get_one::<String>("format").unwrap().as_str() {
+        "json" => (serde_json::to_string_pretty(&notes)?, "RELEASE_NOTES.json"),
+        _ => (notes.to_markdown(), "RELEASE_NOTES.md"),
+    };
+    println!("{}", contents.trim_end());
+
+    if matches.get_flag("attach") {
+        let context = UploadContext {
+            org: &org,
+            project: project.as_deref(),
+            release: Some(version),
+            dist: None,
+            note: None,
+            wait: false,
+            max_wait: Duration::ZERO,
+            dedupe: false,
+            provenance: None,
+            chunk_upload_options: None,
+        };
+        let attached = authenticated_api
+            .region_specific(&org)
+            .upload_release_file(
+                &context,
+                contents.as_bytes(),
+                name,
+                None,
+                ProgressBarMode::Disabled,
+            )?
+            .is_some();
+        if attached {
+            eprintln!("Attached {name} to release {version}.");
+        } else {
+            eprintln!("{name} is already attached to release {version}.");
+        }
+    }
+
+    Ok(())
+}
@@ -1,13 +1,46 @@
-use anyhow::Result;
-use clap::{ArgMatches, Command};
+use anyhow::{format_err, Result};
+use clap::{Arg, ArgMatches, Command};
 
-use crate::utils::releases::detect_release_name;
+use crate::utils::releases::{current_container_id, detect_release_name, get_image_release_name};
 
 pub fn make_command(command: Command) -> Command {
-    command.about("Propose a version name for a new release.")
+    command
+        .about("Propose a version name for a new release.")
+        .arg(
+            Arg::new("from_image_labels")
+                .value_name("IMAGE")
+                .long("from-image-labels")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("")
+                .help(
+                    "Propose the version from the org.opencontainers.image.version or \
+                    org.opencontainers.image.revision label of the given Docker image. \
+                    Without a value, the image of the container sentry-cli runs in is used.",
+                ),
+        )
 }
 
-pub fn execute(_matches: &ArgMatches) -> Result<()> {
-    println!("{}", detect_release_name()?);
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let release = match matches
+        .get_one::<String>("from_image_labels")
+        .map(String::as_str)
+    {
+        None => detect_release_name()?,
+        Some(image) => {
+            let reference = match image {
+                "" => current_container_id().ok_or_else(|| {
+                    format_err!(
+                        "Not running in a container. Pass the image with --from-image-labels=IMAGE"
+                    )
+                })?,
+                image => image.to_owned(),
+            };
+            get_image_release_name(&reference)?.ok_or_else(|| {
+                format_err!("{reference} has no org.opencontainers.image.version or revision label")
+            })?
+        }
+    };
+    println!("{release}");
     Ok(())
 }
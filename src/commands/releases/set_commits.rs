@@ -8,7 +8,7 @@ use crate::config::Config;
 use crate::utils::args::ArgExt;
 use crate::utils::formatting::Table;
 use crate::utils::vcs::{
-    find_heads, generate_patch_set, get_commits_from_git, get_repo_from_remote, CommitSpec,
+    find_heads, find_submodule_heads, get_commits_with_patch_set, get_repo_from_remote, CommitSpec,
 };
 
 pub fn make_command(command: Command) -> Command {
@@ -24,9 +24,18 @@ pub fn make_command(command: Command) -> Command {
             .long("auto")
             .action(ArgAction::SetTrue)
             .help("Enable completely automated commit management.{n}\
-                    This requires that the command is run from within a git repository.  \
+                    This requires that the command is run from within a git repository \
+                    or a Mercurial or Subversion checkout.  \
                     sentry-cli will then automatically find remotely configured \
                     repositories and discover commits."))
+        .arg(Arg::new("submodules")
+            .long("submodules")
+            .requires("auto")
+            .action(ArgAction::SetTrue)
+            .help("Also set commits of all git submodules, including nested ones, \
+                    whose remote URL matches a repository configured in Sentry.  \
+                    Relative submodule URLs are resolved against the URL of the \
+                    superproject.  Requires --auto."))
         .arg(Arg::new("ignore-missing")
             .long("ignore-missing")
             .action(ArgAction::SetTrue)
@@ -38,7 +47,8 @@ pub fn make_command(command: Command) -> Command {
             .long("local")
             .action(ArgAction::SetTrue)
             .help("Set commits of a release from local git.{n}\
-                    This requires that the command is run from within a git repository.  \
+                    This requires that the command is run from within a git repository \
+                    or a Mercurial or Subversion checkout.  \
                     sentry-cli will then automatically find remotely configured \
                     repositories and discover commits."))
         .arg(Arg::new("initial-depth")
@@ -93,7 +103,14 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let heads = if repos.is_empty() {
         None
     } else if matches.get_flag("auto") {
-        let commits = find_heads(None, &repos, Some(config.get_cached_vcs_remote()))?;
+        let mut commits = find_heads(None, &repos, Some(config.get_cached_vcs_remote()))?;
+        if matches.get_flag("submodules") {
+            for head in find_submodule_heads(&repos, Some(config.get_cached_vcs_remote()))? {
+                if !commits.iter().any(|commit| commit.repo == head.repo) {
+                    commits.push(head);
+                }
+            }
+        }
         if commits.is_empty() {
             None
         } else {
@@ -180,20 +197,15 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             OptionalReleaseInfo::None(NoneReleaseInfo {}) => String::new(),
         };
 
-        // Find and connect to local git.
-        let repo = git2::Repository::open_from_env()?;
-
         // Parse the git url.
         let remote = config.get_cached_vcs_remote();
         let parsed = get_repo_from_remote(&remote);
         let ignore_missing = matches.get_flag("ignore-missing");
-        // Fetch all the commits upto the `prev_commit` or return the default (20).
-        // Will return a tuple of Vec<GitCommits> and the `prev_commit` if it exists in the git tree.
-        let (commit_log, prev_commit) =
-            get_commits_from_git(&repo, &prev_commit, default_count, ignore_missing)?;
-
-        // Calculate the diff for each commit in the Vec<GitCommit>.
-        let commits = generate_patch_set(&repo, commit_log, prev_commit, &parsed)?;
+        // Fetch all the commits upto the `prev_commit` or return the default (20)
+        // from the local git, Mercurial or Subversion checkout, including the
+        // diff of each commit.
+        let commits =
+            get_commits_with_patch_set(&prev_commit, default_count, ignore_missing, &parsed)?;
 
         if commits.is_empty() {
             println!("No commits found. Leaving release alone. If you believe there should be some, change commits range or initial depth and try again.");
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use clap::{ArgMatches, Command};
+
+use crate::api::{Api, NewRelease};
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::file_upload::UploadContext;
+use crate::utils::progress::ProgressBarMode;
+use crate::utils::release_trash::{purge_expired, ReleaseTrash, RETENTION_DAYS};
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Restore a release deleted with `releases delete --archive-first`.")
+        .long_about(format!(
+            "Restore a release deleted with `releases delete --archive-first`.{{n}}{{n}}\
+            The release is created again with its projects, URL and release date, and its \
+            artifacts are uploaded again. Releases can be restored for {RETENTION_DAYS} days \
+            after they were deleted, on the machine that deleted them."
+        ))
+        .allow_hyphen_values(true)
+        .version_arg(false)
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let version = matches.get_one::<String>("version").unwrap();
+    let org = config.get_org(matches)?;
+
+    purge_expired();
+    let trash = ReleaseTrash::new(&org, version)?;
+    let Some(release) = trash.load()? else {
+        bail!(
+            "Release {version} cannot be restored. Only releases deleted with --archive-first \
+            within the last {RETENTION_DAYS} days can be restored."
+        );
+    };
+
+    authenticated_api.new_release(
+        &org,
+        &NewRelease {
+            version: release.version.clone(),
+            projects: release.projects.clone(),
+            url: release.url.clone(),
+            date_started: None,
+            date_released: release.date_released,
+        },
+    )?;
+
+    let region_specific_api = authenticated_api.region_specific(&org);
+    for artifact in &release.artifacts {
+        let context = UploadContext {
+            org: &org,
+            project: None,
+            release: Some(version),
+            dist: artifact.dist.as_deref(),
+            note: None,
+            wait: false,
+            max_wait: Duration::ZERO,
+            dedupe: false,
+            provenance: None,
+            chunk_upload_options: None,
+        };
+        let headers: Vec<_> = artifact
+            .headers
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        region_specific_api.upload_release_file(
+            &context,
+            &trash.read_artifact(artifact)?,
+            &artifact.name,
+            Some(&headers),
+            ProgressBarMode::Disabled,
+        )?;
+    }
+
+    trash.remove()?;
+    println!(
+        "Restored release {version} with {} artifacts.",
+        release.artifacts.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::{Api, NewRepo};
+use crate::config::Config;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Add a repository to your organization.")
+        .arg(
+            Arg::new("name")
+                .value_name("NAME")
+                .required(true)
+                .help("The name of the repository, e.g. getsentry/sentry-cli."),
+        )
+        .arg(
+            Arg::new("provider")
+                .long("provider")
+                .value_name("PROVIDER")
+                .required(true)
+                .help("The provider of the repository, e.g. integrations:github."),
+        )
+        .arg(
+            Arg::new("url")
+                .long("url")
+                .value_name("URL")
+                .help("The URL of the repository."),
+        )
+        .arg(
+            Arg::new("integration")
+                .long("integration")
+                .value_name("ID")
+                .help(
+                    "The ID of the integration that provides the repository. \
+                    Required for integration providers.",
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let org = config.get_org(matches)?;
+    let name = matches.get_one::<String>("name").unwrap();
+    let installation = matches.get_one::<String>("integration").cloned();
+
+    let Some(repo) = api.authenticated()?.create_repo(
+        &org,
+        &NewRepo {
+            name: name.to_owned(),
+            provider: matches.get_one::<String>("provider").unwrap().to_owned(),
+            url: matches.get_one::<String>("url").cloned(),
+            identifier: installation.as_ref().map(|_| name.to_owned()),
+            installation,
+        },
+    )?
+    else {
+        return Ok(());
+    };
+
+    println!("Added repository {} (ID {})", repo.name, repo.id);
+
+    Ok(())
+}
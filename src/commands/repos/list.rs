@@ -3,10 +3,13 @@ use clap::{ArgMatches, Command};
 
 use crate::api::Api;
 use crate::config::Config;
-use crate::utils::formatting::Table;
+use crate::utils::args::ArgExt;
+use crate::utils::formatting::{OutputFormat, Table};
 
 pub fn make_command(command: Command) -> Command {
-    command.about("List all repositories in your organization.")
+    command
+        .about("List all repositories in your organization.")
+        .format_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -24,13 +27,14 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
             .add_row()
             .add(&repo.name)
             .add(&repo.provider.name)
-            .add(repo.url.as_deref().unwrap_or("-"));
+            .add_typed(repo.url.as_deref().unwrap_or("-"), &repo.url);
     }
 
-    if table.is_empty() {
+    let format = OutputFormat::from_matches(matches);
+    if table.is_empty() && format == OutputFormat::Table {
         println!("No repos found");
     } else {
-        table.print();
+        table.print_as(format)?;
     }
 
     Ok(())
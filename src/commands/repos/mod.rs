@@ -3,11 +3,15 @@ use clap::{ArgMatches, Command};
 
 use crate::utils::args::ArgExt;
 
+pub mod add;
 pub mod list;
+pub mod remove;
 
 macro_rules! each_subcommand {
     ($mac:ident) => {
+        $mac!(add);
         $mac!(list);
+        $mac!(remove);
     };
 }
 
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::ui::confirm_destructive;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Remove a repository from your organization.")
+        .arg(
+            Arg::new("name")
+                .value_name("NAME")
+                .required(true)
+                .help("The name of the repository to remove."),
+        )
+        .yes_arg()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let org = config.get_org(matches)?;
+    let name = matches.get_one::<String>("name").unwrap();
+
+    let Some(repo) = authenticated_api
+        .list_organization_repos(&org)?
+        .into_iter()
+        .find(|repo| repo.name.eq_ignore_ascii_case(name))
+    else {
+        println!("Did nothing. Repository {name} does not exist.");
+        return Ok(());
+    };
+
+    if !confirm_destructive(matches, &format!("remove repository {}", repo.name))? {
+        println!("Aborted!");
+        return Ok(());
+    }
+
+    if authenticated_api.delete_repo(&org, &repo.id)? {
+        println!("Removed repository {}!", repo.name);
+    } else {
+        println!("Did nothing. Repository {name} does not exist.");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::fs;
+use std::iter;
+
+use anyhow::{bail, Context, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use console::style;
+use rayon::prelude::*;
+use serde::Deserialize;
+
+/// Commands which cannot be run from a manifest, since they take over the
+/// process, read its command line or change its environment, which all steps
+/// share.
+const UNSUPPORTED_COMMANDS: &[&str] = &[
+    "run",
+    "completions",
+    "send-metric",
+    "update",
+    "uninstall",
+    "worker",
+];
+
+/// A manifest of sentry-cli commands to run.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    steps: Vec<Step>,
+}
+
+/// A step in the manifest, which is either a single command or a group of
+/// commands that may run concurrently.
+#[derive(Debug, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+enum Step {
+    Command { run: Vec<String> },
+    Parallel { parallel: Vec<Vec<String>> },
+}
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Run multiple commands defined in a manifest.")
+        .long_about(
+            "Run multiple commands defined in a YAML manifest.{n}{n}\
+            All commands share one connection pool and the configuration of this \
+            invocation, so options such as --auth-token or --url must be passed to \
+            `sentry-cli run` itself. Steps run in order, and the first failing step \
+            stops the run. Commands listed under `parallel` run concurrently if \
+            --parallel is passed.{n}{n}\
+            Example manifest:{n}{n}\
+            steps:{n}  \
+            - run: [releases, new, \"1.0\"]{n}  \
+            - parallel:{n}    \
+            - [sourcemaps, upload, --release, \"1.0\", ./dist]{n}    \
+            - [debug-files, upload, ./build]{n}  \
+            - run: [releases, finalize, \"1.0\"]{n}  \
+            - run: [deploys, new, --release, \"1.0\", --env, production]",
+        )
+        .arg(
+            Arg::new("manifest")
+                .value_name("MANIFEST")
+                .required(true)
+                .help("The path to the YAML manifest."),
+        )
+        .arg(
+            Arg::new("parallel")
+                .long("parallel")
+                .action(ArgAction::SetTrue)
+                .help("Run the commands of `parallel` steps concurrently."),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let path = matches.get_one::<String>("manifest").unwrap();
+    let manifest: Manifest = serde_yaml_ng::from_str(
+        &fs::read_to_string(path).with_context(|| format!("Could not read manifest {path}"))?,
+    )
+    .with_context(|| format!("Invalid manifest {path}"))?;
+
+    // Parse all commands upfront, so that a typo in a later step does not
+    // leave a half-finished run behind.
+    let steps = manifest
+        .steps
+        .iter()
+        .map(|step| match step {
+            Step::Command { run } => Ok(vec![parse_command(run)?]),
+            Step::Parallel { parallel } => {
+                parallel.iter().map(|args| parse_command(args)).collect()
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let concurrent = matches.get_flag("parallel");
+    for commands in &steps {
+        if concurrent && commands.len() > 1 {
+            commands
+                .par_iter()
+                .map(|(args, matches)| run_command(args, matches))
+                .collect::<Result<()>>()?;
+        } else {
+            for (args, matches) in commands {
+                run_command(args, matches)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_command(args: &[String]) -> Result<(String, ArgMatches)> {
+    let Some(command) = args.first() else {
+        bail!("Manifest contains an empty command");
+    };
+    if UNSUPPORTED_COMMANDS.contains(&command.as_str()) {
+        bail!("`{command}` cannot be used in a manifest");
+    }
+
+    let matches = super::add_commands(super::app())
+        .try_get_matches_from(iter::once("sentry-cli").chain(args.iter().map(String::as_str)))?;
+    if let Some(id) = super::find_startup_arg(&matches) {
+        bail!(
+            "The `{}` option must be passed to `sentry-cli run` itself, not to `{}`.",
+            id.replace('_', "-"),
+            matches.subcommand_name().unwrap_or(command)
+        );
+    }
+    Ok((args.join(" "), matches))
+}
+
+fn run_command(args: &str, matches: &ArgMatches) -> Result<()> {
+    println!("{} Running {}", style(">").dim(), style(args).cyan());
+    super::run_command(matches).with_context(|| format!("Failed to run `{args}`"))
+}
@@ -7,6 +7,7 @@ use log::warn;
 use sentry::Envelope;
 
 use crate::api::envelopes_api::EnvelopesApi;
+use crate::utils::args::ArgExt;
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -30,6 +31,7 @@ pub fn make_command(command: Command) -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Send envelopes without attempting to parse their contents."),
         )
+        .queue_dir_arg()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -54,7 +56,23 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         } else {
             Envelope::from_path(p)
         }?;
-        EnvelopesApi::try_new()?.send_envelope(envelope)?;
+        match matches.get_one::<PathBuf>("queue_dir") {
+            Some(queue_dir) => {
+                if let Some(queued) =
+                    EnvelopesApi::try_new()?.send_or_queue_envelope(envelope, queue_dir)?
+                {
+                    println!(
+                        "Envelope from file {} queued at {}",
+                        p.display(),
+                        queued.display()
+                    );
+                    continue;
+                }
+            }
+            None => {
+                EnvelopesApi::try_new()?.send_envelope(envelope)?;
+            }
+        }
         println!("Envelope from file {} dispatched", p.display());
     }
 
@@ -1,25 +1,29 @@
 use std::borrow::Cow;
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
-use anyhow::{anyhow, format_err, Result};
+use anyhow::{anyhow, format_err, Context, Result};
 use chrono::{DateTime, Utc};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use glob::{glob_with, MatchOptions};
 use itertools::Itertools;
 use log::warn;
-use sentry::protocol::{Event, Level, LogEntry, User};
+use sentry::protocol::{self, Attachment, Event, Level, LogEntry, User};
 use sentry::types::Uuid;
 use sentry::{apply_defaults, Client, ClientOptions, Envelope};
 use serde_json::Value;
 use username::get_user_name;
 
 use crate::api::envelopes_api::EnvelopesApi;
+use crate::config::Config;
 use crate::constants::USER_AGENT;
-use crate::utils::args::{get_timestamp, validate_distribution};
-use crate::utils::event::{attach_logfile, get_sdk_info};
+use crate::utils::args::{get_timestamp, validate_distribution, ArgExt};
+use crate::utils::event::{attach_journal, attach_logfile, get_sdk_info};
 use crate::utils::releases::detect_release_name;
+use crate::utils::scrubbing::Scrubber;
+use crate::utils::trace::Trace;
 
 pub fn make_command(command: Command) -> Command {
     command.about("Send a manual event to Sentry.")
@@ -157,9 +161,68 @@ pub fn make_command(command: Command) -> Command {
                     eg. \"INFO: Something broke\" will be parsed as a breadcrumb \
                     \"{\"level\": \"info\", \"message\": \"Something broke\"}\"")
         )
+        .arg(
+            Arg::new("from_journal")
+                .long("from-journal")
+                .action(ArgAction::SetTrue)
+                .requires("unit")
+                .conflicts_with_all(["path", "raw"])
+                .help("Send an event for a failed systemd unit, with its journal as breadcrumbs.")
+                .long_help(
+                    "Send an event for a failed systemd unit. The result and exit status of the \
+                    unit are added to the event, and its journal entries since --since are \
+                    attached as breadcrumbs (last 100 records).{n}{n}\
+                    This is meant to be run from an `OnFailure=` handler. For example, add \
+                    `OnFailure=sentry-failure@%n.service` to the unit and create a \
+                    `sentry-failure@.service` unit with \
+                    `ExecStart=sentry-cli send-event --from-journal --unit %i`.",
+                ),
+        )
+        .arg(
+            Arg::new("unit")
+                .value_name("UNIT")
+                .long("unit")
+                .requires("from_journal")
+                .help("The systemd unit to report with --from-journal."),
+        )
+        .arg(
+            Arg::new("since")
+                .value_name("TIME")
+                .long("since")
+                .allow_hyphen_values(true)
+                .default_value("-5m")
+                .help(
+                    "Attach journal entries since this time, in any format supported by \
+                    journalctl.",
+                ),
+        )
+        .arg(
+            Arg::new("attachments")
+                .value_name("PATH")
+                .long("attachment")
+                .action(ArgAction::Append)
+                .conflicts_with("raw")
+                .help("Attach a file, such as a screenshot or log file, to the event."),
+        )
+        .queue_dir_arg()
+        .trace_id_arg()
 }
 
 pub(super) fn send_raw_event(event: Event<'static>) -> Result<Uuid> {
+    let (event_id, envelope) = prepare_envelope(event, vec![])?;
+    EnvelopesApi::try_new()?.send_envelope(envelope)?;
+    Ok(event_id)
+}
+
+/// Prepares the event and wraps it into an envelope along with the attachments.
+fn prepare_envelope(
+    event: Event<'static>,
+    attachments: Vec<Attachment>,
+) -> Result<(Uuid, Envelope)> {
+    let event = match Config::current().get_scrubber()? {
+        Some(scrubber) => scrub_event(event, &scrubber)?,
+        None => event,
+    };
     let client = Client::from_config(apply_defaults(ClientOptions {
         user_agent: USER_AGENT.into(),
         ..Default::default()
@@ -168,12 +231,73 @@ pub(super) fn send_raw_event(event: Event<'static>) -> Result<Uuid> {
         .prepare_event(event, None)
         .ok_or(anyhow!("Event dropped during preparation"))?;
     let event_id = event.event_id;
-    EnvelopesApi::try_new()?.send_envelope(event)?;
-    Ok(event_id)
+
+    let mut envelope = Envelope::from(event);
+    for attachment in attachments {
+        envelope.add_item(attachment);
+    }
+    Ok((event_id, envelope))
+}
+
+/// Scrubs the sensitive values in an event.
+fn scrub_event(event: Event<'static>, scrubber: &Scrubber) -> Result<Event<'static>> {
+    let mut value = serde_json::to_value(event)?;
+    scrubber.scrub_json(&mut value);
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Sends the envelope, or stores it in `queue_dir` if one is given and Sentry
+/// cannot be reached.  Returns how the event was handled for the output.
+fn dispatch(envelope: Envelope, queue_dir: Option<&PathBuf>) -> Result<&'static str> {
+    let api = EnvelopesApi::try_new()?;
+    match queue_dir {
+        Some(queue_dir) => match api.send_or_queue_envelope(envelope, queue_dir)? {
+            Some(path) => {
+                warn!("Could not reach Sentry, queued event at {}", path.display());
+                Ok("queued")
+            }
+            None => Ok("dispatched"),
+        },
+        None => {
+            api.send_envelope(envelope)?;
+            Ok("dispatched")
+        }
+    }
+}
+
+/// Adds the event to the trace, unless it already belongs to one.
+fn add_trace_context(event: &mut Event, trace: &Trace) {
+    event
+        .contexts
+        .entry("trace".into())
+        .or_insert_with(|| protocol::Context::Trace(Box::new(trace.new_context())));
+}
+
+/// Reads the files passed with `--attachment`.
+fn read_attachments(matches: &ArgMatches) -> Result<Vec<Attachment>> {
+    matches
+        .get_many::<String>("attachments")
+        .unwrap_or_default()
+        .map(|path| {
+            let buffer =
+                fs::read(path).with_context(|| format!("Could not read attachment {path}"))?;
+            let filename = Path::new(path)
+                .file_name()
+                .map_or_else(|| path.clone(), |name| name.to_string_lossy().into_owned());
+            Ok(Attachment {
+                buffer,
+                filename,
+                ..Default::default()
+            })
+        })
+        .collect()
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
     let raw = matches.get_flag("raw");
+    let attachments = read_attachments(matches)?;
+    let queue_dir = matches.get_one::<PathBuf>("queue_dir");
+    let trace = Trace::from_matches(matches);
 
     if let Some(path) = matches.get_one::<String>("path") {
         let collected_paths: Vec<PathBuf> = glob_with(path, MatchOptions::new())
@@ -187,14 +311,18 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         }
 
         for path in collected_paths {
-            let raw_event = std::fs::read(&path)?;
+            let mut raw_event = std::fs::read(&path)?;
 
-            let id = if raw {
+            let (id, envelope) = if raw {
                 use std::io::Write;
 
                 // Its a bit unfortunate that we still need to parse the whole JSON,
                 // but envelopes need an `event_id`, which we also want to report.
-                let json: Value = serde_json::from_slice(&raw_event)?;
+                let mut json: Value = serde_json::from_slice(&raw_event)?;
+                if let Some(scrubber) = Config::current().get_scrubber()? {
+                    scrubber.scrub_json(&mut json);
+                    raw_event = serde_json::to_vec(&json)?;
+                }
                 let id = json
                     .as_object()
                     .and_then(|event| event.get("event_id"))
@@ -205,15 +333,15 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
                 writeln!(buf, r#"{{"event_id":"{id}"}}"#)?;
                 writeln!(buf, r#"{{"type":"event","length":{}}}"#, raw_event.len())?;
                 buf.extend(raw_event);
-                let envelope = Envelope::from_bytes_raw(buf)?;
-                EnvelopesApi::try_new()?.send_envelope(envelope)?;
-                id
+                (id, Envelope::from_bytes_raw(buf)?)
             } else {
-                let event: Event = serde_json::from_slice(&raw_event)?;
-                send_raw_event(event)?
+                let mut event: Event = serde_json::from_slice(&raw_event)?;
+                add_trace_context(&mut event, &trace);
+                prepare_envelope(event, attachments.clone())?
             };
 
-            println!("Event from file {} dispatched: {}", path.display(), id);
+            let outcome = dispatch(envelope, queue_dir)?;
+            println!("Event from file {} {outcome}: {id}", path.display());
         }
 
         return Ok(());
@@ -325,8 +453,17 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         attach_logfile(&mut event, logfile, matches.get_flag("with_categories"))?;
     }
 
-    let id = send_raw_event(event)?;
-    println!("Event dispatched.\nEvent id: {id}");
+    if matches.get_flag("from_journal") {
+        let unit = matches.get_one::<String>("unit").unwrap();
+        let since = matches.get_one::<String>("since").unwrap();
+        attach_journal(&mut event, unit, since)?;
+    }
+
+    add_trace_context(&mut event, &trace);
+
+    let (id, envelope) = prepare_envelope(event, attachments)?;
+    let outcome = dispatch(envelope, queue_dir)?;
+    println!("Event {outcome}.\nEvent id: {id}");
 
     Ok(())
 }
@@ -10,7 +10,7 @@ use self::increment::IncrementMetricArgs;
 use self::set::SetMetricArgs;
 use super::derive_parser::{SentryCLI, SentryCLICommand};
 use anyhow::Result;
-use clap::{command, Args, Subcommand};
+use clap::{Args, Subcommand};
 use clap::{ArgMatches, Command, Parser};
 
 const DEPRECATION_MESSAGE: &str = "DEPRECATION NOTICE: \
@@ -66,8 +66,6 @@ pub(super) fn execute(_: &ArgMatches) -> Result<()> {
     // };
     let SentryCLICommand::SendMetric(SendMetricArgs { subcommand }) = SentryCLI::parse().command;
 
-    log::warn!("{DEPRECATION_MESSAGE}");
-
     match subcommand {
         SendMetricSubcommand::Increment(args) => increment::execute(args),
         SendMetricSubcommand::Distribution(args) => distribution::execute(args),
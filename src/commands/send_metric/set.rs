@@ -1,7 +1,7 @@
 use super::common_args::CommonMetricArgs;
 use crate::{api::envelopes_api::EnvelopesApi, utils::metrics::DefaultTags};
 use anyhow::Result;
-use clap::{command, Args};
+use clap::Args;
 use sentry::metrics::Metric;
 
 #[derive(Args)]
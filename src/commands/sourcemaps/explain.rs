@@ -1,15 +1,17 @@
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, format_err, Result};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use console::style;
 use sentry::protocol::{Frame, Stacktrace};
+use symbolic::debuginfo::js;
 use url::Url;
 
 use crate::api::{Api, Artifact, ProcessedEvent};
 use crate::config::Config;
-use crate::utils::fs::TempFile;
+use crate::utils::file_search::{ReleaseFileMatch, ReleaseFileSearch};
+use crate::utils::fs::{path_as_url, TempFile};
 use crate::utils::system::QuietExit;
 
 use super::resolve::print_source;
@@ -21,15 +23,18 @@ pub fn make_command(command: Command) -> Command {
         .arg(
             Arg::new("event")
                 .value_name("EVENT_ID")
-                .required(true)
+                .required_unless_present("local_dir")
                 .help("ID of an event to be explained."),
         )
         .arg(
             Arg::new("frame")
                 .long("frame")
                 .default_value("0")
-                .value_parser(clap::value_parser!(usize))
-                .help("Position of the frame that should be used for source map resolution."),
+                .value_parser(parse_frame)
+                .help(
+                    "Position of the frame that should be used for source map resolution, \
+                    or the location of a frame as FILE:LINE:COLUMN with --local-dir.",
+                ),
         )
         .arg(
             Arg::new("force")
@@ -38,30 +43,76 @@ pub fn make_command(command: Command) -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Force full validation flow, even when event is already source mapped."),
         )
+        .arg(
+            Arg::new("local_dir")
+                .long("local-dir")
+                .value_name("PATH")
+                .conflicts_with("event")
+                .help(
+                    "Resolve the frame given with --frame against the minified files and \
+                    source maps in this folder, without fetching an event from Sentry.",
+                ),
+        )
+}
+
+/// The frame selected with `--frame`.
+#[derive(Clone, Debug)]
+enum FrameArg {
+    /// The position of an in-app frame of the event.
+    Position(usize),
+    /// The location of a frame in a minified file.
+    Location(FrameLocation),
+}
+
+/// A location given as `FILE:LINE:COLUMN`, with one-based line and column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FrameLocation {
+    file: String,
+    line: u32,
+    column: u32,
+}
+
+fn parse_frame(s: &str) -> Result<FrameArg> {
+    if let Ok(position) = s.parse() {
+        return Ok(FrameArg::Position(position));
+    }
+
+    let mut parts = s.rsplitn(3, ':');
+    let (Some(column), Some(line), Some(file)) = (parts.next(), parts.next(), parts.next()) else {
+        bail!("expected a frame position or FILE:LINE:COLUMN");
+    };
+    let (Ok(line @ 1..), Ok(column @ 1..)) = (line.parse(), column.parse()) else {
+        bail!("line and column must be positive numbers");
+    };
+    Ok(FrameArg::Location(FrameLocation {
+        file: file.to_owned(),
+        line,
+        column,
+    }))
 }
 
-fn tip<S>(msg: S)
+pub(super) fn tip<S>(msg: S)
 where
     S: std::fmt::Display,
 {
     println!("{}", style(format!("ℹ {msg}")).blue());
 }
 
-fn success<S>(msg: S)
+pub(super) fn success<S>(msg: S)
 where
     S: std::fmt::Display,
 {
     println!("{}", style(format!("✔ {msg}")).green());
 }
 
-fn warning<S>(msg: S)
+pub(super) fn warning<S>(msg: S)
 where
     S: std::fmt::Display,
 {
     println!("{}", style(format!("⚠ {msg}")).yellow());
 }
 
-fn error<S>(msg: S)
+pub(super) fn error<S>(msg: S)
 where
     S: std::fmt::Display,
 {
@@ -140,7 +191,7 @@ fn find_matching_artifact(artifacts: &[Artifact], path: &str) -> Result<Artifact
     let full_match = artifacts.iter().find(|a| a.name == path);
     let partial_match = artifacts
         .iter()
-        .find(|a| a.name.ends_with(path.split('/').last().unwrap()));
+        .find(|a| a.name.ends_with(path.split('/').next_back().unwrap()));
 
     if full_match.is_none() {
         error(format!("Uploaded artifacts do not include entry: {path}"));
@@ -274,11 +325,8 @@ fn discover_sourcemaps_location(
     Err(format_err!("Failed to discover source map url"))
 }
 
-fn print_sourcemap(file: &TempFile, line: u32, column: u32) -> Result<()> {
-    let mut f = file.open()?;
-    let mut buf = vec![];
-    f.read_to_end(&mut buf)?;
-    let sm = sourcemap::decode_slice(&buf)?;
+fn print_sourcemap(contents: &[u8], line: u32, column: u32) -> Result<()> {
+    let sm = sourcemap::decode_slice(contents)?;
 
     if let Some(token) = sm.lookup_token(line, column) {
         if let Some(view) = token.get_source_view() {
@@ -368,15 +416,165 @@ fn unify_artifact_url(abs_path: &str) -> Result<String> {
     Ok(filename)
 }
 
+/// Finds the local file with the given URL, printing a hint if there is none.
+fn find_matching_local_file<'a>(
+    files: &'a [(String, ReleaseFileMatch)],
+    url: &str,
+) -> Result<&'a ReleaseFileMatch> {
+    if let Some((_, file)) = files.iter().find(|(file_url, _)| file_url == url) {
+        success(format!("Local file {url} found."));
+        return Ok(file);
+    }
+
+    error(format!("Local files do not include entry: {url}"));
+    let filename = url.rsplit('/').next().unwrap_or(url);
+    if let Some((partial_url, _)) = files
+        .iter()
+        .find(|(file_url, _)| file_url.rsplit('/').next() == Some(filename))
+    {
+        tip(format!(
+            "Found entry with partially matching filename: {partial_url}. \
+            Make sure that --local-dir is the folder served at the root of the URL."
+        ));
+    }
+    Err(QuietExit(1).into())
+}
+
+/// Resolves a frame against the minified files and source maps in a local
+/// folder, so that source maps can be verified before deploying them.
+fn explain_local(local_dir: &Path, location: &FrameLocation) -> Result<()> {
+    let files: Vec<_> = ReleaseFileSearch::new(local_dir.to_path_buf())
+        .extensions(["js", "cjs", "mjs", "map"])
+        .collect_files()?
+        .into_iter()
+        .map(|file| {
+            let path = file.path.strip_prefix(local_dir).unwrap_or(&file.path);
+            (format!("~/{}", path_as_url(path)), file)
+        })
+        .collect();
+
+    let url = unify_artifact_url(&location.file)?;
+    let source = find_matching_local_file(&files, &url)?;
+    let contents = String::from_utf8_lossy(&source.contents);
+
+    let debug_id = js::discover_debug_id(&contents);
+    match debug_id {
+        Some(debug_id) => success(format!("Found debug id {debug_id} in {url}")),
+        None => {
+            warning(format!("{url} has no debug id"));
+            tip("Run `sentry-cli sourcemaps inject` on the build output before uploading it.");
+        }
+    }
+
+    let sourcemap = match js::discover_sourcemaps_location(&contents) {
+        Some(location) if location.starts_with("data:") => {
+            success("Found embedded source map");
+            let encoded = location
+                .split_once(";base64,")
+                .map(|(_, encoded)| encoded)
+                .ok_or_else(|| {
+                    error("Embedded source map is not base64 encoded");
+                    QuietExit(1)
+                })?;
+            data_encoding::BASE64
+                .decode(encoded.as_bytes())
+                .map_err(|err| {
+                    error(format!("Invalid embedded source map: {err}"));
+                    QuietExit(1)
+                })?
+        }
+        Some(location) => {
+            success(format!("Found source map location: {location}"));
+            let sourcemap_url = unify_artifact_url(&resolve_sourcemap_url(
+                &format!("http://localhost{}", url.trim_start_matches('~')),
+                location,
+            )?)?;
+            success(format!("Resolved source map url: {sourcemap_url}"));
+            find_matching_local_file(&files, &sourcemap_url)?
+                .contents
+                .clone()
+        }
+        None => {
+            let by_debug_id = debug_id.and_then(|debug_id| {
+                files.iter().find(|(_, file)| {
+                    std::str::from_utf8(&file.contents)
+                        .ok()
+                        .and_then(js::discover_sourcemap_embedded_debug_id)
+                        == Some(debug_id)
+                })
+            });
+            let Some((sourcemap_url, sourcemap)) = by_debug_id else {
+                error(format!("Failed to discover the source map of {url}"));
+                tip("Make sure that the file ends with a sourceMappingURL comment.");
+                return Err(QuietExit(1).into());
+            };
+            success(format!("Found source map {sourcemap_url} by debug id"));
+            sourcemap.contents.clone()
+        }
+    };
+
+    if let Some(debug_id) = debug_id {
+        let sourcemap_debug_id = std::str::from_utf8(&sourcemap)
+            .ok()
+            .and_then(js::discover_sourcemap_embedded_debug_id);
+        match sourcemap_debug_id {
+            Some(sourcemap_debug_id) if sourcemap_debug_id == debug_id => {
+                success("Source map has a matching debug id");
+            }
+            Some(sourcemap_debug_id) => {
+                error(format!(
+                    "Debug id mismatch. File: {debug_id}, Source map: {sourcemap_debug_id}"
+                ));
+                tip("Run `sentry-cli sourcemaps inject` on the files and their source maps together.");
+                return Err(QuietExit(1).into());
+            }
+            None => warning("Source map has no debug id"),
+        }
+    }
+
+    print_sourcemap(&sourcemap, location.line - 1, location.column - 1).map_err(|err| {
+        error(err);
+        QuietExit(1)
+    })?;
+
+    success("Source Maps should be working fine once the files are uploaded.");
+    Ok(())
+}
+
 pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let frame = matches.get_one::<FrameArg>("frame").unwrap();
+
+    if let Some(local_dir) = matches.get_one::<String>("local_dir") {
+        let FrameArg::Location(location) = frame else {
+            bail!("--local-dir requires the location of a frame as --frame FILE:LINE:COLUMN");
+        };
+        return explain_local(&PathBuf::from(local_dir), location);
+    }
+    let FrameArg::Position(position) = frame else {
+        bail!("Frames given as FILE:LINE:COLUMN can only be explained with --local-dir");
+    };
+
     let config = Config::current();
-    warning("DEPRECATION: `sourcemaps explain` has drifted from how sourcemap processing actually operates \
-        and its output may not be accurate. It will be removed in a future version of `sentry-cli`.");
     let (org, project) = config.get_org_and_project(matches)?;
     let event_id = matches.get_one::<String>("event").unwrap();
 
     let event = fetch_event(&org, &project, event_id)?;
-    let release = extract_release(&event)?;
+    explain_event(&org, &project, &event, *position, matches.get_flag("force"))
+}
+
+/// Walks through the source map resolution of the given event and prints
+/// a hint for the first step that fails.
+///
+/// Failures are reported through `QuietExit` after the corresponding message
+/// has already been printed.
+pub(super) fn explain_event(
+    org: &str,
+    project: &str,
+    event: &ProcessedEvent,
+    frame_position: usize,
+    force: bool,
+) -> Result<()> {
+    let release = extract_release(event)?;
 
     if event.exception.values.is_empty() {
         warning("Event has no exception captured, there is no use for source maps");
@@ -391,23 +589,20 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     })?;
     success("Event has a valid stacktrace present");
 
-    let mut frame = extract_nth_frame(stacktrace, *matches.get_one::<usize>("frame").unwrap())
-        .map_err(|err| {
-            error(err);
-            QuietExit(1)
-        })?;
+    let mut frame = extract_nth_frame(stacktrace, frame_position).map_err(|err| {
+        error(err);
+        QuietExit(1)
+    })?;
 
-    if exception.raw_stacktrace.is_some() {
-        if matches.get_flag("force") {
+    if let Some(raw_stacktrace) = &exception.raw_stacktrace {
+        if force {
             warning(
                 "Exception is already source mapped, however 'force' flag was used. Moving along.",
             );
-            let raw_stacktrace = exception.raw_stacktrace.as_ref().unwrap();
-            frame = extract_nth_frame(raw_stacktrace, *matches.get_one::<usize>("frame").unwrap())
-                .map_err(|err| {
-                    error(err);
-                    QuietExit(1)
-                })?;
+            frame = extract_nth_frame(raw_stacktrace, frame_position).map_err(|err| {
+                error(err);
+                QuietExit(1)
+            })?;
         } else {
             warning("Exception is already source mapped and first resolved frame points to:\n");
             if let Some(frame) = extract_in_app_frames(stacktrace)
@@ -424,18 +619,16 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     }
 
     let abs_path = frame.abs_path.as_ref().expect("Incorrect abs_path value");
-    let artifacts = fetch_release_artifacts(&org, &project, &release)?;
+    let artifacts = fetch_release_artifacts(org, project, &release)?;
     let matched_artifact = find_matching_artifact(&artifacts, &unify_artifact_url(abs_path)?)?;
 
     verify_dists_matches(&matched_artifact, event.dist.as_deref())?;
 
     let sourcemap_location =
-        discover_sourcemaps_location(&org, &project, &release, &matched_artifact).map_err(
-            |err| {
-                error(err);
-                QuietExit(1)
-            },
-        )?;
+        discover_sourcemaps_location(org, project, &release, &matched_artifact).map_err(|err| {
+            error(err);
+            QuietExit(1)
+        })?;
     success(format!(
         "Found source map location: {}",
         &sourcemap_location
@@ -447,11 +640,12 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let sourcemap_artifact = find_matching_artifact(&artifacts, &sourcemap_url)?;
     verify_dists_matches(&sourcemap_artifact, event.dist.as_deref())?;
 
-    let sourcemap_file =
-        fetch_release_artifact_file(&org, &project, &release, &sourcemap_artifact)?;
+    let sourcemap_file = fetch_release_artifact_file(org, project, &release, &sourcemap_artifact)?;
+    let mut buf = vec![];
+    sourcemap_file.open()?.read_to_end(&mut buf)?;
 
     print_sourcemap(
-        &sourcemap_file,
+        &buf,
         frame.lineno.expect("Event frame is missing line number") as u32 - 1,
         frame.colno.expect("Event frame is missing column number") as u32 - 1,
     )
@@ -513,3 +707,25 @@ fn test_unify_artifact_url() {
         assert_eq!(unify_artifact_url(path).unwrap(), expected);
     }
 }
+
+#[test]
+fn test_parse_frame() {
+    assert!(matches!(parse_frame("2").unwrap(), FrameArg::Position(2)));
+
+    let FrameArg::Location(location) =
+        parse_frame("http://localhost:5000/dist/bundle.min.js:1:432").unwrap()
+    else {
+        panic!("expected a frame location");
+    };
+    assert_eq!(
+        location,
+        FrameLocation {
+            file: "http://localhost:5000/dist/bundle.min.js".into(),
+            line: 1,
+            column: 432,
+        }
+    );
+
+    assert!(parse_frame("bundle.min.js:1").is_err());
+    assert!(parse_frame("bundle.min.js:0:1").is_err());
+}
@@ -1,10 +1,13 @@
+use std::fs::File;
+use std::io;
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 
 use crate::utils::file_search::ReleaseFileSearch;
 use crate::utils::fs::path_as_url;
+use crate::utils::logging::{quiet_println, set_quiet_mode};
 use crate::utils::sourcemaps::SourceMapProcessor;
 
 pub fn make_command(command: Command) -> Command {
@@ -17,7 +20,9 @@ pub fn make_command(command: Command) -> Command {
             sourcemap and that sourcemap is locally available, \
             the debug id will be injected into it as well. \
             If the referenced sourcemap already contains a debug id, \
-            that id is used instead.",
+            that id is used instead. \
+            CSS files and their sourcemaps are processed the same way, \
+            except that only a debug id comment is added to CSS files.",
         )
         .arg(
             Arg::new("paths")
@@ -47,6 +52,12 @@ pub fn make_command(command: Command) -> Command {
                     ignore file, e.g. .gitignore.",
                 ),
         )
+        .arg(
+            Arg::new("no_vcs_ignore")
+                .long("no-vcs-ignore")
+                .action(ArgAction::SetTrue)
+                .help("Do not skip files ignored by .gitignore or .ignore files."),
+        )
         .arg(
             Arg::new("extensions")
                 .long("ext")
@@ -54,8 +65,8 @@ pub fn make_command(command: Command) -> Command {
                 .value_name("EXT")
                 .action(ArgAction::Append)
                 .help(
-                    "Set the file extensions of JavaScript files that are considered \
-                    for injection.  This overrides the default extensions (js, cjs, mjs).  \
+                    "Set the file extensions of JavaScript and CSS files that are considered \
+                    for injection.  This overrides the default extensions (js, cjs, mjs, css).  \
                     To add an extension, all default extensions must be repeated.  Specify \
                     once per extension.  Source maps are discovered via those files.",
                 ),
@@ -66,6 +77,31 @@ pub fn make_command(command: Command) -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Don't modify files on disk."),
         )
+        .arg(
+            Arg::new("random_ids")
+                .long("random-ids")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Generate random debug ids instead of deriving them from the file \
+                    contents. By default, identical files get the same debug id in every build.",
+                ),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(["text", "json"])
+                .help(
+                    "The output format. With `json`, a report of all processed files is \
+                    printed to stdout instead of the human readable output.",
+                ),
+        )
+        .arg(
+            Arg::new("report")
+                .long("report")
+                .value_name("PATH")
+                .help("Write a JSON report of all processed files to the given path."),
+        )
 }
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -76,6 +112,13 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         .unwrap()
         .map(PathBuf::from);
     let dry_run = matches.get_flag("dry_run");
+    let random_ids = matches.get_flag("random_ids");
+    let json_output = matches.get_one::<String>("format").map(String::as_str) == Some("json");
+
+    // Human readable progress output would corrupt the JSON written to stdout.
+    if json_output {
+        set_quiet_mode(true);
+    }
 
     let ignore_file = matches
         .get_one::<String>("ignore_file")
@@ -89,17 +132,18 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let mut extensions = matches
         .get_many::<String>("extensions")
         .map(|extensions| extensions.map(|ext| ext.trim_start_matches('.')).collect())
-        .unwrap_or_else(|| vec!["js", "cjs", "mjs"]);
+        .unwrap_or_else(|| vec!["js", "cjs", "mjs", "css"]);
 
-    // Sourcemaps should be discovered regardless of which JavaScript extensions have been selected.
+    // Sourcemaps should be discovered regardless of which extensions have been selected.
     extensions.push("map");
 
     for path in paths {
-        println!("> Searching {}", path.display());
+        quiet_println!("> Searching {}", path.display());
         let sources = ReleaseFileSearch::new(path)
             .ignore_file(ignore_file)
             .ignores(&ignores)
             .extensions(extensions.clone())
+            .respect_vcs_ignore(!matches.get_flag("no_vcs_ignore"))
             .collect_files()?;
         for source in sources {
             let url = path_as_url(&source.path);
@@ -107,6 +151,22 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         }
     }
 
-    processor.inject_debug_ids(dry_run, &extensions)?;
+    let report = processor.inject_debug_ids(dry_run, random_ids, &extensions)?;
+
+    if let Some(report_path) = matches.get_one::<String>("report") {
+        let file = File::create(report_path)
+            .with_context(|| format!("Failed to create report file {report_path}"))?;
+        serde_json::to_writer_pretty(file, &report)?;
+    }
+
+    if json_output {
+        serde_json::to_writer_pretty(&mut io::stdout(), &report)?;
+        println!();
+    } else if !report.is_empty() {
+        quiet_println!("{report}");
+    } else {
+        quiet_println!("> Nothing to inject");
+    }
+
     Ok(())
 }
@@ -1,8 +1,9 @@
+use std::collections::BTreeSet;
 use std::env;
 use std::path::PathBuf;
 use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use glob::{glob_with, MatchOptions};
 use itertools::Itertools;
@@ -11,13 +12,23 @@ use log::{debug, warn};
 use crate::api::{Api, ChunkUploadCapability};
 use crate::config::Config;
 use crate::constants::DEFAULT_MAX_WAIT;
-use crate::utils::args::validate_distribution;
+use crate::utils::args::{validate_distribution, ArgExt};
 use crate::utils::file_search::ReleaseFileSearch;
-use crate::utils::file_upload::UploadContext;
+use crate::utils::file_upload::{fold_precompressed_variants, UploadContext};
 use crate::utils::fs::path_as_url;
+use crate::utils::lockfile::write_atomic;
+use crate::utils::logging::quiet_println;
+use crate::utils::provenance::Provenance;
+use crate::utils::sourcemaps::validation::ValidationReport;
 use crate::utils::sourcemaps::SourceMapProcessor;
+use crate::utils::system::QuietExit;
 
-const DEFAULT_EXTENSIONS: &[&str] = &["js", "cjs", "mjs", "map", "jsbundle", "bundle"];
+use super::explain::{error, explain_event, success, tip, warning};
+
+const DEFAULT_EXTENSIONS: &[&str] = &["js", "cjs", "mjs", "css", "map", "jsbundle", "bundle"];
+
+/// The number of recent events that are inspected with `--verify`.
+const VERIFY_MAX_EVENTS: usize = 5;
 
 pub fn make_command(command: Command) -> Command {
     command
@@ -171,6 +182,12 @@ pub fn make_command(command: Command) -> Command {
                     ignore file, e.g. .gitignore.",
                 ),
         )
+        .arg(
+            Arg::new("no_vcs_ignore")
+                .long("no-vcs-ignore")
+                .action(ArgAction::SetTrue)
+                .help("Do not skip files ignored by .gitignore or .ignore files."),
+        )
         .arg(
             Arg::new("bundle")
                 .long("bundle")
@@ -224,6 +241,44 @@ pub fn make_command(command: Command) -> Command {
                      uploaded.",
                 ),
         )
+        .arg(
+            Arg::new("strict_validation")
+                .long("strict-validation")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Validate all files before uploading and fail without uploading anything \
+                     if source maps are invalid, source files reference source maps that are \
+                     not part of the upload, or source files have no debug ids.",
+                ),
+        )
+        .arg(
+            Arg::new("validation_report")
+                .long("validation-report")
+                .value_name("PATH")
+                .requires("strict_validation")
+                .help(
+                    "Write a machine-readable JSON report of the validation with \
+                     --strict-validation to the given path.",
+                ),
+        )
+        .arg(
+            Arg::new("verify")
+                .long("verify")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "After the upload, check a recent event of the project and print hints \
+                     about likely misconfigurations if it is not source mapped.",
+                ),
+        )
+        .arg(
+            Arg::new("verify_event")
+                .long("verify-event")
+                .value_name("EVENT_ID")
+                .help(
+                    "After the upload, check with Sentry whether the in-app frames of the \
+                     given event resolve with the uploaded files. Implies --wait.",
+                ),
+        )
         // NOTE: Hidden until we decide to expose it publicly
         .arg(
             Arg::new("use_artifact_bundle")
@@ -250,6 +305,8 @@ pub fn make_command(command: Command) -> Command {
                 .short('v')
                 .hide(true),
         )
+        .max_concurrency_arg()
+        .provenance_arg()
 }
 
 fn get_prefixes_from_args(matches: &ArgMatches) -> Vec<&str> {
@@ -354,6 +411,20 @@ fn process_sources_from_paths(
     let opts = MatchOptions::new();
     let collected_paths = paths.flat_map(|path| glob_with(path, opts).unwrap().flatten());
 
+    let url_suffix = matches
+        .get_one::<String>("url_suffix")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let mut url_prefix = matches
+        .get_one::<String>("url_prefix")
+        .map(String::as_str)
+        .unwrap_or("~");
+    // remove a single slash from the end.  so ~/ becomes ~ and app:/// becomes app://
+    if url_prefix.ends_with('/') {
+        url_prefix = &url_prefix[..url_prefix.len() - 1];
+    }
+
+    let mut sources = vec![];
     for path in collected_paths {
         // if we start walking over something that is an actual file then
         // the directory iterator yields that path and terminates.  We
@@ -367,7 +438,9 @@ fn process_sources_from_paths(
         };
 
         let mut search = ReleaseFileSearch::new(path.to_path_buf());
-        search.decompress(matches.get_flag("decompress"));
+        search
+            .decompress(matches.get_flag("decompress"))
+            .respect_vcs_ignore(!matches.get_flag("no_vcs_ignore"));
 
         if check_ignore {
             search
@@ -376,26 +449,17 @@ fn process_sources_from_paths(
                 .extensions(extensions.clone());
         }
 
-        let sources = search.collect_files()?;
-
-        let url_suffix = matches
-            .get_one::<String>("url_suffix")
-            .map(String::as_str)
-            .unwrap_or_default();
-        let mut url_prefix = matches
-            .get_one::<String>("url_prefix")
-            .map(String::as_str)
-            .unwrap_or("~");
-        // remove a single slash from the end.  so ~/ becomes ~ and app:/// becomes app://
-        if url_prefix.ends_with('/') {
-            url_prefix = &url_prefix[..url_prefix.len() - 1];
-        }
+        // The paths are made relative to where the search started.
+        sources.extend(search.collect_files()?.into_iter().map(|mut source| {
+            source.base_path = base_path.to_path_buf();
+            source
+        }));
+    }
 
-        for source in sources {
-            let local_path = source.path.strip_prefix(base_path).unwrap();
-            let url = format!("{}/{}{}", url_prefix, path_as_url(local_path), url_suffix);
-            processor.add(&url, source)?;
-        }
+    for source in fold_precompressed_variants(sources) {
+        let local_path = source.path.strip_prefix(&source.base_path).unwrap();
+        let url = format!("{}/{}{}", url_prefix, path_as_url(local_path), url_suffix);
+        processor.add(&url, source)?;
     }
 
     if !matches.get_flag("no_rewrite") {
@@ -418,6 +482,132 @@ fn process_sources_from_paths(
     Ok(())
 }
 
+/// Prints the result of the validation with `--strict-validation`, and writes the JSON
+/// report if requested.
+fn write_validation_report(matches: &ArgMatches, report: &ValidationReport) -> Result<()> {
+    quiet_println!("{report}");
+    if let Some(path) = matches.get_one::<String>("validation_report") {
+        write_atomic(path, &serde_json::to_vec_pretty(report)?)
+            .context("Could not write the validation report")?;
+    }
+    Ok(())
+}
+
+/// Looks for a recent event of the project that is not source mapped and explains
+/// why resolution failed.  This only prints hints; the caller reports errors as
+/// warnings, so that verifying never fails the upload.
+fn verify_upload(org: &str, project: &str, release: Option<&str>) -> Result<()> {
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let recent_events = authenticated_api.list_organization_project_events(org, project, 1)?;
+    let mut other_releases = BTreeSet::new();
+
+    for recent_event in recent_events.iter().take(VERIFY_MAX_EVENTS) {
+        let event_id = recent_event.event_id.simple().to_string();
+        let Some(event) = authenticated_api.get_event(org, Some(project), &event_id)? else {
+            continue;
+        };
+
+        if let Some(release) = release {
+            if event.release.as_deref() != Some(release) {
+                if let Some(ref event_release) = event.release {
+                    other_releases.insert(event_release.clone());
+                }
+                continue;
+            }
+        }
+
+        let Some(exception) = event.exception.values.first() else {
+            continue;
+        };
+        if exception.raw_stacktrace.is_some() {
+            success(format!("Recent event {event_id} is source mapped."));
+            return Ok(());
+        }
+
+        warning(format!(
+            "Recent event {event_id} is not source mapped. Checking why:"
+        ));
+        if let Err(err) = explain_event(org, project, &event, 0, false) {
+            // Explanation failures are already printed as hints.
+            if err.downcast_ref::<QuietExit>().is_none() {
+                error(err);
+            }
+        }
+        return Ok(());
+    }
+
+    match release {
+        Some(release) if !other_releases.is_empty() => {
+            warning(format!(
+                "None of the recent events were reported for release {release}."
+            ));
+            tip(format!(
+                "Recent events were reported for: {}. Make sure that the 'release' option \
+                 in the SDK matches the release used for the upload.",
+                other_releases.iter().join(", ")
+            ));
+        }
+        _ => tip("No recent events with an exception found to verify the upload against."),
+    }
+
+    Ok(())
+}
+
+/// Checks with Sentry whether the in-app frames of the event resolve with
+/// the uploaded files, and fails if any of them does not.
+fn verify_event(org: &str, project: &str, event_id: &str) -> Result<()> {
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let Some(event) = authenticated_api.get_event(org, Some(project), event_id)? else {
+        error(format!("Could not retrieve event {event_id}"));
+        tip("Make sure that event ID you used is valid.");
+        return Err(QuietExit(1).into());
+    };
+    let debug = authenticated_api.get_sourcemap_debug(org, project, event_id)?;
+
+    let mut checked = 0;
+    let mut unresolved = 0;
+    for (exception, debug_exception) in event.exception.values.iter().zip(&debug.exceptions) {
+        // The raw stacktrace holds the minified frames of source mapped events.
+        let Some(stacktrace) = exception
+            .raw_stacktrace
+            .as_ref()
+            .or(exception.stacktrace.as_ref())
+        else {
+            continue;
+        };
+
+        for (frame, debug_frame) in stacktrace.frames.iter().zip(&debug_exception.frames) {
+            if !frame.in_app.unwrap_or(false) {
+                continue;
+            }
+            checked += 1;
+            let location = frame.abs_path.as_deref().unwrap_or("<unknown>");
+            if debug_frame.resolves() {
+                success(format!("Frame {location} resolves."));
+            } else {
+                unresolved += 1;
+                error(format!("Frame {location} does not resolve."));
+            }
+        }
+    }
+
+    if checked == 0 {
+        warning(format!("Event {event_id} has no in-app frames to verify."));
+    } else if unresolved > 0 {
+        tip(format!(
+            "Run `sentry-cli sourcemaps explain {event_id}` to find out why."
+        ));
+        return Err(QuietExit(1).into());
+    } else {
+        success(format!(
+            "All {checked} in-app frames of event {event_id} resolve."
+        ));
+    }
+    Ok(())
+}
+
 pub fn execute(matches: &ArgMatches) -> Result<()> {
     let config = Config::current();
     let version = config.get_release_with_legacy_fallback(matches).ok();
@@ -436,6 +626,13 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         }
     }
 
+    if let (Some(options), Some(max_concurrency)) = (
+        chunk_upload_options.as_mut(),
+        matches.get_one::<u8>("max_concurrency"),
+    ) {
+        options.concurrency = *max_concurrency;
+    }
+
     if matches.contains_id("bundle") && matches.contains_id("bundle_sourcemap") {
         process_sources_from_bundle(matches, &mut processor)?;
     } else {
@@ -443,8 +640,11 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     }
 
     let wait_for_secs = matches.get_one::<u64>("wait_for").copied();
-    let wait = matches.get_flag("wait") || wait_for_secs.is_some();
+    let verify_event_id = matches.get_one::<String>("verify_event");
+    // Uploaded files must be processed before the event can be checked.
+    let wait = matches.get_flag("wait") || wait_for_secs.is_some() || verify_event_id.is_some();
     let max_wait = wait_for_secs.map_or(DEFAULT_MAX_WAIT, Duration::from_secs);
+    let provenance = Provenance::from_matches(matches);
     let upload_context = UploadContext {
         org: &org,
         project: Some(&project),
@@ -454,14 +654,36 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         wait,
         max_wait,
         dedupe: !matches.get_flag("no_dedupe"),
+        provenance: provenance.as_ref(),
         chunk_upload_options: chunk_upload_options.as_ref(),
     };
 
+    if matches.get_flag("strict_validation") {
+        let report = processor.validate_strict();
+        write_validation_report(matches, &report)?;
+        if !report.valid {
+            bail!(
+                "Found {} problems when validating files.",
+                report.issues.len()
+            );
+        }
+    }
+
     if matches.get_flag("strict") {
         processor.upload_strict(&upload_context)?;
     } else {
         processor.upload(&upload_context)?;
     }
 
+    if matches.get_flag("verify") {
+        if let Err(err) = verify_upload(&org, &project, version.as_deref()) {
+            warning(format!("Could not verify the upload: {err:#}"));
+        }
+    }
+
+    if let Some(event_id) = verify_event_id {
+        verify_event(&org, &project, event_id)?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,370 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::net::UdpSocket;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command as ProcessCommand, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use console::style;
+use log::{debug, info};
+
+use crate::api::envelopes_api::EnvelopesApi;
+use crate::config::Config;
+use crate::utils::args::ArgExt;
+use crate::utils::log_parsing::{
+    make_envelope, parse_line, parse_syslog, JournalEntry, JsonFields, LogEntry, LogFormat,
+    LogSource,
+};
+use crate::utils::trace::Trace;
+
+/// How often the file is checked for new lines and rotation, and how long
+/// other sources wait for entries before sending a partial batch.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The maximum size of a syslog message received over UDP.
+const MAX_SYSLOG_MESSAGE_SIZE: usize = 64 * 1024;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Follow a log file and send new entries to Sentry as logs.")
+        .long_about(
+            "Follow a log file and send new entries to Sentry as logs.{n}{n}\
+            New lines are parsed according to --format, batched and sent as log envelopes \
+            to the project of the configured DSN. When the file is rotated, either by \
+            moving it away and creating a new file or by truncating it, the new file is \
+            followed from its start.{n}{n}\
+            Instead of a file, logs can be received from syslog or read from the systemd \
+            journal with --source.",
+        )
+        .arg(
+            Arg::new("path")
+                .value_name("FILE")
+                .required_unless_present("source")
+                .help("The path to the log file to follow."),
+        )
+        .arg(
+            Arg::new("source")
+                .long("source")
+                .value_name("SOURCE")
+                .conflicts_with_all(["path", "from_start"])
+                .value_parser(|s: &str| s.parse::<LogSource>())
+                .help(
+                    "Read logs from a source instead of a file: `syslog://ADDRESS:PORT` to \
+                    receive syslog messages over UDP, or `journald` to follow the systemd journal.",
+                ),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .default_value("auto")
+                .value_parser(["auto", "nginx", "apache", "plain", "json"])
+                .help(
+                    "The format of the log file. Lines that do not match the format are sent \
+                    as plain lines.",
+                ),
+        )
+        .arg(
+            Arg::new("field_timestamp")
+                .long("field-timestamp")
+                .value_name("FIELD")
+                .help(
+                    "The field of JSON lines that holds the timestamp, as RFC 3339 or seconds \
+                    since the epoch. Nested fields are separated by dots. Defaults to `timestamp`.",
+                ),
+        )
+        .arg(
+            Arg::new("field_level")
+                .long("field-level")
+                .value_name("FIELD")
+                .help("The field of JSON lines that holds the level. Defaults to `level`."),
+        )
+        .arg(
+            Arg::new("field_message")
+                .long("field-message")
+                .value_name("FIELD")
+                .help("The field of JSON lines that holds the message. Defaults to `message`."),
+        )
+        .arg(
+            Arg::new("batch_size")
+                .long("batch-size")
+                .value_name("COUNT")
+                .default_value("100")
+                .value_parser(clap::value_parser!(u16).range(1..))
+                .help("The maximum number of log entries sent in one envelope."),
+        )
+        .arg(
+            Arg::new("from_start")
+                .long("from-start")
+                .action(ArgAction::SetTrue)
+                .help("Also send the entries that are already in the file."),
+        )
+        .trace_id_arg()
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let mut format: LogFormat = matches.get_one::<String>("format").unwrap().parse()?;
+    if let LogFormat::Json(fields) = &mut format {
+        *fields = json_fields(matches);
+    } else if ["field_timestamp", "field_level", "field_message"]
+        .iter()
+        .any(|id| matches.contains_id(id))
+    {
+        bail!("The --field-* options can only be used with --format json");
+    }
+    let batch_size = usize::from(*matches.get_one::<u16>("batch_size").unwrap());
+
+    let api = EnvelopesApi::try_new()?;
+    let mut source = match matches.get_one::<LogSource>("source") {
+        Some(LogSource::Syslog(addr)) => {
+            let socket = UdpSocket::bind(addr)
+                .with_context(|| format!("Could not listen for syslog messages on {addr}"))?;
+            socket.set_read_timeout(Some(POLL_INTERVAL))?;
+            println!(
+                "{} Receiving syslog messages on {} (press Ctrl+C to stop)",
+                style(">").dim(),
+                style(addr).cyan()
+            );
+            Source::Syslog(socket)
+        }
+        Some(LogSource::Journald) => {
+            println!(
+                "{} Following the systemd journal (press Ctrl+C to stop)",
+                style(">").dim(),
+            );
+            Source::journald()?
+        }
+        None => {
+            let path = Path::new(matches.get_one::<String>("path").unwrap());
+            let follower = Follower::open(path, matches.get_flag("from_start"))?;
+            println!(
+                "{} Following {} (press Ctrl+C to stop)",
+                style(">").dim(),
+                style(path.display()).cyan()
+            );
+            Source::File(follower, format)
+        }
+    };
+    let trace_id = Trace::from_matches(matches).trace_id.to_string();
+    let scrubber = Config::current().get_scrubber()?;
+
+    loop {
+        let mut entries = source.read_entries(batch_size)?;
+        if let Some(scrubber) = &scrubber {
+            entries
+                .iter_mut()
+                .for_each(|entry| scrubber.scrub_log_entry(entry));
+        }
+        if !entries.is_empty() {
+            api.send_envelope(make_envelope(&entries, &trace_id)?)?;
+            info!("Sent {} log entries", entries.len());
+        }
+    }
+}
+
+/// Returns the JSON field mapping given on the command line.
+fn json_fields(matches: &ArgMatches) -> JsonFields {
+    let mut fields = JsonFields::default();
+    for (id, field) in [
+        ("field_timestamp", &mut fields.timestamp),
+        ("field_level", &mut fields.level),
+        ("field_message", &mut fields.message),
+    ] {
+        if let Some(value) = matches.get_one::<String>(id) {
+            field.clone_from(value);
+        }
+    }
+    fields
+}
+
+/// A source of log entries.
+enum Source {
+    File(Follower, LogFormat),
+    Syslog(UdpSocket),
+    /// Entries of a `journalctl --follow` process, read on a separate thread.
+    Journald(Child, Receiver<io::Result<String>>),
+}
+
+impl Source {
+    fn journald() -> Result<Self> {
+        let mut child = ProcessCommand::new("journalctl")
+            .args(["--follow", "--lines", "0", "--output", "json"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .context("Could not run journalctl")?;
+
+        let stdout = child.stdout.take().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Source::Journald(child, receiver))
+    }
+
+    /// Returns up to `max` new entries.  Waits for new entries if there are
+    /// none, and returns a partial batch if no more arrive in time.
+    fn read_entries(&mut self, max: usize) -> Result<Vec<LogEntry>> {
+        match self {
+            Source::File(follower, format) => {
+                let lines = follower.read_lines(max)?;
+                // Keep reading without delay while the file has more lines
+                // than fit into a batch.
+                if lines.len() < max {
+                    follower.check_rotation()?;
+                    thread::sleep(POLL_INTERVAL);
+                }
+                Ok(lines
+                    .iter()
+                    .map(|line| {
+                        let mut entry = parse_line(format, line);
+                        entry.attributes.insert(
+                            "log.file.path".into(),
+                            follower.path.display().to_string().into(),
+                        );
+                        entry
+                    })
+                    .collect())
+            }
+            Source::Syslog(socket) => {
+                let mut entries = vec![];
+                let mut buf = vec![0; MAX_SYSLOG_MESSAGE_SIZE];
+                while entries.len() < max {
+                    match socket.recv(&mut buf) {
+                        Ok(size) => {
+                            entries.push(parse_syslog(&String::from_utf8_lossy(&buf[..size])))
+                        }
+                        Err(err)
+                            if matches!(
+                                err.kind(),
+                                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                            ) =>
+                        {
+                            break
+                        }
+                        Err(err) => return Err(err).context("Could not receive syslog message"),
+                    }
+                }
+                Ok(entries)
+            }
+            Source::Journald(child, receiver) => {
+                let mut entries = vec![];
+                while entries.len() < max {
+                    let line = match receiver.recv_timeout(POLL_INTERVAL) {
+                        Ok(line) => line.context("Could not read journal")?,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => {
+                            bail!("journalctl exited with {}", child.wait()?)
+                        }
+                    };
+                    let entry: JournalEntry =
+                        serde_json::from_str(&line).context("Could not parse journal entry")?;
+                    entries.extend(entry.to_log_entry());
+                }
+                Ok(entries)
+            }
+        }
+    }
+}
+
+/// Reads complete lines appended to a file, and reopens the file when it is
+/// rotated.
+struct Follower {
+    path: PathBuf,
+    reader: BufReader<File>,
+    id: Option<FileId>,
+    offset: u64,
+    partial: Vec<u8>,
+}
+
+impl Follower {
+    fn open(path: &Path, from_start: bool) -> Result<Self> {
+        let mut file =
+            File::open(path).with_context(|| format!("Could not open {}", path.display()))?;
+        let offset = if from_start {
+            0
+        } else {
+            file.seek(SeekFrom::End(0))?
+        };
+
+        Ok(Follower {
+            path: path.to_owned(),
+            id: file_id(&file.metadata()?),
+            reader: BufReader::new(file),
+            offset,
+            partial: vec![],
+        })
+    }
+
+    /// Returns up to `max` complete lines that were appended since the last
+    /// call.  An incomplete last line is kept until it is terminated.
+    fn read_lines(&mut self, max: usize) -> Result<Vec<String>> {
+        let mut lines = vec![];
+        while lines.len() < max {
+            let read = self.reader.read_until(b'\n', &mut self.partial)?;
+            if read == 0 {
+                break;
+            }
+            self.offset += read as u64;
+            if self.partial.last() != Some(&b'\n') {
+                break;
+            }
+
+            let line = String::from_utf8_lossy(&self.partial);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if !line.is_empty() {
+                lines.push(line.to_owned());
+            }
+            self.partial.clear();
+        }
+        Ok(lines)
+    }
+
+    /// Follows the new file at the path if the file was moved away and
+    /// another one was created, or starts over if the file was truncated.
+    ///
+    /// This must only be called once all lines of the current file were read.
+    fn check_rotation(&mut self) -> Result<()> {
+        let metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            // The file was moved away, and the new one is not created yet.
+            Err(_) => return Ok(()),
+        };
+
+        if file_id(&metadata) != self.id {
+            debug!(
+                "{} was replaced, following the new file",
+                self.path.display()
+            );
+            *self = Follower::open(&self.path, true)?;
+        } else if metadata.len() < self.offset {
+            debug!("{} was truncated, starting over", self.path.display());
+            self.reader.seek(SeekFrom::Start(0))?;
+            self.offset = 0;
+            self.partial.clear();
+        }
+        Ok(())
+    }
+}
+
+type FileId = (u64, u64);
+
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// File identities are not available here, so replaced files are only
+/// detected when they are shorter than the previous file.
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<FileId> {
+    None
+}
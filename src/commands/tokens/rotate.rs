@@ -0,0 +1,98 @@
+use anyhow::{bail, Context, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use log::warn;
+use secrecy::ExposeSecret;
+
+use crate::api::Api;
+use crate::config::{Auth, Config};
+use crate::utils::auth_token::AuthToken;
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Replace an org auth token with a new one.")
+        .long_about(
+            "Replace an org auth token with a new one.{n}{n}\
+            A new org auth token with the given name is created and validated. Then, the local \
+            config is updated with the new token, or an export line for SENTRY_AUTH_TOKEN is \
+            printed so that the output can be evaluated by a shell. Finally, all previous org \
+            auth tokens with the same name are revoked. Status messages are printed to stderr.{n}{n}\
+            Creating and revoking org auth tokens requires a user auth token with the org:write \
+            scope.",
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .value_name("NAME")
+                .required(true)
+                .help("The name of the org auth token to rotate."),
+        )
+        .arg(
+            Arg::new("update_config")
+                .long("update-config")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Store the new token in the local config file instead of printing an \
+                    export line.",
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+    let authenticated_api = api.authenticated()?;
+    let org = config.get_org(matches)?;
+    let name = matches.get_one::<String>("name").unwrap();
+
+    let old_tokens: Vec<_> = authenticated_api
+        .list_org_auth_tokens(&org)?
+        .into_iter()
+        .filter(|token| &token.name == name)
+        .collect();
+    if old_tokens.is_empty() {
+        warn!("No org auth token named {name} exists yet, nothing will be revoked.");
+    }
+
+    let Some(new_token) = authenticated_api.create_org_auth_token(&org, name)? else {
+        return Ok(());
+    };
+    let Some(raw_token) = new_token.token else {
+        bail!("The server did not return the new org auth token {name}.");
+    };
+    let token = AuthToken::from(raw_token);
+    eprintln!("Created org auth token {name}");
+
+    let test_cfg = config.make_copy(|cfg| {
+        cfg.set_auth(Auth::Token(token.clone()))?;
+        Ok(())
+    })?;
+    if let Err(err) = Api::with_config(test_cfg).authenticated()?.get_auth_info() {
+        authenticated_api.delete_org_auth_token(&org, &new_token.id)?;
+        bail!("The new org auth token {name} is invalid and was revoked again: {err}");
+    }
+    eprintln!("Validated org auth token {name}");
+
+    if matches.get_flag("update_config") {
+        let mut new_cfg = Config::from_cli_config()?;
+        new_cfg.set_auth(Auth::Token(token))?;
+        new_cfg
+            .save()
+            .context("Failed to store the new org auth token, the old token was not revoked")?;
+        eprintln!("Stored token in {}", new_cfg.get_filename().display());
+    } else {
+        println!("export SENTRY_AUTH_TOKEN={}", token.raw().expose_secret());
+    }
+
+    for old_token in old_tokens {
+        if authenticated_api.delete_org_auth_token(&org, &old_token.id)? {
+            eprintln!(
+                "Revoked org auth token {} ending in {} (created {})",
+                old_token.name,
+                old_token.token_last_characters.as_deref().unwrap_or("?"),
+                old_token.date_created
+            );
+        }
+    }
+
+    Ok(())
+}
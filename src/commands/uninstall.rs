@@ -3,10 +3,10 @@ use std::fs;
 
 use anyhow::Result;
 use clap::{Arg, ArgAction, ArgMatches, Command};
-use console::style;
 
 use crate::utils::fs::is_writable;
-use crate::utils::system::{is_homebrew_install, is_npm_install, QuietExit};
+use crate::utils::installer::Installer;
+use crate::utils::system::QuietExit;
 use crate::utils::ui::prompt_to_continue;
 
 pub fn make_command(command: Command) -> Command {
@@ -17,7 +17,7 @@ pub fn make_command(command: Command) -> Command {
             .help("Skip uninstall confirmation prompt."),
     );
 
-    if cfg!(windows) || is_homebrew_install() || is_npm_install() {
+    if cfg!(windows) || Installer::detect().is_some() {
         command.hide(true)
     } else {
         command
@@ -27,23 +27,8 @@ pub fn make_command(command: Command) -> Command {
 pub fn execute(matches: &ArgMatches) -> Result<()> {
     let exe = env::current_exe()?;
 
-    if is_homebrew_install() {
-        println!("This installation of sentry-cli is managed through homebrew");
-        println!("Please use homebrew to uninstall sentry-cli");
-        println!();
-        println!("{} brew uninstall sentry-cli", style("$").dim());
-        return Err(QuietExit(1).into());
-    }
-    if is_npm_install() {
-        println!("This installation of sentry-cli is managed through npm/yarn");
-        println!(
-            "Please use npm/yarn to uninstall sentry-cli, using one of the following commands:"
-        );
-        println!("  yarn remove @sentry/cli");
-        println!("  yarn global remove @sentry/cli");
-        println!("  npm uninstall @sentry/cli");
-        println!("  npm uninstall --global @sentry/cli");
-        return Err(QuietExit(1).into());
+    if let Some(installer) = Installer::detect() {
+        return installer.uninstall();
     }
     if cfg!(windows) {
         println!("Cannot uninstall on Windows :(");
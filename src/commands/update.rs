@@ -1,18 +1,32 @@
 use std::env;
+use std::str::FromStr;
 
 use anyhow::{bail, Result};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 
-use crate::utils::update::{assert_updatable, can_update_sentrycli, get_latest_sentrycli_release};
+use crate::utils::installer::Installer;
+use crate::utils::update::{can_update_sentrycli, get_latest_sentrycli_release, ReleaseTarget};
 
 pub fn make_command(command: Command) -> Command {
-    let command = command.about("Update the sentry-cli executable.").arg(
-        Arg::new("force")
-            .long("force")
-            .short('f')
-            .action(ArgAction::SetTrue)
-            .help("Force the update even if the latest version is already installed."),
-    );
+    let command = command
+        .about("Update the sentry-cli executable.")
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .short('f')
+                .action(ArgAction::SetTrue)
+                .help("Force the update even if the latest version is already installed."),
+        )
+        .arg(
+            Arg::new("platform_override")
+                .long("platform-override")
+                .value_name("TARGET")
+                .value_parser(ReleaseTarget::from_str)
+                .help(
+                    "Download the executable for the given target instead of the detected \
+                    one, e.g. windows-aarch64 or linux-x86_64-musl.",
+                ),
+        );
 
     if can_update_sentrycli() {
         command.hide(true)
@@ -25,11 +39,17 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     // Disable update check in case of errors
     env::set_var("SENTRY_DISABLE_UPDATE_CHECK", "true");
 
-    // Aborts with an error if this installation is not updatable.
-    assert_updatable()?;
+    // Installations managed by a package manager are updated through it.
+    if let Some(installer) = Installer::detect() {
+        return installer.update();
+    }
 
     let exe = env::current_exe()?;
-    let update = get_latest_sentrycli_release()?;
+    let target = matches
+        .get_one::<ReleaseTarget>("platform_override")
+        .cloned()
+        .unwrap_or_else(ReleaseTarget::current);
+    let update = get_latest_sentrycli_release(&target)?;
     if !update.have_version_info() {
         bail!("Could not get the latest release version.");
     }
@@ -57,3 +77,23 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     println!("Updated to {}!", update.latest_version());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_override() {
+        let matches = make_command(Command::new("update"))
+            .try_get_matches_from(["update", "--platform-override", "windows-aarch64"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<ReleaseTarget>("platform_override"),
+            Some(&"windows-aarch64".parse().unwrap())
+        );
+
+        assert!(make_command(Command::new("update"))
+            .try_get_matches_from(["update", "--platform-override", "windows"])
+            .is_err());
+    }
+}
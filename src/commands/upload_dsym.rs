@@ -1,4 +1,6 @@
-// upload-dsym is a backward compatible, hidden alias for `debug-files upload`.
+// upload-dsym is a deprecated, hidden alias for `debug-files upload`. It accepts the
+// same arguments, and deprecated options of `debug-files upload` are reported
+// for it as well.
 
 use anyhow::Result;
 use clap::{ArgMatches, Command};
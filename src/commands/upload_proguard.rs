@@ -15,6 +15,7 @@ use crate::config::Config;
 use crate::utils::android::dump_proguard_uuids_as_properties;
 use crate::utils::args::ArgExt;
 use crate::utils::fs::TempFile;
+use crate::utils::logging::warn_experimental;
 use crate::utils::proguard;
 use crate::utils::proguard::ProguardMapping;
 use crate::utils::system::QuietExit;
@@ -189,12 +190,12 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
     let (org, project);
 
     if env::var(CHUNK_UPLOAD_ENV_VAR) == Ok("1".into()) {
-        log::warn!(
-            "EXPERIMENTAL FEATURE: Uploading proguard mappings using chunked uploading. \
+        warn_experimental(&format!(
+            "Uploading proguard mappings using chunked uploading. \
              Some functionality may be unavailable when using chunked uploading. Please unset \
              the {CHUNK_UPLOAD_ENV_VAR} variable if you encounter any \
              problems."
-        );
+        ));
 
         authenticated_api = api.authenticated()?;
         (org, project) = config.get_org_and_project(matches)?;
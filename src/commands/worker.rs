@@ -0,0 +1,225 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+
+use anyhow::{bail, Context, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use log::{debug, info};
+
+use crate::config::Config;
+use crate::utils::bazel_worker::{WorkResponse, WorkerProtocol};
+use crate::utils::deprecations::warn_deprecated_usages;
+use crate::utils::exit_code::ExitCode;
+use crate::utils::logging::{set_experimental_warnings, set_quiet_mode};
+use crate::utils::system::{print_error, QuietExit};
+
+pub fn make_command(command: Command) -> Command {
+    command
+        .about("Run sentry-cli commands as a Bazel persistent worker.")
+        .long_about(
+            "Run sentry-cli commands as a Bazel persistent worker.{n}{n}\
+            The worker keeps running between build actions, so uploads of sourcemaps and \
+            debug files do not pay for process startup and connection setup every time. \
+            Every work request contains the arguments of a sentry-cli command, such as \
+            `sourcemaps upload dist`. Global options like `--auth-token` or `--url` must \
+            be passed when starting the worker.{n}{n}\
+            Without `--persistent_worker`, the given arguments are run once, and argument \
+            files (`@FILE`) with one argument per line are expanded.",
+        )
+        .arg(
+            Arg::new("persistent_worker")
+                .long("persistent_worker")
+                .action(ArgAction::SetTrue)
+                .help("Read work requests from stdin. Bazel passes this when starting workers."),
+        )
+        .arg(
+            Arg::new("protocol")
+                .long("protocol")
+                .value_name("PROTOCOL")
+                .value_parser(["proto", "json"])
+                .default_value("proto")
+                .help(
+                    "The encoding of work requests. Set it to json if the rule sets the \
+                    `requires-worker-protocol: json` execution requirement.",
+                ),
+        )
+        .arg(
+            Arg::new("args")
+                .value_name("ARGS")
+                .num_args(1..)
+                .trailing_var_arg(true)
+                .allow_hyphen_values(true)
+                .conflicts_with("persistent_worker")
+                .help("The command to run once, without `--persistent_worker`."),
+        )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    if !matches.get_flag("persistent_worker") {
+        let Some(args) = matches.get_many::<String>("args") else {
+            bail!("No command given. Pass `--persistent_worker` to read work requests.");
+        };
+        let args = expand_arg_files(args)?;
+        return run_request(&args);
+    }
+
+    let protocol = WorkerProtocol::from_name(matches.get_one::<String>("protocol").unwrap())
+        .expect("value parser only allows known protocols");
+    let mut stdin = io::stdin().lock();
+    let mut output = Output::redirect()?;
+    info!("Started persistent worker");
+
+    while let Some(request) = protocol.read_request(&mut stdin)? {
+        // Cancellation is not supported, so requests always run to the end.
+        if request.cancel {
+            continue;
+        }
+
+        debug!("Running work request {}", request.request_id);
+        let (exit_code, output_text) = output.capture(|| {
+            let result = run_request(&request.arguments);
+            if let Err(ref err) = result {
+                if err.downcast_ref::<QuietExit>().is_none() {
+                    print_error(err);
+                }
+            }
+            result_exit_code(&result)
+        })?;
+
+        protocol.write_response(
+            &mut output.responses,
+            &WorkResponse {
+                exit_code,
+                output: output_text,
+                request_id: request.request_id,
+            },
+        )?;
+    }
+
+    info!("Stdin closed, stopping persistent worker");
+    Ok(())
+}
+
+/// Runs a sentry-cli command in this process.
+fn run_request(args: &[String]) -> Result<()> {
+    let app = super::add_commands(super::app()).no_binary_name(true);
+    let matches = match app.try_get_matches_from(args) {
+        Ok(matches) => matches,
+        Err(err) => {
+            // Do not let `print_error` exit the worker on invalid arguments.
+            eprint!("{}", err.render());
+            return Err(QuietExit(err.exit_code()).into());
+        }
+    };
+
+    if let Some(id) = super::find_startup_arg(&matches) {
+        bail!(
+            "The `{}` option must be passed when starting the worker.",
+            id.replace('_', "-")
+        );
+    }
+    if let Some(name @ ("worker" | "completions")) = matches.subcommand_name() {
+        bail!("The `{name}` command cannot be run by the worker.");
+    }
+
+    let config = Config::current();
+    set_quiet_mode(matches.get_flag("quiet"));
+    set_experimental_warnings(!config.get_no_experimental_warning(&matches));
+    warn_deprecated_usages(&matches);
+
+    match super::run_command(&matches) {
+        Err(err) if config.get_allow_failure(&matches) => {
+            print_error(&err);
+            eprintln!(
+                "\nCommand failed, however, \"SENTRY_ALLOW_FAILURE\" variable or \
+                \"allow-failure\" flag was set. Exiting with 0 exit code."
+            );
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+fn result_exit_code(result: &Result<()>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(err) => match err.downcast_ref() {
+            Some(&QuietExit(code)) => code,
+            None => ExitCode::from_error(err).code(),
+        },
+    }
+}
+
+/// Replaces arguments of the form `@FILE` with the lines of the file.
+fn expand_arg_files<'a, I>(args: I) -> Result<Vec<String>>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let mut expanded = vec![];
+    for arg in args {
+        match arg.strip_prefix('@') {
+            Some(path) => {
+                let contents = fs::read_to_string(path)
+                    .with_context(|| format!("Could not read argument file {path}"))?;
+                expanded.extend(contents.lines().map(str::to_owned));
+            }
+            None => expanded.push(arg.clone()),
+        }
+    }
+    Ok(expanded)
+}
+
+/// The output streams of the worker.
+///
+/// Stdout is reserved for responses, so everything that commands print is
+/// redirected: to the output of the response while a request runs, and to
+/// stderr, which Bazel writes to the worker log, in between.
+struct Output {
+    responses: File,
+    stderr: RawFd,
+}
+
+impl Output {
+    fn redirect() -> Result<Output> {
+        let responses = unsafe { File::from_raw_fd(dup(io::stdout().as_raw_fd())?) };
+        let stderr = dup(io::stderr().as_raw_fd())?;
+        dup2(stderr, io::stdout().as_raw_fd())?;
+        Ok(Output { responses, stderr })
+    }
+
+    /// Runs the given function and returns its result and everything it
+    /// printed to stdout and stderr.
+    fn capture<F, T>(&mut self, f: F) -> Result<(T, String)>
+    where
+        F: FnOnce() -> T,
+    {
+        let mut file = tempfile::tempfile()?;
+        dup2(file.as_raw_fd(), io::stdout().as_raw_fd())?;
+        dup2(file.as_raw_fd(), io::stderr().as_raw_fd())?;
+
+        let rv = f();
+
+        io::stdout().flush()?;
+        dup2(self.stderr, io::stdout().as_raw_fd())?;
+        dup2(self.stderr, io::stderr().as_raw_fd())?;
+
+        let mut output = vec![];
+        file.rewind()?;
+        file.read_to_end(&mut output)?;
+        Ok((rv, String::from_utf8_lossy(&output).into_owned()))
+    }
+}
+
+fn dup(fd: RawFd) -> io::Result<RawFd> {
+    match unsafe { libc::dup(fd) } {
+        -1 => Err(io::Error::last_os_error()),
+        fd => Ok(fd),
+    }
+}
+
+fn dup2(src: RawFd, dst: RawFd) -> io::Result<()> {
+    match unsafe { libc::dup2(src, dst) } {
+        -1 => Err(io::Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
@@ -1,10 +1,13 @@
 //! This module implements config access.
+use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::fs::OpenOptions;
 use std::io;
+use std::iter;
 use std::path::{Path, PathBuf};
+use std::process;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, format_err, Context, Error, Result};
 use clap::ArgMatches;
@@ -15,13 +18,22 @@ use parking_lot::Mutex;
 use secrecy::ExposeSecret;
 use sentry::types::Dsn;
 
+use crate::api::RateLimitBehavior;
 use crate::constants::CONFIG_INI_FILE_PATH;
 use crate::constants::DEFAULT_MAX_DIF_ITEM_SIZE;
 use crate::constants::DEFAULT_MAX_DIF_UPLOAD_SIZE;
-use crate::constants::{CONFIG_RC_FILE_NAME, DEFAULT_RETRIES, DEFAULT_URL};
+use crate::constants::{
+    CONFIG_RC_FILE_NAME, DEFAULT_MAX_CONNECTIONS, DEFAULT_POOL_IDLE_TIMEOUT, DEFAULT_RETRIES,
+    DEFAULT_TOKEN_COMMAND_TTL, DEFAULT_URL,
+};
+use crate::utils::auth_token::keyring::{self, TokenStorage};
 use crate::utils::auth_token::AuthToken;
 use crate::utils::auth_token::AuthTokenPayload;
 use crate::utils::http::is_absolute_url;
+use crate::utils::lockfile::{AtomicFile, FileLock};
+use crate::utils::proxy;
+use crate::utils::scrubbing::Scrubber;
+use crate::utils::system::shell_command;
 
 #[cfg(target_os = "macos")]
 use crate::utils::xcode;
@@ -33,8 +45,26 @@ pub enum Auth {
     Token(AuthToken),
 }
 
+/// The environment variable selecting the profile of the config.
+pub const PROFILE_ENV: &str = "SENTRY_PROFILE";
+
+/// The environment variable enabling the stateless mode.
+pub const STATELESS_ENV: &str = "SENTRY_CLI_STATELESS";
+
+/// Returns whether sentry-cli runs in stateless mode, enabled with
+/// `--no-state` or `SENTRY_CLI_STATELESS`.
+///
+/// In stateless mode, the configuration is read exclusively from the
+/// environment and the command line, and nothing is written to disk: no
+/// config files, no caches and no update checks.
+pub fn is_stateless() -> bool {
+    env::var(STATELESS_ENV).is_ok_and(|var| var == "1" || var == "true")
+}
+
 lazy_static! {
     static ref CONFIG: Mutex<Option<Arc<Config>>> = Mutex::new(None);
+    static ref TOKEN_COMMAND_CACHE: Mutex<HashMap<String, (String, Instant)>> =
+        Mutex::new(HashMap::new());
 }
 
 /// Represents the `sentry-cli` config.
@@ -43,11 +73,19 @@ pub struct Config {
     process_bound: bool,
     ini: Ini,
     cached_auth: Option<Auth>,
+    token_command: Option<String>,
     cached_base_url: String,
     cached_headers: Option<Vec<String>>,
     cached_log_level: log::LevelFilter,
     cached_vcs_remote: String,
     cached_token_data: Option<AuthTokenPayload>,
+    cached_read_only: bool,
+    cached_rate_limit_behavior: RateLimitBehavior,
+    cached_ca_file: Option<PathBuf>,
+    cached_legacy_exit_codes: bool,
+    cached_dump_response: Option<PathBuf>,
+    cached_max_connections: Option<u32>,
+    profile: Option<String>,
 }
 
 impl Config {
@@ -59,7 +97,7 @@ impl Config {
 
     /// Creates Config based on provided config file.
     pub fn from_file(filename: PathBuf, ini: Ini) -> Result<Config> {
-        let auth = get_default_auth(&ini);
+        let auth = get_default_auth(&ini)?;
         let token_embedded_data = match auth {
             Some(Auth::Token(ref token)) => token.payload().cloned(),
             _ => None, // get_default_auth never returns Auth::Token variant
@@ -81,11 +119,19 @@ impl Config {
         Ok(Config {
             filename,
             process_bound: false,
+            token_command: get_default_token_command(&ini, auth.as_ref()),
             cached_auth: auth,
             cached_base_url: url,
             cached_headers: get_default_headers(&ini),
             cached_log_level: get_default_log_level(&ini),
             cached_vcs_remote: get_default_vcs_remote(&ini),
+            cached_read_only: get_default_read_only(&ini),
+            cached_rate_limit_behavior: get_default_rate_limit_behavior(&ini)?,
+            cached_ca_file: get_default_ca_file(&ini),
+            cached_legacy_exit_codes: get_default_legacy_exit_codes(&ini),
+            cached_dump_response: None,
+            cached_max_connections: None,
+            profile: get_profile(),
             ini,
             cached_token_data: token_embedded_data,
         })
@@ -145,30 +191,86 @@ impl Config {
     }
 
     /// Write the current config state back into the file.
+    ///
+    /// The file is locked while it is updated, and replaced atomically so that
+    /// concurrent processes never read a partially written config.
     pub fn save(&self) -> Result<()> {
-        let mut options = OpenOptions::new();
-        options.write(true).truncate(true).create(true);
+        if is_stateless() {
+            bail!("Cannot write the config file in stateless mode");
+        }
 
-        // Remove all non-user permissions for the newly created file
-        #[cfg(not(windows))]
-        {
-            use std::os::unix::fs::OpenOptionsExt;
-            options.mode(0o600);
+        let _lock = FileLock::acquire(&self.filename)?;
+
+        let (mut ini, auth_section) = match self.profile {
+            // Only the credentials are saved into an active profile, so that
+            // the other values of the profile do not end up in the shared
+            // sections of the file.
+            Some(ref profile) => {
+                let mut ini = match Ini::load_from_file(&self.filename) {
+                    Ok(ini) => ini,
+                    Err(ini::Error::Io(err)) if err.kind() == io::ErrorKind::NotFound => Ini::new(),
+                    Err(err) => return Err(err.into()),
+                };
+                let section = profile_section(profile);
+                for key in ["token", "api_key"] {
+                    ini.delete_from(Some(section.as_str()), key);
+                    if let Some(value) = self.ini.get_from(Some("auth"), key) {
+                        ini.set_to(Some(section.as_str()), key.into(), value.to_owned());
+                    }
+                }
+                (ini, section)
+            }
+            None => (self.ini.clone(), "auth".to_owned()),
+        };
+
+        // Tokens stored in the keyring are not written to the file.
+        if self.get_token_storage()? == TokenStorage::Keyring {
+            if let Some(token) = ini.get_from(Some(auth_section.as_str()), "token") {
+                keyring::store_token(&keyring_url(&self.ini), token)?;
+                ini.delete_from(Some(auth_section.as_str()), "token");
+            }
         }
 
-        let mut file = options.open(&self.filename)?;
-        self.ini.write_to(&mut file)?;
-        Ok(())
+        // Remove all non-user permissions for the newly created file
+        let mut file = AtomicFile::create_private(&self.filename)?;
+        ini.write_to(&mut file)?;
+        file.commit()
+    }
+
+    /// Returns where auth tokens are stored.
+    pub fn get_token_storage(&self) -> Result<TokenStorage> {
+        get_token_storage(&self.ini)
     }
 
     /// Returns the auth info
-    pub fn get_auth(&self) -> Option<&Auth> {
-        self.cached_auth.as_ref()
+    ///
+    /// A token from `auth.token_command` is only obtained here, so the command
+    /// runs only once a request needs authentication.
+    pub fn get_auth(&self) -> Result<Option<Auth>> {
+        if let Some(ref command) = self.token_command {
+            let token = run_token_command(command, self.get_token_command_ttl()?)?;
+            return Ok(Some(Auth::Token(token.into())));
+        }
+        Ok(self.cached_auth.clone())
+    }
+
+    /// Returns how long a token printed by `auth.token_command` is reused
+    /// before the command runs again.
+    pub fn get_token_command_ttl(&self) -> Result<Duration> {
+        let seconds: u64 = if env::var_os("SENTRY_AUTH_TOKEN_COMMAND_TTL").is_some() {
+            env::var("SENTRY_AUTH_TOKEN_COMMAND_TTL")?.parse()?
+        } else if let Some(val) = self.ini.get_from(Some("auth"), "token_command_ttl") {
+            val.parse()?
+        } else {
+            return Ok(DEFAULT_TOKEN_COMMAND_TTL);
+        };
+        Ok(Duration::from_secs(seconds))
     }
 
     /// Updates the auth info
     pub fn set_auth(&mut self, auth: Auth) -> Result<()> {
         self.cached_auth = Some(auth);
+        self.token_command = None;
 
         self.ini.delete_from(Some("auth"), "api_key");
         self.ini.delete_from(Some("auth"), "token");
@@ -249,6 +351,60 @@ impl Config {
         Ok(format!("{}/api/0/{}", base, path))
     }
 
+    /// Indicates whether mutating API requests should be described instead
+    /// of sent.
+    pub fn is_read_only(&self) -> bool {
+        self.cached_read_only
+    }
+
+    /// Enables or disables read-only mode.
+    pub fn set_read_only(&mut self, value: bool) {
+        self.cached_read_only = value;
+    }
+
+    /// Indicates whether all failures exit with 1 instead of a code
+    /// classifying the failure.
+    pub fn uses_legacy_exit_codes(&self) -> bool {
+        self.cached_legacy_exit_codes
+    }
+
+    /// Enables or disables legacy exit codes.
+    pub fn set_legacy_exit_codes(&mut self, value: bool) {
+        self.cached_legacy_exit_codes = value;
+    }
+
+    /// Returns what to do when requests are rate limited.
+    pub fn get_rate_limit_behavior(&self) -> RateLimitBehavior {
+        self.cached_rate_limit_behavior
+    }
+
+    /// Sets what to do when requests are rate limited.
+    pub fn set_rate_limit_behavior(&mut self, value: RateLimitBehavior) {
+        self.cached_rate_limit_behavior = value;
+    }
+
+    /// Returns the CA bundle used to verify the server certificate instead of
+    /// the system's certificates, if one is defined.
+    pub fn get_ca_file(&self) -> Option<&Path> {
+        self.cached_ca_file.as_deref()
+    }
+
+    /// Sets the CA bundle used to verify the server certificate.
+    pub fn set_ca_file(&mut self, path: PathBuf) {
+        self.cached_ca_file = Some(path);
+    }
+
+    /// Returns the file that responses which cannot be parsed are written
+    /// to, if one is defined.
+    pub fn get_dump_response(&self) -> Option<&Path> {
+        self.cached_dump_response.as_deref()
+    }
+
+    /// Sets the file that responses which cannot be parsed are written to.
+    pub fn set_dump_response(&mut self, path: PathBuf) {
+        self.cached_dump_response = Some(path);
+    }
+
     /// Returns the log level.
     pub fn get_log_level(&self) -> log::LevelFilter {
         self.cached_log_level
@@ -275,13 +431,28 @@ impl Config {
     }
 
     /// Returns the proxy URL if defined.
+    ///
+    /// With `proxy_autodetect = true`, the proxy configured in the system
+    /// settings or in the PAC file at `proxy_pac_url` is used if no proxy
+    /// is defined explicitly.  Bypass lists of the system settings are
+    /// checked against the Sentry host.  PAC files are only supported if they
+    /// select the same proxy for every URL; if any branch returns `DIRECT`
+    /// or a different proxy, no proxy is used and `proxy_url` must be set.
     pub fn get_proxy_url(&self) -> Option<String> {
         if env::var_os("http_proxy").is_some() {
             env::var("http_proxy").ok()
+        } else if let Some(proxy_url) = self.ini.get_from(Some("http"), "proxy_url") {
+            Some(proxy_url.to_owned())
+        } else if self.ini.get_from(Some("http"), "proxy_autodetect") == Some("true") {
+            let host = self
+                .get_base_url()
+                .ok()
+                .and_then(|base_url| url::Url::parse(base_url).ok())
+                .and_then(|base_url| base_url.host_str().map(str::to_owned))
+                .unwrap_or_default();
+            proxy::detect_proxy(self.ini.get_from(Some("http"), "proxy_pac_url"), &host)
         } else {
-            self.ini
-                .get_from(Some("http"), "proxy_url")
-                .map(|val| val.to_owned())
+            None
         }
     }
 
@@ -309,6 +480,39 @@ impl Config {
         }
     }
 
+    /// Returns the path of the client certificate for mutual TLS, if defined.
+    pub fn get_client_cert(&self) -> Option<&str> {
+        self.ini.get_from(Some("http"), "client_cert")
+    }
+
+    /// Sets the path of the client certificate for mutual TLS.
+    pub fn set_client_cert(&mut self, path: &str) {
+        self.ini
+            .set_to(Some("http"), "client_cert".into(), path.into());
+    }
+
+    /// Returns the path of the private key of the client certificate, if
+    /// it is not included in the certificate file.
+    pub fn get_client_key(&self) -> Option<&str> {
+        self.ini.get_from(Some("http"), "client_key")
+    }
+
+    /// Sets the path of the private key of the client certificate.
+    pub fn set_client_key(&mut self, path: &str) {
+        self.ini
+            .set_to(Some("http"), "client_key".into(), path.into());
+    }
+
+    /// Returns the command that signs requests for proxies that only let
+    /// signed requests through, if one is configured.
+    pub fn get_signing_command(&self) -> Option<String> {
+        env::var("SENTRY_SIGNING_COMMAND").ok().or_else(|| {
+            self.ini
+                .get_from(Some("http"), "signing_command")
+                .map(str::to_owned)
+        })
+    }
+
     /// Indicates whether uploads may use gzip transfer encoding.
     pub fn allow_transfer_encoding(&self) -> bool {
         let val = self.ini.get_from(Some("http"), "transfer_encoding");
@@ -318,6 +522,16 @@ impl Config {
         }
     }
 
+    /// Indicates whether HTTP/2 should be negotiated with the server.  Servers
+    /// that do not support it transparently fall back to HTTP/1.1.
+    pub fn allow_http2(&self) -> bool {
+        let val = self.ini.get_from(Some("http"), "http2");
+        match val {
+            None => true,
+            Some(val) => val == "true",
+        }
+    }
+
     /// Controls the SSL revocation check on windows.  This can be used as a
     /// workaround for misconfigured local SSL proxies.
     pub fn disable_ssl_revocation_check(&self) -> bool {
@@ -461,6 +675,23 @@ impl Config {
             .unwrap_or(DEFAULT_MAX_DIF_ITEM_SIZE)
     }
 
+    /// Returns the scrubber configured in the `[scrub]` section, if any
+    /// rules are configured.
+    pub fn get_scrubber(&self) -> Result<Option<Scrubber>> {
+        let Some(props) = self.ini.section(Some("scrub")) else {
+            return Ok(None);
+        };
+        let keywords = props
+            .get("keywords")
+            .map(|keywords| keywords.split(',').collect::<Vec<_>>())
+            .unwrap_or_default();
+        let patterns = props
+            .iter()
+            .filter(|(key, _)| key.starts_with("pattern."))
+            .map(|(_, pattern)| pattern);
+        Scrubber::new(keywords, patterns)
+    }
+
     pub fn get_max_retry_count(&self) -> Result<u32> {
         if env::var_os("SENTRY_HTTP_MAX_RETRIES").is_some() {
             Ok(env::var("SENTRY_HTTP_MAX_RETRIES")?.parse()?)
@@ -471,6 +702,35 @@ impl Config {
         }
     }
 
+    /// Returns the maximum number of connections kept in the connection pool.
+    ///
+    /// `--max-connections` takes precedence over SENTRY_HTTP_MAX_CONNECTIONS,
+    /// which takes precedence over the config file.
+    pub fn get_max_connections(&self) -> Result<u32> {
+        let env_value = env::var_os("SENTRY_HTTP_MAX_CONNECTIONS")
+            .map(|_| env::var("SENTRY_HTTP_MAX_CONNECTIONS"))
+            .transpose()?;
+        resolve_max_connections(self.cached_max_connections, env_value.as_deref(), &self.ini)
+    }
+
+    /// Sets the maximum number of connections kept in the connection pool.
+    pub fn set_max_connections(&mut self, value: u32) {
+        self.cached_max_connections = Some(value);
+    }
+
+    /// Returns how long a connection may stay idle in the pool before it is
+    /// closed.  `None` keeps idle connections open.
+    pub fn get_pool_idle_timeout(&self) -> Result<Option<Duration>> {
+        let seconds: u64 = if env::var_os("SENTRY_HTTP_POOL_IDLE_TIMEOUT").is_some() {
+            env::var("SENTRY_HTTP_POOL_IDLE_TIMEOUT")?.parse()?
+        } else if let Some(val) = self.ini.get_from(Some("http"), "pool_idle_timeout") {
+            val.parse()?
+        } else {
+            return Ok(Some(DEFAULT_POOL_IDLE_TIMEOUT));
+        };
+        Ok((seconds > 0).then(|| Duration::from_secs(seconds)))
+    }
+
     /// Return the DSN
     pub fn get_dsn(&self) -> Result<Dsn> {
         if let Ok(val) = env::var("SENTRY_DSN") {
@@ -500,7 +760,9 @@ impl Config {
 
     /// Should we nag about updates?
     pub fn disable_update_nagger(&self) -> bool {
-        if let Ok(var) = env::var("SENTRY_DISABLE_UPDATE_CHECK") {
+        if is_stateless() {
+            true
+        } else if let Ok(var) = env::var("SENTRY_DISABLE_UPDATE_CHECK") {
             &var == "1" || &var == "true"
         } else if let Some(val) = self.ini.get_from(Some("update"), "disable_check") {
             val == "true"
@@ -509,6 +771,43 @@ impl Config {
         }
     }
 
+    /// Checks the `policy` section of the config and fails if the given
+    /// command (e.g. `releases delete`) may not be run.
+    ///
+    /// `policy.deny` and `policy.allow` are comma separated lists of commands.
+    /// A listed command also covers all of its subcommands.  Denied commands
+    /// always fail, and if `policy.allow` is set, only the commands it lists
+    /// may run.  Legacy commands and aliases (e.g. `upload-dif` or `dif`) are
+    /// treated like the commands they stand for, both in the rules and in the
+    /// checked command.
+    pub fn check_command_policy(&self, command: &str) -> Result<()> {
+        let normalized = normalize_command(command);
+        let matches_rule = |rules: &str| {
+            rules
+                .split(',')
+                .map(normalize_command)
+                .filter(|rule| !rule.is_empty())
+                .any(|rule| {
+                    normalized == rule
+                        || normalized
+                            .strip_prefix(&rule)
+                            .is_some_and(|rest| rest.starts_with(' '))
+                })
+        };
+
+        if let Some(deny) = self.ini.get_from(Some("policy"), "deny") {
+            if matches_rule(deny) {
+                bail!("`{command}` is denied by the policy in the sentry-cli config.");
+            }
+        }
+        if let Some(allow) = self.ini.get_from(Some("policy"), "allow") {
+            if !matches_rule(allow) {
+                bail!("`{command}` is not allowed by the policy in the sentry-cli config.");
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_allow_failure(&self, matches: &ArgMatches) -> bool {
         matches.get_flag("allow_failure")
             || if let Ok(var) = env::var("SENTRY_ALLOW_FAILURE") {
@@ -517,6 +816,38 @@ impl Config {
                 false
             }
     }
+
+    pub fn get_no_experimental_warning(&self, matches: &ArgMatches) -> bool {
+        matches.get_flag("no_experimental_warning")
+            || if let Ok(var) = env::var("SENTRY_NO_EXPERIMENTAL_WARNING") {
+                &var == "1" || &var == "true"
+            } else {
+                false
+            }
+    }
+}
+
+/// Legacy commands and aliases, with the commands they stand for.
+const COMMAND_ALIASES: &[(&str, &str)] = &[
+    ("upload-dif", "debug-files upload"),
+    ("upload-dsym", "debug-files upload"),
+    ("dif", "debug-files"),
+    ("difutil", "debug-files"),
+    ("crons", "monitors"),
+];
+
+/// Collapses whitespace in the given command and replaces a leading alias
+/// with the command it stands for.
+fn normalize_command(command: &str) -> String {
+    let mut words = command.split_whitespace();
+    let Some(first) = words.next() else {
+        return String::new();
+    };
+    let first = COMMAND_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == first)
+        .map_or(first, |(_, command)| command);
+    iter::once(first).chain(words).collect::<Vec<_>>().join(" ")
 }
 
 fn warn_about_conflicting_urls(token_url: &str, manually_configured_url: Option<&str>) {
@@ -543,6 +874,9 @@ fn find_global_config_file() -> Result<PathBuf> {
 }
 
 fn find_project_config_file() -> Option<PathBuf> {
+    if is_stateless() {
+        return None;
+    }
     env::current_dir().ok().and_then(|mut path| loop {
         path.push(CONFIG_RC_FILE_NAME);
         if path.exists() {
@@ -562,7 +896,7 @@ fn find_project_config_file() -> Option<PathBuf> {
 fn load_global_config_file() -> Result<(PathBuf, Ini)> {
     // Make sure to not load global configuration, as it can skew the tests results
     // during local development for different environments.
-    if env::var("SENTRY_INTEGRATION_TEST").is_ok() {
+    if env::var("SENTRY_INTEGRATION_TEST").is_ok() || is_stateless() {
         return Ok((PathBuf::new(), Ini::new()));
     }
 
@@ -617,7 +951,15 @@ fn load_cli_config() -> Result<(PathBuf, Ini)> {
         (global_filename, rv)
     };
 
+    if let Some(profile) = get_profile() {
+        apply_profile(&mut rv, &profile)?;
+    }
+
     if let Ok(prop_path) = env::var("SENTRY_PROPERTIES") {
+        if is_stateless() {
+            warn!("Ignoring file referenced by SENTRY_PROPERTIES in stateless mode");
+            return Ok((path, rv));
+        }
         match fs::File::open(&prop_path) {
             Ok(f) => {
                 let props = match java_properties::read(f) {
@@ -666,28 +1008,230 @@ impl Clone for Config {
             process_bound: false,
             ini: self.ini.clone(),
             cached_auth: self.cached_auth.clone(),
+            token_command: self.token_command.clone(),
             cached_base_url: self.cached_base_url.clone(),
             cached_headers: self.cached_headers.clone(),
             cached_log_level: self.cached_log_level,
             cached_vcs_remote: self.cached_vcs_remote.clone(),
             cached_token_data: self.cached_token_data.clone(),
+            cached_read_only: self.cached_read_only,
+            cached_rate_limit_behavior: self.cached_rate_limit_behavior,
+            cached_ca_file: self.cached_ca_file.clone(),
+            cached_legacy_exit_codes: self.cached_legacy_exit_codes,
+            cached_dump_response: self.cached_dump_response.clone(),
+            cached_max_connections: self.cached_max_connections,
+            profile: self.profile.clone(),
         }
     }
 }
 
-#[allow(clippy::manual_map)]
-fn get_default_auth(ini: &Ini) -> Option<Auth> {
-    if let Ok(val) = env::var("SENTRY_AUTH_TOKEN") {
+fn get_default_read_only(ini: &Ini) -> bool {
+    if let Ok(var) = env::var("SENTRY_READ_ONLY") {
+        &var == "1" || &var == "true"
+    } else if let Some(val) = ini.get_from(Some("defaults"), "read_only") {
+        val == "true"
+    } else {
+        false
+    }
+}
+
+fn get_default_legacy_exit_codes(ini: &Ini) -> bool {
+    if let Ok(var) = env::var("SENTRY_LEGACY_EXIT_CODES") {
+        &var == "1" || &var == "true"
+    } else if let Some(val) = ini.get_from(Some("defaults"), "legacy_exit_codes") {
+        val == "true"
+    } else {
+        false
+    }
+}
+
+fn get_default_rate_limit_behavior(ini: &Ini) -> Result<RateLimitBehavior> {
+    let value = if let Ok(var) = env::var("SENTRY_RATE_LIMIT_BEHAVIOR") {
+        var
+    } else if let Some(val) = ini.get_from(Some("http"), "rate_limit_behavior") {
+        val.to_owned()
+    } else {
+        return Ok(RateLimitBehavior::default());
+    };
+    RateLimitBehavior::from_name(&value)
+        .ok_or_else(|| format_err!("Invalid rate limit behavior {value}, expected wait or fail"))
+}
+
+fn get_default_ca_file(ini: &Ini) -> Option<PathBuf> {
+    if let Some(var) = env::var_os("SENTRY_CAFILE") {
+        Some(var.into())
+    } else {
+        ini.get_from(Some("http"), "cafile").map(PathBuf::from)
+    }
+}
+
+fn get_default_auth(ini: &Ini) -> Result<Option<Auth>> {
+    Ok(if let Ok(val) = env::var("SENTRY_AUTH_TOKEN") {
         Some(Auth::Token(val.into()))
     } else if let Ok(val) = env::var("SENTRY_API_KEY") {
         Some(Auth::Key(val))
+    } else if ini.get_from(Some("auth"), "token_command").is_some() {
+        // The command runs on demand, see `Config::get_auth`.
+        None
+    } else if let Some(token) = load_keyring_token(ini)? {
+        Some(Auth::Token(token.into()))
     } else if let Some(val) = ini.get_from(Some("auth"), "token") {
         Some(Auth::Token(val.into()))
-    } else if let Some(val) = ini.get_from(Some("auth"), "api_key") {
-        Some(Auth::Key(val.to_owned()))
     } else {
+        ini.get_from(Some("auth"), "api_key")
+            .map(|val| Auth::Key(val.to_owned()))
+    })
+}
+
+/// Resolves the maximum number of pooled connections from the value of
+/// `--max-connections`, SENTRY_HTTP_MAX_CONNECTIONS and the config file.
+fn resolve_max_connections(arg: Option<u32>, env_value: Option<&str>, ini: &Ini) -> Result<u32> {
+    let value = if let Some(value) = arg {
+        value
+    } else if let Some(val) = env_value {
+        val.parse()?
+    } else if let Some(val) = ini.get_from(Some("http"), "max_connections") {
+        val.parse()?
+    } else {
+        DEFAULT_MAX_CONNECTIONS
+    };
+    if value == 0 {
+        bail!("The maximum number of connections must be at least 1");
+    }
+    Ok(value)
+}
+
+/// Returns `auth.token_command` unless the environment provides credentials.
+fn get_default_token_command(ini: &Ini, auth: Option<&Auth>) -> Option<String> {
+    if auth.is_some() {
+        return None;
+    }
+    ini.get_from(Some("auth"), "token_command")
+        .map(|command| command.to_owned())
+}
+
+/// The profile selected with `--profile` or `SENTRY_PROFILE`.
+fn get_profile() -> Option<String> {
+    env::var(PROFILE_ENV)
+        .ok()
+        .filter(|profile| !profile.is_empty())
+}
+
+fn profile_section(profile: &str) -> String {
+    format!("profile.{profile}")
+}
+
+/// Applies the values of the given profile on top of the rest of the config.
+///
+/// Profiles are defined in `[profile.NAME]` sections.  `url`, `org` and
+/// `project` override the `[defaults]` section, `token`, `api_key` and
+/// `token_command` the `[auth]` section.  Values of other sections are
+/// written as `section.key`.
+fn apply_profile(ini: &mut Ini, profile: &str) -> Result<()> {
+    let section = profile_section(profile);
+    let Some(props) = ini.section(Some(section.as_str())).cloned() else {
+        bail!(
+            "Profile {profile} is not defined. Add a [{section}] section to the \
+            {CONFIG_RC_FILE_NAME} file."
+        );
+    };
+    info!("Using profile {profile}");
+
+    // The credentials of the profile replace all other credentials.
+    const AUTH_KEYS: [&str; 3] = ["token", "api_key", "token_command"];
+    if AUTH_KEYS.iter().any(|key| props.contains_key(key)) {
+        for key in AUTH_KEYS {
+            ini.delete_from(Some("auth"), key);
+        }
+    }
+
+    for (key, value) in props.iter() {
+        let (target_section, target_key) = match key {
+            "url" | "org" | "project" => ("defaults", key),
+            key if AUTH_KEYS.contains(&key) => ("auth", key),
+            key => key.split_once('.').ok_or_else(|| {
+                format_err!(
+                    "Invalid key {key} in profile {profile}. Use section.key to set \
+                    values other than url, org, project and the credentials."
+                )
+            })?,
+        };
+        ini.set_to(
+            Some(target_section),
+            target_key.to_owned(),
+            value.to_owned(),
+        );
+    }
+
+    Ok(())
+}
+
+fn get_token_storage(ini: &Ini) -> Result<TokenStorage> {
+    Ok(match ini.get_from(Some("auth"), "token_storage") {
+        Some(name) => TokenStorage::from_name(name)?,
+        None => TokenStorage::default(),
+    })
+}
+
+/// The Sentry server that tokens in the keyring are stored for.
+fn keyring_url(ini: &Ini) -> String {
+    configured_url(ini)
+        .as_deref()
+        .unwrap_or(DEFAULT_URL)
+        .trim_end_matches('/')
+        .to_owned()
+}
+
+/// Loads the auth token from the keyring if `auth.token_storage` is
+/// `keyring`.
+fn load_keyring_token(ini: &Ini) -> Result<Option<String>> {
+    if get_token_storage(ini)? != TokenStorage::Keyring {
+        return Ok(None);
+    }
+
+    let url = keyring_url(ini);
+    Ok(keyring::load_token(&url).unwrap_or_else(|err| {
+        warn!("Could not load the auth token for {url} from the keyring: {err}");
         None
+    }))
+}
+
+/// Runs the configured `auth.token_command` in a shell and returns the token
+/// it prints to stdout.
+///
+/// The token is cached by command in memory and never written to disk.  It is
+/// reused until the given TTL expires, after which the command runs again to
+/// obtain a fresh token.
+fn run_token_command(command: &str, ttl: Duration) -> Result<String> {
+    let mut cache = TOKEN_COMMAND_CACHE.lock();
+    if let Some((token, obtained)) = cache.get(command) {
+        if obtained.elapsed() < ttl {
+            return Ok(token.clone());
+        }
     }
+
+    let output = shell_command(command)
+        .stdin(process::Stdio::inherit())
+        .stderr(process::Stdio::inherit())
+        .output()
+        .with_context(|| format!("Could not run auth token command `{command}`"))?;
+    if !output.status.success() {
+        bail!(
+            "Auth token command `{command}` failed with {}",
+            output.status
+        );
+    }
+
+    let token = String::from_utf8(output.stdout)
+        .context("Auth token command did not print a valid UTF-8 token")?
+        .trim()
+        .to_owned();
+    if token.is_empty() {
+        bail!("Auth token command `{command}` did not print a token");
+    }
+
+    cache.insert(command.to_owned(), (token.clone(), Instant::now()));
+    Ok(token)
 }
 
 /// Returns the URL configured in the SENTRY_URL environment variable or provided ini (in that
@@ -744,6 +1288,37 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_apply_profile() {
+        let mut ini = Ini::load_from_str(
+            "[defaults]
+url = https://sentry.io/
+org = wat-org
+
+[auth]
+token_command = get-token
+
+[profile.staging]
+url = https://staging.example.com/
+token = staging-token
+http.keepalive = false
+",
+        )
+        .unwrap();
+
+        apply_profile(&mut ini, "staging").unwrap();
+        assert_eq!(
+            ini.get_from(Some("defaults"), "url"),
+            Some("https://staging.example.com/")
+        );
+        assert_eq!(ini.get_from(Some("defaults"), "org"), Some("wat-org"));
+        assert_eq!(ini.get_from(Some("auth"), "token"), Some("staging-token"));
+        assert_eq!(ini.get_from(Some("auth"), "token_command"), None);
+        assert_eq!(ini.get_from(Some("http"), "keepalive"), Some("false"));
+
+        assert!(apply_profile(&mut ini, "prod").is_err());
+    }
+
     #[test]
     fn test_get_api_endpoint() {
         let config = Config {
@@ -751,11 +1326,19 @@ mod tests {
             process_bound: false,
             ini: Default::default(),
             cached_auth: None,
+            token_command: None,
             cached_base_url: "https://sentry.io/".to_string(),
             cached_headers: None,
             cached_log_level: LevelFilter::Off,
             cached_vcs_remote: String::new(),
             cached_token_data: None,
+            cached_read_only: false,
+            cached_rate_limit_behavior: RateLimitBehavior::Wait,
+            cached_ca_file: None,
+            cached_legacy_exit_codes: false,
+            cached_dump_response: None,
+            cached_max_connections: None,
+            profile: None,
         };
 
         assert_eq!(
@@ -782,4 +1365,111 @@ mod tests {
             "https://us.sentry.io/api/0/organizations/test-org/chunk-upload/"
         );
     }
+
+    #[test]
+    fn test_check_command_policy() {
+        let ini = Ini::load_from_str(
+            "[policy]\ndeny = upload-dsym, releases  delete\nallow = dif, releases, crons\n",
+        )
+        .unwrap();
+        let config = Config::from_file(PathBuf::from("/path/to/config"), ini).unwrap();
+
+        assert!(config.check_command_policy("releases list").is_ok());
+        assert!(config.check_command_policy("releases delete").is_err());
+        assert!(config.check_command_policy("releases deletes").is_ok());
+        assert!(config.check_command_policy("monitors run").is_ok());
+        assert!(config.check_command_policy("debug-files check").is_ok());
+        assert!(config.check_command_policy("debug-files upload").is_err());
+        assert!(config.check_command_policy("upload-dif").is_err());
+        assert!(config.check_command_policy("upload-proguard").is_err());
+    }
+
+    #[test]
+    fn test_resolve_max_connections() {
+        let ini = Ini::load_from_str("[http]\nmax_connections = 4\n").unwrap();
+        assert_eq!(resolve_max_connections(None, None, &ini).unwrap(), 4);
+        assert_eq!(resolve_max_connections(None, Some("8"), &ini).unwrap(), 8);
+        assert_eq!(
+            resolve_max_connections(Some(32), Some("8"), &ini).unwrap(),
+            32
+        );
+        assert!(resolve_max_connections(None, Some("0"), &ini).is_err());
+        assert!(resolve_max_connections(Some(0), None, &ini).is_err());
+
+        assert_eq!(
+            resolve_max_connections(None, None, &Ini::new()).unwrap(),
+            DEFAULT_MAX_CONNECTIONS
+        );
+    }
+
+    #[test]
+    fn test_get_pool_idle_timeout() {
+        let config = Config::from_file(PathBuf::from("/path/to/config"), Ini::new()).unwrap();
+        assert_eq!(
+            config.get_pool_idle_timeout().unwrap(),
+            Some(DEFAULT_POOL_IDLE_TIMEOUT)
+        );
+
+        let ini = Ini::load_from_str("[http]\npool_idle_timeout = 30\n").unwrap();
+        let config = Config::from_file(PathBuf::from("/path/to/config"), ini).unwrap();
+        assert_eq!(
+            config.get_pool_idle_timeout().unwrap(),
+            Some(Duration::from_secs(30))
+        );
+
+        let ini = Ini::load_from_str("[http]\npool_idle_timeout = 0\n").unwrap();
+        let config = Config::from_file(PathBuf::from("/path/to/config"), ini).unwrap();
+        assert_eq!(config.get_pool_idle_timeout().unwrap(), None);
+
+        let ini = Ini::load_from_str("[http]\npool_idle_timeout = soon\n").unwrap();
+        let config = Config::from_file(PathBuf::from("/path/to/config"), ini).unwrap();
+        assert!(config.get_pool_idle_timeout().is_err());
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_run_token_command_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let runs = dir.path().join("runs");
+        let command = format!("echo run >> {} && echo secret-token", runs.display());
+
+        let ttl = Duration::from_secs(60);
+
+        assert_eq!(run_token_command(&command, ttl).unwrap(), "secret-token");
+        assert_eq!(run_token_command(&command, ttl).unwrap(), "secret-token");
+        assert_eq!(fs::read_to_string(&runs).unwrap(), "run\n");
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_run_token_command_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let runs = dir.path().join("runs");
+        let command = format!("echo run >> {} && echo secret-token", runs.display());
+
+        assert_eq!(
+            run_token_command(&command, Duration::ZERO).unwrap(),
+            "secret-token"
+        );
+        assert_eq!(
+            run_token_command(&command, Duration::ZERO).unwrap(),
+            "secret-token"
+        );
+        assert_eq!(fs::read_to_string(&runs).unwrap(), "run\nrun\n");
+    }
+
+    #[test]
+    fn test_token_command_runs_on_demand() {
+        let ini = Ini::load_from_str("[auth]\ntoken_command = exit 1\n").unwrap();
+        let mut config = Config::from_file(PathBuf::from("/path/to/config"), ini).unwrap();
+        assert!(config.get_auth().is_err());
+
+        config
+            .set_auth(Auth::Key("explicit-key".to_owned()))
+            .unwrap();
+        assert!(matches!(
+            config.get_auth().unwrap(),
+            Some(Auth::Key(key)) if key == "explicit-key"
+        ));
+    }
 }
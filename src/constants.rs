@@ -21,14 +21,6 @@ pub const CONFIG_INI_FILE_PATH: &str = "sentry/sentrycli.ini";
 pub const RELEASE_REGISTRY_LATEST_URL: &str =
     "https://release-registry.services.sentry.io/apps/sentry-cli/latest";
 
-/// The file extension of the binary (.exe or empty string)
-#[cfg(windows)]
-pub const EXT: &str = ".exe";
-
-/// The file extension of the binary (.exe or empty string)
-#[cfg(not(windows))]
-pub const EXT: &str = "";
-
 /// Backoff multiplier (1.5 which is 50% increase per backoff).
 pub const DEFAULT_MULTIPLIER: f64 = 1.5;
 /// Backoff randomization factor (0 means no randomization).
@@ -39,6 +31,15 @@ pub const DEFAULT_INITIAL_INTERVAL: u64 = 1000;
 pub const DEFAULT_MAX_INTERVAL: u64 = 5000;
 /// Default number of retry attempts
 pub const DEFAULT_RETRIES: u32 = 5;
+/// Default maximum number of curl handles kept in the connection pool.
+///
+/// Every handle keeps its own connection cache, so this also bounds the number
+/// of connections to the server.
+pub const DEFAULT_MAX_CONNECTIONS: u32 = 16;
+/// Default time after which idle connections in the pool are closed.
+pub const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+/// Default time for which a token printed by `auth.token_command` is reused.
+pub const DEFAULT_TOKEN_COMMAND_TTL: Duration = Duration::from_secs(5 * 60);
 /// Default maximum file size of DIF uploads.
 pub const DEFAULT_MAX_DIF_SIZE: u64 = 2 * 1024 * 1024 * 1024; // 2GB
 /// Default maximum file size of a single file inside DIF bundle.
@@ -1,7 +1,11 @@
+use std::path::PathBuf;
+
 use anyhow::{bail, Result};
 use chrono::{DateTime, TimeZone, Utc};
 use clap::{Arg, ArgAction, Command};
 
+use crate::utils::value_parsers::trace_id_parser;
+
 fn validate_org(v: &str) -> Result<String, String> {
     if v.contains('/') || v == "." || v == ".." || v.contains(' ') {
         Err(
@@ -82,6 +86,13 @@ pub trait ArgExt: Sized {
     fn project_arg(self, multiple: bool) -> Self;
     fn release_arg(self) -> Self;
     fn version_arg(self, global: bool) -> Self;
+    fn format_arg(self) -> Self;
+    fn max_concurrency_arg(self) -> Self;
+    fn queue_dir_arg(self) -> Self;
+    fn provenance_arg(self) -> Self;
+    fn source_root_arg(self) -> Self;
+    fn yes_arg(self) -> Self;
+    fn trace_id_arg(self) -> Self;
 }
 
 impl ArgExt for Command {
@@ -138,4 +149,105 @@ impl ArgExt for Command {
                 .help("The version of the release"),
         )
     }
+
+    fn format_arg(self) -> Command {
+        self.arg(
+            Arg::new("format")
+                .value_name("FORMAT")
+                .long("format")
+                .value_parser(["table", "json", "ndjson"])
+                .default_value("table")
+                .help("The output format."),
+        )
+        .arg(
+            Arg::new("template")
+                .value_name("TEMPLATE")
+                .long("template")
+                .conflicts_with("format")
+                .help(
+                    "Print every item on its own line using the given template, e.g. \
+                    '{{version}} {{date_created}}'. The template can use the keys of \
+                    the JSON output.",
+                ),
+        )
+    }
+
+    fn max_concurrency_arg(self) -> Command {
+        self.arg(
+            Arg::new("max_concurrency")
+                .value_name("NUM")
+                .long("max-concurrency")
+                .value_parser(clap::value_parser!(u8).range(1..))
+                .help(
+                    "The maximum number of concurrent upload requests. \
+                    Defaults to the concurrency suggested by the server.",
+                ),
+        )
+    }
+
+    fn queue_dir_arg(self) -> Command {
+        self.arg(
+            Arg::new("queue_dir")
+                .value_name("DIR")
+                .long("queue-dir")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help(
+                    "Store envelopes in this directory if Sentry cannot be reached. \
+                    Send them later with `sentry-cli queue flush`.",
+                ),
+        )
+    }
+
+    fn provenance_arg(self) -> Command {
+        self.arg(
+            Arg::new("provenance")
+                .value_name("MODE")
+                .long("provenance")
+                .value_parser(["auto", "off"])
+                .help(
+                    "Attach the CI provider, workflow run URL, builder and git commit \
+                    to the uploaded bundles. `auto` detects them from the environment.",
+                ),
+        )
+    }
+
+    fn source_root_arg(self) -> Command {
+        self.arg(
+            Arg::new("source_root")
+                .value_name("PATH")
+                .long("source-root")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("")
+                .help(
+                    "Store the paths of sources below PATH relative to it in source \
+                    bundles. Without a value, the root of the git repository is used.",
+                ),
+        )
+    }
+    fn yes_arg(self) -> Command {
+        self.arg(
+            Arg::new("yes")
+                .long("yes")
+                .short('y')
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Do not ask for confirmation. Required when not running in an \
+                    interactive terminal.",
+                ),
+        )
+    }
+
+    fn trace_id_arg(self) -> Command {
+        self.arg(
+            Arg::new("trace_id")
+                .value_name("TRACE_ID")
+                .long("trace-id")
+                .value_parser(trace_id_parser)
+                .help(
+                    "Join the distributed trace with the given ID. Defaults to the trace \
+                    in the SENTRY_TRACE environment variable, or a new trace.",
+                ),
+        )
+    }
 }
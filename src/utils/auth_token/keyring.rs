@@ -0,0 +1,202 @@
+//! Storage of auth tokens in the credential store of the operating system.
+//!
+//! Enabled with `token_storage = keyring` in the `[auth]` section of the
+//! config.  Tokens are stored per Sentry server in the macOS Keychain, the
+//! Windows Credential Manager, or the Secret Service on Linux (through
+//! `secret-tool`).
+use anyhow::{bail, Result};
+
+/// The service name under which tokens are stored.
+const SERVICE: &str = "sentry-cli";
+
+/// Where auth tokens are stored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TokenStorage {
+    /// In plain text in the config file.
+    #[default]
+    File,
+    /// In the credential store of the operating system.
+    Keyring,
+}
+
+impl TokenStorage {
+    /// Parses the value of the `auth.token_storage` config key.
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "file" => Ok(TokenStorage::File),
+            "keyring" => Ok(TokenStorage::Keyring),
+            _ => bail!("Invalid token storage {name}, expected file or keyring"),
+        }
+    }
+}
+
+/// Stores the auth token for the Sentry server at `url`.
+pub fn store_token(url: &str, token: &str) -> Result<()> {
+    platform::store(url, token)
+}
+
+/// Loads the auth token for the Sentry server at `url`, if one is stored.
+pub fn load_token(url: &str) -> Result<Option<String>> {
+    platform::load(url)
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    use anyhow::{bail, Context, Result};
+
+    use super::SERVICE;
+
+    pub fn store(account: &str, token: &str) -> Result<()> {
+        // Commands are passed through stdin, so that the token does not show
+        // up in the process list.
+        let mut child = Command::new("security")
+            .arg("-i")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .context("Could not run `security`")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(
+                stdin,
+                "add-generic-password -U -s {} -a {} -w {}",
+                quote(SERVICE),
+                quote(account),
+                quote(token)
+            )?;
+        }
+        let status = child.wait()?;
+        if !status.success() {
+            bail!("Could not store the auth token in the keychain: {status}");
+        }
+        Ok(())
+    }
+
+    pub fn load(account: &str) -> Result<Option<String>> {
+        let output = Command::new("security")
+            .args(["find-generic-password", "-s", SERVICE, "-a", account, "-w"])
+            .stderr(Stdio::null())
+            .output()
+            .context("Could not run `security`")?;
+        // Exit code 44 means that no matching item was found.
+        match output.status.code() {
+            Some(0) => Ok(Some(String::from_utf8(output.stdout)?.trim().to_owned())),
+            Some(44) => Ok(None),
+            _ => bail!(
+                "Could not load the auth token from the keychain: {}",
+                output.status
+            ),
+        }
+    }
+
+    fn quote(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::ffi::OsStr;
+    use std::io;
+    use std::os::windows::ffi::OsStrExt;
+    use std::{mem, ptr, slice};
+
+    use anyhow::Result;
+    use winapi::shared::winerror::ERROR_NOT_FOUND;
+    use winapi::um::wincred::{
+        CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE,
+        CRED_TYPE_GENERIC, PCREDENTIALW,
+    };
+
+    use super::SERVICE;
+
+    fn target_name(account: &str) -> Vec<u16> {
+        OsStr::new(&format!("{SERVICE}:{account}"))
+            .encode_wide()
+            .chain(Some(0))
+            .collect()
+    }
+
+    pub fn store(account: &str, token: &str) -> Result<()> {
+        let mut target = target_name(account);
+        let mut blob = token.as_bytes().to_vec();
+
+        let mut credential: CREDENTIALW = unsafe { mem::zeroed() };
+        credential.Type = CRED_TYPE_GENERIC;
+        credential.TargetName = target.as_mut_ptr();
+        credential.CredentialBlobSize = blob.len() as u32;
+        credential.CredentialBlob = blob.as_mut_ptr();
+        credential.Persist = CRED_PERSIST_LOCAL_MACHINE;
+
+        if unsafe { CredWriteW(&mut credential, 0) } == 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+
+    pub fn load(account: &str) -> Result<Option<String>> {
+        let target = target_name(account);
+        let mut credential: PCREDENTIALW = ptr::null_mut();
+        if unsafe { CredReadW(target.as_ptr(), CRED_TYPE_GENERIC, 0, &mut credential) } == 0 {
+            let err = io::Error::last_os_error();
+            if err.raw_os_error() == Some(ERROR_NOT_FOUND as i32) {
+                return Ok(None);
+            }
+            return Err(err.into());
+        }
+
+        let token = unsafe {
+            let blob = slice::from_raw_parts(
+                (*credential).CredentialBlob,
+                (*credential).CredentialBlobSize as usize,
+            );
+            let token = String::from_utf8(blob.to_vec());
+            CredFree(credential.cast());
+            token
+        };
+        Ok(Some(token?))
+    }
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+mod platform {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    use anyhow::{bail, Context, Result};
+
+    use super::SERVICE;
+
+    pub fn store(account: &str, token: &str) -> Result<()> {
+        let mut child = Command::new("secret-tool")
+            .args(["store", "--label", "Sentry auth token"])
+            .args(["service", SERVICE, "account", account])
+            .stdin(Stdio::piped())
+            .spawn()
+            .context("Could not run `secret-tool`. Is libsecret installed?")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(token.as_bytes())?;
+        }
+        let status = child.wait()?;
+        if !status.success() {
+            bail!("Could not store the auth token with the Secret Service: {status}");
+        }
+        Ok(())
+    }
+
+    pub fn load(account: &str) -> Result<Option<String>> {
+        let output = Command::new("secret-tool")
+            .args(["lookup", "service", SERVICE, "account", account])
+            .stderr(Stdio::null())
+            .output()
+            .context("Could not run `secret-tool`. Is libsecret installed?")?;
+        // `secret-tool` fails without output if no matching item was found.
+        let token = String::from_utf8(output.stdout)?.trim().to_owned();
+        if token.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(token))
+    }
+}
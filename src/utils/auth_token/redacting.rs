@@ -3,6 +3,39 @@ use lazy_static::lazy_static;
 use regex::Regex;
 use std::borrow::Cow;
 
+/// Arguments whose values are sensitive: the auth token itself, where to read
+/// it from, and the legacy API key.
+const SENSITIVE_ARGS: &[&str] = &[
+    "--auth-token",
+    "--auth-token-fd",
+    "--auth-token-file",
+    "--api-key",
+];
+
+/// Redacts auth tokens and the values of sensitive arguments from a command
+/// line.
+pub fn redact_args<I>(args: I, replacement: &str) -> Vec<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut redact_next = false;
+    args.into_iter()
+        .map(|arg| {
+            if std::mem::take(&mut redact_next) {
+                return replacement.to_owned();
+            }
+            if let Some(name) = SENSITIVE_ARGS
+                .iter()
+                .find(|name| arg.starts_with(&format!("{name}=")))
+            {
+                return format!("{name}={replacement}");
+            }
+            redact_next = SENSITIVE_ARGS.contains(&arg.as_str());
+            redact_token_from_string(&arg, replacement).into_owned()
+        })
+        .collect()
+}
+
 pub fn redact_token_from_string<'r>(to_redact: &'r str, replacement: &'r str) -> Cow<'r, str> {
     if AuthToken::from(to_redact).format_recognized() {
         // The string is itself an auth token, redact the whole thing
@@ -24,7 +57,7 @@ pub fn redact_token_from_string<'r>(to_redact: &'r str, replacement: &'r str) ->
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::auth_token::redacting::redact_token_from_string;
+    use crate::utils::auth_token::redacting::{redact_args, redact_token_from_string};
 
     #[test]
     fn test_no_redaction() {
@@ -63,4 +96,32 @@ mod tests {
         let output = redact_token_from_string(input, "[REDACTED]");
         assert_eq!(expected_output, output);
     }
+
+    #[test]
+    fn test_redact_args() {
+        let args = [
+            "sentry-cli",
+            "--auth-token-fd",
+            "3",
+            "--auth-token-file=/run/secrets/token",
+            "--auth-token",
+            "secret",
+            "--api-key=secret",
+            "info",
+        ];
+        let output = redact_args(args.map(String::from), "[REDACTED]");
+        assert_eq!(
+            output,
+            [
+                "sentry-cli",
+                "--auth-token-fd",
+                "[REDACTED]",
+                "--auth-token-file=[REDACTED]",
+                "--auth-token",
+                "[REDACTED]",
+                "--api-key=[REDACTED]",
+                "info",
+            ]
+        );
+    }
 }
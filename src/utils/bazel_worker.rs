@@ -0,0 +1,274 @@
+//! Bazel's persistent worker protocol.
+//!
+//! A persistent worker is started once and then receives one `WorkRequest`
+//! per build action on stdin, answering each with a `WorkResponse` on
+//! stdout.  Bazel speaks either protobuf, where every message is prefixed
+//! with its length as a varint, or JSON, if the rule sets the
+//! `requires-worker-protocol: json` execution requirement.
+//!
+//! Only the fields used by sentry-cli are decoded.  Since the messages are
+//! tiny, the protobuf wire format is implemented here instead of generating
+//! code from `worker_protocol.proto`.
+use std::io::{self, Read, Write};
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// The encoding of the worker protocol messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkerProtocol {
+    Proto,
+    Json,
+}
+
+impl WorkerProtocol {
+    /// Returns the protocol for the given name, as accepted on the command
+    /// line.
+    pub fn from_name(name: &str) -> Option<WorkerProtocol> {
+        match name {
+            "proto" => Some(WorkerProtocol::Proto),
+            "json" => Some(WorkerProtocol::Json),
+            _ => None,
+        }
+    }
+
+    /// Reads the next request.  Returns `None` once Bazel closes stdin.
+    pub fn read_request<R: Read>(self, reader: &mut R) -> Result<Option<WorkRequest>> {
+        match self {
+            WorkerProtocol::Proto => {
+                let Some(len) = read_varint(reader)? else {
+                    return Ok(None);
+                };
+                let mut buf = vec![0; len as usize];
+                reader.read_exact(&mut buf)?;
+                WorkRequest::decode(&buf).map(Some)
+            }
+            WorkerProtocol::Json => {
+                let mut requests =
+                    serde_json::Deserializer::from_reader(reader).into_iter::<WorkRequest>();
+                Ok(requests.next().transpose()?)
+            }
+        }
+    }
+
+    /// Writes a response and flushes the writer.
+    pub fn write_response<W: Write>(self, writer: &mut W, response: &WorkResponse) -> Result<()> {
+        match self {
+            WorkerProtocol::Proto => {
+                let buf = response.encode();
+                write_varint(writer, buf.len() as u64)?;
+                writer.write_all(&buf)?;
+            }
+            WorkerProtocol::Json => {
+                serde_json::to_writer(&mut *writer, response)?;
+                writeln!(writer)?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// A request to run a single action.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WorkRequest {
+    /// The command line arguments of the action, with flag files expanded.
+    pub arguments: Vec<String>,
+    /// Non-zero for multiplex workers, and echoed in the response.
+    pub request_id: i32,
+    /// Set if Bazel asks to cancel the request with the same id.
+    pub cancel: bool,
+}
+
+/// The result of a request.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkResponse {
+    pub exit_code: i32,
+    /// Printed by Bazel if the action fails.
+    pub output: String,
+    pub request_id: i32,
+}
+
+const WIRE_VARINT: u64 = 0;
+const WIRE_I64: u64 = 1;
+const WIRE_LEN: u64 = 2;
+const WIRE_I32: u64 = 5;
+
+impl WorkRequest {
+    fn decode(mut buf: &[u8]) -> Result<WorkRequest> {
+        let mut request = WorkRequest::default();
+        while let Some(key) = read_varint(&mut buf)? {
+            match (key >> 3, key & 7) {
+                (1, WIRE_LEN) => {
+                    let value = read_len_delimited(&mut buf)?;
+                    request.arguments.push(String::from_utf8(value.to_vec())?);
+                }
+                (3, WIRE_VARINT) => request.request_id = read_required_varint(&mut buf)? as i32,
+                (4, WIRE_VARINT) => request.cancel = read_required_varint(&mut buf)? != 0,
+                // Skip inputs, verbosity, the sandbox dir and unknown fields.
+                (_, WIRE_VARINT) => {
+                    read_required_varint(&mut buf)?;
+                }
+                (_, WIRE_LEN) => {
+                    read_len_delimited(&mut buf)?;
+                }
+                (_, WIRE_I64) => skip(&mut buf, 8)?,
+                (_, WIRE_I32) => skip(&mut buf, 4)?,
+                (field, wire_type) => {
+                    bail!("Invalid work request: field {field} has wire type {wire_type}")
+                }
+            }
+        }
+        Ok(request)
+    }
+}
+
+impl WorkResponse {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        // Default values are omitted, like protobuf does.  Negative numbers
+        // are sign extended to ten bytes.
+        if self.exit_code != 0 {
+            write_varint(&mut buf, (1 << 3) | WIRE_VARINT).unwrap();
+            write_varint(&mut buf, self.exit_code as i64 as u64).unwrap();
+        }
+        if !self.output.is_empty() {
+            write_varint(&mut buf, (2 << 3) | WIRE_LEN).unwrap();
+            write_varint(&mut buf, self.output.len() as u64).unwrap();
+            buf.extend_from_slice(self.output.as_bytes());
+        }
+        if self.request_id != 0 {
+            write_varint(&mut buf, (3 << 3) | WIRE_VARINT).unwrap();
+            write_varint(&mut buf, self.request_id as i64 as u64).unwrap();
+        }
+        buf
+    }
+}
+
+/// Reads a varint.  Returns `None` at the end of the input.
+fn read_varint<R: Read>(reader: &mut R) -> io::Result<Option<u64>> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        if reader.read(&mut byte)? == 0 {
+            if shift == 0 {
+                return Ok(None);
+            }
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "varint too long",
+    ))
+}
+
+fn read_required_varint(buf: &mut &[u8]) -> io::Result<u64> {
+    read_varint(buf)?.ok_or_else(|| io::ErrorKind::UnexpectedEof.into())
+}
+
+fn read_len_delimited<'a>(buf: &mut &'a [u8]) -> io::Result<&'a [u8]> {
+    let len = read_required_varint(buf)? as usize;
+    if len > buf.len() {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    let (value, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(value)
+}
+
+fn skip(buf: &mut &[u8], len: usize) -> io::Result<()> {
+    if len > buf.len() {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    *buf = &buf[len..];
+    Ok(())
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    while value >= 0x80 {
+        writer.write_all(&[(value as u8 & 0x7f) | 0x80])?;
+        value >>= 7;
+    }
+    writer.write_all(&[value as u8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_proto_request() {
+        // arguments: ["upload", "é"], inputs: [{path: "a"}], request_id: 300
+        let message = b"\x0a\x06upload\x0a\x02\xc3\xa9\x12\x03\x0a\x01a\x18\xac\x02";
+        let mut input = vec![message.len() as u8];
+        input.extend_from_slice(message);
+        let mut reader = input.as_slice();
+
+        let request = WorkerProtocol::Proto.read_request(&mut reader).unwrap();
+        assert_eq!(
+            request,
+            Some(WorkRequest {
+                arguments: vec!["upload".into(), "é".into()],
+                request_id: 300,
+                cancel: false,
+            })
+        );
+        assert_eq!(
+            WorkerProtocol::Proto.read_request(&mut reader).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_write_proto_response() {
+        let response = WorkResponse {
+            exit_code: -1,
+            output: "error".into(),
+            request_id: 2,
+        };
+        let mut output = vec![];
+        WorkerProtocol::Proto
+            .write_response(&mut output, &response)
+            .unwrap();
+        assert_eq!(
+            output,
+            b"\x14\x08\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01\x12\x05error\x18\x02"
+        );
+    }
+
+    #[test]
+    fn test_json_protocol() {
+        let mut reader = &br#"{"arguments": ["upload"], "requestId": 1} {"arguments": []}"#[..];
+        let request = WorkerProtocol::Json.read_request(&mut reader).unwrap();
+        assert_eq!(request.unwrap().arguments, ["upload"]);
+        let request = WorkerProtocol::Json.read_request(&mut reader).unwrap();
+        assert_eq!(request, Some(WorkRequest::default()));
+        assert_eq!(
+            WorkerProtocol::Json.read_request(&mut reader).unwrap(),
+            None
+        );
+
+        let mut output = vec![];
+        WorkerProtocol::Json
+            .write_response(
+                &mut output,
+                &WorkResponse {
+                    exit_code: 1,
+                    output: "error".into(),
+                    request_id: 1,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            output,
+            b"{\"exitCode\":1,\"output\":\"error\",\"requestId\":1}\n"
+        );
+    }
+}
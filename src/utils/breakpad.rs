@@ -0,0 +1,177 @@
+//! Conversion of native debug information files to Breakpad symbols.
+//!
+//! The written symbol files contain the module, file, function, line and
+//! public symbol records.  Stack unwinding (`STACK`) records are not written.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::Result;
+use symbolic::common::{join_path, Name};
+use symbolic::debuginfo::{FileFormat, Function, LineInfo, Object};
+
+/// Returns the Breakpad name of the operating system an object was built for.
+fn os_name(object: &Object) -> &'static str {
+    match object.file_format() {
+        FileFormat::Elf => "Linux",
+        FileFormat::MachO => "mac",
+        FileFormat::Pe | FileFormat::Pdb => "windows",
+        _ => "unknown",
+    }
+}
+
+/// A line record of a function.
+struct Line {
+    address: u64,
+    size: Option<u64>,
+    line: u64,
+    file: String,
+}
+
+/// Collects the lines of a function, including the lines of its inlinees.
+fn collect_lines(function: &Function, lines: &mut Vec<Line>) {
+    let compilation_dir = String::from_utf8_lossy(function.compilation_dir);
+    let file_path = |info: &LineInfo| join_path(&compilation_dir, &info.file.path_str());
+    lines.extend(function.lines.iter().map(|info| Line {
+        address: info.address,
+        size: info.size,
+        line: info.line,
+        file: file_path(info),
+    }));
+    for inlinee in &function.inlinees {
+        collect_lines(inlinee, lines);
+    }
+}
+
+fn function_name<'a>(name: &'a Name<'a>) -> &'a str {
+    match name.as_str() {
+        "" => "<unknown>",
+        name => name,
+    }
+}
+
+/// Writes the Breakpad symbols of an object.
+///
+/// Like in all Breakpad symbols, addresses are relative to the load address
+/// of the object, which is how symbolic reports them already.
+pub fn write_breakpad_symbols<W: Write>(object: &Object, name: &str, out: &mut W) -> Result<()> {
+    writeln!(
+        out,
+        "MODULE {} {} {} {name}",
+        os_name(object),
+        object.arch().name(),
+        object.debug_id().breakpad()
+    )?;
+    if let Some(code_id) = object.code_id() {
+        writeln!(
+            out,
+            "INFO CODE_ID {} {name}",
+            code_id.as_str().to_uppercase()
+        )?;
+    }
+
+    let mut files = BTreeMap::new();
+    let mut functions = vec![];
+    let session = object.debug_session()?;
+    for function in session.functions() {
+        let function = function?;
+        if function.size == 0 {
+            continue;
+        }
+
+        let mut lines = vec![];
+        collect_lines(&function, &mut lines);
+        lines.sort_by_key(|line| line.address);
+        for line in &lines {
+            let next_id = files.len();
+            files.entry(line.file.clone()).or_insert(next_id);
+        }
+        functions.push((function.address, function.size, function.name, lines));
+    }
+    functions.sort_by_key(|(address, ..)| *address);
+
+    let mut file_records: Vec<_> = files.iter().collect();
+    file_records.sort_by_key(|(_, id)| **id);
+    for (path, id) in file_records {
+        writeln!(out, "FILE {id} {path}")?;
+    }
+
+    for (address, size, name, lines) in &functions {
+        writeln!(out, "FUNC {address:x} {size:x} 0 {}", function_name(name))?;
+        let end = address + size;
+        for (index, line) in lines.iter().enumerate() {
+            let next = lines.get(index + 1).map_or(end, |next| next.address);
+            let size = line.size.unwrap_or(next.saturating_sub(line.address));
+            if size == 0 {
+                continue;
+            }
+            writeln!(
+                out,
+                "{:x} {size:x} {} {}",
+                line.address, line.line, files[&line.file]
+            )?;
+        }
+    }
+
+    // Public records cover symbols without debug information.  The symbol
+    // map is sorted by address, just like the functions.
+    let mut function_index = 0;
+    for symbol in object.symbol_map().iter() {
+        let symbol_address = symbol.address;
+        while functions
+            .get(function_index)
+            .is_some_and(|(address, size, ..)| address + size <= symbol_address)
+        {
+            function_index += 1;
+        }
+        let covered = functions
+            .get(function_index)
+            .is_some_and(|(address, ..)| *address <= symbol_address);
+        if let (false, Some(name)) = (covered, symbol.name()) {
+            writeln!(out, "PUBLIC {symbol_address:x} 0 {name}")?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use symbolic::common::ByteView;
+
+    #[test]
+    fn test_write_breakpad_symbols() {
+        let data = ByteView::open("tests/integration/_fixtures/breakpad/fib").unwrap();
+        let object = Object::parse(&data).unwrap();
+
+        let mut out = vec![];
+        write_breakpad_symbols(&object, "fib", &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "MODULE Linux x86_64 B4764F72C14A555CF404E3D5436172700 fib\n\
+            INFO CODE_ID 724F76B44AC15C55F404E3D543617270215CEB55 fib\n\
+            FILE 0 /build/fib.c\n\
+            FUNC 1129 3b 0 fib\n\
+            1129 c 1 0\n\
+            1135 6 2 0\n\
+            113b 5 3 0\n\
+            1140 f 4 0\n\
+            114f d 4 0\n\
+            115c 2 4 0\n\
+            115e 6 5 0\n\
+            FUNC 1164 10 0 main\n\
+            1164 4 7 0\n\
+            1168 a 8 0\n\
+            1172 2 9 0\n\
+            PUBLIC 1000 0 _init\n\
+            PUBLIC 1040 0 _start\n\
+            PUBLIC 1070 0 deregister_tm_clones\n\
+            PUBLIC 10a0 0 register_tm_clones\n\
+            PUBLIC 10e0 0 __do_global_dtors_aux\n\
+            PUBLIC 1120 0 frame_dummy\n\
+            PUBLIC 1174 0 _fini\n"
+        );
+    }
+}
@@ -57,6 +57,24 @@ impl<T> Chunked<T> {
     }
 }
 
+impl<T> Chunked<T> {
+    /// Creates a new `ChunkedObject` from the given object and its previously
+    /// computed checksums, using the given chunk size.
+    pub fn from_checksums(
+        object: T,
+        checksum: Digest,
+        chunks: Vec<Digest>,
+        chunk_size: usize,
+    ) -> Self {
+        Self {
+            object,
+            checksum,
+            chunks,
+            chunk_size,
+        }
+    }
+}
+
 impl<T> Chunked<T>
 where
     T: AsRef<[u8]>,
@@ -98,7 +116,7 @@ impl<T> Assemblable for &T
 where
     T: Assemblable,
 {
-    fn name(&self) -> Cow<str> {
+    fn name(&self) -> Cow<'_, str> {
         (*self).name()
     }
 
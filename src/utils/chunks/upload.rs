@@ -88,9 +88,10 @@ where
                 // it shows up in the final report.
                 missing_objects.push(chunked_match);
             }
-            ChunkedFileState::Assembling => {
-                // This file is currently assembling. The caller will have to poll this file later
-                // until it either resolves or errors.
+            ChunkedFileState::Assembling | ChunkedFileState::Unknown => {
+                // This file is currently assembling, or in a state that this version does not
+                // know yet. The caller will have to poll this file later until it either
+                // resolves or errors.
                 missing_objects.push(chunked_match);
             }
             ChunkedFileState::NotFound => {
@@ -297,7 +298,9 @@ where
     let has_errors = !errored.is_empty();
     for (object, error) in errored {
         let fallback = match error.state {
-            ChunkedFileState::Assembling => Some("The file is still processing and not ready yet"),
+            ChunkedFileState::Assembling | ChunkedFileState::Unknown => {
+                Some("The file is still processing and not ready yet")
+            }
             ChunkedFileState::NotFound => Some("The file could not be saved"),
             _ => Some("An unknown error occurred"),
         };
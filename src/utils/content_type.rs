@@ -0,0 +1,112 @@
+//! Detection of content types for uploaded release files.
+use std::path::Path;
+use std::str;
+
+/// Returns the content type of a file based on its extension, falling back to
+/// sniffing its contents.
+///
+/// Textual content types carry a `charset=utf-8` parameter if the contents are
+/// valid UTF-8.
+pub fn detect_content_type(path: &Path, contents: &[u8]) -> String {
+    let mime = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(content_type_from_extension)
+        .unwrap_or_else(|| sniff_content_type(contents));
+
+    if is_textual(mime) && str::from_utf8(contents).is_ok() {
+        format!("{mime}; charset=utf-8")
+    } else {
+        mime.to_owned()
+    }
+}
+
+fn content_type_from_extension(ext: &str) -> Option<&'static str> {
+    Some(match ext.to_ascii_lowercase().as_str() {
+        "js" | "mjs" | "cjs" | "jsbundle" | "bundle" => "application/javascript",
+        "map" | "json" => "application/json",
+        "css" => "text/css",
+        "html" | "htm" => "text/html",
+        "txt" => "text/plain",
+        "ts" | "tsx" | "jsx" => "text/plain",
+        "svg" => "image/svg+xml",
+        "wasm" => "application/wasm",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        _ => return None,
+    })
+}
+
+fn sniff_content_type(contents: &[u8]) -> &'static str {
+    if contents.starts_with(b"\0asm") {
+        return "application/wasm";
+    }
+
+    let Ok(text) = str::from_utf8(contents) else {
+        return "application/octet-stream";
+    };
+
+    let text = text.trim_start();
+    if text.starts_with('{') && serde_json::from_str::<serde_json::Value>(text).is_ok() {
+        "application/json"
+    } else if starts_with_ignore_case(text, "<html") || starts_with_ignore_case(text, "<!doctype") {
+        "text/html"
+    } else {
+        "text/plain"
+    }
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+fn is_textual(mime: &str) -> bool {
+    mime.starts_with("text/")
+        || matches!(
+            mime,
+            "application/javascript" | "application/json" | "image/svg+xml"
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_content_type_by_extension() {
+        assert_eq!(
+            detect_content_type(Path::new("app.min.js"), b"console.log(1)"),
+            "application/javascript; charset=utf-8"
+        );
+        assert_eq!(
+            detect_content_type(Path::new("app.min.js.map"), b"{}"),
+            "application/json; charset=utf-8"
+        );
+        assert_eq!(
+            detect_content_type(Path::new("logo.PNG"), b"\x89PNG"),
+            "image/png"
+        );
+    }
+
+    #[test]
+    fn test_detect_content_type_by_contents() {
+        assert_eq!(
+            detect_content_type(Path::new("manifest"), b"  {\"version\": 3}"),
+            "application/json; charset=utf-8"
+        );
+        assert_eq!(
+            detect_content_type(Path::new("index"), b"<!DOCTYPE html><html></html>"),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(
+            detect_content_type(Path::new("module"), b"\0asm\x01\0\0\0"),
+            "application/wasm"
+        );
+        assert_eq!(
+            detect_content_type(Path::new("blob"), b"\xff\xfe\xfd"),
+            "application/octet-stream"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! A registry of deprecated commands and options.
+//!
+//! Every deprecated usage is registered in [`DEPRECATIONS`], together with
+//! the version in which it will be removed. Warnings for deprecated usages in
+//! the current invocation are emitted centrally, before the command runs.
+//!
+//! The `SENTRY_DEPRECATION_WARNINGS` environment variable controls how these
+//! warnings are emitted: `off` suppresses them, `json` prints them as JSON
+//! objects to stderr. By default, they are logged as warnings.
+use std::env;
+
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+use serde::Serialize;
+
+/// A deprecated command or option.
+#[derive(Debug, Serialize)]
+pub struct Deprecation {
+    /// The deprecated usage as it is invoked, e.g. `debug-files upload --force-foreground`.
+    pub usage: &'static str,
+    /// The version of sentry-cli in which the usage will be removed.
+    pub removal_version: &'static str,
+    /// Explains the deprecation and what to use instead.
+    pub message: &'static str,
+    /// The path of subcommands leading to the deprecated command.
+    #[serde(skip)]
+    command: &'static [&'static str],
+    /// The id of the deprecated argument, if only an argument is deprecated.
+    #[serde(skip)]
+    arg: Option<&'static str>,
+}
+
+/// Legacy commands that are aliases of another command, together with the
+/// path of subcommands they stand for.
+///
+/// Deprecated options of the aliased command are also reported when they are
+/// passed to the legacy command.
+const LEGACY_ALIASES: &[(&str, &[&str])] = &[
+    ("upload-dif", &["debug-files", "upload"]),
+    ("upload-dsym", &["debug-files", "upload"]),
+];
+
+/// Returns the matches of the subcommand at the given path, resolving
+/// legacy aliases.
+fn subcommand_matches<'a>(matches: &'a ArgMatches, command: &[&str]) -> Option<&'a ArgMatches> {
+    let direct = command
+        .iter()
+        .try_fold(matches, |matches, name| matches.subcommand_matches(name));
+    direct.or_else(|| {
+        LEGACY_ALIASES
+            .iter()
+            .filter(|(_, target)| *target == command)
+            .find_map(|(alias, _)| matches.subcommand_matches(alias))
+    })
+}
+
+impl Deprecation {
+    /// Returns whether this deprecated usage occurs in the given matches.
+    fn is_used(&self, matches: &ArgMatches) -> bool {
+        let Some(matches) = subcommand_matches(matches, self.command) else {
+            return false;
+        };
+
+        match self.arg {
+            Some(arg) => matches.value_source(arg) == Some(ValueSource::CommandLine),
+            None => true,
+        }
+    }
+}
+
+/// All deprecated commands and options.
+pub static DEPRECATIONS: &[Deprecation] = &[
+    Deprecation {
+        usage: "send-metric",
+        removal_version: "3.0.0",
+        message: "Sentry rejects all metrics sent after October 7, 2024. Learn more: \
+            https://sentry.zendesk.com/hc/en-us/articles/26369339769883-Upcoming-API-Changes-to-Metrics",
+        command: &["send-metric"],
+        arg: None,
+    },
+    Deprecation {
+        usage: "sourcemaps explain",
+        removal_version: "3.0.0",
+        message: "It has drifted from how sourcemap processing actually operates and its \
+            output may not be accurate.",
+        command: &["sourcemaps", "explain"],
+        arg: None,
+    },
+    Deprecation {
+        usage: "debug-files upload --force-foreground",
+        removal_version: "3.0.0",
+        message: "Foreground uploads are now the default behavior. This flag has no effect.",
+        command: &["debug-files", "upload"],
+        arg: Some("force_foreground"),
+    },
+    Deprecation {
+        usage: "debug-files upload --upload-symbol-maps",
+        removal_version: "3.0.0",
+        message: "Symbol maps are always uploaded. This flag has no effect.",
+        command: &["debug-files", "upload"],
+        arg: Some("upload_symbol_maps"),
+    },
+    Deprecation {
+        usage: "upload-dif",
+        removal_version: "3.0.0",
+        message: "Use `sentry-cli debug-files upload` instead. It accepts the same arguments \
+            and behaves identically.",
+        command: &["upload-dif"],
+        arg: None,
+    },
+    Deprecation {
+        usage: "upload-dsym",
+        removal_version: "3.0.0",
+        message: "Use `sentry-cli debug-files upload` instead. It accepts the same arguments \
+            and behaves identically.",
+        command: &["upload-dsym"],
+        arg: None,
+    },
+];
+
+/// Returns all deprecated usages in the given matches.
+pub fn find_deprecated_usages(matches: &ArgMatches) -> Vec<&'static Deprecation> {
+    DEPRECATIONS
+        .iter()
+        .filter(|deprecation| deprecation.is_used(matches))
+        .collect()
+}
+
+/// Emits a warning for every deprecated usage in the given matches.
+pub fn warn_deprecated_usages(matches: &ArgMatches) {
+    let mode = env::var("SENTRY_DEPRECATION_WARNINGS").unwrap_or_default();
+    if mode == "off" {
+        return;
+    }
+
+    for deprecation in find_deprecated_usages(matches) {
+        if mode == "json" {
+            if let Ok(json) = serde_json::to_string(deprecation) {
+                eprintln!("{json}");
+            }
+        } else {
+            log::warn!(
+                "DEPRECATION: `{}` is deprecated and will be removed in sentry-cli {}. {}",
+                deprecation.usage,
+                deprecation.removal_version,
+                deprecation.message
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::path::Path;
 use std::str;
@@ -98,7 +99,7 @@ impl str::FromStr for DifType {
 }
 
 /// Declares which features an object may have to be uploaded.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ObjectDifFeatures {
     /// Includes object files with debug information.
     pub debug: bool,
@@ -129,6 +130,41 @@ impl ObjectDifFeatures {
         }
     }
 
+    /// Returns the features of the given object.
+    pub fn of(object: &Object) -> Self {
+        ObjectDifFeatures {
+            debug: object.has_debug_info(),
+            symtab: object.has_symbols(),
+            unwind: object.has_unwind_info(),
+            sources: object.has_sources(),
+        }
+    }
+
+    /// Returns the features that are in either `self` or `other`.
+    pub fn union(self, other: Self) -> Self {
+        ObjectDifFeatures {
+            debug: self.debug || other.debug,
+            symtab: self.symtab || other.symtab,
+            unwind: self.unwind || other.unwind,
+            sources: self.sources || other.sources,
+        }
+    }
+
+    /// Returns whether all features of `other` are also in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.union(other) == self
+    }
+
+    /// Returns the features that are not in `self`.
+    pub fn missing(self) -> Self {
+        ObjectDifFeatures {
+            debug: !self.debug,
+            symtab: !self.symtab,
+            unwind: !self.unwind,
+            sources: !self.sources,
+        }
+    }
+
     fn has_some(self) -> bool {
         self.debug || self.symtab || self.unwind || self.sources
     }
@@ -461,3 +497,104 @@ impl DebuggingInformation for Archive<'_> {
         Ok(false)
     }
 }
+
+/// The name of the file in which Unity builds store the il2cpp line mapping.
+pub const IL2CPP_LINE_MAPPING_FILE_NAME: &str = "LineNumberMappings.json";
+
+/// An il2cpp line mapping written by Unity builds.
+///
+/// The mapping associates lines of the generated C++ files with the lines of
+/// the C# files they were generated from, in the form
+/// `{ "<cpp file>": { "<cs file>": { "<cpp line>": <cs line> } } }`.
+pub struct Il2CppLineMapping {
+    cpp_files: BTreeSet<String>,
+}
+
+impl Il2CppLineMapping {
+    /// Parses a line mapping, or returns `None` if the data is not one.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mapping: BTreeMap<String, BTreeMap<String, BTreeMap<String, u32>>> =
+            serde_json::from_slice(data).ok()?;
+        let cpp_files = mapping
+            .into_keys()
+            .map(|path| normalize_cpp_path(&path))
+            .collect();
+        Some(Il2CppLineMapping { cpp_files })
+    }
+
+    /// Returns whether the object contains code of the C++ files in this
+    /// mapping.
+    pub fn covers(&self, object: &Object) -> bool {
+        let Ok(session) = object.debug_session() else {
+            return false;
+        };
+        session
+            .files()
+            .filter_map(Result::ok)
+            .any(|file| self.contains_file(&file.abs_path_str()))
+    }
+
+    /// Returns whether the given C++ file is part of this mapping.
+    fn contains_file(&self, path: &str) -> bool {
+        self.cpp_files.contains(&normalize_cpp_path(path))
+    }
+}
+
+/// Normalizes the path of a C++ file for comparison.
+///
+/// Backslashes become forward slashes, and Windows paths are lowercased since
+/// they are case insensitive.
+fn normalize_cpp_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let bytes = path.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        path.to_lowercase()
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn test_parse_il2cpp_line_mapping() {
+        let mapping = Il2CppLineMapping::parse(
+            br#"{"C:\\build\\Bulk_Assembly-CSharp_0.cpp": {"C:\\game\\Player.cs": {"12": 7}}}"#,
+        )
+        .unwrap();
+        assert!(mapping.contains_file("C:/build/Bulk_Assembly-CSharp_0.cpp"));
+
+        assert!(Il2CppLineMapping::parse(b"{}").is_some());
+        assert!(Il2CppLineMapping::parse(br#"{"a.cpp": {"b.cs": {"1": "x"}}}"#).is_none());
+        assert!(Il2CppLineMapping::parse(b"[]").is_none());
+    }
+
+    #[test]
+    fn test_il2cpp_line_mapping_windows_paths() {
+        let mapping = Il2CppLineMapping::parse(
+            br#"{"C:\\Build\\Il2CppOutput\\Bulk_Assembly-CSharp_0.cpp": {}, "/build/Main.cpp": {}}"#,
+        )
+        .unwrap();
+        assert!(mapping.contains_file("c:/build/il2cppoutput/bulk_assembly-csharp_0.cpp"));
+        assert!(mapping.contains_file("C:\\BUILD\\Il2CppOutput\\Bulk_Assembly-CSharp_0.cpp"));
+        assert!(!mapping.contains_file("D:\\Build\\Il2CppOutput\\Bulk_Assembly-CSharp_0.cpp"));
+        assert!(mapping.contains_file("/build/Main.cpp"));
+        assert!(!mapping.contains_file("/build/main.cpp"));
+    }
+
+    #[test]
+    fn test_il2cpp_line_mapping_covers() {
+        let data = fs::read("tests/integration/_fixtures/breakpad/fib").unwrap();
+        let object = Object::parse(&data).unwrap();
+
+        let mapping = Il2CppLineMapping::parse(br#"{"/build/fib.c": {}}"#).unwrap();
+        assert!(mapping.covers(&object));
+
+        let mapping = Il2CppLineMapping::parse(br#"{"/build/other.c": {}}"#).unwrap();
+        assert!(!mapping.covers(&object));
+    }
+}
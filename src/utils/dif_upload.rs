@@ -7,7 +7,7 @@ use std::convert::TryInto;
 use std::ffi::{OsStr, OsString};
 use std::fmt::{self, Display, Formatter};
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, Read, Seek, Write};
+use std::io::{BufReader, BufWriter, Cursor, Read, Seek, Write};
 use std::iter::IntoIterator;
 use std::mem::transmute;
 use std::ops::Deref;
@@ -20,11 +20,16 @@ use anyhow::{bail, format_err, Error, Result};
 use console::style;
 use indicatif::HumanBytes;
 use log::{debug, info, warn};
+use rayon::prelude::*;
 use sha1_smol::Digest;
 use symbolic::common::{Arch, AsSelf, ByteView, DebugId, SelfCell, Uuid};
+use symbolic::debuginfo::dwarf::Dwarf;
+use symbolic::debuginfo::elf::ElfObject;
 use symbolic::debuginfo::macho::{BcSymbolMap, UuidMapping};
 use symbolic::debuginfo::pe::PeObject;
-use symbolic::debuginfo::sourcebundle::{SourceBundleWriter, SourceFileDescriptor};
+use symbolic::debuginfo::sourcebundle::{
+    SourceBundle, SourceBundleWriter, SourceFileDescriptor, SourceFileInfo,
+};
 use symbolic::debuginfo::{Archive, FileEntry, FileFormat, Object};
 use symbolic::il2cpp::ObjectLineMapping;
 use walkdir::WalkDir;
@@ -37,10 +42,16 @@ use crate::config::Config;
 use crate::constants::{DEFAULT_MAX_DIF_SIZE, DEFAULT_MAX_WAIT};
 use crate::utils::chunks;
 use crate::utils::chunks::{Assemblable, BatchedSliceExt, ChunkOptions, Chunked, ItemSize};
-use crate::utils::dif::ObjectDifFeatures;
+use crate::utils::dif::{Il2CppLineMapping, ObjectDifFeatures, IL2CPP_LINE_MAPPING_FILE_NAME};
 use crate::utils::fs::{get_sha1_checksum, TempDir, TempFile};
 use crate::utils::progress::{ProgressBar, ProgressStyle};
+use crate::utils::provenance::Provenance;
+use crate::utils::remote_sources::RemoteSources;
+use crate::utils::scan_journal::{JournalKey, ScanJournal};
+use crate::utils::scrubbing::Scrubber;
+use crate::utils::source_root::SourceRoot;
 use crate::utils::ui::{copy_with_progress, make_byte_progress_bar};
+use crate::utils::upload_cache::UploadCache;
 
 /// A debug info file on the server.
 pub use crate::api::DebugInfoFile;
@@ -89,6 +100,7 @@ struct DifMatch<'data> {
     name: String,
     debug_id: Option<DebugId>,
     attachments: Option<BTreeMap<String, ByteView<'static>>>,
+    journal_key: Option<JournalKey>,
 }
 
 impl<'data> DifMatch<'data> {
@@ -107,6 +119,7 @@ impl<'data> DifMatch<'data> {
             name: name.into(),
             debug_id,
             attachments: None,
+            journal_key: None,
         })
     }
 
@@ -127,8 +140,25 @@ impl<'data> DifMatch<'data> {
             name: name.into(),
             debug_id,
             attachments: None,
+            journal_key: None,
         })
     }
+
+    /// Creates a [`DifMatch`] from an il2cpp line mapping file written by a
+    /// Unity build, for the object with the given `debug_id`.
+    fn from_line_mapping(debug_id: DebugId, name: String, data: ByteView<'static>) -> Result<Self> {
+        let dif = SelfCell::try_new(data, |_| Ok::<_, Error>(ParsedDif::Il2Cpp))?;
+
+        Ok(Self {
+            _backing: None,
+            dif,
+            name,
+            debug_id: Some(debug_id),
+            attachments: None,
+            journal_key: None,
+        })
+    }
+
     /// Creates a [`DifMatch`] from a `.bcsymbolmap` file.
     ///
     /// The `uuid` is the DebugID of the symbolmap while `name` is the filename of the file.
@@ -144,6 +174,7 @@ impl<'data> DifMatch<'data> {
             name,
             debug_id: Some(uuid),
             attachments: None,
+            journal_key: None,
         })
     }
 
@@ -158,6 +189,32 @@ impl<'data> DifMatch<'data> {
             name,
             debug_id: Some(uuid),
             attachments: None,
+            journal_key: None,
+        })
+    }
+
+    /// Creates a [`DifMatch`] from a file holding debug information of another
+    /// object file, such as a separate debug file or a DWARF package.
+    ///
+    /// The file keeps its own debug identifier. DWARF packages do not have
+    /// one, so they are assigned the `debug_id` of the object file instead.
+    fn from_companion(path: &Path, name: String, debug_id: DebugId) -> Result<Self> {
+        let buffer = ByteView::open(path).map_err(Error::new)?;
+        let dif = SelfCell::try_new(buffer, |b| {
+            Object::parse(unsafe { &*b }).map(|object| ParsedDif::Object(Box::new(object)))
+        })?;
+        let debug_id = match dif.get() {
+            ParsedDif::Object(object) if !object.debug_id().is_nil() => object.debug_id(),
+            _ => debug_id,
+        };
+
+        Ok(DifMatch {
+            _backing: None,
+            dif,
+            name,
+            debug_id: Some(debug_id),
+            attachments: None,
+            journal_key: JournalKey::new(path, &debug_id.to_string()),
         })
     }
 
@@ -302,7 +359,7 @@ impl Display for DifMatch<'_> {
 
 impl Assemblable for DifMatch<'_> {
     /// A DIF's name is its file name.
-    fn name(&self) -> Cow<str> {
+    fn name(&self) -> Cow<'_, str> {
         self.file_name().into()
     }
 
@@ -589,10 +646,7 @@ fn find_uuid_plists(
     //        └─ DWARF
     //           └─ App
     let plist_name = format!("{:X}.plist", uuid.as_hyphenated());
-    let plist = match source.get_relative(format!("../{}", &plist_name)) {
-        Some(plist) => plist,
-        None => return None,
-    };
+    let plist = source.get_relative(format!("../{}", &plist_name))?;
 
     let mut plists = BTreeMap::new();
     plists.insert(plist_name, plist);
@@ -629,6 +683,91 @@ fn fix_pdb_ages(difs: &mut [DifMatch<'_>], age_overrides: &BTreeMap<Uuid, u32>)
     }
 }
 
+/// Which files to upload for a debug identifier that has both native debug
+/// information files and Breakpad symbols.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum BreakpadPairing {
+    /// The native files include all features of the Breakpad symbols.
+    Native,
+    /// The Breakpad symbols include all features of the native files.
+    Breakpad,
+    /// Both have features the other lacks.
+    Both,
+}
+
+impl BreakpadPairing {
+    /// Prefers native files over Breakpad symbols with the same features.
+    fn decide(native: ObjectDifFeatures, breakpad: ObjectDifFeatures) -> Self {
+        if native.contains(breakpad) {
+            BreakpadPairing::Native
+        } else if breakpad.contains(native) {
+            BreakpadPairing::Breakpad
+        } else {
+            BreakpadPairing::Both
+        }
+    }
+}
+
+fn is_native_format(format: FileFormat) -> bool {
+    matches!(
+        format,
+        FileFormat::Elf | FileFormat::MachO | FileFormat::Pdb | FileFormat::Pe | FileFormat::Wasm
+    )
+}
+
+/// Removes redundant files where both native debug information files and
+/// Breakpad symbols were found for the same debug identifier, for instance
+/// when a pipeline converts binaries with `dump_syms` next to the originals.
+fn pair_breakpad_symbols(difs: &mut Vec<DifMatch<'_>>) {
+    let mut native = BTreeMap::new();
+    let mut breakpad = BTreeMap::new();
+    for dif in difs.iter() {
+        let (Some(object), Some(debug_id)) = (dif.object(), dif.debug_id()) else {
+            continue;
+        };
+        let features = match object.file_format() {
+            FileFormat::Breakpad => breakpad.entry(debug_id),
+            format if is_native_format(format) => native.entry(debug_id),
+            _ => continue,
+        }
+        .or_insert_with(ObjectDifFeatures::none);
+        *features = features.union(ObjectDifFeatures::of(object));
+    }
+
+    let mut decisions = BTreeMap::new();
+    for (debug_id, breakpad_features) in breakpad {
+        let Some(native_features) = native.get(&debug_id) else {
+            continue;
+        };
+        let decision = BreakpadPairing::decide(*native_features, breakpad_features);
+        let message = match decision {
+            BreakpadPairing::Native => {
+                "uploading native files only, Breakpad symbols add no features".to_owned()
+            }
+            BreakpadPairing::Breakpad => {
+                "uploading Breakpad symbols only, native files add no features".to_owned()
+            }
+            BreakpadPairing::Both => format!(
+                "uploading native files ({native_features}) and Breakpad symbols \
+                ({breakpad_features})"
+            ),
+        };
+        println!("{} {debug_id}: {message}", style(">").dim());
+        decisions.insert(debug_id, decision);
+    }
+
+    difs.retain(|dif| {
+        let (Some(object), Some(debug_id)) = (dif.object(), dif.debug_id()) else {
+            return true;
+        };
+        match (object.file_format(), decisions.get(&debug_id)) {
+            (FileFormat::Breakpad, Some(BreakpadPairing::Native)) => false,
+            (format, Some(BreakpadPairing::Breakpad)) => !is_native_format(format),
+            _ => true,
+        }
+    });
+}
+
 /// Searches matching debug information files.
 fn search_difs(options: &DifUpload) -> Result<Vec<DifMatch<'static>>> {
     let progress_style = ProgressStyle::default_spinner().template(
@@ -641,6 +780,9 @@ fn search_difs(options: &DifUpload) -> Result<Vec<DifMatch<'static>>> {
     pb.set_style(progress_style);
 
     let mut age_overrides = BTreeMap::new();
+    let mut split_dwarf = Vec::new();
+    let mut scanned = BTreeSet::new();
+    let mut line_mappings = Vec::new();
     let mut collected = Vec::new();
     for base_path in &options.paths {
         if base_path == Path::new("") {
@@ -655,8 +797,17 @@ fn search_difs(options: &DifUpload) -> Result<Vec<DifMatch<'static>>> {
             pb.set_message(&name);
 
             if Archive::peek(&buffer) != FileFormat::Unknown {
-                let mut difs =
-                    collect_object_dif(source, name, buffer, options, &mut age_overrides);
+                if let DifSource::FileSystem(path) = &source {
+                    scanned.extend(path.canonicalize());
+                }
+                let mut difs = collect_object_dif(
+                    source,
+                    name,
+                    buffer,
+                    options,
+                    &mut age_overrides,
+                    &mut split_dwarf,
+                );
                 collected.append(difs.as_mut());
             } else if BcSymbolMap::test(&buffer) {
                 if let Some(dif) = collect_auxdif(name, buffer, options, AuxDifKind::BcSymbolMap) {
@@ -666,6 +817,10 @@ fn search_difs(options: &DifUpload) -> Result<Vec<DifMatch<'static>>> {
                 if let Some(dif) = collect_auxdif(name, buffer, options, AuxDifKind::UuidMap) {
                     collected.push(dif);
                 }
+            } else if Path::new(&name).file_name()
+                == Some(OsStr::new(IL2CPP_LINE_MAPPING_FILE_NAME))
+            {
+                line_mappings.push((name, buffer));
             };
 
             pb.set_prefix(&collected.len().to_string());
@@ -673,12 +828,21 @@ fn search_difs(options: &DifUpload) -> Result<Vec<DifMatch<'static>>> {
         })?;
     }
 
+    collected.extend(collect_split_dwarf(split_dwarf, scanned, options));
+
     if !age_overrides.is_empty() {
         fix_pdb_ages(&mut collected, &age_overrides);
     }
 
     pb.finish_and_clear();
 
+    // Collected after the progress bar is cleared, so that warnings about
+    // skipped mappings are not swallowed by a hidden progress bar.
+    let line_mappings = collect_il2cpp_line_mappings(line_mappings, &collected, options);
+    collected.extend(line_mappings);
+
+    pair_breakpad_symbols(&mut collected);
+
     print!(
         "{} Found {} debug information {}",
         style(">").dim(),
@@ -774,6 +938,51 @@ fn collect_auxdif<'a>(
     Some(dif)
 }
 
+/// Collects il2cpp line mapping files written by Unity builds into [`DifMatch`]es.
+///
+/// The files have no debug identifier of their own. Instead, each file is
+/// uploaded for every native object in `difs` that contains code of the C++
+/// files it maps.
+fn collect_il2cpp_line_mappings<'a>(
+    files: Vec<(String, ByteView<'static>)>,
+    difs: &[DifMatch<'_>],
+    options: &DifUpload,
+) -> Vec<DifMatch<'a>> {
+    let mut collected = Vec::new();
+    for (name, buffer) in files {
+        let Some(mapping) = Il2CppLineMapping::parse(&buffer) else {
+            warn!("Skipping invalid il2cpp line mapping file {name}");
+            continue;
+        };
+
+        let debug_ids: BTreeSet<_> = difs
+            .iter()
+            .filter(|dif| {
+                dif.object().is_some_and(|object| {
+                    is_native_format(object.file_format()) && mapping.covers(object)
+                })
+            })
+            .filter_map(|dif| dif.debug_id())
+            .collect();
+        if debug_ids.is_empty() {
+            warn!(
+                "Skipping il2cpp line mapping file {name}: \
+                no debug information file contains its C++ code"
+            );
+            continue;
+        }
+
+        for debug_id in debug_ids {
+            match DifMatch::from_line_mapping(debug_id, name.clone(), buffer.clone()) {
+                Ok(dif) if options.validate_dif(&dif) => collected.push(dif),
+                Ok(_) => (),
+                Err(err) => warn!("Skipping invalid il2cpp line mapping file {name}: {err}"),
+            }
+        }
+    }
+    collected
+}
+
 /// Processes and [`DifSource`] which is expected to be an object file.
 fn collect_object_dif<'a>(
     mut source: DifSource<'_>,
@@ -781,6 +990,7 @@ fn collect_object_dif<'a>(
     buffer: ByteView<'static>,
     options: &DifUpload,
     age_overrides: &mut BTreeMap<Uuid, u32>,
+    split_dwarf: &mut Vec<SplitDwarf>,
 ) -> Vec<DifMatch<'a>> {
     let mut collected = Vec::with_capacity(2);
 
@@ -808,6 +1018,13 @@ fn collect_object_dif<'a>(
         }
     };
 
+    // Objects read directly from the file system can reuse checksums computed
+    // during previous scans, as long as the file has not been modified.
+    let journal_path = match source {
+        DifSource::FileSystem(path) => Some(path),
+        DifSource::Zip(..) => None,
+    };
+
     // Each `FatObject` might contain multiple matching objects, each of
     // which needs to retain a reference to the original fat file. We
     // create a shared instance here and clone it into `DifMatch`es
@@ -842,6 +1059,12 @@ fn collect_object_dif<'a>(
             }
         };
 
+        // ELF files may keep their debug information in separate files, which
+        // are resolved once the scan has completed.
+        if let (Object::Elf(elf), Some(path)) = (&object, journal_path) {
+            split_dwarf.extend(SplitDwarf::find(elf, path, &name, id));
+        }
+
         // Store a mapping of "age" values for all encountered PE files,
         // regardless of whether they will be uploaded. This is used later
         // to fix up PDB files.
@@ -875,6 +1098,7 @@ fn collect_object_dif<'a>(
             name: name.clone(),
             debug_id: Some(id),
             attachments,
+            journal_key: journal_path.and_then(|path| JournalKey::new(path, &id.to_string())),
         };
 
         // Skip this file if we don't want to process it.
@@ -888,6 +1112,209 @@ fn collect_object_dif<'a>(
     collected
 }
 
+/// The folder holding separate debug files of the system, as used by GDB.
+const GLOBAL_DEBUG_DIR: &str = "/usr/lib/debug";
+
+/// The files holding debug information of an ELF file outside of the file
+/// itself.
+#[derive(Debug)]
+struct SplitDwarf {
+    /// The path of the ELF file.
+    path: PathBuf,
+    name: String,
+    debug_id: DebugId,
+    /// Separate debug files referenced by `.gnu_debuglink` and
+    /// `.gnu_debugaltlink`, and the DWARF package next to the ELF file, along
+    /// with the debug ID used for files that do not have one.
+    companions: Vec<(PathBuf, DebugId)>,
+    /// Whether the ELF file references `.dwo` files, but no DWARF package
+    /// was found.
+    needs_dwp: bool,
+}
+
+impl SplitDwarf {
+    /// Finds the split debug information of the ELF file at `path`.
+    ///
+    /// Separate debug files are looked up in the same locations as GDB does:
+    /// next to the ELF file, in a `.debug` folder next to it, and in the
+    /// global `/usr/lib/debug` folder. The dwz supplementary file may be
+    /// referenced by the ELF file or by its separate debug file.
+    fn find(elf: &ElfObject, path: &Path, name: &str, debug_id: DebugId) -> Option<Self> {
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let debug_dirs = [PathBuf::from(GLOBAL_DEBUG_DIR)];
+        let mut companions = vec![];
+
+        if let Ok(Some(link)) = elf.debug_link() {
+            let file_name = link.filename().to_string_lossy();
+            let global_dir = dir
+                .canonicalize()
+                .ok()
+                .and_then(|dir| dir.strip_prefix("/").map(Path::to_owned).ok())
+                .map(|dir| Path::new(GLOBAL_DEBUG_DIR).join(dir));
+            let debug_file = [Some(dir.to_owned()), Some(dir.join(".debug")), global_dir]
+                .into_iter()
+                .flatten()
+                .map(|dir| dir.join(file_name.as_ref()))
+                .find(|path| path.is_file());
+
+            if let Some(debug_file) = debug_file {
+                let alt_link = ByteView::open(&debug_file).ok().and_then(|buffer| {
+                    let debug_elf = ElfObject::parse(&buffer).ok()?;
+                    find_alt_link(&debug_elf, &debug_dirs)
+                });
+                companions.push((debug_file, debug_id));
+                companions.extend(alt_link);
+            }
+        }
+
+        companions.extend(find_alt_link(elf, &debug_dirs));
+
+        let references_dwo = elf
+            .section("debug_str")
+            .is_some_and(|section| memmem(&section.data, b".dwo\0"));
+        let mut dwp_path = path.as_os_str().to_owned();
+        dwp_path.push(".dwp");
+        let dwp_path = PathBuf::from(dwp_path);
+        let has_dwp = dwp_path.is_file();
+        if has_dwp {
+            companions.push((dwp_path, debug_id));
+        }
+
+        let split_dwarf = SplitDwarf {
+            path: path.to_owned(),
+            name: name.to_owned(),
+            debug_id,
+            companions,
+            needs_dwp: references_dwo && !has_dwp,
+        };
+        (!split_dwarf.companions.is_empty() || split_dwarf.needs_dwp).then_some(split_dwarf)
+    }
+
+    /// Packages the `.dwo` files referenced by the ELF file into a DWARF
+    /// package using `llvm-dwp` or `dwp`.
+    fn package_dwo_files<'a>(&self) -> Result<DifMatch<'a>> {
+        let dwp = which("llvm-dwp")
+            .or_else(|_| which("dwp"))
+            .map_err(|_| format_err!("Command `llvm-dwp` or `dwp` not found"))?;
+        let temp_file = TempFile::create()?;
+        let output = Command::new(dwp)
+            .arg("-e")
+            .arg(&self.path)
+            .arg("-o")
+            .arg(temp_file.path())
+            .output()?;
+        if !output.status.success() {
+            bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        DifMatch::from_temp_object(temp_file, format!("{}.dwp", self.name), Some(self.debug_id))
+    }
+}
+
+/// Finds the dwz supplementary file referenced by the `.gnu_debugaltlink`
+/// section of the ELF file.
+///
+/// Returns the path of the file and the debug ID derived from its build ID.
+fn find_alt_link(elf: &ElfObject, debug_dirs: &[PathBuf]) -> Option<(PathBuf, DebugId)> {
+    let section = elf.section("gnu_debugaltlink")?;
+    // The fifth byte of the ELF identification is 1 for little endian files.
+    let little_endian = elf.data().get(5) == Some(&1);
+    resolve_alt_link(&section.data, little_endian, debug_dirs)
+}
+
+/// Resolves the contents of a `.gnu_debugaltlink` section, which consist of
+/// the file name of the supplementary file followed by its build ID.
+///
+/// Like GDB, the file is looked up by its build ID in the `.build-id` folders
+/// of the debug folders. The file name is only used if it is absolute, since
+/// relative names are relative to the debug file as it was installed.
+fn resolve_alt_link(
+    data: &[u8],
+    little_endian: bool,
+    debug_dirs: &[PathBuf],
+) -> Option<(PathBuf, DebugId)> {
+    let separator = data.iter().position(|b| *b == 0)?;
+    let (name, build_id) = (&data[..separator], &data[separator + 1..]);
+    if build_id.len() < 2 {
+        return None;
+    }
+
+    let build_id_hex: String = build_id.iter().map(|b| format!("{b:02x}")).collect();
+    let name = PathBuf::from(String::from_utf8_lossy(name).as_ref());
+    debug_dirs
+        .iter()
+        .map(|dir| {
+            dir.join(".build-id")
+                .join(&build_id_hex[..2])
+                .join(format!("{}.debug", &build_id_hex[2..]))
+        })
+        .chain(name.is_absolute().then_some(name))
+        .find(|path| path.is_file())
+        .map(|path| (path, build_id_debug_id(build_id, little_endian)))
+}
+
+/// Converts an ELF build ID into a debug ID, the same way as `symbolic` does
+/// for the build ID of an ELF file.
+fn build_id_debug_id(build_id: &[u8], little_endian: bool) -> DebugId {
+    let mut data = [0u8; 16];
+    let len = build_id.len().min(16);
+    data[..len].copy_from_slice(&build_id[..len]);
+    // Convert the identifier of little endian files to the byte order of a UUID.
+    if little_endian {
+        data[0..4].reverse();
+        data[4..6].reverse();
+        data[6..8].reverse();
+    }
+    DebugId::from_uuid(Uuid::from_bytes(data))
+}
+
+fn memmem(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// Collects the split debug information of ELF files found during the scan.
+///
+/// Files that were already found by the scan are skipped.
+fn collect_split_dwarf<'a>(
+    binaries: Vec<SplitDwarf>,
+    mut scanned: BTreeSet<PathBuf>,
+    options: &DifUpload,
+) -> Vec<DifMatch<'a>> {
+    let mut collected = vec![];
+    for binary in binaries {
+        for (path, debug_id) in &binary.companions {
+            let Ok(canonical) = path.canonicalize() else {
+                continue;
+            };
+            if !scanned.insert(canonical) {
+                continue;
+            }
+
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            debug!("found split debug information {} for {}", name, binary.name);
+            match DifMatch::from_companion(path, name, *debug_id) {
+                Ok(dif) if options.validate_dif(&dif) => collected.push(dif),
+                Ok(_) => (),
+                Err(err) => warn!("Skipping invalid debug file {}: {err}", path.display()),
+            }
+        }
+
+        if binary.needs_dwp {
+            match binary.package_dwo_files() {
+                Ok(dif) if options.validate_dif(&dif) => collected.push(dif),
+                Ok(_) => (),
+                Err(err) => warn!("Could not package the .dwo files of {}: {err}", binary.name),
+            }
+        }
+    }
+    collected
+}
+
 /// Resolves BCSymbolMaps and replaces hidden symbols in a `DifMatch` using
 /// `dsymutil`. If successful, this will return a new `DifMatch` based on a
 /// temporary file. The original dSYM is not touched.
@@ -1103,13 +1530,78 @@ pub fn filter_bad_sources(
     true
 }
 
+/// Rewrites the source bundle at the given path after it has been written.
+///
+/// Sources below the `source_root` are stored with relative paths, and the
+/// contents of all sources are passed through the `scrubber`.
+pub fn rewrite_source_bundle(
+    path: &Path,
+    source_root: Option<&SourceRoot>,
+    scrubber: Option<&Scrubber>,
+) -> Result<()> {
+    if source_root.is_none() && scrubber.is_none() {
+        return Ok(());
+    }
+
+    let data = fs::read(path)?;
+    let bundle = SourceBundle::parse(&data)?;
+    let session = bundle.debug_session()?;
+
+    let mut writer = SourceBundleWriter::create(path)?;
+    writer.set_attribute("debug_id", bundle.debug_id().to_string());
+    writer.set_attribute("arch", bundle.arch().to_string());
+    if let Some(code_id) = bundle.code_id() {
+        writer.set_attribute("code_id", code_id.to_string());
+    }
+    if let Some(name) = bundle.name() {
+        writer.set_attribute("object_name", name.to_owned());
+    }
+    if let Some(source_root) = source_root {
+        writer.set_attribute("source_root_sha1", source_root.hash());
+    }
+
+    for file in session.files() {
+        let abs_path = file?.abs_path_str();
+        let Some(source) = session.source_by_path(&abs_path)? else {
+            continue;
+        };
+        let Some(contents) = source.contents() else {
+            continue;
+        };
+
+        let path = source_root
+            .and_then(|source_root| source_root.relative_path(&abs_path))
+            .unwrap_or(abs_path);
+        let contents = match scrubber {
+            Some(scrubber) => scrubber.scrub_str(contents),
+            None => Cow::Borrowed(contents),
+        };
+
+        let mut info = SourceFileInfo::new();
+        info.set_ty(source.ty());
+        info.set_path(path.clone());
+        let bundle_path = path.trim_start_matches('/').replace(':', "");
+        writer.add_file(bundle_path, contents.as_bytes(), info)?;
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
 /// Creates a source bundle containing the source files referenced by the input DIFs.
 ///
 /// If `include_il2cpp_sources` is true, C# files referenced by il2cpp line mapping comments
-/// will also be included.
+/// will also be included. The `provenance`, if given, is stored in the bundle manifests.
+/// If a `source_root` is given, sources below it are stored with relative paths. Sources are
+/// scrubbed with the `scrubber`, if one is configured. With `remote_sources`, sources are read
+/// from the remote archive instead of the local file system.
 fn create_source_bundles<'a>(
     difs: &[DifMatch<'a>],
     include_il2cpp_sources: bool,
+    provenance: Option<&Provenance>,
+    source_root: Option<&SourceRoot>,
+    scrubber: Option<&Scrubber>,
+    mut remote_sources: Option<&mut RemoteSources>,
 ) -> Result<Vec<DifMatch<'a>>> {
     let mut source_bundles = Vec::new();
 
@@ -1136,18 +1628,28 @@ fn create_source_bundles<'a>(
         let temp_file = TempFile::create()?;
         let mut writer = SourceBundleWriter::start(BufWriter::new(temp_file.open()?))?;
         writer.collect_il2cpp_sources(include_il2cpp_sources);
+        for (key, value) in provenance.iter().flat_map(|p| p.attributes()) {
+            writer.set_attribute(key, value.to_owned());
+        }
 
         // Resolve source files from the object and write their contents into the archive. Skip to
         // upload this bundle if no source could be written. This can happen if there is no file or
         // line information in the object file, or if none of the files could be resolved.
-        let written = writer
-            .with_skipped_file_callback(|skipped_info| info!("{skipped_info}"))
-            .write_object_with_filter(object, dif.file_name(), filter_bad_sources)?;
+        let written = match remote_sources.as_deref_mut() {
+            Some(remote_sources) => {
+                remote_sources.write_bundle(object, dif.file_name(), writer, source_root)?
+            }
+            None => writer
+                .with_skipped_file_callback(|skipped_info| info!("{skipped_info}"))
+                .write_object_with_filter(object, dif.file_name(), filter_bad_sources)?,
+        };
         if !written {
             debug!("No sources found for {}", name);
             continue;
         }
 
+        rewrite_source_bundle(temp_file.path(), source_root, scrubber)?;
+
         source_bundles.push(DifMatch::from_temp_object(temp_file, name, dif.debug_id)?);
     }
 
@@ -1169,6 +1671,13 @@ fn create_source_bundles<'a>(
 fn create_il2cpp_mappings<'a>(difs: &[DifMatch<'a>]) -> Result<Vec<DifMatch<'a>>> {
     let mut line_mappings = Vec::new();
 
+    // Objects with a line mapping file from the Unity build need no computed one.
+    let mapped: BTreeSet<_> = difs
+        .iter()
+        .filter(|dif| dif.format() == DifFormat::Il2Cpp)
+        .filter_map(|dif| dif.debug_id)
+        .collect();
+
     let progress_style = ProgressStyle::default_bar().template(
         "{prefix:.dim} Resolving il2cpp mappings... {msg:.dim}\
          \n{wide_bar}  {pos}/{len}",
@@ -1182,6 +1691,10 @@ fn create_il2cpp_mappings<'a>(difs: &[DifMatch<'a>]) -> Result<Vec<DifMatch<'a>>
         pb.inc(1);
         pb.set_message(dif.path());
 
+        if dif.debug_id.is_some_and(|id| mapped.contains(&id)) {
+            continue;
+        }
+
         if let Some(object) = dif.object() {
             let temp_file = TempFile::create()?;
             let written = {
@@ -1240,17 +1753,91 @@ fn upload_difs_chunked(
 
     // Resolve source code context if specified
     if options.include_sources {
-        let source_bundles = create_source_bundles(&processed, options.upload_il2cpp_mappings)?;
+        let mut remote_sources = options
+            .sources_url
+            .as_deref()
+            .map(RemoteSources::fetch)
+            .transpose()?;
+        let source_bundles = create_source_bundles(
+            &processed,
+            options.upload_il2cpp_mappings,
+            options.provenance.as_ref(),
+            options.source_root.as_ref(),
+            Config::current().get_scrubber()?.as_ref(),
+            remote_sources.as_mut(),
+        )?;
         processed.extend(source_bundles);
     }
 
-    // Calculate checksums and chunks
+    // Skip files that were uploaded by a previous run, unless the cache is
+    // disabled.
+    let cache = UploadCache::load(options.org, options.project);
+    let found = processed.len();
+    if !options.no_cache {
+        processed.retain(|m| {
+            !m.journal_key
+                .as_ref()
+                .is_some_and(|key| cache.contains(key))
+        });
+    }
+    if found > 0 && processed.is_empty() {
+        println!(
+            "{} Nothing to upload, all files were uploaded before",
+            style(">").dim()
+        );
+        return Ok(Default::default());
+    }
+
+    // Calculate checksums and chunks. Unless a full scan is requested, the
+    // checksums of files that did not change since the last run are reused.
+    let chunk_size = chunk_options.chunk_size as usize;
+    let mut journal = ScanJournal::load();
     let chunked = prepare_difs(processed, |m| {
-        Chunked::from(m, chunk_options.chunk_size as usize)
+        let cached = match &m.journal_key {
+            Some(key) if !options.full_scan => journal.get(key, chunk_size),
+            _ => None,
+        };
+
+        if let Some((checksum, chunks)) = cached {
+            return Ok(Chunked::from_checksums(m, checksum, chunks, chunk_size));
+        }
+
+        let chunked = Chunked::from(m, chunk_size)?;
+        if let Some(key) = &chunked.object().journal_key {
+            journal.insert(key, chunk_size, chunked.checksum(), chunked.chunk_hashes());
+        }
+        Ok(chunked)
     })?;
 
+    if let Err(err) = journal.save() {
+        warn!("Could not save scan journal: {err}");
+    }
+
+    let wait = options.wait;
     let options = options.into_chunk_options(chunk_options);
-    chunks::upload_chunked_objects(&chunked, options)
+    let (uploaded, has_errors) = chunks::upload_chunked_objects(&chunked, options)?;
+
+    // Only remember files once the server has processed them. Without waiting,
+    // only files that were returned as processed are known to be complete.
+    let processed: BTreeSet<_> = uploaded.iter().map(|dif| dif.checksum.as_str()).collect();
+    let complete = chunked.iter().filter(|m| {
+        let checksum = m.checksum().to_string();
+        (wait && !has_errors) || processed.contains(checksum.as_str())
+    });
+    record_uploads(cache, complete.map(|m| &m.object().journal_key));
+
+    Ok((uploaded, has_errors))
+}
+
+/// Records the given files in the upload cache.
+fn record_uploads<'a, I>(mut cache: UploadCache, keys: I)
+where
+    I: IntoIterator<Item = &'a Option<JournalKey>>,
+{
+    cache.insert(keys.into_iter().flatten());
+    if let Err(err) = cache.save() {
+        warn!("Could not save upload cache: {err}");
+    }
 }
 
 /// Returns debug files missing on the server.
@@ -1285,17 +1872,58 @@ fn create_batch_archive(difs: &[HashedDifMatch<'_>]) -> Result<TempFile> {
     let pb = make_byte_progress_bar(total_bytes);
     let tf = TempFile::create()?;
 
-    {
-        let mut zip = ZipWriter::new(tf.open()?);
+    let entries = difs
+        .iter()
+        .map(|symbol| (symbol.file_name(), symbol.data()))
+        .collect::<Vec<_>>();
+    write_zip_archive(tf.open()?, &entries, &pb)?;
+
+    pb.finish_and_clear();
+    Ok(tf)
+}
 
-        for symbol in difs {
-            zip.start_file(symbol.file_name(), FileOptions::default())?;
-            copy_with_progress(&pb, &mut symbol.data(), &mut zip)?;
+/// Writes the given entries into a ZIP archive.
+///
+/// Compression is the expensive part of building large archives, so entries are
+/// compressed in parallel, each into its own in-memory archive. The compressed entries
+/// are then copied into the final archive in their original order without
+/// recompressing. Entries are processed in windows of one entry per thread, so only
+/// the compressed entries of the current window are held in memory. Without parallelism,
+/// entries are compressed directly into the final archive.
+fn write_zip_archive<W>(writer: W, entries: &[(&str, &[u8])], pb: &ProgressBar) -> Result<()>
+where
+    W: Write + Seek,
+{
+    let mut zip = ZipWriter::new(writer);
+    let threads = rayon::current_num_threads();
+    if threads <= 1 {
+        for &(name, mut data) in entries {
+            zip.start_file(name, FileOptions::default())?;
+            copy_with_progress(pb, &mut data, &mut zip)?;
+        }
+        zip.finish()?;
+        return Ok(());
+    }
+
+    for window in entries.chunks(threads) {
+        let compressed = window
+            .par_iter()
+            .map(|&(name, mut data)| -> Result<Vec<u8>> {
+                let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+                zip.start_file(name, FileOptions::default())?;
+                copy_with_progress(pb, &mut data, &mut zip)?;
+                Ok(zip.finish()?.into_inner())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for entry in compressed {
+            let mut archive = ZipArchive::new(Cursor::new(entry))?;
+            zip.raw_copy_file(archive.by_index(0)?)?;
         }
     }
+    zip.finish()?;
 
-    pb.finish_and_clear();
-    Ok(tf)
+    Ok(())
 }
 
 /// Uploads the given DIFs to the server in batched ZIP archives.
@@ -1339,14 +1967,35 @@ fn upload_difs_batched(options: &DifUpload) -> Result<Vec<DebugInfoFile>> {
 
     // Try to resolve BCSymbolMaps
     let symbol_map = options.symbol_map.as_deref();
-    let processed = process_symbol_maps(found, symbol_map)?;
+    let mut processed = process_symbol_maps(found, symbol_map)?;
+
+    // Skip files that were uploaded by a previous run, unless the cache is
+    // disabled.
+    let cache = UploadCache::load(options.org, options.project);
+    let found = processed.len();
+    if !options.no_cache {
+        processed.retain(|m| {
+            !m.journal_key
+                .as_ref()
+                .is_some_and(|key| cache.contains(key))
+        });
+    }
+    if found > 0 && processed.is_empty() {
+        println!(
+            "{} Nothing to upload, all files were uploaded before",
+            style(">").dim()
+        );
+        return Ok(Default::default());
+    }
 
     // Calculate checksums
     let hashed = prepare_difs(processed, HashedDifMatch::from)?;
 
     // Check which files are missing on the server
+    let keys: Vec<_> = hashed.iter().map(|m| m.journal_key.clone()).collect();
     let missing = get_missing_difs(hashed, options)?;
     if missing.is_empty() {
+        record_uploads(cache, &keys);
         println!(
             "{} Nothing to upload, all files are on the server",
             style(">").dim()
@@ -1357,6 +2006,7 @@ fn upload_difs_batched(options: &DifUpload) -> Result<Vec<DebugInfoFile>> {
 
     // Upload missing DIFs in batches
     let uploaded = upload_in_batches(&missing, options)?;
+    record_uploads(cache, &keys);
     if !uploaded.is_empty() {
         println!("{} File upload complete:\n", style(">").dim());
         for dif in &uploaded {
@@ -1422,7 +2072,7 @@ pub enum DifFormat {
 /// If `symbol_map` is set and Apple dSYMs with hidden symbols are found, the
 /// uploader will first try to locate BCSymbolMaps and generate new dSYMs with
 /// resolved symbols.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct DifUpload<'a> {
     org: &'a str,
     project: &'a str,
@@ -1439,10 +2089,16 @@ pub struct DifUpload<'a> {
     portablepdbs_allowed: bool,
     sources_allowed: bool,
     include_sources: bool,
+    sources_url: Option<String>,
     bcsymbolmaps_allowed: bool,
     wait: bool,
     upload_il2cpp_mappings: bool,
     il2cpp_mappings_allowed: bool,
+    full_scan: bool,
+    max_concurrency: Option<u8>,
+    no_cache: bool,
+    provenance: Option<Provenance>,
+    source_root: Option<SourceRoot>,
 }
 
 impl<'a> DifUpload<'a> {
@@ -1479,10 +2135,16 @@ impl<'a> DifUpload<'a> {
             portablepdbs_allowed: false,
             sources_allowed: false,
             include_sources: false,
+            sources_url: None,
             bcsymbolmaps_allowed: false,
             wait: false,
             upload_il2cpp_mappings: false,
             il2cpp_mappings_allowed: false,
+            full_scan: false,
+            max_concurrency: None,
+            no_cache: false,
+            provenance: None,
+            source_root: None,
         }
     }
 
@@ -1569,6 +2231,59 @@ impl<'a> DifUpload<'a> {
         self
     }
 
+    /// Set the URL of a ZIP archive to read the sources from when they are
+    /// included, instead of the local file system.
+    ///
+    /// Defaults to `None`.
+    pub fn sources_url(&mut self, url: Option<String>) -> &mut Self {
+        self.sources_url = url;
+        self
+    }
+
+    /// Set whether all files should be hashed, instead of reusing checksums of
+    /// files that did not change since a previous upload.
+    ///
+    /// Defaults to `false`.
+    pub fn full_scan(&mut self, full_scan: bool) -> &mut Self {
+        self.full_scan = full_scan;
+        self
+    }
+
+    /// Sets the maximum number of concurrent chunk upload requests.
+    ///
+    /// Defaults to the concurrency suggested by the server.
+    pub fn max_concurrency(&mut self, max_concurrency: Option<u8>) -> &mut Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Set whether to upload all files, even those that a previous run
+    /// recorded as uploaded.
+    ///
+    /// Defaults to `false`.
+    pub fn no_cache(&mut self, no_cache: bool) -> &mut Self {
+        self.no_cache = no_cache;
+        self
+    }
+
+    /// Set the build provenance stored in the manifests of source bundles
+    /// created with `include_sources`.
+    ///
+    /// Defaults to `None`.
+    pub fn provenance(&mut self, provenance: Option<Provenance>) -> &mut Self {
+        self.provenance = provenance;
+        self
+    }
+
+    /// Set the root below which sources are stored with relative paths in
+    /// source bundles created with `include_sources`.
+    ///
+    /// Defaults to `None`.
+    pub fn source_root(&mut self, source_root: Option<SourceRoot>) -> &mut Self {
+        self.source_root = source_root;
+        self
+    }
+
     /// Set whether the upload should wait for the server to complete processing
     /// files or exit immediately after the upload.
     ///
@@ -1613,7 +2328,10 @@ impl<'a> DifUpload<'a> {
         }
 
         let api = Api::current();
-        if let Some(chunk_options) = api.authenticated()?.get_chunk_upload_options(self.org)? {
+        if let Some(mut chunk_options) = api.authenticated()?.get_chunk_upload_options(self.org)? {
+            if let Some(max_concurrency) = self.max_concurrency {
+                chunk_options.concurrency = max_concurrency;
+            }
             if chunk_options.max_file_size > 0 {
                 self.max_file_size = chunk_options.max_file_size;
             }
@@ -1691,7 +2409,7 @@ impl<'a> DifUpload<'a> {
 
     /// Determines if this file extension matches the search criteria.
     fn valid_extension(&self, ext: Option<&OsStr>) -> bool {
-        self.extensions.is_empty() || ext.map_or(false, |e| self.extensions.contains(e))
+        self.extensions.is_empty() || ext.is_some_and(|e| self.extensions.contains(e))
     }
 
     /// Determines if this [`DifFormat`] matches the search criteria.
@@ -1703,6 +2421,7 @@ impl<'a> DifUpload<'a> {
             DifFormat::Object(FileFormat::SourceBundle) if !self.sources_allowed => false,
             DifFormat::Object(FileFormat::PortablePdb) if !self.portablepdbs_allowed => false,
             DifFormat::BcSymbolMap | DifFormat::PList if !self.bcsymbolmaps_allowed => false,
+            DifFormat::Il2Cpp if !self.il2cpp_mappings_allowed => false,
             format => self.formats.is_empty() || self.formats.contains(&format),
         }
     }
@@ -1788,3 +2507,256 @@ impl<'a> DifUpload<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Instant;
+
+    #[test]
+    fn test_breakpad_pairing() {
+        let features = |symtab, debug, unwind| ObjectDifFeatures {
+            symtab,
+            debug,
+            unwind,
+            sources: false,
+        };
+
+        assert_eq!(
+            BreakpadPairing::decide(features(true, true, true), features(true, true, true)),
+            BreakpadPairing::Native
+        );
+        assert_eq!(
+            BreakpadPairing::decide(features(true, true, true), features(true, false, true)),
+            BreakpadPairing::Native
+        );
+        assert_eq!(
+            BreakpadPairing::decide(features(true, false, false), features(true, true, true)),
+            BreakpadPairing::Breakpad
+        );
+        assert_eq!(
+            BreakpadPairing::decide(features(true, true, false), features(true, false, true)),
+            BreakpadPairing::Both
+        );
+    }
+
+    #[test]
+    fn test_write_zip_archive_preserves_entries() {
+        let contents = (0..64)
+            .map(|i| (format!("file-{i}.debug"), vec![i as u8; 1024 * (i + 1)]))
+            .collect::<Vec<_>>();
+        let entries = contents
+            .iter()
+            .map(|(name, data)| (name.as_str(), data.as_slice()))
+            .collect::<Vec<_>>();
+
+        let mut buf = Cursor::new(Vec::new());
+        write_zip_archive(&mut buf, &entries, &ProgressBar::hidden()).unwrap();
+
+        let mut archive = ZipArchive::new(buf).unwrap();
+        assert_eq!(archive.len(), contents.len());
+        for (index, (name, data)) in contents.iter().enumerate() {
+            let mut file = archive.by_index(index).unwrap();
+            assert_eq!(file.name(), name.as_str());
+
+            let mut read = Vec::new();
+            file.read_to_end(&mut read).unwrap();
+            assert_eq!(&read, data);
+        }
+    }
+
+    #[test]
+    fn test_build_id_debug_id() {
+        let data = fs::read("tests/integration/_fixtures/elf-Linux-ARMv7-ls").unwrap();
+        let elf = ElfObject::parse(&data).unwrap();
+        let code_id = elf.code_id().unwrap();
+        let build_id = (0..code_id.as_str().len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&code_id.as_str()[i..i + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(build_id_debug_id(&build_id, true), elf.debug_id());
+    }
+
+    #[test]
+    fn test_resolve_alt_link_by_build_id() {
+        let dir = TempDir::create().unwrap();
+        let build_id_dir = dir.path().join(".build-id").join("ab");
+        fs::create_dir_all(&build_id_dir).unwrap();
+        fs::write(build_id_dir.join("cdef01.debug"), b"dwz").unwrap();
+
+        let data = b"../../.dwz/app.debug\0\xab\xcd\xef\x01";
+        let (path, debug_id) = resolve_alt_link(data, true, &[dir.path().to_owned()]).unwrap();
+        assert_eq!(path, build_id_dir.join("cdef01.debug"));
+        assert_eq!(
+            debug_id,
+            DebugId::from_uuid(Uuid::from_bytes([
+                0x01, 0xef, 0xcd, 0xab, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_resolve_alt_link_by_name() {
+        let dir = TempDir::create().unwrap();
+        let alt_path = dir.path().join("app.debug");
+        fs::write(&alt_path, b"dwz").unwrap();
+
+        let mut data = alt_path.to_string_lossy().into_owned().into_bytes();
+        data.extend(b"\0\xab\xcd");
+        let (path, _) = resolve_alt_link(&data, true, &[]).unwrap();
+        assert_eq!(path, alt_path);
+
+        // Relative names are not resolved against the current folder.
+        let data = b"app.debug\0\xab\xcd";
+        assert!(resolve_alt_link(data, true, &[dir.path().to_owned()]).is_none());
+        assert!(resolve_alt_link(b"app.debug", true, &[]).is_none());
+    }
+
+    fn object_dif(name: &str, data: Vec<u8>) -> DifMatch<'static> {
+        let dif = SelfCell::try_new(ByteView::from_vec(data), |b| {
+            Object::parse(unsafe { &*b }).map(|object| ParsedDif::Object(Box::new(object)))
+        })
+        .unwrap();
+        let mut dif = DifMatch {
+            _backing: None,
+            dif,
+            name: name.into(),
+            debug_id: None,
+            attachments: None,
+            journal_key: None,
+        };
+        dif.debug_id = dif.object().map(|object| object.debug_id());
+        dif
+    }
+
+    fn line_mapping_files(files: &[(&str, &str)]) -> Vec<(String, ByteView<'static>)> {
+        files
+            .iter()
+            .map(|(name, data)| (name.to_string(), ByteView::from_vec(data.as_bytes().into())))
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_il2cpp_line_mappings() {
+        let fib = object_dif(
+            "fib",
+            fs::read("tests/integration/_fixtures/breakpad/fib").unwrap(),
+        );
+        let files = line_mapping_files(&[
+            ("covered/LineNumberMappings.json", r#"{"/build/fib.c": {}}"#),
+            (
+                "other/LineNumberMappings.json",
+                r#"{"/build/other.cpp": {}}"#,
+            ),
+            ("invalid/LineNumberMappings.json", "[]"),
+        ]);
+        let mut options = DifUpload::new("org", "project");
+        options.il2cpp_mappings_allowed = true;
+
+        let collected = collect_il2cpp_line_mappings(files, std::slice::from_ref(&fib), &options);
+        assert_eq!(collected.len(), 1);
+        assert_eq!(collected[0].name, "covered/LineNumberMappings.json");
+        assert_eq!(collected[0].format(), DifFormat::Il2Cpp);
+        assert_eq!(collected[0].debug_id(), fib.debug_id());
+    }
+
+    #[test]
+    fn test_collect_il2cpp_line_mappings_filtered() {
+        let fib = object_dif(
+            "fib",
+            fs::read("tests/integration/_fixtures/breakpad/fib").unwrap(),
+        );
+        let files =
+            || line_mapping_files(&[("LineNumberMappings.json", r#"{"/build/fib.c": {}}"#)]);
+
+        // Servers without il2cpp support do not accept mappings.
+        let options = DifUpload::new("org", "project");
+        let collected = collect_il2cpp_line_mappings(files(), std::slice::from_ref(&fib), &options);
+        assert!(collected.is_empty());
+
+        let mut options = DifUpload::new("org", "project");
+        options.il2cpp_mappings_allowed = true;
+        options.filter_ids([DebugId::from_uuid(Uuid::from_bytes([1; 16]))]);
+        let collected = collect_il2cpp_line_mappings(files(), std::slice::from_ref(&fib), &options);
+        assert!(collected.is_empty());
+    }
+
+    #[test]
+    fn test_create_il2cpp_mappings_skips_mapped_objects() {
+        let dir = TempDir::create().unwrap();
+        let cpp_path = dir.path().join("Bulk_Assembly-CSharp_0.cpp");
+        fs::write(
+            &cpp_path,
+            "void Player_Update() {\n    //<source_info:Assets/Player.cs:7>\n    Move();\n}\n",
+        )
+        .unwrap();
+        let symbols = object_dif(
+            "GameAssembly.sym",
+            format!(
+                "MODULE Linux x86_64 B4764F72C14A555CF404E3D5436172700 GameAssembly\n\
+                 FILE 0 {}\n\
+                 FUNC 1000 10 0 Player_Update\n\
+                 1000 10 3 0\n",
+                cpp_path.display()
+            )
+            .into_bytes(),
+        );
+
+        let computed = create_il2cpp_mappings(std::slice::from_ref(&symbols)).unwrap();
+        assert_eq!(computed.len(), 1);
+        assert_eq!(computed[0].debug_id(), symbols.debug_id());
+
+        let mapping = DifMatch::from_line_mapping(
+            symbols.debug_id().unwrap(),
+            "LineNumberMappings.json".into(),
+            ByteView::from_vec(b"{}".to_vec()),
+        )
+        .unwrap();
+        let computed = create_il2cpp_mappings(&[symbols, mapping]).unwrap();
+        assert!(computed.is_empty());
+    }
+
+    /// Compares the parallel archive writer with compressing all entries on one thread.
+    ///
+    /// Runs in the Benchmarks CI job, locally with
+    /// `cargo test --release -- --ignored bench_write_zip_archive --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_write_zip_archive() {
+        let contents = (0..256)
+            .map(|i| {
+                let data = (0..1024 * 1024)
+                    .map(|j| ((i * j) % 251) as u8)
+                    .collect::<Vec<_>>();
+                (format!("file-{i}.debug"), data)
+            })
+            .collect::<Vec<_>>();
+        let entries = contents
+            .iter()
+            .map(|(name, data)| (name.as_str(), data.as_slice()))
+            .collect::<Vec<_>>();
+
+        let start = Instant::now();
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for &(name, data) in &entries {
+            zip.start_file(name, FileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+        let sequential = start.elapsed();
+
+        let start = Instant::now();
+        write_zip_archive(Cursor::new(Vec::new()), &entries, &ProgressBar::hidden()).unwrap();
+        let parallel = start.elapsed();
+
+        println!("sequential: {sequential:?}, parallel: {parallel:?}");
+        if rayon::current_num_threads() > 1 {
+            assert!(
+                parallel < sequential,
+                "parallel compression is slower than sequential compression"
+            );
+        }
+    }
+}
@@ -1,35 +1,50 @@
 use std::borrow::Cow;
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::process::Command;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use chrono::Utc;
 use lazy_static::lazy_static;
 use regex::Regex;
-use sentry::protocol::{Breadcrumb, ClientSdkInfo, Event};
+use sentry::protocol::{Breadcrumb, ClientSdkInfo, Event, Level, LogEntry};
+
+use crate::utils::log_parsing::{JournalEntry, LogLevel};
+use crate::utils::support_bundle::sanitize;
+
+/// Only the end of large logfiles is read, since only the last 100 records
+/// are attached.
+const MAX_LOGFILE_TAIL: u64 = 64 * 1024;
 
 lazy_static! {
     static ref COMPONENT_RE: Regex = Regex::new(r#"^([^:]+): (.*)$"#).unwrap();
 }
 
-/// Attaches all logs from a logfile as breadcrumbs to the given event.
+/// Attaches the last 100 logs from a logfile as breadcrumbs to the given
+/// event.  Auth tokens and credentials are removed from the messages.
 pub fn attach_logfile(event: &mut Event<'_>, logfile: &str, with_component: bool) -> Result<()> {
-    let f = fs::File::open(logfile).context("Could not open logfile")?;
+    let mut f = fs::File::open(logfile).context("Could not open logfile")?;
 
     // sentry currently requires timestamps for breadcrumbs at all times.
     // Because we might not be able to parse a timestamp from the log file
     // we fall back to either the modified time of the file or if that does
     // not work we use the current timestamp.
-    let fallback_timestamp = fs::metadata(logfile)
-        .context("Could not get metadata for logfile")?
+    let metadata = fs::metadata(logfile).context("Could not get metadata for logfile")?;
+    let fallback_timestamp = metadata
         .modified()
         .map(Into::into)
         .unwrap_or_else(|_| Utc::now());
 
+    let truncated = metadata.len() > MAX_LOGFILE_TAIL;
+    if truncated {
+        f.seek(SeekFrom::Start(metadata.len() - MAX_LOGFILE_TAIL))?;
+    }
+
     let reader = BufReader::new(f);
-    for line in reader.lines() {
+    // The first line of a truncated logfile is most likely incomplete.
+    for line in reader.split(b'\n').skip(usize::from(truncated)) {
         let line = line?;
-        let rec = anylog::LogEntry::parse(line.as_bytes());
+        let rec = anylog::LogEntry::parse(line.strip_suffix(b"\r").unwrap_or(&line));
 
         let (component, message) = if with_component {
             let (component, message) = rec.component_and_message();
@@ -40,7 +55,7 @@ pub fn attach_logfile(event: &mut Event<'_>, logfile: &str, with_component: bool
 
         event.breadcrumbs.values.push(Breadcrumb {
             timestamp: rec.utc_timestamp().unwrap_or(fallback_timestamp).into(),
-            message: Some(message.to_string()),
+            message: Some(sanitize(message)),
             category: Some(component.to_string()),
             ..Default::default()
         })
@@ -54,6 +69,102 @@ pub fn attach_logfile(event: &mut Event<'_>, logfile: &str, with_component: bool
     Ok(())
 }
 
+/// Attaches the last 100 journal entries of a systemd unit since the given
+/// time as breadcrumbs to the given event, and adds the result and exit
+/// status of the unit.  Auth tokens and credentials are removed from the
+/// messages.
+pub fn attach_journal(event: &mut Event<'_>, unit: &str, since: &str) -> Result<()> {
+    let output = Command::new("journalctl")
+        .args(["--unit", unit, "--since", since, "--lines", "100"])
+        .args(["--output", "json", "--no-pager"])
+        .output()
+        .context("Could not run journalctl")?;
+    if !output.status.success() {
+        bail!(
+            "journalctl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    for line in output.stdout.split(|byte| *byte == b'\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let entry: JournalEntry =
+            serde_json::from_slice(line).context("Could not parse journal entry")?;
+        let Some(message) = entry.message() else {
+            continue;
+        };
+
+        event.breadcrumbs.values.push(Breadcrumb {
+            timestamp: entry.timestamp().unwrap_or_else(Utc::now).into(),
+            message: Some(sanitize(&message)),
+            category: Some(entry.identifier.clone().unwrap_or_else(|| unit.into())),
+            level: match entry.level() {
+                LogLevel::Trace | LogLevel::Debug => Level::Debug,
+                LogLevel::Info => Level::Info,
+                LogLevel::Warn => Level::Warning,
+                LogLevel::Error => Level::Error,
+                LogLevel::Fatal => Level::Fatal,
+            },
+            ..Default::default()
+        });
+    }
+
+    event.tags.insert("unit".into(), unit.into());
+
+    let output = Command::new("systemctl")
+        .args([
+            "show",
+            unit,
+            "--property=Result,ExecMainCode,ExecMainStatus",
+        ])
+        .output()
+        .context("Could not run systemctl")?;
+    let properties = String::from_utf8_lossy(&output.stdout);
+    let mut result = None;
+    let mut exit_status = None;
+    for (key, value) in properties.lines().filter_map(|line| line.split_once('=')) {
+        match key {
+            "Result" => {
+                event.tags.insert("unit_result".into(), value.into());
+                result = Some(value.to_owned());
+            }
+            // See `CLD_*` in waitid(2).
+            "ExecMainCode" => {
+                let code = match value {
+                    "1" => "exited",
+                    "2" => "killed",
+                    "3" => "dumped",
+                    _ => continue,
+                };
+                event.extra.insert("exit_code".into(), code.into());
+            }
+            "ExecMainStatus" => {
+                event.extra.insert("exit_status".into(), value.into());
+                exit_status = Some(value.to_owned());
+            }
+            _ => {}
+        }
+    }
+
+    if event.logentry.is_none() {
+        let mut message = format!("{unit} failed");
+        if let Some(result) = result {
+            message.push_str(&format!(" with result '{result}'"));
+        }
+        if let Some(status) = exit_status {
+            message.push_str(&format!(" (status {status})"));
+        }
+        event.logentry = Some(LogEntry {
+            message,
+            params: vec![],
+        });
+    }
+
+    Ok(())
+}
+
 /// Returns SDK information for sentry-cli.
 pub fn get_sdk_info() -> Cow<'static, ClientSdkInfo> {
     Cow::Owned(ClientSdkInfo {
@@ -0,0 +1,50 @@
+//! The exit codes of sentry-cli.
+//!
+//! Failures are classified, so that scripts can react to them, e.g. by
+//! retrying after network errors:
+//!
+//! | Code | Meaning                                                          |
+//! |------|------------------------------------------------------------------|
+//! | 0    | Success                                                          |
+//! | 1    | Any other failure                                                |
+//! | 2    | The command line is invalid                                      |
+//! | 3    | Authentication failed, or the auth token lacks permissions       |
+//! | 4    | Sentry could not be reached, or the request was rate limited     |
+//! | 5    | The organization, project or other resource was not found        |
+//! | 6    | Sentry rejected the request as invalid                           |
+//! | 7    | Some files were uploaded, but others failed to upload or process |
+//!
+//! With `--legacy-exit-codes` or `SENTRY_LEGACY_EXIT_CODES=1`, all failures
+//! except invalid command lines exit with 1, as in earlier versions.
+use anyhow::Error;
+
+use crate::api;
+use crate::config::Config;
+
+/// The classification of a failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitCode {
+    Failure = 1,
+    Auth = 3,
+    Network = 4,
+    NotFound = 5,
+    Validation = 6,
+    PartialUpload = 7,
+}
+
+impl ExitCode {
+    /// Classifies the error that made a command fail.
+    pub fn from_error(err: &Error) -> ExitCode {
+        api::exit_code_for_error(err).unwrap_or(ExitCode::Failure)
+    }
+
+    /// Returns the code the process exits with, taking legacy exit codes
+    /// into account.
+    pub fn code(self) -> i32 {
+        if Config::current_opt().is_some_and(|config| config.uses_legacy_exit_codes()) {
+            1
+        } else {
+            self as i32
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
 use std::fs;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use console::style;
@@ -9,17 +9,33 @@ use ignore::overrides::OverrideBuilder;
 use ignore::types::TypesBuilder;
 use ignore::WalkBuilder;
 use log::{info, warn};
+use rayon::prelude::*;
+use symbolic::common::ByteView;
 
+use crate::utils::logging::quiet_println;
 use crate::utils::progress::{ProgressBar, ProgressStyle};
 
 use super::fs::{decompress_gzip_content, is_gzip_compressed};
 
+/// Returns a builder for walking `path` that follows symlinks and, with
+/// `respect_vcs_ignore`, skips files matched by `.gitignore` and `.ignore` files.
+pub fn walk_builder(path: &Path, respect_vcs_ignore: bool) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(path);
+    builder
+        .follow_links(true)
+        .git_exclude(respect_vcs_ignore)
+        .git_ignore(respect_vcs_ignore)
+        .ignore(respect_vcs_ignore);
+    builder
+}
+
 pub struct ReleaseFileSearch {
     path: PathBuf,
     extensions: BTreeSet<String>,
     ignores: BTreeSet<String>,
     ignore_file: Option<String>,
     decompress: bool,
+    respect_vcs_ignore: bool,
 }
 
 #[derive(Eq, PartialEq, Hash)]
@@ -37,6 +53,7 @@ impl ReleaseFileSearch {
             ignore_file: None,
             ignores: BTreeSet::new(),
             decompress: false,
+            respect_vcs_ignore: true,
         }
     }
 
@@ -45,6 +62,13 @@ impl ReleaseFileSearch {
         self
     }
 
+    /// Sets whether files matched by `.gitignore` and `.ignore` files are
+    /// skipped. This is enabled by default.
+    pub fn respect_vcs_ignore(&mut self, respect: bool) -> &mut Self {
+        self.respect_vcs_ignore = respect;
+        self
+    }
+
     pub fn extensions<E>(&mut self, extensions: E) -> &mut Self
     where
         E: IntoIterator,
@@ -102,14 +126,9 @@ impl ReleaseFileSearch {
         pb.enable_steady_tick(100);
         pb.set_style(progress_style);
 
-        let mut collected = Vec::new();
+        let mut paths = Vec::new();
 
-        let mut builder = WalkBuilder::new(&self.path);
-        builder
-            .follow_links(true)
-            .git_exclude(false)
-            .git_ignore(false)
-            .ignore(false);
+        let mut builder = walk_builder(&self.path, self.respect_vcs_ignore);
 
         if !&self.extensions.is_empty() {
             let mut types_builder = TypesBuilder::new();
@@ -136,7 +155,7 @@ impl ReleaseFileSearch {
 
         for result in builder.build() {
             let file = result?;
-            if file.file_type().map_or(false, |t| t.is_dir()) {
+            if file.file_type().is_some_and(|t| t.is_dir()) {
                 continue;
             }
             pb.set_message(&format!("{}", file.path().display()));
@@ -147,29 +166,19 @@ impl ReleaseFileSearch {
                 file.metadata().unwrap().len()
             );
 
-            let mut f = fs::File::open(file.path())?;
-            let mut contents = Vec::new();
-            f.read_to_end(&mut contents)?;
-
-            if self.decompress && is_gzip_compressed(&contents) {
-                contents = decompress_gzip_content(&contents).unwrap_or_else(|_| {
-                    warn!("Could not decompress: {}", file.path().display());
-                    contents
-                });
-            }
-
-            let file_match = ReleaseFileMatch {
-                base_path: self.path.clone(),
-                path: file.path().to_path_buf(),
-                contents,
-            };
-            collected.push(file_match);
-
-            pb.set_prefix(&collected.len().to_string());
+            paths.push(file.into_path());
+            pb.set_prefix(&paths.len().to_string());
         }
 
+        // Reading is the expensive part for large builds, so files are read in
+        // parallel. The order of the walk is preserved.
+        let collected = paths
+            .into_par_iter()
+            .map(|path| self.read_file(path))
+            .collect::<Result<Vec<_>>>()?;
+
         pb.finish_and_clear();
-        println!(
+        quiet_println!(
             "{} Found {} {}",
             style(">").dim(),
             style(collected.len()).yellow(),
@@ -181,4 +190,26 @@ impl ReleaseFileSearch {
 
         Ok(collected)
     }
+
+    /// Reads a found file, decompressing it if requested.
+    ///
+    /// Files are memory mapped, so that compressed files are not read into
+    /// memory before decompressing them.
+    fn read_file(&self, path: PathBuf) -> Result<ReleaseFileMatch> {
+        let view = ByteView::open(&path)?;
+        let contents = if self.decompress && is_gzip_compressed(&view) {
+            decompress_gzip_content(&view).unwrap_or_else(|_| {
+                warn!("Could not decompress: {}", path.display());
+                view.to_vec()
+            })
+        } else {
+            view.to_vec()
+        };
+
+        Ok(ReleaseFileMatch {
+            base_path: self.path.clone(),
+            path,
+            contents,
+        })
+    }
 }
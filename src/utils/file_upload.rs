@@ -1,15 +1,16 @@
 //! Searches, processes and uploads release files.
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
 use std::io::BufWriter;
-use std::path::PathBuf;
+use std::mem;
+use std::path::{Path, PathBuf};
 use std::str;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, bail, Result};
 use console::style;
-use log::info;
+use log::{info, warn};
 use parking_lot::RwLock;
 use rayon::prelude::*;
 use rayon::ThreadPoolBuilder;
@@ -22,15 +23,23 @@ use symbolic::debuginfo::sourcebundle::{
 use url::Url;
 
 use crate::api::NewRelease;
-use crate::api::{Api, ChunkServerOptions, ChunkUploadCapability};
+use crate::api::{Api, ChunkServerOptions, ChunkUploadCapability, ChunkedFileState};
 use crate::constants::DEFAULT_MAX_WAIT;
 use crate::utils::chunks::{upload_chunks, Chunk, ASSEMBLE_POLL_INTERVAL};
-use crate::utils::fs::{get_sha1_checksum, get_sha1_checksums, TempFile};
+use crate::utils::file_search::ReleaseFileMatch;
+use crate::utils::fs::{
+    decompress_brotli_content, decompress_gzip_content, get_sha1_checksum, get_sha1_checksums,
+    is_gzip_compressed, TempFile,
+};
 use crate::utils::progress::{ProgressBar, ProgressBarMode, ProgressStyle};
+use crate::utils::provenance::Provenance;
 
 /// Fallback concurrency for release file uploads.
 static DEFAULT_CONCURRENCY: usize = 4;
 
+/// File extensions of pre-compressed variants of files, such as `app.js.br`.
+const PRECOMPRESSED_EXTENSIONS: &[&str] = &["br", "gz"];
+
 /// Old versions of Sentry cannot assemble artifact bundles straight away, they require
 /// that those bundles are associated to a release.
 ///
@@ -42,7 +51,7 @@ pub fn initialize_legacy_release_upload(context: &UploadContext) -> Result<()> {
     // if a project is provided which is technically unnecessary for the
     // legacy upload though it will unlikely to be what users want.
     if context.project.is_some()
-        && context.chunk_upload_options.map_or(false, |x| {
+        && context.chunk_upload_options.is_some_and(|x| {
             x.supports(ChunkUploadCapability::ArtifactBundles)
                 || x.supports(ChunkUploadCapability::ArtifactBundlesV2)
         })
@@ -90,6 +99,7 @@ pub struct UploadContext<'a> {
     pub wait: bool,
     pub max_wait: Duration,
     pub dedupe: bool,
+    pub provenance: Option<&'a Provenance>,
     pub chunk_upload_options: Option<&'a ChunkServerOptions>,
 }
 
@@ -313,6 +323,70 @@ fn upload_files_parallel(
     Ok(())
 }
 
+/// Returns the path of the canonical file if the path is a pre-compressed
+/// variant of it, such as `app.js` for `app.js.br`.
+fn precompressed_canonical_path(path: &Path) -> Option<PathBuf> {
+    let extension = path.extension()?.to_str()?;
+    if !PRECOMPRESSED_EXTENSIONS.contains(&extension) {
+        return None;
+    }
+    let canonical = path.with_extension("");
+    // Only `app.js.gz` is a variant, not a standalone `archive.gz`.
+    canonical.extension()?;
+    Some(canonical)
+}
+
+/// Folds pre-compressed variants of files, such as `app.js.br` and
+/// `app.js.gz`, into their canonical file, so they are not uploaded as
+/// separate artifacts.
+///
+/// Variants are skipped if the canonical file is also present.  Otherwise, the
+/// first variant is decompressed and uploaded in place of the canonical file,
+/// as Sentry needs the uncompressed contents to process it.
+pub fn fold_precompressed_variants(files: Vec<ReleaseFileMatch>) -> Vec<ReleaseFileMatch> {
+    let mut canonical_paths: HashSet<_> = files
+        .iter()
+        .filter(|file| precompressed_canonical_path(&file.path).is_none())
+        .map(|file| file.path.clone())
+        .collect();
+
+    files
+        .into_iter()
+        .filter_map(|mut file| {
+            let Some(canonical) = precompressed_canonical_path(&file.path) else {
+                return Some(file);
+            };
+            if canonical_paths.contains(&canonical) {
+                info!(
+                    "Skipping pre-compressed {}, uploading {} instead",
+                    file.path.display(),
+                    canonical.display()
+                );
+                return None;
+            }
+
+            let contents = match file.path.extension().and_then(|ext| ext.to_str()) {
+                Some("br") => decompress_brotli_content(&file.contents),
+                // The file may have been decompressed already with `--decompress`.
+                _ if !is_gzip_compressed(&file.contents) => Ok(mem::take(&mut file.contents)),
+                _ => decompress_gzip_content(&file.contents),
+            };
+            match contents {
+                Ok(contents) => {
+                    canonical_paths.insert(canonical.clone());
+                    file.contents = contents;
+                    file.path = canonical;
+                    Some(file)
+                }
+                Err(err) => {
+                    warn!("Could not decompress {}: {err}", file.path.display());
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
 fn poll_assemble(
     checksum: Digest,
     chunks: &[Digest],
@@ -357,6 +431,11 @@ fn poll_assemble(
                 chunks,
             )?
         };
+        // Nothing is assembled in read-only mode.
+        let Some(response) = response else {
+            pb.finish_and_clear();
+            return Ok(());
+        };
 
         // Poll until there is a response, unless the user has specified to skip polling. In
         // that case, we return the potentially partial response from the server. This might
@@ -421,6 +500,28 @@ fn upload_files_chunked(
 
     pb.finish_with_duration("Optimizing");
 
+    // Skip the upload entirely if the server already has a bundle with the same
+    // checksum, e.g. from a previous CI run.  Assembling with the checksum alone
+    // reports the state of an existing bundle without creating a new one.
+    if let Some(project) = context
+        .project
+        .filter(|_| context.dedupe && options.supports(ChunkUploadCapability::ArtifactBundlesV2))
+    {
+        let response = Api::current().authenticated()?.assemble_artifact_bundle(
+            context.org,
+            vec![project.to_string()],
+            checksum,
+            &[],
+            context.release,
+            context.dist,
+        )?;
+        if response.is_some_and(|response| response.state == ChunkedFileState::Ok) {
+            println!("{} Bundle skipped (already uploaded)", style(">").dim());
+            print_upload_context_details(context);
+            return Ok(());
+        }
+    }
+
     let progress_style = ProgressStyle::default_bar().template(&format!(
         "{} Uploading files...\
        \n{{wide_bar}}  {{bytes}}/{{total_bytes}} ({{eta}})",
@@ -429,17 +530,22 @@ fn upload_files_chunked(
 
     // Filter out chunks that are already on the server. This only matters if the server supports
     // `ArtifactBundlesV2`, otherwise the `missing_chunks` field is meaningless.
-    if options.supports(ChunkUploadCapability::ArtifactBundlesV2) && context.project.is_some() {
+    if let Some(project) = context
+        .project
+        .filter(|_| options.supports(ChunkUploadCapability::ArtifactBundlesV2))
+    {
         let api = Api::current();
         let response = api.authenticated()?.assemble_artifact_bundle(
             context.org,
-            vec![context.project.unwrap().to_string()],
+            vec![project.to_string()],
             checksum,
             &checksums,
             context.release,
             context.dist,
         )?;
-        chunks.retain(|Chunk((digest, _))| response.missing_chunks.contains(digest));
+        if let Some(response) = response {
+            chunks.retain(|Chunk((digest, _))| response.missing_chunks.contains(digest));
+        }
     };
 
     if !chunks.is_empty() {
@@ -509,6 +615,11 @@ fn build_artifact_bundle(
     if let Some(dist) = context.dist {
         bundle.set_attribute("dist".to_owned(), dist.to_owned());
     }
+    if let Some(provenance) = context.provenance {
+        for (key, value) in provenance.attributes() {
+            bundle.set_attribute(key, value.to_owned());
+        }
+    }
 
     for file in files.values() {
         pb.inc(1);
@@ -650,6 +761,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fold_precompressed_variants() {
+        use std::io::Write;
+
+        let file = |path: &str, contents: &[u8]| ReleaseFileMatch {
+            base_path: PathBuf::from("dist"),
+            path: PathBuf::from(path),
+            contents: contents.to_vec(),
+        };
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+        encoder.write_all(b"vendor();").unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let files = fold_precompressed_variants(vec![
+            file("dist/app.js", b"app();"),
+            file("dist/app.js.br", b"\x0b\x02\x80app();\x03"),
+            file("dist/app.js.gz", b"\x1f\x8b"),
+            file("dist/vendor.js.gz", &gzipped),
+            file("dist/archive.gz", b"\x1f\x8b"),
+        ]);
+
+        let files: Vec<_> = files
+            .iter()
+            .map(|file| (file.path.to_str().unwrap(), file.contents.as_slice()))
+            .collect();
+        assert_eq!(
+            files,
+            [
+                ("dist/app.js", &b"app();"[..]),
+                ("dist/vendor.js", &b"vendor();"[..]),
+                ("dist/archive.gz", &b"\x1f\x8b"[..]),
+            ]
+        );
+    }
+
     #[test]
     fn build_artifact_bundle_deterministic() {
         let context = UploadContext {
@@ -661,6 +807,7 @@ mod tests {
             wait: false,
             max_wait: DEFAULT_MAX_WAIT,
             dedupe: true,
+            provenance: None,
             chunk_upload_options: None,
         };
 
@@ -1,6 +1,13 @@
+use std::collections::BTreeMap;
 use std::fmt;
+use std::io;
 
+use anyhow::{bail, Context, Result};
 use chrono::Duration;
+use clap::ArgMatches;
+use minijinja::{Environment, UndefinedBehavior};
+use serde::Serialize;
+use serde_json::Value;
 
 /// Helper for formatting durations.
 pub struct HumanDuration(pub Duration);
@@ -24,29 +31,107 @@ impl fmt::Display for HumanDuration {
     }
 }
 
+/// The format in which command output is printed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// A human readable table.
+    Table,
+    /// A single JSON array.
+    Json,
+    /// One JSON object per line.
+    Ndjson,
+    /// One line per item, rendered with a template whose variables are the
+    /// keys of the JSON objects.
+    Template(String),
+}
+
+impl OutputFormat {
+    /// Returns the format selected with the `--format` or `--template`
+    /// argument.
+    pub fn from_matches(matches: &ArgMatches) -> OutputFormat {
+        if let Some(template) = matches.get_one::<String>("template") {
+            return OutputFormat::Template(template.clone());
+        }
+        match matches.get_one::<String>("format").map(String::as_str) {
+            Some("json") => OutputFormat::Json,
+            Some("ndjson") => OutputFormat::Ndjson,
+            _ => OutputFormat::Table,
+        }
+    }
+
+    /// Prints the given items in this format, which must not be `Table`.
+    pub fn print_items<T: Serialize>(self, items: &[T]) -> Result<()> {
+        match self {
+            OutputFormat::Table => bail!("Cannot print items as a table"),
+            OutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut io::stdout(), items)?;
+                println!();
+            }
+            OutputFormat::Ndjson => {
+                for item in items {
+                    println!("{}", serde_json::to_string(item)?);
+                }
+            }
+            OutputFormat::Template(template) => {
+                // Fail on unknown variables, so that typos do not silently
+                // print empty values.
+                let mut env = Environment::new();
+                env.set_undefined_behavior(UndefinedBehavior::Strict);
+                let template = env
+                    .template_from_str(&template)
+                    .context("Invalid output template")?;
+                for item in items {
+                    println!("{}", template.render(item)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 pub struct Table {
     title_row: Option<TableRow>,
     rows: Vec<TableRow>,
 }
 
 pub struct TableRow {
-    cells: Vec<prettytable::Cell>,
+    cells: Vec<String>,
+    /// The values of the cells in JSON formats.
+    values: Vec<Value>,
 }
 
 impl TableRow {
     pub fn new() -> TableRow {
-        TableRow { cells: vec![] }
+        TableRow {
+            cells: vec![],
+            values: vec![],
+        }
     }
 
     pub fn add<D: fmt::Display>(&mut self, text: D) -> &mut TableRow {
-        self.cells.push(prettytable::Cell::new(&text.to_string()));
+        let text = text.to_string();
+        self.values.push(Value::String(text.clone()));
+        self.cells.push(text);
+        self
+    }
+
+    /// Adds a cell that shows `text` in tables and keeps the type of `value`
+    /// in JSON formats, e.g. for dates, numbers and flags.
+    pub fn add_typed<D, T>(&mut self, text: D, value: T) -> &mut TableRow
+    where
+        D: fmt::Display,
+        T: Serialize,
+    {
+        self.cells.push(text.to_string());
+        self.values
+            .push(serde_json::to_value(value).unwrap_or(Value::Null));
         self
     }
 
     fn make_row(&self) -> prettytable::Row {
         let mut row = prettytable::Row::empty();
         for cell in &self.cells {
-            row.add_cell(cell.clone());
+            row.add_cell(prettytable::Cell::new(cell));
         }
         row
     }
@@ -97,6 +182,50 @@ impl Table {
         }
         tbl.print_tty(false).ok();
     }
+
+    /// Prints the table in the given format.
+    ///
+    /// In JSON formats, every row becomes an object whose keys are derived from
+    /// the column titles, e.g. `Last Event` becomes `last_event`. Keys are sorted.
+    /// Cells added with [`TableRow::add_typed`] keep their type, all other
+    /// cells are strings.
+    /// Templates refer to the cells of a row by the same keys.
+    /// Unlike [`Table::print`], an empty table is printed as an empty JSON array.
+    pub fn print_as(&self, format: OutputFormat) -> Result<()> {
+        if format == OutputFormat::Table {
+            self.print();
+            return Ok(());
+        }
+
+        let keys = match self.title_row {
+            Some(ref title_row) => title_row
+                .cells
+                .iter()
+                .map(|title| title.to_lowercase().replace(' ', "_"))
+                .collect(),
+            None => vec![],
+        };
+
+        let objects = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        let key = keys
+                            .get(index)
+                            .cloned()
+                            .unwrap_or_else(|| index.to_string());
+                        (key, value.clone())
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .collect::<Vec<_>>();
+
+        format.print_items(&objects)
+    }
 }
 
 impl Default for Table {
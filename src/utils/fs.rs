@@ -5,6 +5,7 @@ use std::io::{Read, Seek};
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
+use brotli2::read::BrotliDecoder;
 use flate2::read::GzDecoder;
 use log::error;
 use sha1_smol::{Digest, Sha1};
@@ -204,6 +205,14 @@ pub fn decompress_gzip_content(slice: &[u8]) -> Result<Vec<u8>> {
     Ok(decoded)
 }
 
+/// Gets brotli decompressed contents.
+pub fn decompress_brotli_content(slice: &[u8]) -> Result<Vec<u8>> {
+    let mut decoder = BrotliDecoder::new(slice);
+    let mut decoded = vec![];
+    decoder.read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
 #[cfg(windows)]
 pub fn path_as_url(path: &Path) -> String {
     path.display().to_string().replace('\\', "/")
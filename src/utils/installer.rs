@@ -0,0 +1,183 @@
+//! Detection of package managers that installed sentry-cli.
+//!
+//! Installations managed by a package manager must not be updated or removed
+//! by sentry-cli itself, as this corrupts the state of the package manager.
+use std::env;
+use std::path::Path;
+
+#[cfg(not(feature = "managed"))]
+use anyhow::Result;
+#[cfg(not(feature = "managed"))]
+use console::{style, user_attended};
+
+#[cfg(not(feature = "managed"))]
+use crate::utils::system::{shell_command, QuietExit};
+#[cfg(not(feature = "managed"))]
+use crate::utils::ui::prompt_to_continue;
+
+/// The package manager that installed this executable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Installer {
+    Homebrew,
+    Npm,
+    Cargo,
+    Scoop,
+    Docker,
+}
+
+impl Installer {
+    /// Detects the package manager that installed this executable, if any.
+    pub fn detect() -> Option<Installer> {
+        let exe = env::current_exe().ok()?.canonicalize().ok()?;
+        Self::detect_for(&exe)
+    }
+
+    fn detect_for(exe: &Path) -> Option<Installer> {
+        let bin_dir = exe.parent()?;
+        let prefix = bin_dir.parent();
+
+        if !cfg!(windows) && prefix.is_some_and(|p| p.join("INSTALL_RECEIPT.json").is_file()) {
+            Some(Installer::Homebrew)
+        } else if bin_dir.join("package.json").is_file() {
+            Some(Installer::Npm)
+        } else if prefix
+            .is_some_and(|p| p.join(".crates.toml").is_file() || p.join(".crates2.json").is_file())
+        {
+            Some(Installer::Cargo)
+        } else if is_scoop_app(exe) {
+            Some(Installer::Scoop)
+        } else if exe == Path::new("/bin/sentry-cli") && Path::new("/.dockerenv").exists() {
+            // The official Docker image puts the executable into /bin.
+            Some(Installer::Docker)
+        } else {
+            None
+        }
+    }
+
+    /// The commands that update sentry-cli, the preferred one first.
+    pub fn update_commands(self) -> &'static [&'static str] {
+        match self {
+            Installer::Homebrew => &["brew upgrade sentry-cli"],
+            Installer::Npm => &[
+                "yarn upgrade @sentry/cli",
+                "yarn global upgrade @sentry/cli",
+                "npm update @sentry/cli",
+                "npm update -g @sentry/cli",
+            ],
+            Installer::Cargo => &["cargo install sentry-cli"],
+            Installer::Scoop => &["scoop update sentry-cli"],
+            Installer::Docker => &["docker pull getsentry/sentry-cli"],
+        }
+    }
+}
+
+#[cfg(not(feature = "managed"))]
+impl Installer {
+    /// The name of the package manager, as shown to users.
+    pub fn name(self) -> &'static str {
+        match self {
+            Installer::Homebrew => "homebrew",
+            Installer::Npm => "npm/yarn",
+            Installer::Cargo => "cargo",
+            Installer::Scoop => "scoop",
+            Installer::Docker => "Docker",
+        }
+    }
+
+    /// The commands that uninstall sentry-cli, the preferred one first.
+    pub fn uninstall_commands(self) -> &'static [&'static str] {
+        match self {
+            Installer::Homebrew => &["brew uninstall sentry-cli"],
+            Installer::Npm => &[
+                "yarn remove @sentry/cli",
+                "yarn global remove @sentry/cli",
+                "npm uninstall @sentry/cli",
+                "npm uninstall --global @sentry/cli",
+            ],
+            Installer::Cargo => &["cargo uninstall sentry-cli"],
+            Installer::Scoop => &["scoop uninstall sentry-cli"],
+            Installer::Docker => &["docker rmi getsentry/sentry-cli"],
+        }
+    }
+
+    /// Updates sentry-cli through the package manager.
+    pub fn update(self) -> Result<()> {
+        self.delegate("update", self.update_commands())
+    }
+
+    /// Uninstalls sentry-cli through the package manager.
+    pub fn uninstall(self) -> Result<()> {
+        self.delegate("uninstall", self.uninstall_commands())
+    }
+
+    /// Prints the package manager commands for the action.  If there is a
+    /// single command, it is run once the user confirms it.
+    ///
+    /// npm has one command per kind of installation and Docker commands have
+    /// to run on the host, so these are only printed.
+    fn delegate(self, action: &str, commands: &[&str]) -> Result<()> {
+        println!(
+            "This installation of sentry-cli is managed through {}",
+            self.name()
+        );
+
+        let [command] = commands else {
+            println!(
+                "Please use {} to {action} sentry-cli, using one of the following commands:",
+                self.name()
+            );
+            for command in commands {
+                println!("  {command}");
+            }
+            return Err(QuietExit(1).into());
+        };
+
+        println!("Please use {} to {action} sentry-cli:", self.name());
+        println!();
+        println!("{} {command}", style("$").dim());
+
+        if self == Installer::Docker || !user_attended() {
+            return Err(QuietExit(1).into());
+        }
+        println!();
+        if !prompt_to_continue(&format!("Run `{command}` now?"))? {
+            return Err(QuietExit(1).into());
+        }
+
+        let status = shell_command(command).status()?;
+        if !status.success() {
+            return Err(QuietExit(status.code().unwrap_or(1)).into());
+        }
+        Ok(())
+    }
+}
+
+/// Checks if the executable is part of an app installed by scoop, which lives
+/// in `<scoop>/apps/<app>/<version>/`.
+fn is_scoop_app(exe: &Path) -> bool {
+    let Some(apps_dir) = exe.ancestors().nth(3) else {
+        return false;
+    };
+    apps_dir.ends_with("apps")
+        && apps_dir
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|name| name.eq_ignore_ascii_case("scoop"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_scoop_app() {
+        assert!(is_scoop_app(Path::new(
+            "/Users/me/scoop/apps/sentry-cli/2.40.0/sentry-cli.exe"
+        )));
+        assert!(is_scoop_app(Path::new(
+            "/Users/me/scoop/apps/sentry-cli/current/sentry-cli.exe"
+        )));
+        assert!(!is_scoop_app(Path::new("/usr/local/bin/sentry-cli")));
+        assert!(!is_scoop_app(Path::new("/sentry-cli")));
+    }
+}
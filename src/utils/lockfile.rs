@@ -0,0 +1,297 @@
+//! Advisory locking and atomic writes of state files.
+//!
+//! Parallel CI jobs on the same runner share the cache folder, and often the
+//! config file and working directory.  State files like the upload cache,
+//! the scan journal or the state of resumable uploads are therefore updated
+//! while holding a [`FileLock`], and written through an [`AtomicFile`], so
+//! that other processes never read a partially written file.
+//!
+//! Locks are advisory: they only exclude other sentry-cli processes that
+//! lock the same file.  The lock files live in the cache folder rather than
+//! next to the locked files, so that they do not litter home and working
+//! directories.  The operating system releases locks of processes that
+//! exit, even if they crash.  In stateless mode, no lock files are created
+//! and locks do not exclude anything.
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use log::info;
+use uuid::Uuid;
+
+use crate::config::is_stateless;
+use crate::constants::APP_NAME;
+
+const LOCKS_DIR_NAME: &str = "locks";
+
+/// An exclusive lock on a file, released when dropped.
+#[derive(Debug)]
+pub struct FileLock {
+    // Closing the lock file releases the lock.
+    _file: Option<File>,
+}
+
+impl FileLock {
+    /// Locks the file at the given path, waiting for other processes to
+    /// release it.
+    pub fn acquire<P: AsRef<Path>>(path: P) -> Result<Self> {
+        if is_stateless() {
+            return Ok(FileLock { _file: None });
+        }
+
+        let path = path.as_ref();
+        let file = open_lock_file(path)?;
+        if !sys::lock(&file, false)? {
+            info!("Waiting for another process to release {}", path.display());
+            sys::lock(&file, true).with_context(|| format!("Could not lock {}", path.display()))?;
+        }
+        Ok(FileLock { _file: Some(file) })
+    }
+
+    /// Locks the file at the given path, unless another process holds the
+    /// lock already.
+    pub fn try_acquire<P: AsRef<Path>>(path: P) -> Result<Option<Self>> {
+        if is_stateless() {
+            return Ok(Some(FileLock { _file: None }));
+        }
+
+        let file = open_lock_file(path.as_ref())?;
+        Ok(sys::lock(&file, false)?.then_some(FileLock { _file: Some(file) }))
+    }
+}
+
+/// Opens the lock file for the given path, creating it if needed.
+fn open_lock_file(path: &Path) -> Result<File> {
+    let path = std::path::absolute(path)?;
+    let mut lock_path = dirs::cache_dir().unwrap_or_else(env::temp_dir);
+    lock_path.push(APP_NAME);
+    lock_path.push(LOCKS_DIR_NAME);
+    fs::create_dir_all(&lock_path)?;
+    lock_path.push(format!(
+        "{}.lock",
+        sha1_smol::Sha1::from(path.to_string_lossy().as_bytes()).digest()
+    ));
+
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Could not open lock file {}", lock_path.display()))
+}
+
+/// A file that replaces the file at its path only once it has been written
+/// completely.
+///
+/// Contents are written to a temporary file in the same folder, which is
+/// renamed over the destination by [`AtomicFile::commit`].  If the file is
+/// dropped without being committed, the destination is left untouched.
+#[derive(Debug)]
+pub struct AtomicFile {
+    file: File,
+    temp_path: PathBuf,
+    path: PathBuf,
+    committed: bool,
+}
+
+impl AtomicFile {
+    /// Creates an atomic file for the given path.  If the destination exists,
+    /// its permissions are preserved.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::create_impl(path.as_ref(), false)
+    }
+
+    /// Like [`AtomicFile::create`], but new files are only readable by the
+    /// current user.  Use this for files that contain credentials.
+    pub fn create_private<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::create_impl(path.as_ref(), true)
+    }
+
+    fn create_impl(path: &Path, private: bool) -> Result<Self> {
+        // Replace the target of a symlink rather than the symlink itself.
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+        let file_name = path
+            .file_name()
+            .with_context(|| format!("Invalid file path {}", path.display()))?;
+        let temp_path = path.with_file_name(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            Uuid::new_v4().simple()
+        ));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        // Remove all non-user permissions for new files with credentials.
+        #[cfg(not(windows))]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            if private {
+                options.mode(0o600);
+            }
+        }
+        #[cfg(windows)]
+        let _ = private;
+
+        let file = options
+            .open(&temp_path)
+            .with_context(|| format!("Could not create {}", temp_path.display()))?;
+        if let Ok(metadata) = fs::metadata(&path) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+
+        Ok(AtomicFile {
+            file,
+            temp_path,
+            path,
+            committed: false,
+        })
+    }
+
+    /// Returns the underlying temporary file.
+    pub fn file_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Replaces the destination with the written contents.
+    pub fn commit(mut self) -> Result<()> {
+        self.file.flush()?;
+        self.file.sync_all()?;
+        fs::rename(&self.temp_path, &self.path)
+            .with_context(|| format!("Could not write {}", self.path.display()))?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// Writes the contents to the file at the given path, such that other
+/// processes read either the old or the new contents.
+pub fn write_atomic<P: AsRef<Path>>(path: P, contents: &[u8]) -> Result<()> {
+    let mut file = AtomicFile::create(path)?;
+    file.write_all(contents)?;
+    file.commit()
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+
+    /// Locks the file exclusively.  Returns `false` if the file is locked
+    /// by another process and `wait` is false.
+    pub fn lock(file: &File, wait: bool) -> io::Result<bool> {
+        let operation = if wait {
+            libc::LOCK_EX
+        } else {
+            libc::LOCK_EX | libc::LOCK_NB
+        };
+        loop {
+            if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+                return Ok(true);
+            }
+            let err = io::Error::last_os_error();
+            match err.kind() {
+                io::ErrorKind::Interrupted => continue,
+                io::ErrorKind::WouldBlock => return Ok(false),
+                _ => return Err(err),
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::fs::File;
+    use std::os::windows::io::AsRawHandle;
+    use std::{io, mem};
+
+    use winapi::shared::winerror::ERROR_LOCK_VIOLATION;
+    use winapi::um::fileapi::LockFileEx;
+    use winapi::um::minwinbase::{LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY, OVERLAPPED};
+
+    /// Locks the file exclusively.  Returns `false` if the file is locked
+    /// by another process and `wait` is false.
+    pub fn lock(file: &File, wait: bool) -> io::Result<bool> {
+        let flags = if wait {
+            LOCKFILE_EXCLUSIVE_LOCK
+        } else {
+            LOCKFILE_EXCLUSIVE_LOCK | LOCKFILE_FAIL_IMMEDIATELY
+        };
+        let mut overlapped: OVERLAPPED = unsafe { mem::zeroed() };
+        let locked = unsafe {
+            LockFileEx(
+                file.as_raw_handle().cast(),
+                flags,
+                0,
+                !0,
+                !0,
+                &mut overlapped,
+            )
+        };
+        if locked != 0 {
+            return Ok(true);
+        }
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() == Some(ERROR_LOCK_VIOLATION as i32) {
+            return Ok(false);
+        }
+        Err(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::utils::fs::TempDir;
+
+    #[test]
+    fn test_file_lock() {
+        let dir = TempDir::create().unwrap();
+        let path = dir.path().join("state");
+
+        let lock = FileLock::try_acquire(&path).unwrap();
+        assert!(lock.is_some());
+        assert!(FileLock::try_acquire(&path).unwrap().is_none());
+
+        drop(lock);
+        assert!(FileLock::try_acquire(&path).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_atomic_file() {
+        let dir = TempDir::create().unwrap();
+        let path = dir.path().join("state");
+        write_atomic(&path, b"old").unwrap();
+
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"new").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"old");
+        drop(file);
+        assert_eq!(fs::read(&path).unwrap(), b"old");
+
+        write_atomic(&path, b"new").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
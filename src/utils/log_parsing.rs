@@ -0,0 +1,651 @@
+//! Parses lines of common log file formats, syslog messages and journal
+//! entries into structured log entries, and packs those entries into
+//! envelopes.
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use anyhow::{bail, format_err, Context, Error, Result};
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone, Utc};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use sentry::Envelope;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+lazy_static! {
+    /// The combined log format shared by nginx and Apache access logs.
+    static ref ACCESS_LOG_RE: Regex = Regex::new(
+        r#"^(?P<client>\S+) \S+ \S+ \[(?P<time>[^\]]+)\] "(?P<method>[A-Z]+) (?P<path>\S+)[^"]*" (?P<status>\d{3}) \S+"#
+    )
+    .unwrap();
+    static ref NGINX_ERROR_RE: Regex = Regex::new(
+        r"^(?P<time>\d{4}/\d{2}/\d{2} \d{2}:\d{2}:\d{2}) \[(?P<level>[a-z]+)\] \d+#\d+: (?:\*\d+ )?(?P<message>.*)$"
+    )
+    .unwrap();
+    static ref APACHE_ERROR_RE: Regex = Regex::new(
+        r"^\[(?P<time>[^\]]+)\] \[(?:[^:\]]*:)?(?P<level>[a-z0-9]+)\](?: \[pid [^\]]*\])?(?: \[client [^\]]*\])? (?P<message>.*)$"
+    )
+    .unwrap();
+    static ref SYSLOG_5424_RE: Regex = Regex::new(
+        r"^<(?P<pri>\d{1,3})>1 (?P<time>\S+) (?P<host>\S+) (?P<app>\S+) \S+ \S+ (?:-|(?:\[(?:[^\]\\]|\\.)*\])+) ?(?P<message>.*)$"
+    )
+    .unwrap();
+    static ref SYSLOG_3164_RE: Regex = Regex::new(
+        r"^<(?P<pri>\d{1,3})>(?P<time>[A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}) (?P<host>\S+) (?P<app>[^:\[\s]+)(?:\[\d+\])?: ?(?P<message>.*)$"
+    )
+    .unwrap();
+    static ref SYSLOG_PRI_RE: Regex = Regex::new(r"^<(?P<pri>\d{1,3})>(?P<message>.*)$").unwrap();
+    static ref PLAIN_LEVEL_RE: Regex = Regex::new(
+        r"(?i)\b(?P<level>trace|debug|info|notice|warn|warning|error|err|fatal|critical|crit)\b"
+    )
+    .unwrap();
+}
+
+/// The format of a log file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Detects the format of every line.
+    Auto,
+    /// nginx access and error logs.
+    Nginx,
+    /// Apache access and error logs.
+    Apache,
+    /// Unstructured lines, with the level taken from a level keyword.
+    Plain,
+    /// One JSON object per line.
+    Json(JsonFields),
+}
+
+impl FromStr for LogFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<LogFormat> {
+        Ok(match s {
+            "auto" => LogFormat::Auto,
+            "nginx" => LogFormat::Nginx,
+            "apache" => LogFormat::Apache,
+            "plain" => LogFormat::Plain,
+            "json" => LogFormat::Json(JsonFields::default()),
+            _ => bail!("unknown log format {s}"),
+        })
+    }
+}
+
+/// The fields of JSON log lines that hold the timestamp, level and message.
+///
+/// Fields of nested objects are referred to by their path, separated by
+/// dots, e.g. `log.level`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonFields {
+    pub timestamp: String,
+    pub level: String,
+    pub message: String,
+}
+
+impl Default for JsonFields {
+    fn default() -> Self {
+        JsonFields {
+            timestamp: "timestamp".into(),
+            level: "level".into(),
+            message: "message".into(),
+        }
+    }
+}
+
+/// Where log entries are read from, other than a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogSource {
+    /// Syslog messages received over UDP on the given address.
+    Syslog(SocketAddr),
+    /// The systemd journal.
+    Journald,
+}
+
+impl FromStr for LogSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<LogSource> {
+        if s == "journald" {
+            return Ok(LogSource::Journald);
+        }
+        if let Some(addr) = s.strip_prefix("syslog://") {
+            let addr = addr
+                .parse()
+                .with_context(|| format!("Invalid syslog address {addr}"))?;
+            return Ok(LogSource::Syslog(addr));
+        }
+        bail!("Unknown log source {s}, expected syslog://ADDRESS:PORT or journald")
+    }
+}
+
+/// The severity of a log entry, as understood by Sentry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl LogLevel {
+    /// Maps the level names used by nginx, Apache and common logging
+    /// libraries to a level.
+    pub fn from_name(name: &str) -> Option<LogLevel> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "trace" | "trace1" | "trace2" | "trace3" | "trace4" | "trace5" | "trace6"
+            | "trace7" | "trace8" => LogLevel::Trace,
+            "debug" => LogLevel::Debug,
+            "info" | "notice" => LogLevel::Info,
+            "warn" | "warning" => LogLevel::Warn,
+            "error" | "err" => LogLevel::Error,
+            "crit" | "critical" | "alert" | "emerg" | "fatal" => LogLevel::Fatal,
+            _ => return None,
+        })
+    }
+
+    /// Maps a syslog severity to a level.
+    pub fn from_syslog_severity(severity: u8) -> LogLevel {
+        match severity {
+            0..=2 => LogLevel::Fatal,
+            3 => LogLevel::Error,
+            4 => LogLevel::Warn,
+            5 | 6 => LogLevel::Info,
+            _ => LogLevel::Debug,
+        }
+    }
+
+    /// Maps the status code of an access log entry to a level.
+    fn from_status(status: u16) -> LogLevel {
+        match status {
+            500.. => LogLevel::Error,
+            400..=499 => LogLevel::Warn,
+            _ => LogLevel::Info,
+        }
+    }
+}
+
+/// A parsed line of a log file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub level: LogLevel,
+    pub body: String,
+    pub attributes: BTreeMap<Cow<'static, str>, Value>,
+}
+
+/// Detects the format of a log file from its first non-empty line.
+///
+/// Files of JSON objects are detected as JSON with the default field
+/// mapping, everything else is detected line by line.
+pub fn detect_log_format(lines: &[String]) -> LogFormat {
+    let first = lines.iter().find(|line| !line.trim().is_empty());
+    match first.map(|line| serde_json::from_str::<Value>(line)) {
+        Some(Ok(Value::Object(_))) => LogFormat::Json(JsonFields::default()),
+        _ => LogFormat::Auto,
+    }
+}
+
+/// Parses a line of a log file in the given format.
+///
+/// Lines that do not match the format are treated as plain lines, and the
+/// current time is used if a line has no parseable timestamp.
+pub fn parse_line(format: &LogFormat, line: &str) -> LogEntry {
+    let parsed = match format {
+        LogFormat::Auto => parse_nginx_error(line)
+            .or_else(|| parse_apache_error(line))
+            .or_else(|| parse_access(line)),
+        LogFormat::Nginx => parse_nginx_error(line).or_else(|| parse_access(line)),
+        LogFormat::Apache => parse_apache_error(line).or_else(|| parse_access(line)),
+        LogFormat::Plain => None,
+        LogFormat::Json(fields) => parse_json(fields, line),
+    };
+    parsed.unwrap_or_else(|| parse_plain(line))
+}
+
+fn parse_access(line: &str) -> Option<LogEntry> {
+    let caps = ACCESS_LOG_RE.captures(line)?;
+    let status: u16 = caps["status"].parse().ok()?;
+    let timestamp = DateTime::parse_from_str(&caps["time"], "%d/%b/%Y:%H:%M:%S %z")
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+
+    let mut attributes = BTreeMap::new();
+    attributes.insert("client.address".into(), caps["client"].into());
+    attributes.insert("http.request.method".into(), caps["method"].into());
+    attributes.insert("url.path".into(), caps["path"].into());
+    attributes.insert("http.response.status_code".into(), status.into());
+
+    Some(LogEntry {
+        timestamp,
+        level: LogLevel::from_status(status),
+        body: format!("{} {} {status}", &caps["method"], &caps["path"]),
+        attributes,
+    })
+}
+
+fn parse_nginx_error(line: &str) -> Option<LogEntry> {
+    let caps = NGINX_ERROR_RE.captures(line)?;
+    parse_error(&caps, "%Y/%m/%d %H:%M:%S")
+}
+
+fn parse_apache_error(line: &str) -> Option<LogEntry> {
+    let caps = APACHE_ERROR_RE.captures(line)?;
+    parse_error(&caps, "%a %b %d %H:%M:%S%.f %Y")
+}
+
+/// Creates an entry from the captures of an error log, which are logged in
+/// local time.
+fn parse_error(caps: &Captures<'_>, time_format: &str) -> Option<LogEntry> {
+    let level = LogLevel::from_name(&caps["level"])?;
+    let timestamp = NaiveDateTime::parse_from_str(&caps["time"], time_format)
+        .ok()
+        .and_then(|time| Local.from_local_datetime(&time).single())
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+
+    Some(LogEntry {
+        timestamp,
+        level,
+        body: caps["message"].to_owned(),
+        attributes: BTreeMap::new(),
+    })
+}
+
+/// Creates an entry from a JSON object, taking the timestamp, level and
+/// message from the mapped fields and all other top-level fields as
+/// attributes.
+fn parse_json(fields: &JsonFields, line: &str) -> Option<LogEntry> {
+    let object: Value = serde_json::from_str(line).ok()?;
+    let lookup = |field: &str| {
+        field
+            .split('.')
+            .try_fold(&object, |value, key| value.get(key))
+            .filter(|value| !value.is_null())
+    };
+
+    let timestamp = lookup(&fields.timestamp)
+        .and_then(parse_json_timestamp)
+        .unwrap_or_else(Utc::now);
+    let level = lookup(&fields.level)
+        .and_then(Value::as_str)
+        .and_then(LogLevel::from_name)
+        .unwrap_or(LogLevel::Info);
+    let body = match lookup(&fields.message) {
+        Some(Value::String(message)) => message.clone(),
+        Some(message) => message.to_string(),
+        None => line.to_owned(),
+    };
+
+    let mapped = [&fields.timestamp, &fields.level, &fields.message];
+    let attributes = object
+        .as_object()?
+        .iter()
+        .filter(|(key, _)| !mapped.contains(key))
+        .filter_map(|(key, value)| {
+            let value = match value {
+                Value::Null => return None,
+                Value::Array(_) | Value::Object(_) => value.to_string().into(),
+                _ => value.clone(),
+            };
+            Some((Cow::Owned(key.clone()), value))
+        })
+        .collect();
+
+    Some(LogEntry {
+        timestamp,
+        level,
+        body,
+        attributes,
+    })
+}
+
+/// Parses an RFC 3339 timestamp, or a number of seconds since the epoch.
+fn parse_json_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    let seconds = match value {
+        Value::String(time) => {
+            if let Ok(time) = DateTime::parse_from_rfc3339(time) {
+                return Some(time.with_timezone(&Utc));
+            }
+            time.parse::<f64>().ok()?
+        }
+        Value::Number(number) => number.as_f64()?,
+        _ => return None,
+    };
+    Utc.timestamp_micros((seconds * 1_000_000.0) as i64)
+        .single()
+}
+
+fn parse_plain(line: &str) -> LogEntry {
+    let level = PLAIN_LEVEL_RE
+        .captures(line)
+        .and_then(|caps| LogLevel::from_name(&caps["level"]))
+        .unwrap_or(LogLevel::Info);
+
+    LogEntry {
+        timestamp: Utc::now(),
+        level,
+        body: line.to_owned(),
+        attributes: BTreeMap::new(),
+    }
+}
+
+/// Parses a syslog message in the RFC 5424 or RFC 3164 format.
+///
+/// Messages without a syslog header are treated as plain lines.
+pub fn parse_syslog(message: &str) -> LogEntry {
+    let message = message.trim_end_matches(['\n', '\r', '\0']);
+    let (caps, timestamp) = if let Some(caps) = SYSLOG_5424_RE.captures(message) {
+        let timestamp = DateTime::parse_from_rfc3339(&caps["time"])
+            .ok()
+            .map(|time| time.with_timezone(&Utc));
+        (caps, timestamp)
+    } else if let Some(caps) = SYSLOG_3164_RE.captures(message) {
+        // The timestamp is in local time and has no year.
+        let timestamp = NaiveDateTime::parse_from_str(
+            &format!("{} {}", Local::now().year(), &caps["time"]),
+            "%Y %b %e %H:%M:%S",
+        )
+        .ok()
+        .and_then(|time| Local.from_local_datetime(&time).single())
+        .map(|time| time.with_timezone(&Utc));
+        (caps, timestamp)
+    } else if let Some(caps) = SYSLOG_PRI_RE.captures(message) {
+        (caps, None)
+    } else {
+        return parse_plain(message);
+    };
+
+    let Ok(pri) = caps["pri"].parse::<u8>() else {
+        return parse_plain(message);
+    };
+
+    let mut attributes = BTreeMap::new();
+    attributes.insert("syslog.facility".into(), (pri / 8).into());
+    for (key, group) in [("host.name", "host"), ("syslog.appname", "app")] {
+        if let Some(value) = caps.name(group).filter(|value| value.as_str() != "-") {
+            attributes.insert(key.into(), value.as_str().into());
+        }
+    }
+
+    LogEntry {
+        timestamp: timestamp.unwrap_or_else(Utc::now),
+        level: LogLevel::from_syslog_severity(pri % 8),
+        body: caps["message"].to_owned(),
+        attributes,
+    }
+}
+
+/// An entry of `journalctl --output json`.
+#[derive(Debug, Deserialize)]
+pub struct JournalEntry {
+    /// A string, or an array of bytes if the message is not valid UTF-8.
+    #[serde(rename = "MESSAGE", default)]
+    message: Value,
+    #[serde(rename = "PRIORITY")]
+    priority: Option<String>,
+    #[serde(rename = "SYSLOG_IDENTIFIER")]
+    pub identifier: Option<String>,
+    #[serde(rename = "_SYSTEMD_UNIT")]
+    unit: Option<String>,
+    #[serde(rename = "_HOSTNAME")]
+    hostname: Option<String>,
+    /// Microseconds since the epoch.
+    #[serde(rename = "__REALTIME_TIMESTAMP")]
+    timestamp: Option<String>,
+}
+
+impl JournalEntry {
+    /// Returns the message, if the entry has one.
+    pub fn message(&self) -> Option<String> {
+        match &self.message {
+            Value::String(message) => Some(message.clone()),
+            Value::Array(bytes) => {
+                let bytes: Vec<u8> = bytes
+                    .iter()
+                    .filter_map(|byte| u8::try_from(byte.as_u64()?).ok())
+                    .collect();
+                Some(String::from_utf8_lossy(&bytes).into_owned())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the level of the entry's syslog priority.
+    pub fn level(&self) -> LogLevel {
+        self.priority
+            .as_deref()
+            .and_then(|priority| priority.parse().ok())
+            .map_or(LogLevel::Info, LogLevel::from_syslog_severity)
+    }
+
+    /// Returns when the entry was logged.
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        let micros = self.timestamp.as_deref()?.parse().ok()?;
+        Utc.timestamp_micros(micros).single()
+    }
+
+    /// Converts the entry to a log entry, unless it has no message.
+    pub fn to_log_entry(&self) -> Option<LogEntry> {
+        let mut attributes = BTreeMap::new();
+        let fields = [
+            ("host.name", &self.hostname),
+            ("syslog.appname", &self.identifier),
+            ("systemd.unit", &self.unit),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                attributes.insert(key.into(), value.as_str().into());
+            }
+        }
+
+        Some(LogEntry {
+            timestamp: self.timestamp().unwrap_or_else(Utc::now),
+            level: self.level(),
+            body: self.message()?,
+            attributes,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct LogItem<'a> {
+    timestamp: f64,
+    trace_id: &'a str,
+    level: LogLevel,
+    body: &'a str,
+    attributes: BTreeMap<&'a str, Attribute<'a>>,
+}
+
+#[derive(Serialize)]
+struct Attribute<'a> {
+    value: &'a Value,
+    #[serde(rename = "type")]
+    ty: &'static str,
+}
+
+impl<'a> Attribute<'a> {
+    fn new(value: &'a Value) -> Self {
+        let ty = match value {
+            Value::Bool(_) => "boolean",
+            Value::Number(number) if number.is_f64() => "double",
+            Value::Number(_) => "integer",
+            _ => "string",
+        };
+        Attribute { value, ty }
+    }
+}
+
+/// Creates an envelope with a single log item containing the given entries.
+pub fn make_envelope(entries: &[LogEntry], trace_id: &str) -> Result<Envelope> {
+    let items: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            let attributes = entry
+                .attributes
+                .iter()
+                .map(|(key, value)| (key.as_ref(), Attribute::new(value)))
+                .collect();
+
+            LogItem {
+                timestamp: entry.timestamp.timestamp_micros() as f64 / 1_000_000.0,
+                trace_id,
+                level: entry.level,
+                body: &entry.body,
+                attributes,
+            }
+        })
+        .collect();
+
+    let mut body = b"{}\n".to_vec();
+    serde_json::to_writer(
+        &mut body,
+        &serde_json::json!({
+            "type": "log",
+            "item_count": items.len(),
+            "content_type": "application/vnd.sentry.items.log+json",
+        }),
+    )?;
+    body.push(b'\n');
+    serde_json::to_writer(&mut body, &serde_json::json!({ "items": items }))?;
+    body.push(b'\n');
+
+    Envelope::from_bytes_raw(body).map_err(|err| format_err!("Invalid log envelope: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_access_log() {
+        let entry = parse_line(
+            &LogFormat::Auto,
+            r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 404 2326 "-" "curl/8.0""#,
+        );
+        assert_eq!(entry.level, LogLevel::Warn);
+        assert_eq!(entry.body, "GET /apache_pb.gif 404");
+        assert_eq!(entry.timestamp.to_rfc3339(), "2000-10-10T20:55:36+00:00");
+        assert_eq!(entry.attributes["http.response.status_code"], 404);
+        assert_eq!(entry.attributes["client.address"], "127.0.0.1");
+    }
+
+    #[test]
+    fn test_parse_nginx_error_log() {
+        let entry = parse_line(
+            &LogFormat::Nginx,
+            "2024/03/01 12:00:00 [crit] 1234#0: *5 connect() failed (111: Connection refused)",
+        );
+        assert_eq!(entry.level, LogLevel::Fatal);
+        assert_eq!(entry.body, "connect() failed (111: Connection refused)");
+    }
+
+    #[test]
+    fn test_parse_apache_error_log() {
+        let entry = parse_line(
+            &LogFormat::Apache,
+            "[Wed Oct 11 14:32:52.123456 2000] [core:error] [pid 35708:tid 4328636416] [client 127.0.0.1] File does not exist",
+        );
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.body, "File does not exist");
+    }
+
+    #[test]
+    fn test_parse_plain_line() {
+        let entry = parse_line(&LogFormat::Auto, "2024-03-01 WARNING disk almost full");
+        assert_eq!(entry.level, LogLevel::Warn);
+        assert_eq!(entry.body, "2024-03-01 WARNING disk almost full");
+
+        let entry = parse_line(&LogFormat::Plain, "worker started");
+        assert_eq!(entry.level, LogLevel::Info);
+    }
+
+    #[test]
+    fn test_detect_log_format() {
+        let lines = [String::new(), r#"{"message":"worker started"}"#.to_owned()];
+        assert_eq!(
+            detect_log_format(&lines),
+            LogFormat::Json(JsonFields::default())
+        );
+
+        let lines = ["2024/03/01 12:00:00 [error] 1#0: failed".to_owned()];
+        assert_eq!(detect_log_format(&lines), LogFormat::Auto);
+        assert_eq!(detect_log_format(&[]), LogFormat::Auto);
+    }
+
+    #[test]
+    fn test_parse_json_line() {
+        let entry = parse_line(
+            &LogFormat::Json(JsonFields::default()),
+            r#"{"timestamp":"2024-03-01T12:00:00Z","level":"warning","message":"disk almost full","disk":"/dev/sda1","free":0.05,"tags":["a"]}"#,
+        );
+        assert_eq!(entry.level, LogLevel::Warn);
+        assert_eq!(entry.body, "disk almost full");
+        assert_eq!(entry.timestamp.to_rfc3339(), "2024-03-01T12:00:00+00:00");
+        assert_eq!(entry.attributes["disk"], "/dev/sda1");
+        assert_eq!(entry.attributes["free"], 0.05);
+        assert_eq!(entry.attributes["tags"], r#"["a"]"#);
+        assert!(!entry.attributes.contains_key("level"));
+
+        let format = LogFormat::Json(JsonFields {
+            timestamp: "ts".into(),
+            level: "log.severity".into(),
+            message: "msg".into(),
+        });
+        let entry = parse_line(
+            &format,
+            r#"{"ts":1700000000,"log":{"severity":"ERROR"},"msg":"request failed"}"#,
+        );
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.body, "request failed");
+        assert_eq!(entry.timestamp.to_rfc3339(), "2023-11-14T22:13:20+00:00");
+
+        let entry = parse_line(&format, "not json: error");
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.body, "not json: error");
+    }
+
+    #[test]
+    fn test_parse_syslog() {
+        let entry = parse_syslog(
+            "<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - 'su root' failed",
+        );
+        assert_eq!(entry.level, LogLevel::Fatal);
+        assert_eq!(entry.body, "'su root' failed");
+        assert_eq!(
+            entry.timestamp.to_rfc3339(),
+            "2003-10-11T22:14:15.003+00:00"
+        );
+        assert_eq!(entry.attributes["host.name"], "mymachine.example.com");
+        assert_eq!(entry.attributes["syslog.appname"], "su");
+        assert_eq!(entry.attributes["syslog.facility"], 4);
+
+        let entry = parse_syslog("<13>Feb  5 17:32:18 10.0.0.99 myapp[123]: Use the BFG!\n");
+        assert_eq!(entry.level, LogLevel::Info);
+        assert_eq!(entry.body, "Use the BFG!");
+        assert_eq!(entry.attributes["syslog.appname"], "myapp");
+
+        let entry = parse_syslog("just a message");
+        assert_eq!(entry.body, "just a message");
+    }
+
+    #[test]
+    fn test_parse_journal_entry() {
+        let entry: JournalEntry = serde_json::from_str(
+            r#"{"MESSAGE":"Started nginx","PRIORITY":"3","_SYSTEMD_UNIT":"nginx.service","__REALTIME_TIMESTAMP":"1700000000000000"}"#,
+        )
+        .unwrap();
+        let entry = entry.to_log_entry().unwrap();
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.body, "Started nginx");
+        assert_eq!(entry.timestamp.to_rfc3339(), "2023-11-14T22:13:20+00:00");
+        assert_eq!(entry.attributes["systemd.unit"], "nginx.service");
+
+        let entry: JournalEntry = serde_json::from_str(r#"{"MESSAGE":[104,105]}"#).unwrap();
+        assert_eq!(entry.message().as_deref(), Some("hi"));
+    }
+}
@@ -10,6 +10,8 @@ use lazy_static::lazy_static;
 use log::max_level;
 use parking_lot::RwLock;
 
+use crate::utils::support_bundle;
+
 // Quiet mode decides whether to print something based on `--quiet` flag.
 lazy_static! {
     static ref QUIET_MODE: AtomicBool = AtomicBool::new(false);
@@ -23,8 +25,6 @@ pub fn set_quiet_mode(is_quiet: bool) {
     QUIET_MODE.store(is_quiet, Ordering::Relaxed);
 }
 
-// NOTE: Remove `allow`s after first use.
-#[allow(unused_macros)]
 macro_rules! quiet_println {
     ($($tt:tt)*) => {{
         if !crate::utils::logging::is_quiet_mode() {
@@ -32,7 +32,6 @@ macro_rules! quiet_println {
         }
     }};
 }
-#[allow(unused_imports)]
 pub(crate) use quiet_println;
 
 // NOTE: Remove `allow`s after first use.
@@ -47,6 +46,24 @@ macro_rules! quiet_eprintln {
 #[allow(unused_imports)]
 pub(crate) use quiet_eprintln;
 
+// Decides whether warnings about experimental features are printed, based on
+// the `--no-experimental-warning` flag.
+lazy_static! {
+    static ref EXPERIMENTAL_WARNINGS: AtomicBool = AtomicBool::new(true);
+}
+
+pub fn set_experimental_warnings(enabled: bool) {
+    EXPERIMENTAL_WARNINGS.store(enabled, Ordering::Relaxed);
+}
+
+/// Warns that an experimental feature is in use, unless these warnings have
+/// been suppressed with `--no-experimental-warning`.
+pub fn warn_experimental(message: &str) {
+    if EXPERIMENTAL_WARNINGS.load(Ordering::Relaxed) {
+        log::warn!("EXPERIMENTAL FEATURE: {message}");
+    }
+}
+
 // Globally shared ProgressBar instance.
 lazy_static! {
     static ref PROGRESS_BAR: RwLock<Option<Weak<ProgressBar>>> = RwLock::new(None);
@@ -112,6 +129,10 @@ impl log::Log for Logger {
             return;
         }
 
+        if !support_bundle::record_log(level, record.target(), &record.args().to_string()) {
+            return;
+        }
+
         if let Some(pb) = get_progress_bar() {
             pb.println(msg);
         } else {
@@ -3,26 +3,48 @@ pub mod android;
 pub mod appcenter;
 pub mod args;
 pub mod auth_token;
+#[cfg(unix)]
+pub mod bazel_worker;
+pub mod breakpad;
 pub mod chunks;
+pub mod content_type;
 pub mod cordova;
+pub mod deprecations;
 pub mod dif;
 pub mod dif_upload;
 pub mod event;
+pub mod exit_code;
 pub mod file_search;
 pub mod file_upload;
 pub mod formatting;
 pub mod fs;
 pub mod http;
+pub mod installer;
+pub mod lockfile;
+pub mod log_parsing;
 pub mod logging;
 pub mod metrics;
+pub mod notify;
 pub mod progress;
 pub mod proguard;
+pub mod provenance;
+pub mod proxy;
+pub mod release_notes;
+pub mod release_trash;
 pub mod releases;
+pub mod remote_sources;
 pub mod retry;
+pub mod scan_journal;
+pub mod scrubbing;
+pub mod source_root;
 pub mod sourcemaps;
+pub mod support_bundle;
+pub mod symbol_server;
 pub mod system;
+pub mod trace;
 pub mod ui;
 pub mod update;
+pub mod upload_cache;
 pub mod value_parsers;
 pub mod vcs;
 pub mod xcode;
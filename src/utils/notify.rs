@@ -0,0 +1,166 @@
+//! Formats and posts notifications about releases and deploys to webhooks
+//! of chat tools and CI systems.
+use std::fmt::Write;
+
+use anyhow::{format_err, Result};
+use log::warn;
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::api::{Api, ReleaseCommit};
+use crate::config::Config;
+
+/// A summary of a release, and of its deploy if an environment is given.
+#[derive(Debug, Default)]
+pub struct Notification {
+    pub release: String,
+    pub environment: Option<String>,
+    pub commit_range: Option<String>,
+    pub release_url: Option<String>,
+    pub message: Option<String>,
+}
+
+impl Notification {
+    /// Returns the summary as text, one detail per line.
+    pub fn to_text(&self) -> String {
+        let mut text = match self.environment {
+            Some(ref env) => format!("Deployed release {} to {env}", self.release),
+            None => format!("Release {}", self.release),
+        };
+        if let Some(ref range) = self.commit_range {
+            write!(text, "\nCommits: {range}").ok();
+        }
+        if let Some(ref url) = self.release_url {
+            write!(text, "\n{url}").ok();
+        }
+        if let Some(ref message) = self.message {
+            write!(text, "\n{message}").ok();
+        }
+        text
+    }
+
+    /// Returns the payload that is posted to webhooks.
+    ///
+    /// Slack and compatible tools show the `text` field, other consumers can
+    /// use the individual fields.
+    pub fn to_payload(&self) -> Value {
+        json!({
+            "text": self.to_text(),
+            "release": self.release,
+            "environment": self.environment,
+            "commit_range": self.commit_range,
+            "release_url": self.release_url,
+            "message": self.message,
+        })
+    }
+}
+
+/// Returns the range of commits of a release as `OLDEST..NEWEST`, given the
+/// commits from newest to oldest as returned by the API.
+pub fn commit_range(commits: &[ReleaseCommit]) -> Option<String> {
+    let short = |commit: &ReleaseCommit| commit.id.chars().take(7).collect::<String>();
+    let newest = short(commits.first()?);
+    let oldest = short(commits.last()?);
+    Some(if commits.len() == 1 {
+        newest
+    } else {
+        format!("{oldest}..{newest}")
+    })
+}
+
+/// Returns the URL of the release's page on Sentry.
+pub fn release_url(base_url: &str, org: &str, release: &str) -> Result<String> {
+    let mut url = Url::parse(base_url)?;
+    url.path_segments_mut()
+        .map_err(|_| format_err!("Invalid Sentry URL {base_url}"))?
+        .pop_if_empty()
+        .extend(["organizations", org, "releases", release, ""]);
+    Ok(url.into())
+}
+
+/// Posts a summary of the release to the webhook.
+///
+/// The commit range is looked up on Sentry if the CLI is authenticated.
+/// Failing to look it up does not prevent the notification from being sent.
+pub fn notify(
+    webhook: &str,
+    org: &str,
+    release: &str,
+    environment: Option<&str>,
+    message: Option<&str>,
+) -> Result<()> {
+    let config = Config::current();
+    let api = Api::current();
+
+    let commit_range = match api.authenticated() {
+        Ok(authenticated_api) => match authenticated_api.get_release_commits(org, None, release) {
+            Ok(commits) => commits.as_deref().and_then(commit_range),
+            Err(err) => {
+                warn!("Could not look up the commits of release {release}: {err}");
+                None
+            }
+        },
+        Err(_) => None,
+    };
+
+    let notification = Notification {
+        release: release.to_owned(),
+        environment: environment.map(str::to_owned),
+        commit_range,
+        release_url: Some(release_url(config.get_base_url()?, org, release)?),
+        message: message.map(str::to_owned),
+    };
+    api.post_webhook(webhook, &notification.to_payload())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(id: &str) -> ReleaseCommit {
+        ReleaseCommit {
+            id: id.to_owned(),
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_notification_text() {
+        let notification = Notification {
+            release: "app@1.0.0".into(),
+            environment: Some("production".into()),
+            commit_range: commit_range(&[
+                commit("f2d3ce0bd1fc2d9c6b4d3a1e4f6a2a1d4e2d1c2b"),
+                commit("0c3a40d7a9b2e1f3c5d7e9f1a3b5c7d9e1f3a5b7"),
+            ]),
+            release_url: Some(release_url("https://sentry.io/", "my-org", "app@1.0.0").unwrap()),
+            message: None,
+        };
+        assert_eq!(
+            notification.to_text(),
+            "Deployed release app@1.0.0 to production\n\
+            Commits: 0c3a40d..f2d3ce0\n\
+            https://sentry.io/organizations/my-org/releases/app@1.0.0/"
+        );
+        assert_eq!(notification.to_payload()["environment"], "production");
+        assert_eq!(notification.to_payload()["message"], Value::Null);
+    }
+
+    #[test]
+    fn test_commit_range() {
+        assert_eq!(commit_range(&[]), None);
+        assert_eq!(
+            commit_range(&[commit("0c3a40d7a9b2")]).as_deref(),
+            Some("0c3a40d")
+        );
+    }
+
+    #[test]
+    fn test_release_url() {
+        assert_eq!(
+            release_url("https://sentry.example.com/sentry", "org", "1.0 beta").unwrap(),
+            "https://sentry.example.com/sentry/organizations/org/releases/1.0%20beta/"
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Detects where an upload was built, so that uploaded bundles can be traced
+//! back to the pipeline that produced them.
+use std::env;
+
+use clap::ArgMatches;
+use log::debug;
+use username::get_user_name;
+
+use crate::utils::vcs;
+
+/// The build information attached to uploaded bundles.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Provenance {
+    /// The name of the CI provider, if running on CI.
+    pub ci_provider: Option<String>,
+    /// The URL of the CI workflow run.
+    pub run_url: Option<String>,
+    /// The CI runner or the local user that ran the upload.
+    pub builder: Option<String>,
+    /// The commit that was built.
+    pub git_sha: Option<String>,
+}
+
+impl Provenance {
+    /// Returns the provenance requested with `--provenance`, if any.
+    pub fn from_matches(matches: &ArgMatches) -> Option<Self> {
+        match matches.get_one::<String>("provenance").map(String::as_str) {
+            Some("auto") => Some(Provenance::detect()),
+            _ => None,
+        }
+    }
+
+    /// Detects the provenance from the environment of the supported CI
+    /// providers, falling back to the local user and git repository.
+    fn detect() -> Self {
+        let mut provenance = Provenance::from_ci_env(|key| env::var(key).ok());
+        if provenance.builder.is_none() {
+            provenance.builder = get_user_name().ok();
+        }
+        if provenance.git_sha.is_none() {
+            provenance.git_sha = vcs::find_head().ok();
+        }
+
+        debug!("Detected provenance: {provenance:?}");
+        provenance
+    }
+
+    /// Detects the provenance from the environment variables of the supported
+    /// CI providers, looked up with `env`.
+    fn from_ci_env<F>(env: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let var = |key: &str| env(key).filter(|value| !value.is_empty());
+
+        if var("GITHUB_ACTIONS").is_some() {
+            Provenance {
+                ci_provider: Some("github-actions".into()),
+                run_url: match (
+                    var("GITHUB_SERVER_URL"),
+                    var("GITHUB_REPOSITORY"),
+                    var("GITHUB_RUN_ID"),
+                ) {
+                    (Some(server), Some(repo), Some(run)) => {
+                        Some(format!("{server}/{repo}/actions/runs/{run}"))
+                    }
+                    _ => None,
+                },
+                builder: var("RUNNER_NAME"),
+                git_sha: var("GITHUB_SHA"),
+            }
+        } else if var("GITLAB_CI").is_some() {
+            Provenance {
+                ci_provider: Some("gitlab".into()),
+                run_url: var("CI_PIPELINE_URL"),
+                builder: var("CI_RUNNER_DESCRIPTION"),
+                git_sha: var("CI_COMMIT_SHA"),
+            }
+        } else if var("CIRCLECI").is_some() {
+            Provenance {
+                ci_provider: Some("circleci".into()),
+                run_url: var("CIRCLE_BUILD_URL"),
+                builder: var("CIRCLE_USERNAME"),
+                git_sha: var("CIRCLE_SHA1"),
+            }
+        } else if var("BUILDKITE").is_some() {
+            Provenance {
+                ci_provider: Some("buildkite".into()),
+                run_url: var("BUILDKITE_BUILD_URL"),
+                builder: var("BUILDKITE_AGENT_NAME"),
+                git_sha: var("BUILDKITE_COMMIT"),
+            }
+        } else if var("JENKINS_URL").is_some() {
+            Provenance {
+                ci_provider: Some("jenkins".into()),
+                run_url: var("BUILD_URL"),
+                builder: var("NODE_NAME"),
+                git_sha: var("GIT_COMMIT"),
+            }
+        } else if var("CI").is_some() {
+            Provenance {
+                ci_provider: Some("unknown".into()),
+                ..Default::default()
+            }
+        } else {
+            Provenance::default()
+        }
+    }
+
+    /// Returns the provenance as attributes for a bundle manifest.
+    pub fn attributes(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("provenance_ci", &self.ci_provider),
+            ("provenance_run_url", &self.run_url),
+            ("provenance_builder", &self.builder),
+            ("provenance_git_sha", &self.git_sha),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value.as_deref()?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use clap::Command;
+
+    use crate::utils::args::ArgExt as _;
+
+    fn from_env(vars: &[(&str, &str)]) -> Provenance {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        Provenance::from_ci_env(|key| vars.get(key).map(|value| (*value).to_owned()))
+    }
+
+    #[test]
+    fn test_github_actions() {
+        let provenance = from_env(&[
+            ("GITHUB_ACTIONS", "true"),
+            ("GITHUB_SERVER_URL", "https://github.com"),
+            ("GITHUB_REPOSITORY", "getsentry/sentry-cli"),
+            ("GITHUB_RUN_ID", "42"),
+            ("RUNNER_NAME", "runner-1"),
+            ("GITHUB_SHA", "a1b2c3"),
+        ]);
+
+        assert_eq!(
+            provenance,
+            Provenance {
+                ci_provider: Some("github-actions".into()),
+                run_url: Some("https://github.com/getsentry/sentry-cli/actions/runs/42".into()),
+                builder: Some("runner-1".into()),
+                git_sha: Some("a1b2c3".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_github_actions_incomplete_run_url() {
+        let provenance = from_env(&[
+            ("GITHUB_ACTIONS", "true"),
+            ("GITHUB_SERVER_URL", "https://github.com"),
+            ("GITHUB_RUN_ID", "42"),
+        ]);
+
+        assert_eq!(provenance.ci_provider.as_deref(), Some("github-actions"));
+        assert_eq!(provenance.run_url, None);
+    }
+
+    #[test]
+    fn test_gitlab() {
+        let provenance = from_env(&[
+            ("GITLAB_CI", "true"),
+            (
+                "CI_PIPELINE_URL",
+                "https://gitlab.com/group/project/-/pipelines/7",
+            ),
+            ("CI_RUNNER_DESCRIPTION", "shared-runner"),
+            ("CI_COMMIT_SHA", "d4e5f6"),
+        ]);
+
+        assert_eq!(
+            provenance,
+            Provenance {
+                ci_provider: Some("gitlab".into()),
+                run_url: Some("https://gitlab.com/group/project/-/pipelines/7".into()),
+                builder: Some("shared-runner".into()),
+                git_sha: Some("d4e5f6".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_ci() {
+        let provenance = from_env(&[("CI", "true")]);
+        assert_eq!(provenance.ci_provider.as_deref(), Some("unknown"));
+        assert_eq!(provenance.run_url, None);
+    }
+
+    #[test]
+    fn test_ignores_empty_variables() {
+        assert_eq!(from_env(&[]), Provenance::default());
+        assert_eq!(from_env(&[("CI", "")]), Provenance::default());
+        assert_eq!(
+            from_env(&[("CIRCLECI", "true"), ("CIRCLE_SHA1", "")]).git_sha,
+            None
+        );
+    }
+
+    #[test]
+    fn test_attributes() {
+        let provenance = Provenance {
+            ci_provider: Some("jenkins".into()),
+            git_sha: Some("a1b2c3".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            provenance.attributes().collect::<Vec<_>>(),
+            [
+                ("provenance_ci", "jenkins"),
+                ("provenance_git_sha", "a1b2c3")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_matches() {
+        let command = Command::new("upload").provenance_arg();
+
+        let matches = command.clone().get_matches_from(["upload"]);
+        assert_eq!(Provenance::from_matches(&matches), None);
+
+        let matches = command.get_matches_from(["upload", "--provenance", "off"]);
+        assert_eq!(Provenance::from_matches(&matches), None);
+    }
+}
@@ -0,0 +1,446 @@
+//! Detection of the proxy configured in the system settings.
+//!
+//! Enabled with `proxy_autodetect = true` in the `[http]` section of the
+//! config.  On macOS the proxy is read from the network settings with
+//! `scutil`, on Windows from the WinHTTP settings and the proxy server or
+//! proxy auto-config URL of the Internet Settings.  The proxy is not used
+//! if the Sentry host is on the bypass list of these settings.
+//!
+//! Proxy auto-config (PAC) files are supported as long as they select the
+//! same proxy for every URL.  Evaluating scripts that pick proxies by host
+//! would need a JavaScript engine, so scripts that return `DIRECT` for some
+//! hosts and a proxy for others are not supported and no proxy is used.
+use std::fs;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use regex::Regex;
+
+lazy_static! {
+    static ref PAC_RETURN_RE: Regex = Regex::new(r#"return\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+}
+
+/// How long to wait for a PAC file to download.
+const PAC_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Returns the proxy to use for connections to `host` according to the PAC
+/// file at `pac_url` or, if none is given, according to the system settings.
+///
+/// The proxy is only detected once and then reused for all requests.
+pub fn detect_proxy(pac_url: Option<&str>, host: &str) -> Option<String> {
+    static DETECTED: OnceLock<Option<String>> = OnceLock::new();
+    DETECTED
+        .get_or_init(|| {
+            let proxy = match pac_url {
+                Some(pac_url) => proxy_from_pac_url(pac_url),
+                None => detect_system_proxy(host),
+            };
+            debug!("detected proxy: {proxy:?}");
+            proxy
+        })
+        .clone()
+}
+
+/// Loads the PAC file at the given URL or path and returns the proxy it
+/// selects.
+fn proxy_from_pac_url(pac_url: &str) -> Option<String> {
+    let script = match load_pac_script(pac_url) {
+        Ok(script) => script,
+        Err(err) => {
+            warn!("Could not load proxy auto-config file {pac_url}: {err}");
+            return None;
+        }
+    };
+
+    proxy_from_pac_script(&script).unwrap_or_else(|| {
+        warn!(
+            "The proxy auto-config file {pac_url} selects proxies by URL, which is not \
+            supported. Set http.proxy_url instead."
+        );
+        None
+    })
+}
+
+fn load_pac_script(pac_url: &str) -> Result<String> {
+    if !pac_url.starts_with("http://") && !pac_url.starts_with("https://") {
+        let path = pac_url.strip_prefix("file://").unwrap_or(pac_url);
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    // The PAC file must not be requested through the proxy it configures.
+    let mut handle = curl::easy::Easy::new();
+    handle.url(pac_url)?;
+    handle.noproxy("*")?;
+    handle.follow_location(true)?;
+    handle.timeout(PAC_TIMEOUT)?;
+
+    let mut body = vec![];
+    {
+        let mut transfer = handle.transfer();
+        transfer.write_function(|data| {
+            body.extend_from_slice(data);
+            Ok(data.len())
+        })?;
+        transfer.perform()?;
+    }
+    let status = handle.response_code()?;
+    if status != 200 {
+        bail!("server responded with status {status}");
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Returns the proxy selected by a PAC script, or `Some(None)` for direct
+/// connections.  Returns `None` if the script selects different proxies
+/// depending on the URL.
+fn proxy_from_pac_script(script: &str) -> Option<Option<String>> {
+    let mut results = PAC_RETURN_RE
+        .captures_iter(script)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .map(|result| result.as_str().trim());
+    let first = results.next()?;
+    if results.any(|result| result != first) {
+        return None;
+    }
+    Some(proxy_from_pac_result(first))
+}
+
+/// Returns whether `host` matches one of the entries of a proxy bypass list.
+///
+/// Entries may contain `*` wildcards.  `<local>` matches host names without
+/// dots, and a plain domain also matches its subdomains.
+#[cfg(any(target_os = "macos", windows, test))]
+fn is_bypassed<'a>(host: &str, bypass_list: impl IntoIterator<Item = &'a str>) -> bool {
+    let host = host.to_ascii_lowercase();
+    bypass_list.into_iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        if entry == "<local>" {
+            return !host.contains('.');
+        }
+        if !entry.contains('*') {
+            return host == entry || host.ends_with(&format!(".{entry}"));
+        }
+        let pattern = regex::escape(&entry).replace(r"\*", ".*");
+        Regex::new(&format!("^{pattern}$")).is_ok_and(|re| re.is_match(&host))
+    })
+}
+
+/// Converts the first entry of a PAC result like `PROXY host:8080; DIRECT`
+/// into a proxy URL.
+fn proxy_from_pac_result(result: &str) -> Option<String> {
+    let entry = result.split(';').next()?.trim();
+    let (kind, host) = entry.split_once(char::is_whitespace)?;
+    let scheme = match kind.to_ascii_uppercase().as_str() {
+        "PROXY" | "HTTP" => "http",
+        "HTTPS" => "https",
+        "SOCKS" | "SOCKS5" => "socks5",
+        "SOCKS4" => "socks4",
+        _ => return None,
+    };
+    Some(format!("{scheme}://{}", host.trim()))
+}
+
+#[cfg(target_os = "macos")]
+fn detect_system_proxy(host: &str) -> Option<String> {
+    let output = std::process::Command::new("scutil")
+        .arg("--proxy")
+        .output()
+        .ok()?;
+    let settings = parse_scutil_proxy(&String::from_utf8_lossy(&output.stdout));
+    if settings.proxy.is_some() && is_bypassed(host, settings.exceptions.iter().map(String::as_str))
+    {
+        debug!("{host} is in the proxy exceptions list");
+        return None;
+    }
+    settings.proxy.or_else(|| {
+        settings
+            .pac_url
+            .and_then(|pac_url| proxy_from_pac_url(&pac_url))
+    })
+}
+
+#[cfg(windows)]
+fn detect_system_proxy(host: &str) -> Option<String> {
+    use std::process::Command;
+
+    let output = Command::new("netsh")
+        .args(["winhttp", "show", "proxy"])
+        .output()
+        .ok()?;
+    let output = String::from_utf8_lossy(&output.stdout);
+    if let Some(proxy) = parse_netsh_proxy(&output) {
+        if is_bypassed(host, parse_netsh_bypass_list(&output).split(';')) {
+            debug!("{host} is in the WinHTTP proxy bypass list");
+            return None;
+        }
+        return Some(proxy);
+    }
+
+    let output = Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings",
+        ])
+        .output()
+        .ok()?;
+    let output = String::from_utf8_lossy(&output.stdout);
+    if parse_reg_value(&output, "ProxyEnable").as_deref() == Some("0x1") {
+        if let Some(proxy) = parse_reg_value(&output, "ProxyServer")
+            .and_then(|servers| proxy_from_server_list(&servers))
+        {
+            let bypass_list = parse_reg_value(&output, "ProxyOverride").unwrap_or_default();
+            if is_bypassed(host, bypass_list.split(';')) {
+                debug!("{host} is in the proxy override list");
+                return None;
+            }
+            return Some(proxy);
+        }
+    }
+    parse_reg_value(&output, "AutoConfigURL").and_then(|pac_url| proxy_from_pac_url(&pac_url))
+}
+
+/// Other platforms configure proxies with the `http_proxy` environment
+/// variable, which is always respected.
+#[cfg(not(any(target_os = "macos", windows)))]
+fn detect_system_proxy(_host: &str) -> Option<String> {
+    None
+}
+
+/// The proxy settings of macOS.
+#[cfg(any(target_os = "macos", test))]
+#[derive(Debug, Default, PartialEq)]
+struct MacProxySettings {
+    proxy: Option<String>,
+    pac_url: Option<String>,
+    exceptions: Vec<String>,
+}
+
+/// Parses the output of `scutil --proxy`, preferring the HTTPS proxy.
+#[cfg(any(target_os = "macos", test))]
+fn parse_scutil_proxy(output: &str) -> MacProxySettings {
+    let mut settings = std::collections::HashMap::new();
+    let mut exceptions = vec![];
+    let mut in_exceptions = false;
+    for line in output.lines().map(str::trim) {
+        if in_exceptions {
+            match line.split_once(" : ") {
+                Some((_, value)) => exceptions.push(value.trim().to_owned()),
+                None => in_exceptions = false,
+            }
+        } else if let Some((key, value)) = line.split_once(" : ") {
+            in_exceptions = key == "ExceptionsList";
+            settings.insert(key, value.trim());
+        }
+    }
+    let enabled = |key: &str| settings.get(key) == Some(&"1");
+    if enabled("ExcludeSimpleHostnames") {
+        exceptions.push("<local>".into());
+    }
+
+    let proxy = ["HTTPS", "HTTP"].iter().find_map(|kind| {
+        if !enabled(format!("{kind}Enable").as_str()) {
+            return None;
+        }
+        let host = settings.get(format!("{kind}Proxy").as_str())?;
+        Some(match settings.get(format!("{kind}Port").as_str()) {
+            Some(port) => format!("http://{host}:{port}"),
+            None => format!("http://{host}"),
+        })
+    });
+    let pac_url = enabled("ProxyAutoConfigEnable")
+        .then(|| settings.get("ProxyAutoConfigURLString"))
+        .flatten()
+        .map(|url| (*url).to_owned());
+
+    MacProxySettings {
+        proxy,
+        pac_url,
+        exceptions,
+    }
+}
+
+/// Parses the output of `netsh winhttp show proxy`.
+#[cfg(any(windows, test))]
+fn parse_netsh_proxy(output: &str) -> Option<String> {
+    proxy_from_server_list(parse_netsh_field(output, "Proxy Server(s)")?)
+}
+
+/// Returns the bypass list from the output of `netsh winhttp show proxy`.
+#[cfg(any(windows, test))]
+fn parse_netsh_bypass_list(output: &str) -> &str {
+    parse_netsh_field(output, "Bypass List")
+        .filter(|list| *list != "(none)")
+        .unwrap_or_default()
+}
+
+#[cfg(any(windows, test))]
+fn parse_netsh_field<'a>(output: &'a str, name: &str) -> Option<&'a str> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(name))
+        .map(|value| value.trim_start_matches([' ', ':']).trim())
+}
+
+/// Returns the proxy from a Windows proxy server list.  Per-scheme lists
+/// such as `http=a:80;https=b:443` prefer the HTTPS proxy.
+#[cfg(any(windows, test))]
+fn proxy_from_server_list(servers: &str) -> Option<String> {
+    if servers.is_empty() {
+        return None;
+    }
+
+    let entries: Vec<_> = servers.split(';').map(str::trim).collect();
+    let proxy = ["https=", "http="]
+        .iter()
+        .find_map(|prefix| entries.iter().find_map(|entry| entry.strip_prefix(prefix)))
+        .unwrap_or(entries[0]);
+    Some(format!("http://{proxy}"))
+}
+
+/// Returns a value from the output of `reg query`.
+#[cfg(any(windows, test))]
+fn parse_reg_value(output: &str, name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        if parts.next()? != name || !parts.next()?.starts_with("REG_") {
+            return None;
+        }
+        Some(parts.collect::<Vec<_>>().join(" "))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_from_pac_script() {
+        let script = r#"
+            function FindProxyForURL(url, host) {
+                return "PROXY proxy.example.com:8080; DIRECT";
+            }
+        "#;
+        assert_eq!(
+            proxy_from_pac_script(script),
+            Some(Some("http://proxy.example.com:8080".into()))
+        );
+
+        let script = r#"
+            function FindProxyForURL(url, host) {
+                if (isPlainHostName(host)) return 'DIRECT';
+                return "PROXY proxy.example.com:8080";
+            }
+        "#;
+        assert_eq!(proxy_from_pac_script(script), None);
+
+        assert_eq!(
+            proxy_from_pac_script("function FindProxyForURL() { return 'DIRECT'; }"),
+            Some(None)
+        );
+    }
+
+    #[test]
+    fn test_parse_scutil_proxy() {
+        let output = "<dictionary> {
+  ExceptionsList : <array> {
+    0 : *.local
+    1 : 169.254/16
+  }
+  ExcludeSimpleHostnames : 1
+  HTTPEnable : 1
+  HTTPPort : 3128
+  HTTPProxy : http.example.com
+  HTTPSEnable : 0
+  ProxyAutoConfigEnable : 1
+  ProxyAutoConfigURLString : http://wpad.example.com/proxy.pac
+}";
+        assert_eq!(
+            parse_scutil_proxy(output),
+            MacProxySettings {
+                proxy: Some("http://http.example.com:3128".into()),
+                pac_url: Some("http://wpad.example.com/proxy.pac".into()),
+                exceptions: vec!["*.local".into(), "169.254/16".into(), "<local>".into()],
+            }
+        );
+        assert_eq!(
+            parse_scutil_proxy("<dictionary> {\n}"),
+            MacProxySettings::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_netsh_proxy() {
+        let output = "
+Current WinHTTP proxy settings:
+
+    Proxy Server(s) :  http=a.example.com:80;https=b.example.com:443
+    Bypass List     :  (none)
+";
+        assert_eq!(
+            parse_netsh_proxy(output),
+            Some("http://b.example.com:443".into())
+        );
+        assert_eq!(parse_netsh_bypass_list(output), "");
+
+        let output = "
+Current WinHTTP proxy settings:
+
+    Proxy Server(s) :  proxy.example.com:8080
+    Bypass List     :  *.example.com;<local>
+";
+        assert_eq!(
+            parse_netsh_proxy(output),
+            Some("http://proxy.example.com:8080".into())
+        );
+        assert_eq!(parse_netsh_bypass_list(output), "*.example.com;<local>");
+
+        let output = "
+Current WinHTTP proxy settings:
+
+    Direct access (no proxy server).
+";
+        assert_eq!(parse_netsh_proxy(output), None);
+    }
+
+    #[test]
+    fn test_parse_reg_value() {
+        let output = r"
+HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Internet Settings
+    AutoConfigURL    REG_SZ    http://wpad.example.com/proxy.pac
+";
+        assert_eq!(
+            parse_reg_value(output, "AutoConfigURL"),
+            Some("http://wpad.example.com/proxy.pac".into())
+        );
+
+        let output = r"
+HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Internet Settings
+    ProxyEnable    REG_DWORD    0x1
+    ProxyServer    REG_SZ    proxy.example.com:8080
+    ProxyOverride    REG_SZ    *.example.com;<local>
+";
+        assert_eq!(parse_reg_value(output, "ProxyEnable"), Some("0x1".into()));
+        assert_eq!(
+            parse_reg_value(output, "ProxyOverride"),
+            Some("*.example.com;<local>".into())
+        );
+        assert_eq!(parse_reg_value(output, "AutoConfigURL"), None);
+    }
+
+    #[test]
+    fn test_is_bypassed() {
+        let bypass_list = ["*.example.com", "<local>", "sentry.internal", "10.*"];
+        assert!(is_bypassed("sentry.example.com", bypass_list));
+        assert!(is_bypassed("SENTRY.EXAMPLE.COM", bypass_list));
+        assert!(is_bypassed("sentry", bypass_list));
+        assert!(is_bypassed("sentry.internal", bypass_list));
+        assert!(is_bypassed("eu.sentry.internal", bypass_list));
+        assert!(is_bypassed("10.0.0.1", bypass_list));
+        assert!(!is_bypassed("sentry.io", bypass_list));
+        assert!(!is_bypassed("example.com.evil.io", bypass_list));
+        assert!(!is_bypassed("sentry.io", [""]));
+    }
+}
@@ -0,0 +1,190 @@
+//! Generates release notes from commit messages, grouped by their
+//! conventional commit type.
+use std::fmt::Write;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+
+lazy_static! {
+    static ref CONVENTIONAL_COMMIT_RE: Regex = Regex::new(
+        r"^(?P<type>[a-zA-Z]+)(?:\((?P<scope>[^)]*)\))?(?P<breaking>!)?: (?P<summary>.+)$"
+    )
+    .unwrap();
+}
+
+/// The sections of the release notes in the order they are printed, with
+/// the commit types they contain.
+const SECTIONS: &[(&str, &[&str])] = &[
+    ("Breaking Changes", &[]),
+    ("Features", &["feat"]),
+    ("Bug Fixes", &["fix"]),
+    ("Performance Improvements", &["perf"]),
+    ("Other Changes", &[]),
+];
+
+/// A change listed in the release notes.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub scope: Option<String>,
+    pub summary: String,
+    pub commit: String,
+}
+
+/// A group of changes of the same kind.
+#[derive(Debug, Serialize)]
+pub struct Section {
+    pub title: &'static str,
+    pub changes: Vec<Change>,
+}
+
+/// Release notes of a release.
+#[derive(Debug, Serialize)]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub sections: Vec<Section>,
+}
+
+impl ReleaseNotes {
+    /// Groups the given commits, as pairs of commit ID and message.
+    ///
+    /// Commits that do not follow the conventional commits format are listed
+    /// under other changes.
+    pub fn from_commits<'a, I>(version: &str, commits: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut sections: Vec<_> = SECTIONS
+            .iter()
+            .map(|&(title, _)| Section {
+                title,
+                changes: vec![],
+            })
+            .collect();
+
+        for (id, message) in commits {
+            let Some(subject) = message.lines().next().filter(|line| !line.is_empty()) else {
+                continue;
+            };
+            let commit = id.chars().take(7).collect();
+
+            let Some(caps) = CONVENTIONAL_COMMIT_RE.captures(subject) else {
+                sections[SECTIONS.len() - 1].changes.push(Change {
+                    scope: None,
+                    summary: subject.to_owned(),
+                    commit,
+                });
+                continue;
+            };
+
+            let breaking = caps.name("breaking").is_some()
+                || message
+                    .lines()
+                    .any(|line| line.starts_with("BREAKING CHANGE:"));
+            let ty = caps["type"].to_ascii_lowercase();
+            let index = if breaking {
+                0
+            } else {
+                SECTIONS
+                    .iter()
+                    .position(|(_, types)| types.contains(&ty.as_str()))
+                    .unwrap_or(SECTIONS.len() - 1)
+            };
+
+            sections[index].changes.push(Change {
+                scope: caps
+                    .name("scope")
+                    .map(|scope| scope.as_str().to_owned())
+                    .filter(|scope| !scope.is_empty()),
+                summary: caps["summary"].to_owned(),
+                commit,
+            });
+        }
+
+        sections.retain(|section| !section.changes.is_empty());
+        ReleaseNotes {
+            version: version.to_owned(),
+            sections,
+        }
+    }
+
+    /// Formats the release notes as Markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut rv = format!("# {}\n", self.version);
+        if self.sections.is_empty() {
+            rv.push_str("\nNo changes.\n");
+        }
+        for section in &self.sections {
+            write!(rv, "\n## {}\n\n", section.title).ok();
+            for change in &section.changes {
+                rv.push_str("- ");
+                if let Some(scope) = &change.scope {
+                    write!(rv, "**{scope}:** ").ok();
+                }
+                writeln!(rv, "{} ({})", change.summary, change.commit).ok();
+            }
+        }
+        rv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_commits() {
+        let notes = ReleaseNotes::from_commits(
+            "1.0.0",
+            [
+                ("1111111111", "feat(issues): Add issues assign"),
+                ("2222222222", "fix: Do not crash on empty files\n\nDetails."),
+                ("3333333333", "refactor!: Drop the legacy API"),
+                ("4444444444", "chore: Bump dependencies"),
+                ("5555555555", "Update README"),
+                (
+                    "6666666666",
+                    "feat: New config\n\nBREAKING CHANGE: The old one is gone.",
+                ),
+            ],
+        );
+
+        let titles: Vec<_> = notes.sections.iter().map(|s| s.title).collect();
+        assert_eq!(
+            titles,
+            ["Breaking Changes", "Features", "Bug Fixes", "Other Changes"]
+        );
+        assert_eq!(notes.sections[0].changes.len(), 2);
+        assert_eq!(
+            notes.sections[1].changes,
+            [Change {
+                scope: Some("issues".into()),
+                summary: "Add issues assign".into(),
+                commit: "1111111".into(),
+            }]
+        );
+        assert_eq!(notes.sections[3].changes.len(), 2);
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let notes = ReleaseNotes::from_commits(
+            "1.0.0",
+            [
+                ("1111111111", "feat(issues): Add issues assign"),
+                ("2222222222", "fix: Do not crash on empty files"),
+            ],
+        );
+        assert_eq!(
+            notes.to_markdown(),
+            "# 1.0.0\n\n\
+            ## Features\n\n\
+            - **issues:** Add issues assign (1111111)\n\n\
+            ## Bug Fixes\n\n\
+            - Do not crash on empty files (2222222)\n"
+        );
+
+        let notes = ReleaseNotes::from_commits("1.0.0", []);
+        assert_eq!(notes.to_markdown(), "# 1.0.0\n\nNo changes.\n");
+    }
+}
@@ -0,0 +1,276 @@
+//! A local trash for deleted releases.
+//!
+//! `releases delete --archive-first` records the metadata and downloads the
+//! artifacts of a release before deleting it, so that `releases undelete` can
+//! restore it within the retention window.
+//!
+//! Only release files, as uploaded by the legacy upload, are archived.
+//! Artifact bundles are not part of the release files and are not restored.
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, format_err, Result};
+use chrono::{DateTime, Duration, Utc};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::api::AuthenticatedApi;
+use crate::config::{is_stateless, Config};
+use crate::constants::APP_NAME;
+use crate::utils::lockfile::{AtomicFile, FileLock};
+
+const TRASH_DIR_NAME: &str = "release-trash";
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// For how many days deleted releases can be restored.
+pub const RETENTION_DAYS: i64 = 7;
+
+/// An artifact of a deleted release.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrashedArtifact {
+    pub name: String,
+    pub dist: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    /// The name of the file in the trash holding the contents.
+    pub file: String,
+}
+
+/// The manifest of a deleted release.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrashedRelease {
+    pub version: String,
+    pub url: Option<String>,
+    pub date_released: Option<DateTime<Utc>>,
+    pub projects: Vec<String>,
+    pub deleted: DateTime<Utc>,
+    pub artifacts: Vec<TrashedArtifact>,
+}
+
+impl TrashedRelease {
+    /// Returns whether the release can no longer be restored.
+    pub fn is_expired(&self) -> bool {
+        self.deleted + Duration::days(RETENTION_DAYS) < Utc::now()
+    }
+}
+
+/// The trash folder of a single release.
+pub struct ReleaseTrash {
+    path: PathBuf,
+}
+
+impl ReleaseTrash {
+    /// Returns the trash folder of the release of the given organization on
+    /// the configured server.
+    pub fn new(org: &str, version: &str) -> Result<Self> {
+        let key = format!(
+            "{}#{org}/{version}",
+            Config::current().get_base_url().unwrap_or_default()
+        );
+        let mut path = trash_path()?;
+        path.push(sha1_smol::Sha1::from(key).digest().to_string());
+        Ok(ReleaseTrash { path })
+    }
+
+    /// Records the release and downloads its artifacts into the trash.
+    ///
+    /// Returns `None` if the release does not exist.
+    pub fn archive(
+        &self,
+        api: &AuthenticatedApi,
+        org: &str,
+        project: Option<&str>,
+        version: &str,
+    ) -> Result<Option<TrashedRelease>> {
+        let Some(info) = api.get_release(org, project, version)? else {
+            return Ok(None);
+        };
+
+        let _lock = FileLock::acquire(&self.path)?;
+        if self.path.exists() {
+            fs::remove_dir_all(&self.path)?;
+        }
+        fs::create_dir_all(&self.path)?;
+
+        let mut artifacts = vec![];
+        for artifact in api.list_release_files(org, project, version)? {
+            let mut file = fs::File::create(self.path.join(&artifact.id))?;
+            api.get_release_file(org, project, version, &artifact.id, &mut file)?;
+            artifacts.push(TrashedArtifact {
+                name: artifact.name,
+                dist: artifact.dist,
+                headers: artifact.headers.into_iter().collect(),
+                file: artifact.id,
+            });
+        }
+
+        let release = TrashedRelease {
+            version: info.version,
+            url: info.url,
+            date_released: info.date_released,
+            projects: info.projects.into_iter().map(|p| p.slug).collect(),
+            deleted: Utc::now(),
+            artifacts,
+        };
+        // The manifest is written last, so that releases are only restored
+        // once all of their artifacts are in the trash.
+        self.write_manifest(&release)?;
+        Ok(Some(release))
+    }
+
+    fn write_manifest(&self, release: &TrashedRelease) -> Result<()> {
+        let mut manifest = AtomicFile::create(self.path.join(MANIFEST_FILE_NAME))?;
+        serde_json::to_writer_pretty(&mut manifest, release)?;
+        manifest.commit()?;
+        Ok(())
+    }
+
+    /// Loads the recorded release, unless it is not in the trash or expired.
+    pub fn load(&self) -> Result<Option<TrashedRelease>> {
+        let file = match fs::File::open(self.path.join(MANIFEST_FILE_NAME)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let release: TrashedRelease = serde_json::from_reader(io::BufReader::new(file))?;
+        Ok(Some(release).filter(|release| !release.is_expired()))
+    }
+
+    /// Reads the contents of an artifact.
+    pub fn read_artifact(&self, artifact: &TrashedArtifact) -> Result<Vec<u8>> {
+        Ok(fs::read(self.path.join(&artifact.file))?)
+    }
+
+    /// Removes the release from the trash.
+    pub fn remove(&self) -> Result<()> {
+        fs::remove_dir_all(&self.path)?;
+        Ok(())
+    }
+}
+
+/// Removes releases from the trash that were deleted longer ago than the
+/// retention window.
+pub fn purge_expired() {
+    if let Ok(path) = trash_path() {
+        purge_expired_in(&path);
+    }
+}
+
+fn purge_expired_in(path: &Path) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let trash = ReleaseTrash { path: entry.path() };
+        // Skip releases that another process is archiving right now.
+        let Ok(Some(_lock)) = FileLock::try_acquire(&trash.path) else {
+            continue;
+        };
+        let restorable = fs::File::open(trash.path.join(MANIFEST_FILE_NAME))
+            .ok()
+            .and_then(|file| serde_json::from_reader::<_, TrashedRelease>(file).ok())
+            .is_some_and(|release| !release.is_expired());
+        if !restorable {
+            debug!("Removing {} from the release trash", trash.path.display());
+            if let Err(err) = trash.remove() {
+                warn!("Could not remove {}: {err}", trash.path.display());
+            }
+        }
+    }
+}
+
+fn trash_path() -> Result<PathBuf> {
+    if is_stateless() {
+        bail!("The release trash is not available in stateless mode");
+    }
+    let mut path = dirs::cache_dir().ok_or_else(|| format_err!("Could not get cache folder"))?;
+    path.push(APP_NAME);
+    path.push(TRASH_DIR_NAME);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trashed_release(version: &str, deleted: DateTime<Utc>) -> TrashedRelease {
+        TrashedRelease {
+            version: version.to_owned(),
+            url: None,
+            date_released: None,
+            projects: vec!["wat-project".to_owned()],
+            deleted,
+            artifacts: vec![TrashedArtifact {
+                name: "~/app.js".to_owned(),
+                dist: None,
+                headers: BTreeMap::new(),
+                file: "1".to_owned(),
+            }],
+        }
+    }
+
+    fn store(path: PathBuf, release: &TrashedRelease) -> ReleaseTrash {
+        let trash = ReleaseTrash { path };
+        fs::create_dir_all(&trash.path).unwrap();
+        fs::write(trash.path.join("1"), "console.log(1);").unwrap();
+        trash.write_manifest(release).unwrap();
+        trash
+    }
+
+    #[test]
+    fn test_restore_archived_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let trash = store(
+            dir.path().join("release"),
+            &trashed_release("1.0.0", Utc::now()),
+        );
+
+        let release = trash.load().unwrap().unwrap();
+        assert_eq!(release.version, "1.0.0");
+        assert_eq!(release.projects, ["wat-project"]);
+        assert_eq!(release.artifacts[0].name, "~/app.js");
+        assert_eq!(
+            trash.read_artifact(&release.artifacts[0]).unwrap(),
+            b"console.log(1);"
+        );
+
+        trash.remove().unwrap();
+        assert!(trash.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_load_expired_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let deleted = Utc::now() - Duration::days(RETENTION_DAYS + 1);
+        let trash = store(
+            dir.path().join("release"),
+            &trashed_release("1.0.0", deleted),
+        );
+
+        assert!(trash.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_purge_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let expired = Utc::now() - Duration::days(RETENTION_DAYS + 1);
+        store(
+            dir.path().join("fresh"),
+            &trashed_release("1.0.0", Utc::now()),
+        );
+        store(
+            dir.path().join("expired"),
+            &trashed_release("0.9.0", expired),
+        );
+        // An interrupted archive, which has no manifest.
+        fs::create_dir_all(dir.path().join("partial")).unwrap();
+
+        purge_expired_in(dir.path());
+
+        assert!(dir.path().join("fresh").join(MANIFEST_FILE_NAME).exists());
+        assert!(!dir.path().join("expired").exists());
+        assert!(!dir.path().join("partial").exists());
+    }
+}
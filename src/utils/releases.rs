@@ -1,10 +1,12 @@
+use std::collections::HashMap;
 use std::env;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use if_chain::if_chain;
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -84,6 +86,52 @@ pub fn infer_gradle_release_name(path: Option<PathBuf>) -> Result<Option<String>
 }
 
 /// Detects the release name for the current working directory.
+/// The OCI annotation with the version of the software packaged in an image.
+const IMAGE_VERSION_LABEL: &str = "org.opencontainers.image.version";
+
+/// The OCI annotation with the source control revision of the software
+/// packaged in an image.
+const IMAGE_REVISION_LABEL: &str = "org.opencontainers.image.revision";
+
+/// Returns the ID of the Docker or Podman container sentry-cli runs in, if
+/// any.
+pub fn current_container_id() -> Option<String> {
+    if !Path::new("/.dockerenv").exists() && !Path::new("/run/.containerenv").exists() {
+        return None;
+    }
+    // Containers use the short container ID as their host name by default.
+    env::var("HOSTNAME").ok().filter(|id| !id.is_empty())
+}
+
+/// Reads the release name from the OCI labels of a Docker image or container,
+/// preferring the version over the revision.
+///
+/// Containers carry the labels of their image, so the labels of a running
+/// container can be read as well.  This requires the docker CLI and access to
+/// the Docker daemon.
+pub fn get_image_release_name(reference: &str) -> Result<Option<String>> {
+    let output = Command::new("docker")
+        .args(["inspect", "--format", "{{json .Config.Labels}}", reference])
+        .output()
+        .context("Could not run docker inspect")?;
+    if !output.status.success() {
+        bail!(
+            "Could not inspect {reference}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    // Images without labels have `null` labels.
+    let labels: Option<HashMap<String, String>> = serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("Could not read the labels of {reference}"))?;
+    let labels = labels.unwrap_or_default();
+    Ok([IMAGE_VERSION_LABEL, IMAGE_REVISION_LABEL]
+        .iter()
+        .filter_map(|label| labels.get(*label))
+        .find(|value| !value.is_empty())
+        .cloned())
+}
+
 pub fn detect_release_name() -> Result<String> {
     // try SENTRY_RELEASE environment variable
     if let Ok(release) = env::var("SENTRY_RELEASE") {
@@ -156,6 +204,14 @@ pub fn detect_release_name() -> Result<String> {
         }
     }
 
+    // try the OCI labels of the image of the container we run in
+    if let Some(release) = current_container_id()
+        .and_then(|id| get_image_release_name(&id).ok())
+        .flatten()
+    {
+        return Ok(release);
+    }
+
     match vcs::find_head() {
         Ok(head) => Ok(head),
         Err(e) => Err(anyhow!(
@@ -0,0 +1,186 @@
+//! Source bundles from sources in a remote archive.
+//!
+//! Builds in containers often do not have the sources on disk anymore when
+//! debug files are uploaded.  With `--include-sources=URL`, the sources are
+//! read from a ZIP archive instead, such as one created by `git archive
+//! --format=zip` or the archive download of a repository hosting service.
+//!
+//! Paths in debug files are absolute paths on the build machine, while paths
+//! in archives are relative to the repository root.  Below the source root
+//! (`--source-root`), the relative path is looked up in the archive.
+//! Otherwise, the longest trailing part of the path that exists in the
+//! archive is used.
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+
+use anyhow::{bail, Context, Result};
+use log::{debug, info, warn};
+use symbolic::debuginfo::sourcebundle::{SourceBundleWriter, SourceFileInfo, SourceFileType};
+use symbolic::debuginfo::Object;
+use zip::ZipArchive;
+
+use crate::api::Api;
+use crate::config::Config;
+use crate::utils::source_root::SourceRoot;
+
+/// Sources of a build in a remote ZIP archive.
+pub struct RemoteSources {
+    archive: ZipArchive<File>,
+    /// The top-level folder that all entries share, such as `repo-main/`.
+    prefix: String,
+    /// The names of all files in the archive.
+    files: HashSet<String>,
+}
+
+impl RemoteSources {
+    /// Downloads the archive at the given URL.
+    pub fn fetch(url: &str) -> Result<Self> {
+        info!("Fetching sources from {url}");
+        let mut file = tempfile::tempfile()?;
+        let api = Api::current();
+        #[cfg(not(feature = "managed"))]
+        let response = api.download_with_progress(url, &mut file);
+        #[cfg(feature = "managed")]
+        let response = api.download(url, &mut file);
+        let response = response.with_context(|| format!("Could not fetch sources from {url}"))?;
+        if !response.ok() {
+            bail!(
+                "Could not fetch sources from {url} (status {})",
+                response.status()
+            );
+        }
+
+        let archive = ZipArchive::new(file)
+            .with_context(|| format!("The sources at {url} are not a ZIP archive"))?;
+        Ok(Self::from_archive(archive))
+    }
+
+    fn from_archive(archive: ZipArchive<File>) -> Self {
+        let names: Vec<_> = archive
+            .file_names()
+            .filter(|name| !name.ends_with('/'))
+            .map(str::to_owned)
+            .collect();
+        let prefix = common_prefix(&names);
+        debug!("Archive contains {} sources", names.len());
+        let files = names.into_iter().collect();
+
+        RemoteSources {
+            archive,
+            prefix,
+            files,
+        }
+    }
+
+    /// Returns the name of the file at a path relative to the repository
+    /// root, with or without the top-level folder of the archive.
+    fn entry_name(&self, path: &str) -> Option<String> {
+        [format!("{}{path}", self.prefix), path.to_owned()]
+            .into_iter()
+            .find(|name| self.files.contains(name))
+    }
+
+    /// Returns the name in the archive of a source referenced by a debug
+    /// file.
+    fn find(&self, abs_path: &str, source_root: Option<&SourceRoot>) -> Option<String> {
+        if let Some(source_root) = source_root {
+            return source_root
+                .relative_path(abs_path)
+                .and_then(|path| self.entry_name(&path));
+        }
+
+        let components: Vec<_> = abs_path
+            .split(['/', '\\'])
+            .filter(|c| !c.is_empty())
+            .collect();
+        (0..components.len()).find_map(|start| self.entry_name(&components[start..].join("/")))
+    }
+
+    /// Writes the sources referenced by an object into a source bundle.
+    /// Returns `false` if none of the sources are in the archive.
+    pub fn write_bundle<W>(
+        &mut self,
+        object: &Object,
+        object_name: &str,
+        mut writer: SourceBundleWriter<W>,
+        source_root: Option<&SourceRoot>,
+    ) -> Result<bool>
+    where
+        W: Seek + Write,
+    {
+        writer.set_attribute("debug_id", object.debug_id().to_string());
+        writer.set_attribute("arch", object.arch().to_string());
+        if let Some(code_id) = object.code_id() {
+            writer.set_attribute("code_id", code_id.to_string());
+        }
+        writer.set_attribute("object_name", object_name.to_owned());
+
+        let max_size = Config::current().get_max_dif_item_size();
+        let session = object.debug_session()?;
+        let mut seen = HashSet::new();
+        let mut written = false;
+        for file in session.files() {
+            let abs_path = file?.abs_path_str();
+            if abs_path.ends_with(".pch") || !seen.insert(abs_path.clone()) {
+                continue;
+            }
+            let Some(name) = self.find(&abs_path, source_root) else {
+                debug!("Source not in archive: {abs_path}");
+                continue;
+            };
+
+            let mut entry = self.archive.by_name(&name)?;
+            if entry.size() > max_size {
+                warn!(
+                    "Source exceeded maximum item size limit ({}). {abs_path}",
+                    entry.size()
+                );
+                continue;
+            }
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents)?;
+
+            let mut info = SourceFileInfo::new();
+            info.set_ty(SourceFileType::Source);
+            info.set_path(abs_path.clone());
+            let bundle_path = abs_path.trim_start_matches('/').replace(':', "");
+            writer.add_file(bundle_path, contents.as_slice(), info)?;
+            written = true;
+        }
+
+        writer.finish()?;
+        Ok(written)
+    }
+}
+
+/// Returns the top-level folder shared by all paths, including the trailing
+/// slash, or an empty string.
+fn common_prefix(paths: &[String]) -> String {
+    let Some((folder, _)) = paths.first().and_then(|path| path.split_once('/')) else {
+        return String::new();
+    };
+    let prefix = format!("{folder}/");
+    if paths.iter().all(|path| path.starts_with(&prefix)) {
+        prefix
+    } else {
+        String::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_prefix() {
+        let paths = |paths: &[&str]| paths.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            common_prefix(&paths(&["repo-main/src/main.c", "repo-main/README"])),
+            "repo-main/"
+        );
+        assert_eq!(common_prefix(&paths(&["src/main.c", "README"])), "");
+        assert_eq!(common_prefix(&paths(&["src/main.c", "src/lib.c"])), "src/");
+        assert_eq!(common_prefix(&[]), "");
+    }
+}
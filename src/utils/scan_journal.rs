@@ -0,0 +1,335 @@
+//! A journal of file checksums, used to skip hashing files that have not
+//! changed since a previous scan.
+//!
+//! Files are identified by their canonical path, size and modification time.
+//! If any of these change, the journal entry is considered stale and the file
+//! is hashed again.
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::UNIX_EPOCH;
+
+use anyhow::{format_err, Result};
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use sha1_smol::Digest;
+
+use crate::config::is_stateless;
+use crate::constants::APP_NAME;
+use crate::utils::lockfile::{AtomicFile, FileLock};
+
+const JOURNAL_FILE_NAME: &str = "scan-journal";
+
+/// Identifies the state of an object within a file on disk.
+#[derive(Clone, Debug)]
+pub struct JournalKey {
+    id: String,
+    path: PathBuf,
+    size: u64,
+    mtime: u128,
+}
+
+impl JournalKey {
+    /// Creates a key for the object `name` within the file at `path`.
+    ///
+    /// Returns `None` if the file's metadata cannot be read.
+    pub fn new(path: &Path, name: &str) -> Option<Self> {
+        let path = fs::canonicalize(path).ok()?;
+        let metadata = fs::metadata(&path).ok()?;
+        let mtime = metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_nanos();
+
+        Some(JournalKey {
+            id: format!("{}#{name}", path.display()),
+            path,
+            size: metadata.len(),
+            mtime,
+        })
+    }
+
+    /// Returns the identifier of the object within the file.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the canonical path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the size of the file.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the modification time of the file in nanoseconds since the
+    /// UNIX epoch.
+    pub fn mtime(&self) -> u128 {
+        self.mtime
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    path: PathBuf,
+    size: u64,
+    mtime: u128,
+    chunk_size: usize,
+    checksum: String,
+    chunks: Vec<String>,
+}
+
+/// Checksums of previously scanned files, persisted in the cache folder.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanJournal {
+    entries: BTreeMap<String, JournalEntry>,
+}
+
+impl ScanJournal {
+    /// Loads the journal from the cache folder.
+    ///
+    /// A missing or unreadable journal results in an empty journal.
+    pub fn load() -> Self {
+        if is_stateless() {
+            return Default::default();
+        }
+
+        match journal_path().and_then(|path| read_journal(&path)) {
+            Ok(journal) => journal,
+            Err(err) => {
+                debug!("Not using scan journal: {err}");
+                Default::default()
+            }
+        }
+    }
+
+    /// Returns the checksum and the chunk checksums recorded for the given key,
+    /// if the file has not changed since.
+    pub fn get(&self, key: &JournalKey, chunk_size: usize) -> Option<(Digest, Vec<Digest>)> {
+        let entry = self.entries.get(&key.id)?;
+        if entry.size != key.size || entry.mtime != key.mtime || entry.chunk_size != chunk_size {
+            return None;
+        }
+
+        let checksum = Digest::from_str(&entry.checksum).ok()?;
+        let chunks = entry
+            .chunks
+            .iter()
+            .map(|chunk| Digest::from_str(chunk).ok())
+            .collect::<Option<_>>()?;
+
+        info!("Reusing checksum of unchanged file {}", key.path.display());
+        Some((checksum, chunks))
+    }
+
+    /// Records the checksum and the chunk checksums of the given key.
+    pub fn insert(
+        &mut self,
+        key: &JournalKey,
+        chunk_size: usize,
+        checksum: Digest,
+        chunks: &[Digest],
+    ) {
+        self.entries.insert(
+            key.id.clone(),
+            JournalEntry {
+                path: key.path.clone(),
+                size: key.size,
+                mtime: key.mtime,
+                chunk_size,
+                checksum: checksum.to_string(),
+                chunks: chunks.iter().map(Digest::to_string).collect(),
+            },
+        );
+    }
+
+    /// Writes the journal to the cache folder, dropping entries of files that
+    /// no longer exist.
+    ///
+    /// Entries that other processes saved in the meantime are kept, unless
+    /// this journal has newer entries for the same files.
+    pub fn save(&mut self) -> Result<()> {
+        if is_stateless() {
+            return Ok(());
+        }
+
+        self.save_to(&journal_path()?)
+    }
+
+    fn save_to(&mut self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let _lock = FileLock::acquire(path)?;
+        if let Ok(saved) = read_journal(path) {
+            for (id, entry) in saved.entries {
+                self.entries.entry(id).or_insert(entry);
+            }
+        }
+        self.entries.retain(|_, entry| entry.path.exists());
+
+        let mut file = io::BufWriter::new(AtomicFile::create(path)?);
+        serde_json::to_writer(&mut file, self)?;
+        file.write_all(b"\n")?;
+        file.into_inner()?.commit()
+    }
+}
+
+fn read_journal(path: &Path) -> Result<ScanJournal> {
+    let file = fs::File::open(path)?;
+    Ok(serde_json::from_reader(io::BufReader::new(file))?)
+}
+
+fn journal_path() -> Result<PathBuf> {
+    let mut path = dirs::cache_dir().ok_or_else(|| format_err!("Could not get cache folder"))?;
+    path.push(APP_NAME);
+    path.push(JOURNAL_FILE_NAME);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{Duration, SystemTime};
+
+    use crate::utils::fs::TempDir;
+
+    const CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+    fn digest(data: &str) -> Digest {
+        sha1_smol::Sha1::from(data).digest()
+    }
+
+    #[test]
+    fn test_get_unchanged_file() {
+        let dir = TempDir::create().unwrap();
+        let path = dir.path().join("app.debug");
+        fs::write(&path, b"debug").unwrap();
+
+        let mut journal = ScanJournal::default();
+        let key = JournalKey::new(&path, "app").unwrap();
+        assert_eq!(journal.get(&key, CHUNK_SIZE), None);
+
+        journal.insert(&key, CHUNK_SIZE, digest("app"), &[digest("chunk")]);
+        assert_eq!(
+            journal.get(&key, CHUNK_SIZE),
+            Some((digest("app"), vec![digest("chunk")]))
+        );
+        assert_eq!(journal.get(&key, CHUNK_SIZE / 2), None);
+        assert_eq!(
+            journal.get(&JournalKey::new(&path, "other").unwrap(), CHUNK_SIZE),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalidate_changed_size() {
+        let dir = TempDir::create().unwrap();
+        let path = dir.path().join("app.debug");
+        fs::write(&path, b"debug").unwrap();
+
+        let mut journal = ScanJournal::default();
+        let key = JournalKey::new(&path, "app").unwrap();
+        journal.insert(&key, CHUNK_SIZE, digest("app"), &[]);
+
+        let mtime = fs::metadata(&path).unwrap().modified().unwrap();
+        fs::write(&path, b"changed debug").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+        assert_eq!(
+            journal.get(&JournalKey::new(&path, "app").unwrap(), CHUNK_SIZE),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalidate_changed_mtime() {
+        let dir = TempDir::create().unwrap();
+        let path = dir.path().join("app.debug");
+        fs::write(&path, b"debug").unwrap();
+
+        let mut journal = ScanJournal::default();
+        let key = JournalKey::new(&path, "app").unwrap();
+        journal.insert(&key, CHUNK_SIZE, digest("app"), &[]);
+
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(
+            journal.get(&JournalKey::new(&path, "app").unwrap(), CHUNK_SIZE),
+            None
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = TempDir::create().unwrap();
+        let journal_path = dir.path().join("journal");
+        let path = dir.path().join("app.debug");
+        fs::write(&path, b"debug").unwrap();
+        let key = JournalKey::new(&path, "app").unwrap();
+
+        let mut journal = ScanJournal::default();
+        journal.insert(&key, CHUNK_SIZE, digest("app"), &[digest("chunk")]);
+        journal.save_to(&journal_path).unwrap();
+
+        let journal = read_journal(&journal_path).unwrap();
+        assert_eq!(
+            journal.get(&key, CHUNK_SIZE),
+            Some((digest("app"), vec![digest("chunk")]))
+        );
+    }
+
+    #[test]
+    fn test_save_merges_entries() {
+        let dir = TempDir::create().unwrap();
+        let journal_path = dir.path().join("journal");
+        let first_path = dir.path().join("first.debug");
+        let second_path = dir.path().join("second.debug");
+        let removed_path = dir.path().join("removed.debug");
+        fs::write(&first_path, b"first").unwrap();
+        fs::write(&second_path, b"second").unwrap();
+        fs::write(&removed_path, b"removed").unwrap();
+        let first = JournalKey::new(&first_path, "first").unwrap();
+        let second = JournalKey::new(&second_path, "second").unwrap();
+        let removed = JournalKey::new(&removed_path, "removed").unwrap();
+
+        let mut journal = ScanJournal::default();
+        journal.insert(&first, CHUNK_SIZE, digest("old"), &[]);
+        journal.insert(&second, CHUNK_SIZE, digest("second"), &[]);
+        journal.insert(&removed, CHUNK_SIZE, digest("removed"), &[]);
+        journal.save_to(&journal_path).unwrap();
+        fs::remove_file(&removed_path).unwrap();
+
+        // Another process saves a newer entry for the first file.
+        let mut journal = ScanJournal::default();
+        journal.insert(&first, CHUNK_SIZE, digest("new"), &[]);
+        journal.save_to(&journal_path).unwrap();
+
+        let journal = read_journal(&journal_path).unwrap();
+        assert_eq!(
+            journal.get(&first, CHUNK_SIZE),
+            Some((digest("new"), vec![]))
+        );
+        assert_eq!(
+            journal.get(&second, CHUNK_SIZE),
+            Some((digest("second"), vec![]))
+        );
+        assert_eq!(journal.get(&removed, CHUNK_SIZE), None);
+    }
+}
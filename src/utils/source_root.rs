@@ -0,0 +1,103 @@
+//! Rewriting of source paths in source bundles relative to a repository root.
+//!
+//! Debug information files reference sources by the absolute path on the
+//! machine that built them.  With `--source-root`, source bundles store the
+//! paths of all sources below the root relative to it instead, so that they
+//! neither leak the layout of the build machine nor depend on it for stack
+//! trace linking.  Sources outside of the root keep their absolute paths.
+//!
+//! The root itself is not stored.  Instead, the bundle records the SHA-1 hash
+//! of the root in the `source_root_sha1` attribute.  To resolve an absolute
+//! path from a stack frame, the server hashes its ancestor directories until
+//! one matches and looks up the remainder of the path in the bundle.
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::ArgMatches;
+use log::debug;
+
+/// The root directory of the sources in a source bundle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceRoot {
+    path: PathBuf,
+}
+
+impl SourceRoot {
+    /// Returns the source root given with `--source-root`, if any.
+    ///
+    /// Without a value, the root of the git repository containing the
+    /// current directory is used.
+    pub fn from_matches(matches: &ArgMatches) -> Result<Option<Self>> {
+        match matches.get_one::<String>("source_root").map(String::as_str) {
+            None => Ok(None),
+            Some("") => Self::detect().map(Some),
+            Some(path) => Ok(Some(SourceRoot::new(path)?)),
+        }
+    }
+
+    /// Creates a source root from a path, relative to the current directory.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = std::path::absolute(path.as_ref())
+            .with_context(|| format!("Invalid source root {}", path.as_ref().display()))?;
+        Ok(SourceRoot { path })
+    }
+
+    /// Detects the root of the git repository containing the current
+    /// directory.
+    fn detect() -> Result<Self> {
+        let repo = git2::Repository::open_from_env()
+            .context("Could not detect the source root. Pass --source-root=PATH instead")?;
+        let Some(workdir) = repo.workdir() else {
+            bail!("Could not detect the source root in a bare repository");
+        };
+        debug!("Detected source root: {}", workdir.display());
+        SourceRoot::new(workdir)
+    }
+
+    /// Returns the path relative to the source root with forward slashes, or
+    /// `None` if the path is not below the root.
+    pub fn relative_path(&self, path: &str) -> Option<String> {
+        let relative = Path::new(path).strip_prefix(&self.path).ok()?;
+        let components = relative
+            .components()
+            .map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if components.is_empty() {
+            return None;
+        }
+        Some(components.join("/"))
+    }
+
+    /// Returns the SHA-1 hash of the root path that is recorded in bundles.
+    pub fn hash(&self) -> String {
+        let path = self.path.to_string_lossy().replace('\\', "/");
+        sha1_smol::Sha1::from(path.trim_end_matches('/'))
+            .digest()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_relative_path() {
+        let root = SourceRoot::new("/home/builder/repo").unwrap();
+        assert_eq!(
+            root.relative_path("/home/builder/repo/src/main.c"),
+            Some("src/main.c".into())
+        );
+        assert_eq!(root.relative_path("/home/builder/repo"), None);
+        assert_eq!(root.relative_path("/home/builder/repository/main.c"), None);
+        assert_eq!(root.relative_path("/usr/include/stdio.h"), None);
+        assert_eq!(
+            root.hash(),
+            SourceRoot::new("/home/builder/repo/").unwrap().hash()
+        );
+    }
+}
@@ -11,6 +11,7 @@ use anyhow::{anyhow, bail, Context, Error, Result};
 use console::style;
 use indicatif::ProgressStyle;
 use log::{debug, info, warn};
+use rayon::prelude::*;
 use sentry::types::DebugId;
 use sha1_smol::Digest;
 use sourcemap::SourceMap;
@@ -25,11 +26,15 @@ use crate::utils::file_search::ReleaseFileMatch;
 use crate::utils::file_upload::{
     initialize_legacy_release_upload, FileUpload, SourceFile, SourceFiles, UploadContext,
 };
-use crate::utils::logging::is_quiet_mode;
+use crate::utils::logging::{is_quiet_mode, quiet_println};
 use crate::utils::progress::ProgressBar;
 use crate::utils::sourcemaps::inject::InjectReport;
+use crate::utils::sourcemaps::validation::{
+    ValidationIssue, ValidationIssueKind, ValidationReport,
+};
 
 pub mod inject;
+pub mod validation;
 
 /// The string prefix denoting a data URL.
 ///
@@ -200,13 +205,28 @@ pub struct SourceMapProcessor {
     debug_ids: HashMap<String, DebugId>,
 }
 
-fn is_hermes_bytecode(slice: &[u8]) -> bool {
+pub fn is_hermes_bytecode(slice: &[u8]) -> bool {
     // The hermes bytecode format magic is defined here:
     // https://github.com/facebook/hermes/blob/5243222ef1d92b7393d00599fc5cff01d189a88a/include/hermes/BCGen/HBC/BytecodeFileFormat.h#L24-L25
     const HERMES_MAGIC: [u8; 8] = [0xC6, 0x1F, 0xBC, 0x03, 0xC1, 0x03, 0x19, 0x1F];
     slice.starts_with(&HERMES_MAGIC)
 }
 
+/// Composes the source map of a React Native packager bundle with the source
+/// map the Hermes compiler emitted for the bytecode of that bundle.
+///
+/// The Hermes source map maps the bytecode to positions in the bundle, so the
+/// composed source map maps the bytecode to the original sources. The debug
+/// id of the packager source map is kept.
+pub fn compose_hermes_sourcemap(packager: &mut SourceMap, hermes: &SourceMap) {
+    let debug_id = packager.get_debug_id().or_else(|| hermes.get_debug_id());
+    packager.adjust_mappings(hermes);
+    if let Some(file) = hermes.get_file() {
+        packager.set_file(Some(file.to_owned()));
+    }
+    packager.set_debug_id(debug_id);
+}
+
 fn url_matches_extension(url: &str, extensions: &[&str]) -> bool {
     if extensions.is_empty() {
         return true;
@@ -220,6 +240,74 @@ fn url_matches_extension(url: &str, extensions: &[&str]) -> bool {
     }
 }
 
+/// Returns whether the url refers to a CSS file.
+///
+/// CSS files reference their sourcemaps and debug ids in `/*# ... */` comments
+/// rather than the `//# ...` comments of JavaScript.
+fn is_css_url(url: &str) -> bool {
+    url_matches_extension(url, &["css"])
+}
+
+/// Detects the type and the debug id of a file added to a [`SourceMapProcessor`].
+fn analyze_source(url: String, mut file: ReleaseFileMatch) -> (SourceFile, Option<DebugId>) {
+    let (ty, debug_id) = if sourcemap::is_sourcemap_slice(&file.contents) {
+        (
+            SourceFileType::SourceMap,
+            std::str::from_utf8(&file.contents)
+                .ok()
+                .and_then(discover_sourcemap_embedded_debug_id),
+        )
+    } else if file
+        .path
+        .file_name()
+        .and_then(OsStr::to_str)
+        .map(|x| x.ends_with("bundle"))
+        .unwrap_or(false)
+        && sourcemap::ram_bundle::is_ram_bundle_slice(&file.contents)
+    {
+        (SourceFileType::IndexedRamBundle, None)
+    } else if is_hermes_bytecode(&file.contents) {
+        // This is actually a big hack:
+        // For the react-native Hermes case, we skip uploading the bytecode bundle,
+        // and rather flag it as an empty "minified source". That way, it
+        // will get a SourceMap reference, and the server side processor
+        // should deal with it accordingly.
+        file.contents.clear();
+        (SourceFileType::MinifiedSource, None)
+    } else {
+        // Here, we use MinifiedSource for historical reasons. We used to guess whether
+        // a JS file was a minified file or a source file, and we would treat these files
+        // differently when uploading or injecting them. However, the desired behavior is
+        // and has always been to treat all JS files the same, since users should be
+        // responsible for providing the file paths for only files they would like to have
+        // uploaded or injected. The minified file guessing furthermore was not reliable,
+        // since minification is not a necessary step in the JS build process.
+        //
+        // We use MinifiedSource here rather than Source because we want to treat all JS
+        // files the way we used to treat minified files only. To use Source, we would need
+        // to analyze all possible code paths that check this value, and update those as
+        // well. To keep the change minimal, we use MinifiedSource here.
+        let contents = std::str::from_utf8(&file.contents).ok();
+        let debug_id = if is_css_url(&url) {
+            contents.and_then(inject::discover_css_debug_id)
+        } else {
+            contents.and_then(discover_debug_id)
+        };
+        (SourceFileType::MinifiedSource, debug_id)
+    };
+
+    let source_file = SourceFile {
+        url,
+        path: file.path,
+        contents: file.contents,
+        ty,
+        headers: BTreeMap::new(),
+        messages: vec![],
+        already_uploaded: false,
+    };
+    (source_file, debug_id)
+}
+
 /// Return true iff url is a remote url (not a local path or embedded sourcemap).
 fn is_remote_url(url: &str) -> bool {
     match Url::parse(url) {
@@ -270,76 +358,32 @@ impl SourceMapProcessor {
         let pb = ProgressBar::new(self.pending_sources.len());
         pb.set_style(progress_style);
 
-        println!(
+        quiet_println!(
             "{} Analyzing {} sources",
             style(">").dim(),
             style(self.pending_sources.len()).yellow()
         );
-        for (url, mut file) in self.pending_sources.drain() {
-            pb.set_message(&url);
-
-            let (ty, debug_id) = if sourcemap::is_sourcemap_slice(&file.contents) {
-                (
-                    SourceFileType::SourceMap,
-                    std::str::from_utf8(&file.contents)
-                        .ok()
-                        .and_then(discover_sourcemap_embedded_debug_id),
-                )
-            } else if file
-                .path
-                .file_name()
-                .and_then(OsStr::to_str)
-                .map(|x| x.ends_with("bundle"))
-                .unwrap_or(false)
-                && sourcemap::ram_bundle::is_ram_bundle_slice(&file.contents)
-            {
-                (SourceFileType::IndexedRamBundle, None)
-            } else if is_hermes_bytecode(&file.contents) {
-                // This is actually a big hack:
-                // For the react-native Hermes case, we skip uploading the bytecode bundle,
-                // and rather flag it as an empty "minified source". That way, it
-                // will get a SourceMap reference, and the server side processor
-                // should deal with it accordingly.
-                file.contents.clear();
-                (SourceFileType::MinifiedSource, None)
-            } else {
-                // Here, we use MinifiedSource for historical reasons. We used to guess whether
-                // a JS file was a minified file or a source file, and we would treat these files
-                // differently when uploading or injecting them. However, the desired behavior is
-                // and has always been to treat all JS files the same, since users should be
-                // responsible for providing the file paths for only files they would like to have
-                // uploaded or injected. The minified file guessing furthermore was not reliable,
-                // since minification is not a necessary step in the JS build process.
-                //
-                // We use MinifiedSource here rather than Source because we want to treat all JS
-                // files the way we used to treat minified files only. To use Source, we would need
-                // to analyze all possible code paths that check this value, and update those as
-                // well. To keep the change minimal, we use MinifiedSource here.
-                (
-                    SourceFileType::MinifiedSource,
-                    std::str::from_utf8(&file.contents)
-                        .ok()
-                        .and_then(discover_debug_id),
-                )
-            };
 
-            let mut source_file = SourceFile {
-                url: url.clone(),
-                path: file.path,
-                contents: file.contents,
-                ty,
-                headers: BTreeMap::new(),
-                messages: vec![],
-                already_uploaded: false,
-            };
+        // Detecting the file types and debug ids scans the contents of every
+        // file, so files are analyzed in parallel.
+        let pending: Vec<_> = self.pending_sources.drain().collect();
+        let analyzed: Vec<_> = pending
+            .into_par_iter()
+            .map(|(url, file)| {
+                pb.set_message(&url);
+                let analyzed = analyze_source(url, file);
+                pb.inc(1);
+                analyzed
+            })
+            .collect();
 
+        for (mut source_file, debug_id) in analyzed {
             if let Some(debug_id) = debug_id {
                 source_file.set_debug_id(debug_id.to_string());
-                self.debug_ids.insert(url.clone(), debug_id);
+                self.debug_ids.insert(source_file.url.clone(), debug_id);
             }
 
-            self.sources.insert(url.clone(), source_file);
-            pb.inc(1);
+            self.sources.insert(source_file.url.clone(), source_file);
         }
         pb.finish_with_duration("Analyzing");
     }
@@ -356,7 +400,7 @@ impl SourceMapProcessor {
             .collect();
 
         for source in self.sources.values_mut() {
-            // Skip everything but minified JS files.
+            // Skip everything but minified JS and CSS files.
             if source.ty != SourceFileType::MinifiedSource {
                 continue;
             }
@@ -375,8 +419,12 @@ impl SourceMapProcessor {
             // that can't be resolved to a source map file.
             // Instead, we pretend we failed to discover the location, and we fall back to
             // guessing the source map location based on the source location.
-            let location =
-                discover_sourcemaps_location(contents).filter(|loc| !is_remote_sourcemap(loc));
+            let location = if is_css_url(&source.url) {
+                inject::discover_css_sourcemaps_location(contents)
+            } else {
+                discover_sourcemaps_location(contents)
+            }
+            .filter(|loc| !is_remote_sourcemap(loc));
             let sourcemap_reference = match location {
                 Some(url) => SourceMapReference::from_url(url.to_string()),
                 None => match guess_sourcemap_reference(&sourcemaps, &source.url) {
@@ -879,6 +927,91 @@ impl SourceMapProcessor {
         }
     }
 
+    /// Validates all files before an upload with `--strict-validation`.
+    ///
+    /// Unlike [`SourceMapProcessor::validate_all`], this does not only check that
+    /// sourcemaps can be parsed, but also that every source file references a
+    /// sourcemap that is part of the upload, and that every source file has a
+    /// debug id.
+    pub fn validate_strict(&mut self) -> ValidationReport {
+        self.flush_pending_sources();
+        self.collect_sourcemap_references();
+
+        let sourcemaps: Vec<_> = self
+            .sources
+            .values()
+            .filter(|source| source.ty == SourceFileType::SourceMap)
+            .map(|source| source.url.clone())
+            .collect();
+        let mut issues = vec![];
+        let mut issue = |source: &SourceFile, kind, message| {
+            issues.push(ValidationIssue {
+                url: source.url.clone(),
+                path: source.path.clone(),
+                kind,
+                message,
+            })
+        };
+
+        for source in self.sources.values() {
+            match source.ty {
+                SourceFileType::SourceMap => {
+                    if let Err(err) = sourcemap::decode_slice(&source.contents) {
+                        issue(
+                            source,
+                            ValidationIssueKind::InvalidSourcemap,
+                            format!("invalid sourcemap ({err})"),
+                        );
+                    }
+                }
+                SourceFileType::MinifiedSource => {
+                    match self.sourcemap_references.get(&source.url) {
+                        Some(Some(reference)) if reference.url.starts_with("data:") => {
+                            if let Err(err) = sourcemap::decode_data_url(&reference.url) {
+                                issue(
+                                    source,
+                                    ValidationIssueKind::InvalidSourcemap,
+                                    format!("invalid embedded sourcemap ({err})"),
+                                );
+                            }
+                        }
+                        Some(Some(reference)) => {
+                            let url = reference.original_url.clone().unwrap_or_else(|| {
+                                resolve_sourcemap_url(&sourcemaps, &source.url, &reference.url)
+                            });
+                            if !self.sources.contains_key(&url) {
+                                issue(
+                                    source,
+                                    ValidationIssueKind::UnresolvedSourcemap,
+                                    format!(
+                                        "references sourcemap {}, which is not part of the upload",
+                                        reference.url
+                                    ),
+                                );
+                            }
+                        }
+                        _ => issue(
+                            source,
+                            ValidationIssueKind::MissingSourcemap,
+                            "no sourcemap reference found".into(),
+                        ),
+                    }
+
+                    if source.debug_id().is_none() && !self.debug_ids.contains_key(&source.url) {
+                        issue(
+                            source,
+                            ValidationIssueKind::MissingDebugId,
+                            "missing debug id".into(),
+                        );
+                    }
+                }
+                SourceFileType::Source | SourceFileType::IndexedRamBundle => (),
+            }
+        }
+
+        ValidationReport::new(self.sources.len(), issues)
+    }
+
     /// Injects debug ids into minified source files and sourcemaps.
     ///
     /// This iterates over contained minified source files and adds debug ids
@@ -889,14 +1022,31 @@ impl SourceMapProcessor {
     /// them together. If for whatever reason the sourcemap already contains
     /// a debug id, it will be reused for the source file.
     ///
+    /// Debug ids are derived from the file contents so that repeated builds of
+    /// identical files get identical ids. If `random_ids` is true, random debug ids
+    /// are generated instead.
+    ///
     /// If `dry_run` is false, this will modify the source and sourcemap files on disk!
+    /// Files whose contents would not change are not rewritten.
+    ///
+    /// The `extensions` is a list of file extensions that should be considered
+    /// for JavaScript and CSS files. CSS files only get a `/*# debugId=... */`
+    /// comment, since they cannot run the code snippet that registers the debug id.
+    ///
+    /// Files are processed in parallel. Source files that share a sourcemap are
+    /// processed one after another, so that they all end up with the debug id of
+    /// that sourcemap.
     ///
-    /// The `js_extensions` is a list of file extensions that should be considered
-    /// for JavaScript files.
-    pub fn inject_debug_ids(&mut self, dry_run: bool, js_extensions: &[&str]) -> Result<()> {
+    /// Returns a report of all modified and skipped files.
+    pub fn inject_debug_ids(
+        &mut self,
+        dry_run: bool,
+        random_ids: bool,
+        extensions: &[&str],
+    ) -> Result<InjectReport> {
         self.flush_pending_sources();
         self.collect_sourcemap_references();
-        println!("{} Injecting debug ids", style(">").dim());
+        quiet_println!("{} Injecting debug ids", style(">").dim());
 
         let mut report = InjectReport::default();
 
@@ -907,9 +1057,21 @@ impl SourceMapProcessor {
             .collect::<Vec<_>>();
         sourcemaps.sort();
 
-        for (source_url, sourcemap_url) in self.sourcemap_references.iter_mut() {
+        // Files are planned in a fixed order, so that the report does not depend
+        // on the order in which they were found.
+        let mut source_urls = self
+            .sourcemap_references
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        source_urls.sort();
+
+        let mut jobs = vec![];
+        let mut deferred = vec![];
+        let mut claimed_sourcemaps = HashSet::new();
+        for source_url in source_urls {
             // We only allow injection into files that match the extension
-            if !url_matches_extension(source_url, js_extensions) {
+            if !url_matches_extension(&source_url, extensions) {
                 debug!(
                     "skipping potential js file {} because it does not match extension",
                     source_url
@@ -917,197 +1079,335 @@ impl SourceMapProcessor {
                 continue;
             }
 
-            if let Some(debug_id) = self.debug_ids.get(source_url) {
+            if let Some(debug_id) = self.debug_ids.get(&source_url) {
                 report
                     .previously_injected
                     .push((source_url.into(), *debug_id));
                 continue;
             }
 
-            // Modify the source file and the sourcemap.
-            // There are several cases to consider according to whether we have a sourcemap for the source file and
-            // whether it's embedded or external.
-            let debug_id = match sourcemap_url {
-                None => {
-                    // Case 1: We have no sourcemap for the source file. Hash the file contents for the debug id.
-                    let source_file = self.sources.get_mut(source_url).unwrap();
-                    let debug_id = inject::debug_id_from_bytes_hashed(&source_file.contents);
-
-                    // If we don't have a sourcemap, it's not safe to inject the code snippet at the beginning,
-                    // because that would throw off all the mappings. Instead, inject the snippet at the very end.
-                    // This isn't ideal, but it's the best we can do in this case.
-                    inject::fixup_js_file_end(&mut source_file.contents, debug_id)
-                        .context(format!("Failed to process {}", source_file.path.display()))?;
-                    debug_id
+            let sourcemap_url = match &self.sourcemap_references[&source_url] {
+                None => None,
+                Some(sourcemap) => match sourcemap.url.strip_prefix(DATA_PREAMBLE) {
+                    Some(encoded) => Some(InjectSourceMapUrl::Embedded(encoded.to_owned())),
+                    None => Some(InjectSourceMapUrl::External(resolve_sourcemap_url(
+                        &sourcemaps,
+                        &source_url,
+                        &sourcemap.url,
+                    ))),
+                },
+            };
+
+            // Source files sharing a sourcemap cannot be processed in parallel.
+            if let Some(InjectSourceMapUrl::External(ref sourcemap_url)) = sourcemap_url {
+                if !claimed_sourcemaps.insert(sourcemap_url.clone()) {
+                    deferred.push((source_url, sourcemap_url.clone()));
+                    continue;
+                }
+            }
+
+            jobs.extend(self.take_inject_job(&source_url, sourcemap_url));
+        }
+
+        let results = jobs
+            .into_par_iter()
+            .map(|job| job.run(dry_run, random_ids))
+            .collect::<Vec<_>>();
+        for result in results {
+            self.finish_inject_job(result?, &mut report);
+        }
+
+        for (source_url, sourcemap_url) in deferred {
+            let sourcemap_url = InjectSourceMapUrl::External(sourcemap_url);
+            if let Some(job) = self.take_inject_job(&source_url, Some(sourcemap_url)) {
+                self.finish_inject_job(job.run(dry_run, random_ids)?, &mut report);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Removes a source file and its sourcemap from the processor, so that debug
+    /// ids can be injected into them on another thread.
+    ///
+    /// Returns `None` if the source file was already taken as the sourcemap of
+    /// another job.
+    fn take_inject_job(
+        &mut self,
+        source_url: &str,
+        sourcemap_url: Option<InjectSourceMapUrl>,
+    ) -> Option<InjectJob> {
+        let source = self.sources.remove(source_url)?;
+        let sourcemap = match sourcemap_url {
+            None => InjectSourceMap::None,
+            Some(InjectSourceMapUrl::Embedded(encoded)) => InjectSourceMap::Embedded(encoded),
+            Some(InjectSourceMapUrl::External(sourcemap_url)) => {
+                match self.sources.remove(&sourcemap_url) {
+                    Some(sourcemap) => InjectSourceMap::External(sourcemap),
+                    None => {
+                        debug!("Sourcemap file {} not found", sourcemap_url);
+                        InjectSourceMap::None
+                    }
                 }
-                Some(sourcemap) => {
-                    if let Some(encoded) = sourcemap.url.strip_prefix(DATA_PREAMBLE) {
-                        // Case 2: The source file has an embedded sourcemap.
+            }
+        };
+        Some(InjectJob { source, sourcemap })
+    }
 
-                        let Ok(mut decoded) = data_encoding::BASE64.decode(encoded.as_bytes())
-                        else {
-                            bail!("Invalid embedded sourcemap in source file {source_url}");
-                        };
+    /// Puts the files of a finished job back into the processor and records
+    /// them in the report.
+    fn finish_inject_job(&mut self, result: InjectResult, report: &mut InjectReport) {
+        let InjectResult {
+            source,
+            sourcemap,
+            debug_id,
+        } = result;
+
+        match sourcemap {
+            InjectedSourceMap::None => {}
+            InjectedSourceMap::Embedded(url) => {
+                self.sourcemap_references
+                    .insert(source.url.clone(), Some(SourceMapReference::from_url(url)));
+            }
+            InjectedSourceMap::External { file, fresh } => {
+                if fresh {
+                    report.sourcemaps.push((file.path.clone(), debug_id));
+                } else {
+                    report
+                        .skipped_sourcemaps
+                        .push((file.path.clone(), debug_id));
+                }
+                self.sources.insert(file.url.clone(), file);
+            }
+        }
 
-                        let mut sourcemap = SourceMap::from_slice(&decoded).with_context(|| {
-                            format!("Invalid embedded sourcemap in source file {source_url}")
-                        })?;
+        self.debug_ids.insert(source.url.clone(), debug_id);
+        report.injected.push((source.path.clone(), debug_id));
+        self.sources.insert(source.url.clone(), source);
+    }
+}
 
-                        let debug_id = sourcemap
-                            .get_debug_id()
-                            .unwrap_or_else(|| inject::debug_id_from_bytes_hashed(&decoded));
+/// Resolves the URL of an external sourcemap referenced by a source file to the
+/// URL of one of the given sourcemaps, if possible.
+fn resolve_sourcemap_url(sourcemaps: &[String], source_url: &str, sourcemap_url: &str) -> String {
+    let normalized = inject::normalize_sourcemap_url(source_url, sourcemap_url);
+    let matches = inject::find_matching_paths(sourcemaps, &normalized);
+
+    match &matches[..] {
+        [] => normalized,
+        [x] => x.to_string(),
+        _ => {
+            warn!("Ambiguous matches for sourcemap path {normalized}:");
+            for path in matches {
+                warn!("{path}");
+            }
+            normalized
+        }
+    }
+}
 
-                        let source_file = self.sources.get_mut(source_url).unwrap();
-                        let adjustment_map =
-                            inject::fixup_js_file(&mut source_file.contents, debug_id).context(
-                                format!("Failed to process {}", source_file.path.display()),
-                            )?;
+/// Where the sourcemap of a source file is, before the files are taken out
+/// of the processor.
+enum InjectSourceMapUrl {
+    /// The base64 encoded sourcemap embedded in the source file.
+    Embedded(String),
+    /// The URL of the sourcemap file.
+    External(String),
+}
 
-                        sourcemap.adjust_mappings(&adjustment_map);
-                        sourcemap.set_debug_id(Some(debug_id));
+/// The sourcemap of a source file that debug ids are injected into.
+enum InjectSourceMap {
+    /// The source file has no sourcemap, or it cannot be found.
+    None,
+    /// The base64 encoded sourcemap embedded in the source file.
+    Embedded(String),
+    /// A sourcemap file.
+    External(SourceFile),
+}
 
-                        decoded.clear();
-                        sourcemap.to_writer(&mut decoded)?;
+/// The sourcemap of a source file after injecting debug ids.
+enum InjectedSourceMap {
+    None,
+    /// The data URL of the updated embedded sourcemap.
+    Embedded(String),
+    /// The updated sourcemap file. `fresh` is false if the sourcemap already
+    /// had a debug id.
+    External {
+        file: SourceFile,
+        fresh: bool,
+    },
+}
 
-                        let encoded = data_encoding::BASE64.encode(&decoded);
-                        let new_sourcemap_url = format!("{DATA_PREAMBLE}{encoded}");
+/// A source file to inject a debug id into, along with its sourcemap.
+struct InjectJob {
+    source: SourceFile,
+    sourcemap: InjectSourceMap,
+}
 
-                        inject::replace_sourcemap_url(
-                            &mut source_file.contents,
-                            &new_sourcemap_url,
-                        )?;
-                        *sourcemap_url = Some(SourceMapReference::from_url(new_sourcemap_url));
+/// The files of an [`InjectJob`] after injecting the debug id.
+struct InjectResult {
+    source: SourceFile,
+    sourcemap: InjectedSourceMap,
+    debug_id: DebugId,
+}
 
-                        debug_id
-                    } else {
-                        // Handle external sourcemaps
-
-                        let normalized =
-                            inject::normalize_sourcemap_url(source_url, &sourcemap.url);
-                        let matches = inject::find_matching_paths(&sourcemaps, &normalized);
-
-                        let sourcemap_url = match &matches[..] {
-                            [] => normalized,
-                            [x] => x.to_string(),
-                            _ => {
-                                warn!("Ambiguous matches for sourcemap path {normalized}:");
-                                for path in matches {
-                                    warn!("{path}");
-                                }
-                                normalized
-                            }
-                        };
-
-                        if self.sources.contains_key(&sourcemap_url) {
-                            // Case 3: We have an external sourcemap for the source file.
-
-                            // We need to do a bit of a dance here because we can't mutably
-                            // borrow the source file and the sourcemap at the same time.
-                            let (mut sourcemap, debug_id, debug_id_fresh) = {
-                                let sourcemap_file = &self.sources[&sourcemap_url];
-
-                                let sm = SourceMap::from_slice(&sourcemap_file.contents).context(
-                                    format!("Invalid sourcemap at {}", sourcemap_file.url),
-                                )?;
-
-                                match sm.get_debug_id() {
-                                    Some(debug_id) => (sm, debug_id, false),
-                                    None => {
-                                        let debug_id = inject::debug_id_from_bytes_hashed(
-                                            &sourcemap_file.contents,
-                                        );
-                                        (sm, debug_id, true)
-                                    }
-                                }
-                            };
-
-                            let source_file = self.sources.get_mut(source_url).unwrap();
-                            let adjustment_map =
-                                inject::fixup_js_file(&mut source_file.contents, debug_id)
-                                    .context(format!(
-                                        "Failed to process {}",
-                                        source_file.path.display()
-                                    ))?;
-
-                            sourcemap.adjust_mappings(&adjustment_map);
-                            sourcemap.set_debug_id(Some(debug_id));
-
-                            let sourcemap_file = self.sources.get_mut(&sourcemap_url).unwrap();
-                            sourcemap_file.contents.clear();
-                            sourcemap.to_writer(&mut sourcemap_file.contents)?;
-
-                            sourcemap_file.set_debug_id(debug_id.to_string());
-
-                            if !dry_run {
-                                let mut file = std::fs::File::create(&sourcemap_file.path)?;
-                                file.write_all(&sourcemap_file.contents).context(format!(
-                                    "Failed to write sourcemap file {}",
-                                    sourcemap_file.path.display()
-                                ))?;
-                            }
+impl InjectJob {
+    /// Injects a debug id into the source file and its sourcemap, and writes
+    /// them to disk unless `dry_run` is true.
+    fn run(self, dry_run: bool, random_ids: bool) -> Result<InjectResult> {
+        let InjectJob {
+            source: mut source_file,
+            sourcemap,
+        } = self;
+        let source_url = source_file.url.clone();
+        let is_css = is_css_url(&source_url);
+
+        let new_debug_id = |contents: &[u8]| {
+            if random_ids {
+                inject::debug_id_random()
+            } else {
+                inject::debug_id_from_bytes_hashed(contents)
+            }
+        };
 
-                            if debug_id_fresh {
-                                report
-                                    .sourcemaps
-                                    .push((sourcemap_file.path.clone(), debug_id));
-                            } else {
-                                report
-                                    .skipped_sourcemaps
-                                    .push((sourcemap_file.path.clone(), debug_id));
-                            }
+        // Modify the source file and the sourcemap.
+        // There are several cases to consider according to whether we have a sourcemap for the source file and
+        // whether it's embedded or external.
+        let (debug_id, sourcemap) = match sourcemap {
+            InjectSourceMap::None => {
+                // Case 1: We have no sourcemap for the source file, or we have a URL for the external sourcemap,
+                // but we can't find it. Hash the file contents for the debug id.
+                let debug_id = new_debug_id(&source_file.contents);
+
+                // If we don't have a sourcemap, it's not safe to inject the code snippet at the beginning,
+                // because that would throw off all the mappings. Instead, inject the snippet at the very end.
+                // This isn't ideal, but it's the best we can do in this case.
+                let fixup = if is_css {
+                    inject::fixup_css_file(&mut source_file.contents, debug_id)
+                } else {
+                    inject::fixup_js_file_end(&mut source_file.contents, debug_id)
+                };
+                fixup.context(format!("Failed to process {}", source_file.path.display()))?;
+                (debug_id, InjectedSourceMap::None)
+            }
+            InjectSourceMap::Embedded(encoded) => {
+                // Case 2: The source file has an embedded sourcemap.
+                let Ok(mut decoded) = data_encoding::BASE64.decode(encoded.as_bytes()) else {
+                    bail!("Invalid embedded sourcemap in source file {source_url}");
+                };
+
+                let mut sourcemap = SourceMap::from_slice(&decoded).with_context(|| {
+                    format!("Invalid embedded sourcemap in source file {source_url}")
+                })?;
+
+                let debug_id = sourcemap
+                    .get_debug_id()
+                    .unwrap_or_else(|| new_debug_id(&decoded));
+
+                let fixup = if is_css {
+                    inject::fixup_css_file(&mut source_file.contents, debug_id)
+                } else {
+                    inject::fixup_js_file(&mut source_file.contents, debug_id)
+                        .map(|adjustment_map| sourcemap.adjust_mappings(&adjustment_map))
+                };
+                fixup.context(format!("Failed to process {}", source_file.path.display()))?;
 
-                            debug_id
-                        } else {
-                            // Case 4: We have a URL for the external sourcemap, but we can't find it.
-                            // This is substantially the same as case 1.
-                            debug!("Sourcemap file {} not found", sourcemap_url);
-                            // source map cannot be found, fall back to hashing the contents.
-                            let source_file = self.sources.get_mut(source_url).unwrap();
-                            let debug_id =
-                                inject::debug_id_from_bytes_hashed(&source_file.contents);
-
-                            // If we don't have a sourcemap, it's not safe to inject the code snippet at the beginning,
-                            // because that would throw off all the mappings. Instead, inject the snippet at the very end.
-                            // This isn't ideal, but it's the best we can do in this case.
-                            inject::fixup_js_file_end(&mut source_file.contents, debug_id)
-                                .context(format!(
-                                    "Failed to process {}",
-                                    source_file.path.display()
-                                ))?;
-
-                            debug_id
-                        }
-                    }
+                sourcemap.set_debug_id(Some(debug_id));
+
+                decoded.clear();
+                sourcemap.to_writer(&mut decoded)?;
+
+                let encoded = data_encoding::BASE64.encode(&decoded);
+                let new_sourcemap_url = format!("{DATA_PREAMBLE}{encoded}");
+
+                if is_css {
+                    inject::replace_css_sourcemap_url(
+                        &mut source_file.contents,
+                        &new_sourcemap_url,
+                    )?;
+                } else {
+                    inject::replace_sourcemap_url(&mut source_file.contents, &new_sourcemap_url)?;
                 }
-            };
 
-            // Finally, some housekeeping.
-            let source_file = self.sources.get_mut(source_url).unwrap();
+                (debug_id, InjectedSourceMap::Embedded(new_sourcemap_url))
+            }
+            InjectSourceMap::External(mut sourcemap_file) => {
+                // Case 3: We have an external sourcemap for the source file.
+                let mut sourcemap = SourceMap::from_slice(&sourcemap_file.contents)
+                    .context(format!("Invalid sourcemap at {}", sourcemap_file.url))?;
+
+                let (debug_id, debug_id_fresh) = match sourcemap.get_debug_id() {
+                    Some(debug_id) => (debug_id, false),
+                    None => (new_debug_id(&sourcemap_file.contents), true),
+                };
+
+                let fixup = if is_css {
+                    inject::fixup_css_file(&mut source_file.contents, debug_id)
+                } else {
+                    inject::fixup_js_file(&mut source_file.contents, debug_id)
+                        .map(|adjustment_map| sourcemap.adjust_mappings(&adjustment_map))
+                };
+                fixup.context(format!("Failed to process {}", source_file.path.display()))?;
+
+                sourcemap.set_debug_id(Some(debug_id));
 
-            source_file.set_debug_id(debug_id.to_string());
-            self.debug_ids.insert(source_url.clone(), debug_id);
+                sourcemap_file.contents.clear();
+                sourcemap.to_writer(&mut sourcemap_file.contents)?;
 
-            if !dry_run {
-                let mut file = std::fs::File::create(&source_file.path)?;
-                file.write_all(&source_file.contents).context(format!(
-                    "Failed to write source file {}",
-                    source_file.path.display()
-                ))?;
+                sourcemap_file.set_debug_id(debug_id.to_string());
+
+                if !dry_run {
+                    write_if_changed(&sourcemap_file.path, &sourcemap_file.contents).context(
+                        format!(
+                            "Failed to write sourcemap file {}",
+                            sourcemap_file.path.display()
+                        ),
+                    )?;
+                }
+
+                (
+                    debug_id,
+                    InjectedSourceMap::External {
+                        file: sourcemap_file,
+                        fresh: debug_id_fresh,
+                    },
+                )
             }
+        };
 
-            report.injected.push((source_file.path.clone(), debug_id));
-        }
+        // Finally, some housekeeping.
+        source_file.set_debug_id(debug_id.to_string());
 
-        if !report.is_empty() {
-            println!("{report}");
-        } else {
-            println!("> Nothing to inject")
+        if !dry_run {
+            write_if_changed(&source_file.path, &source_file.contents).context(format!(
+                "Failed to write source file {}",
+                source_file.path.display()
+            ))?;
         }
 
-        Ok(())
+        Ok(InjectResult {
+            source: source_file,
+            sourcemap,
+            debug_id,
+        })
     }
 }
 
+/// Writes `contents` to `path` unless the file already holds exactly these bytes.
+///
+/// Leaving unchanged files untouched preserves their modification times, which
+/// keeps build caches valid.
+fn write_if_changed(path: &Path, contents: &[u8]) -> Result<()> {
+    if std::fs::read(path).is_ok_and(|existing| existing == contents) {
+        debug!("skipping unchanged file {}", path.display());
+        return Ok(());
+    }
+
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(contents)?;
+    Ok(())
+}
+
 fn validate_script(source: &mut SourceFile) -> Result<()> {
     if let Some(sm_ref) = get_sourcemap_ref(source) {
         if let sourcemap::SourceMapRef::LegacyRef(_) = sm_ref {
@@ -1220,4 +1520,104 @@ mod tests {
         assert!(!url_matches_extension("js", &["js"][..]));
         assert!(url_matches_extension("foo.test.js", &["test.js"][..]));
     }
+
+    #[test]
+    fn test_inject_debug_ids_shared_sourcemap() {
+        let mut processor = SourceMapProcessor::new();
+        let files = [
+            ("~/a.js", "a();\n//# sourceMappingURL=shared.js.map\n"),
+            ("~/b.js", "b();\n//# sourceMappingURL=shared.js.map\n"),
+            (
+                "~/shared.js.map",
+                r#"{"version":3,"sources":["a.ts"],"names":[],"mappings":"AAAA"}"#,
+            ),
+        ];
+        for (url, contents) in files {
+            let file = ReleaseFileMatch {
+                base_path: PathBuf::new(),
+                path: PathBuf::from(url.trim_start_matches("~/")),
+                contents: contents.into(),
+            };
+            processor.add(url, file).unwrap();
+        }
+
+        let report = processor
+            .inject_debug_ids(true, false, &["js", "map"])
+            .unwrap();
+
+        assert_eq!(report.injected.len(), 2);
+        assert_eq!(report.injected[0].1, report.injected[1].1);
+        assert_eq!(report.sourcemaps.len(), 1);
+        assert_eq!(report.skipped_sourcemaps.len(), 1);
+        assert_eq!(report.sourcemaps[0].1, report.injected[0].1);
+    }
+
+    #[test]
+    fn test_validate_strict() {
+        let mut processor = SourceMapProcessor::new();
+        let files = [
+            (
+                "~/ok.js",
+                "ok();\n//# debugId=2f7ff3cb-3d4a-4ab0-9ecb-bc04e5e0bb5c\n//# sourceMappingURL=ok.js.map\n",
+            ),
+            (
+                "~/ok.js.map",
+                r#"{"version":3,"sources":["ok.ts"],"names":[],"mappings":"AAAA"}"#,
+            ),
+            ("~/missing.js", "m();\n//# sourceMappingURL=gone.js.map\n"),
+            (
+                "~/broken.js.map",
+                r#"{"version":3,"sources":["b.ts"],"names":[],"mappings":"!!!"}"#,
+            ),
+        ];
+        for (url, contents) in files {
+            let file = ReleaseFileMatch {
+                base_path: PathBuf::new(),
+                path: PathBuf::from(url.trim_start_matches("~/")),
+                contents: contents.into(),
+            };
+            processor.add(url, file).unwrap();
+        }
+
+        let report = processor.validate_strict();
+
+        assert!(!report.valid);
+        assert_eq!(report.files, 4);
+        let issues: Vec<_> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.url.as_str(), issue.kind))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                ("~/broken.js.map", ValidationIssueKind::InvalidSourcemap),
+                ("~/missing.js", ValidationIssueKind::UnresolvedSourcemap),
+                ("~/missing.js", ValidationIssueKind::MissingDebugId),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compose_hermes_sourcemap() {
+        let mut packager = SourceMap::from_slice(
+            br#"{"version":3,"file":"index.android.bundle","sources":["add.js"],"names":[],"mappings":"AAAA,mBACE","debugId":"5d8e1c2a-9b3f-4e6d-8a7c-1f2e3d4c5b6a"}"#,
+        )
+        .unwrap();
+        let hermes = SourceMap::from_slice(
+            br#"{"version":3,"file":"index.android.bundle.hbc","sources":["index.android.bundle"],"names":[],"mappings":"AAAA,UAAmB"}"#,
+        )
+        .unwrap();
+
+        compose_hermes_sourcemap(&mut packager, &hermes);
+
+        assert_eq!(packager.get_file(), Some("index.android.bundle.hbc"));
+        assert_eq!(
+            packager.get_debug_id().unwrap().to_string(),
+            "5d8e1c2a-9b3f-4e6d-8a7c-1f2e3d4c5b6a"
+        );
+        let token = packager.lookup_token(0, 10).unwrap();
+        assert_eq!(token.get_source(), Some("add.js"));
+        assert_eq!((token.get_src_line(), token.get_src_col()), (1, 2));
+    }
 }
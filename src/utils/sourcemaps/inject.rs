@@ -5,18 +5,21 @@ use symbolic::common::{clean_path, join_path};
 
 use std::fmt;
 use std::io::{BufRead, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use lazy_static::lazy_static;
 
 use magic_string::{GenerateDecodedMapOptions, MagicString};
 use sentry::types::DebugId;
+use serde::ser::{SerializeStruct, Serializer};
+use serde::Serialize;
 use sourcemap::SourceMap;
 
 const CODE_SNIPPET_TEMPLATE: &str = r#"!function(){try{var e="undefined"!=typeof window?window:"undefined"!=typeof global?global:"undefined"!=typeof globalThis?globalThis:"undefined"!=typeof self?self:{},n=(new e.Error).stack;n&&(e._sentryDebugIds=e._sentryDebugIds||{},e._sentryDebugIds[n]="__SENTRY_DEBUG_ID__")}catch(e){}}();"#;
 const DEBUGID_PLACEHOLDER: &str = "__SENTRY_DEBUG_ID__";
 const DEBUGID_COMMENT_PREFIX: &str = "//# debugId";
+const CSS_DEBUGID_COMMENT_PREFIX: &str = "/*# debugId";
 
 lazy_static! {
     // A regex that captures
@@ -27,6 +30,14 @@ lazy_static! {
         r#"^(#!.*[\n\r])?(?:\s+|/\*(?:.|\r|\n)*?\*/|//.*[\n\r])*(?:"[^"]*";|'[^']*';[\n\r]?)?"#
     )
     .unwrap();
+
+    // A regex that captures the URL of a `/*# sourceMappingURL=<url> */` comment in CSS.
+    static ref CSS_SOURCEMAP_URL_RE: Regex =
+        Regex::new(r"/\*[#@]\s*sourceMappingURL=(\S+?)\s*\*/").unwrap();
+
+    // A regex that captures the debug id of a `/*# debugId=<debug_id> */` comment in CSS.
+    static ref CSS_DEBUGID_RE: Regex =
+        Regex::new(r"/\*#\s*debugId=([0-9a-fA-F-]+)\s*\*/").unwrap();
 }
 
 fn print_section_with_debugid(
@@ -53,6 +64,34 @@ pub struct InjectReport {
     pub skipped_sourcemaps: Vec<(PathBuf, DebugId)>,
 }
 
+/// The kind of file an [`InjectReportEntry`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectFileKind {
+    Source,
+    Sourcemap,
+}
+
+/// What happened to a file during injection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectStatus {
+    Injected,
+    Skipped,
+}
+
+/// A single file of an [`InjectReport`], as emitted in machine-readable output.
+#[derive(Debug, Serialize)]
+pub struct InjectReportEntry<'a> {
+    pub path: &'a Path,
+    #[serde(rename = "type")]
+    pub kind: InjectFileKind,
+    pub debug_id: DebugId,
+    pub status: InjectStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<&'static str>,
+}
+
 impl InjectReport {
     pub fn is_empty(&self) -> bool {
         self.injected.is_empty()
@@ -60,6 +99,61 @@ impl InjectReport {
             && self.sourcemaps.is_empty()
             && self.skipped_sourcemaps.is_empty()
     }
+
+    /// Returns all files of the report, sorted by path.
+    pub fn entries(&self) -> Vec<InjectReportEntry<'_>> {
+        let sections = [
+            (
+                &self.injected,
+                InjectFileKind::Source,
+                InjectStatus::Injected,
+                None,
+            ),
+            (
+                &self.sourcemaps,
+                InjectFileKind::Sourcemap,
+                InjectStatus::Injected,
+                None,
+            ),
+            (
+                &self.previously_injected,
+                InjectFileKind::Source,
+                InjectStatus::Skipped,
+                Some("already has a debug id"),
+            ),
+            (
+                &self.skipped_sourcemaps,
+                InjectFileKind::Sourcemap,
+                InjectStatus::Skipped,
+                Some("already has a debug id"),
+            ),
+        ];
+
+        sections
+            .into_iter()
+            .flat_map(|(files, kind, status, reason)| {
+                files.iter().map(move |(path, debug_id)| InjectReportEntry {
+                    path,
+                    kind,
+                    debug_id: *debug_id,
+                    status,
+                    reason,
+                })
+            })
+            .sorted_by(|a, b| a.path.cmp(b.path))
+            .collect()
+    }
+}
+
+impl Serialize for InjectReport {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("InjectReport", 1)?;
+        state.serialize_field("files", &self.entries())?;
+        state.end()
+    }
 }
 
 impl fmt::Display for InjectReport {
@@ -221,6 +315,57 @@ pub fn replace_sourcemap_url(js_contents: &mut Vec<u8>, new_url: &str) -> Result
     Ok(())
 }
 
+/// Fixes up a CSS file with a debug id.
+///
+/// A comment of the form `/*# debugId=<debug_id> */` is appended to the file. Unlike
+/// [`fixup_js_file`], no code is inserted, so the mappings of the file's sourcemap
+/// remain valid.
+pub fn fixup_css_file(css_contents: &mut Vec<u8>, debug_id: DebugId) -> Result<()> {
+    if !css_contents.is_empty() && !css_contents.ends_with(b"\n") {
+        css_contents.push(b'\n');
+    }
+    writeln!(css_contents, "{CSS_DEBUGID_COMMENT_PREFIX}={debug_id} */")?;
+    Ok(())
+}
+
+/// Replaces a CSS file's source mapping url with a new one.
+///
+/// Only the bottommost `/*# sourceMappingURL=<url> */` comment will be updated. If there
+/// are no source mapping url comments in the file, this is a no-op.
+pub fn replace_css_sourcemap_url(css_contents: &mut Vec<u8>, new_url: &str) -> Result<()> {
+    let contents = std::str::from_utf8(css_contents)?;
+    let Some(m) = CSS_SOURCEMAP_URL_RE.find_iter(contents).last() else {
+        return Ok(());
+    };
+
+    let replaced = format!(
+        "{}/*# sourceMappingURL={new_url} */{}",
+        &contents[..m.start()],
+        &contents[m.end()..]
+    );
+    *css_contents = replaced.into_bytes();
+
+    Ok(())
+}
+
+/// Returns the URL of the last `/*# sourceMappingURL=<url> */` comment in a CSS file.
+pub fn discover_css_sourcemaps_location(contents: &str) -> Option<&str> {
+    CSS_SOURCEMAP_URL_RE
+        .captures_iter(contents)
+        .last()
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str())
+}
+
+/// Returns the debug id of the last `/*# debugId=<debug_id> */` comment in a CSS file.
+pub fn discover_css_debug_id(contents: &str) -> Option<DebugId> {
+    CSS_DEBUGID_RE
+        .captures_iter(contents)
+        .last()
+        .and_then(|caps| caps.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
 /// Generates a debug ID from bytes.
 pub fn debug_id_from_bytes_hashed(bytes: &[u8]) -> DebugId {
     let mut hash = sha1_smol::Sha1::new();
@@ -230,6 +375,11 @@ pub fn debug_id_from_bytes_hashed(bytes: &[u8]) -> DebugId {
     DebugId::from_uuid(uuid::Builder::from_sha1_bytes(sha1_bytes).into_uuid())
 }
 
+/// Generates a random debug ID.
+pub fn debug_id_random() -> DebugId {
+    DebugId::from_uuid(uuid::Uuid::new_v4())
+}
+
 /// Computes a normalized sourcemap URL from a source file's own URL und the relative URL of its sourcemap.
 ///
 /// Roughly, this will combine a source URL of `some/dir/source.js` and a sourcemap URL of `path/to/source.min.js`
@@ -284,7 +434,7 @@ pub fn find_matching_paths(candidate_paths: &[String], expected_path: &str) -> V
         while candidate_segments
             .peek()
             .zip(expected_segments.peek())
-            .map_or(false, |(x, y)| x == y)
+            .is_some_and(|(x, y)| x == y)
         {
             candidate_segments.next();
             expected_segments.next();
@@ -535,6 +685,34 @@ more text
         assert_eq!(std::str::from_utf8(&js_contents).unwrap(), expected);
     }
 
+    #[test]
+    fn test_fixup_css_file() {
+        let css_contents = ".a{color:red}\n/*# sourceMappingURL=main.css.map */";
+        let mut css_contents = Vec::from(css_contents);
+
+        assert_eq!(
+            discover_css_sourcemaps_location(std::str::from_utf8(&css_contents).unwrap()),
+            Some("main.css.map")
+        );
+
+        let debug_id = DebugId::default();
+        fixup_css_file(&mut css_contents, debug_id).unwrap();
+
+        let expected = r#".a{color:red}
+/*# sourceMappingURL=main.css.map */
+/*# debugId=00000000-0000-0000-0000-000000000000 */
+"#;
+        let contents = std::str::from_utf8(&css_contents).unwrap();
+        assert_eq!(contents, expected);
+        assert_eq!(discover_css_debug_id(contents), Some(debug_id));
+
+        replace_css_sourcemap_url(&mut css_contents, "data:new").unwrap();
+        assert_eq!(
+            discover_css_sourcemaps_location(std::str::from_utf8(&css_contents).unwrap()),
+            Some("data:new")
+        );
+    }
+
     #[test]
     fn test_find_matching_paths_unique() {
         let expected = "./foo/bar/baz/quux";
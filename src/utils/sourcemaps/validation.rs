@@ -0,0 +1,64 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use console::style;
+use serde::Serialize;
+
+/// The kind of problem found by `--strict-validation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationIssueKind {
+    /// A sourcemap could not be parsed.
+    InvalidSourcemap,
+    /// A source file does not reference a sourcemap.
+    MissingSourcemap,
+    /// A source file references a sourcemap that is not part of the upload.
+    UnresolvedSourcemap,
+    /// A source file has no debug id.
+    MissingDebugId,
+}
+
+/// A problem with a single file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub url: String,
+    pub path: PathBuf,
+    pub kind: ValidationIssueKind,
+    pub message: String,
+}
+
+/// The result of validating all files before an upload with
+/// `--strict-validation`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub files: usize,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Creates a report from the problems found in the given number of files.
+    pub fn new(files: usize, mut issues: Vec<ValidationIssue>) -> Self {
+        issues.sort_by(|a, b| (&a.url, a.kind).cmp(&(&b.url, b.kind)));
+        ValidationReport {
+            valid: issues.is_empty(),
+            files,
+            issues,
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f)?;
+        writeln!(f, "{}", style("Source Map Validation Report").dim().bold())?;
+        if self.valid {
+            return writeln!(f, "  All {} files are valid", self.files);
+        }
+        writeln!(f, "  {}", style("Problems").yellow().bold())?;
+        for issue in &self.issues {
+            writeln!(f, "    {}: {}", issue.url, style(&issue.message).red())?;
+        }
+        Ok(())
+    }
+}
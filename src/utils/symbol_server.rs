@@ -0,0 +1,228 @@
+//! Fetching PDBs of PE binaries from symbol servers.
+//!
+//! Symbol servers, such as the Microsoft public symbol server, store PDBs in
+//! the symsrv layout: `<name>/<signature><age>/<name>`.  Fetched PDBs are
+//! cached in the same layout, so that they are only downloaded once.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, format_err, Context, Result};
+use log::{debug, info, warn};
+use symbolic::common::{ByteView, DebugId};
+use symbolic::debuginfo::Object;
+use walkdir::WalkDir;
+
+use crate::api::Api;
+use crate::config::is_stateless;
+use crate::constants::APP_NAME;
+
+/// The name of the folder in the cache directory that holds fetched PDBs.
+const CACHE_FOLDER_NAME: &str = "symbols";
+
+/// Returns the folder in the user's cache directory that holds fetched PDBs.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join(APP_NAME).join(CACHE_FOLDER_NAME))
+}
+
+/// A symbol server to fetch PDBs from.
+pub struct SymbolServer {
+    url: String,
+    cache_dir: PathBuf,
+}
+
+impl SymbolServer {
+    /// Creates a symbol server that caches PDBs in the given directory, or in
+    /// the user's cache directory.
+    pub fn new(url: &str, cache_dir: Option<PathBuf>) -> Result<Self> {
+        let cache_dir = match cache_dir {
+            Some(cache_dir) => cache_dir,
+            None if is_stateless() => {
+                bail!("Pass --symbol-cache to fetch PDBs from a symbol server in stateless mode")
+            }
+            None => default_cache_dir().ok_or_else(|| format_err!("Could not get cache folder"))?,
+        };
+        Ok(SymbolServer {
+            url: url.trim_end_matches('/').to_owned(),
+            cache_dir,
+        })
+    }
+
+    /// Fetches the PDBs of all PE binaries in the given paths and returns the
+    /// paths of the fetched PDBs.  PDBs the server does not have are skipped.
+    pub fn fetch_pdbs<I>(&self, paths: I) -> Result<Vec<PathBuf>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<Path>,
+    {
+        let mut pdbs = vec![];
+        for path in paths {
+            for entry in WalkDir::new(path).into_iter().filter_map(Result::ok) {
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let Some((name, id)) = read_pdb_reference(entry.path()) else {
+                    continue;
+                };
+                if let Some(pdb) = self.fetch_pdb(&name, id)? {
+                    pdbs.push(pdb);
+                }
+            }
+        }
+        Ok(pdbs)
+    }
+
+    /// Fetches a single PDB, unless it is cached already.
+    fn fetch_pdb(&self, name: &str, id: DebugId) -> Result<Option<PathBuf>> {
+        self.fetch_pdb_with(name, id, |url, file| {
+            Ok(Api::current().download(url, file)?.status())
+        })
+    }
+
+    /// Fetches a single PDB with the given function, which downloads a URL
+    /// into a file and returns the HTTP status.
+    ///
+    /// Symbol servers are best effort: PDBs that cannot be fetched, because
+    /// the server does not have them or cannot be reached, are skipped with
+    /// a warning.
+    fn fetch_pdb_with<F>(&self, name: &str, id: DebugId, download: F) -> Result<Option<PathBuf>>
+    where
+        F: FnOnce(&str, &mut fs::File) -> Result<u32>,
+    {
+        let key = symsrv_path(name, id);
+        let path = self.cache_dir.join(&key);
+        if path.is_file() {
+            debug!("Using cached {}", path.display());
+            return Ok(Some(path));
+        }
+
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Could not create symbol cache {}", dir.display()))?;
+
+        let url = format!("{}/{key}", self.url);
+        info!("Fetching {url}");
+        let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+        match download(&url, temp.as_file_mut()) {
+            Ok(status) if (200..400).contains(&status) => {}
+            Ok(status) => {
+                warn!("Symbol server has no {name} ({id}), status {status}");
+                return Ok(None);
+            }
+            Err(err) => {
+                warn!("Could not fetch {name} ({id}) from {url}: {err:#}");
+                return Ok(None);
+            }
+        }
+
+        temp.persist(&path)?;
+        println!("Fetched {name} ({id}) from symbol server");
+        Ok(Some(path))
+    }
+}
+
+/// Returns the file name and debug id of the PDB referenced by a PE binary.
+fn read_pdb_reference(path: &Path) -> Option<(String, DebugId)> {
+    let data = ByteView::open(path).ok()?;
+    let Ok(Object::Pe(pe)) = Object::parse(&data) else {
+        return None;
+    };
+    let id = pe.debug_id();
+    let debug_file = pe.debug_file_name()?;
+    // The PDB path is the one on the build machine, only its name is relevant.
+    let name = debug_file.rsplit(['\\', '/']).next()?;
+    (!id.is_nil() && !name.is_empty()).then(|| (name.to_owned(), id))
+}
+
+/// Returns the path of a PDB on a symbol server.
+fn symsrv_path(name: &str, id: DebugId) -> String {
+    format!("{name}/{}/{name}", id.breakpad())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn symbol_server(cache_dir: &Path) -> SymbolServer {
+        SymbolServer::new("https://symbols.example.com/", Some(cache_dir.to_owned())).unwrap()
+    }
+
+    #[test]
+    fn test_read_pdb_reference() {
+        let (name, id) = read_pdb_reference(Path::new(
+            "tests/integration/_fixtures/Sentry.Samples.Console.Basic-embedded-ppdb-with-sources.dll",
+        ))
+        .unwrap();
+        assert_eq!(name, "Sentry.Samples.Console.Basic.pdb");
+        assert_eq!(
+            id.to_string(),
+            "623535c7-c0ea-4dee-b99b-4669e99a7ecc-a878d1fa"
+        );
+
+        // Not a PE binary.
+        assert!(
+            read_pdb_reference(Path::new("tests/integration/_fixtures/elf-Linux-ARMv7-ls"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_fetch_pdb() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = symbol_server(dir.path());
+        let id: DebugId = "3249d99d-0c40-4931-8610-f4e4fb0b6936-1".parse().unwrap();
+
+        let path = server
+            .fetch_pdb_with("crash.pdb", id, |url, file| {
+                assert_eq!(
+                    url,
+                    "https://symbols.example.com/crash.pdb/3249D99D0C4049318610F4E4FB0B69361/crash.pdb"
+                );
+                file.write_all(b"pdb")?;
+                Ok(200)
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(path, dir.path().join(symsrv_path("crash.pdb", id)));
+        assert_eq!(fs::read(&path).unwrap(), b"pdb");
+
+        // The second fetch is served from the cache.
+        let cached = server
+            .fetch_pdb_with("crash.pdb", id, |_, _| panic!("PDB fetched again"))
+            .unwrap();
+        assert_eq!(cached, Some(path));
+    }
+
+    #[test]
+    fn test_fetch_missing_pdb() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = symbol_server(dir.path());
+        let id: DebugId = "3249d99d-0c40-4931-8610-f4e4fb0b6936-1".parse().unwrap();
+
+        let fetched = server
+            .fetch_pdb_with("crash.pdb", id, |_, file| {
+                file.write_all(b"Not Found")?;
+                Ok(404)
+            })
+            .unwrap();
+        assert_eq!(fetched, None);
+        // Error pages are not cached as PDBs.
+        assert!(!dir.path().join(symsrv_path("crash.pdb", id)).exists());
+
+        let fetched = server
+            .fetch_pdb_with("crash.pdb", id, |_, _| bail!("Connection refused"))
+            .unwrap();
+        assert_eq!(fetched, None);
+    }
+
+    #[test]
+    fn test_symsrv_path() {
+        let id: DebugId = "3249d99d-0c40-4931-8610-f4e4fb0b6936-1".parse().unwrap();
+        assert_eq!(
+            symsrv_path("crash.pdb", id),
+            "crash.pdb/3249D99D0C4049318610F4E4FB0B69361/crash.pdb"
+        );
+    }
+}
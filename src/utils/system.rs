@@ -1,14 +1,14 @@
 use std::borrow::Cow;
 use std::env;
-#[cfg(target_os = "macos")]
 use std::process;
 
-use anyhow::{Error, Result};
+use anyhow::Error;
 use console::style;
 use dotenv::Result as DotenvResult;
 use lazy_static::lazy_static;
 use regex::{Captures, Regex};
 
+use crate::api::is_bad_json_error;
 use crate::config::Config;
 
 /// Propagate an exit status outwarts.
@@ -24,33 +24,19 @@ pub fn propagate_exit_status(status: process::ExitStatus) {
     }
 }
 
-#[cfg(not(windows))]
-fn is_homebrew_install_result() -> Result<bool> {
-    let mut exe = env::current_exe()?.canonicalize()?;
-    exe.pop();
-    exe.set_file_name("INSTALL_RECEIPT.json");
-    Ok(exe.is_file())
-}
-
-#[cfg(windows)]
-fn is_homebrew_install_result() -> Result<bool> {
-    Ok(false)
-}
-
-fn is_npm_install_result() -> Result<bool> {
-    let mut exe = env::current_exe()?.canonicalize()?;
-    exe.set_file_name("package.json");
-    Ok(exe.is_file())
-}
-
-/// Checks if we were installed from homebrew
-pub fn is_homebrew_install() -> bool {
-    is_homebrew_install_result().unwrap_or(false)
-}
-
-/// Checks if we were installed via npm
-pub fn is_npm_install() -> bool {
-    is_npm_install_result().unwrap_or(false)
+/// Returns a command that runs the given command line in the system shell.
+pub fn shell_command(command: &str) -> process::Command {
+    let mut shell = if cfg!(windows) {
+        let mut shell = process::Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = process::Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+    shell
 }
 
 /// Expands variables in a string
@@ -79,6 +65,16 @@ pub fn print_error(err: &Error) {
     // Debug style for error includes cause chain and backtrace (if available).
     eprintln!("{} {:?}", style("error:").red(), err);
 
+    if is_bad_json_error(err)
+        && Config::current_opt().map_or(true, |config| config.get_dump_response().is_none())
+    {
+        eprintln!();
+        eprintln!(
+            "{}",
+            style("Add --dump-response <PATH> to save the response for a bug report.").dim()
+        );
+    }
+
     if Config::current_opt().map_or(true, |config| {
         config.get_log_level() < log::LevelFilter::Info
     }) {
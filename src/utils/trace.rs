@@ -0,0 +1,140 @@
+//! Propagation of distributed traces into the events, check-ins and logs sent
+//! by sentry-cli.
+//!
+//! The trace is taken from `--trace-id`, or from a `sentry-trace` header in
+//! the `SENTRY_TRACE` environment variable set by the calling system.  If
+//! neither is given, or the header is malformed, a new trace is started.
+use std::env;
+
+use anyhow::{format_err, Result};
+use clap::ArgMatches;
+use log::warn;
+use sentry::protocol::{SpanId, TraceContext, TraceId};
+
+use crate::utils::value_parsers::trace_id_parser;
+
+/// The environment variable holding a `sentry-trace` header.
+pub const SENTRY_TRACE_ENV: &str = "SENTRY_TRACE";
+
+/// The distributed trace that sent data joins.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trace {
+    pub trace_id: TraceId,
+    /// The span of the calling system, if it is known.
+    pub parent_span_id: Option<SpanId>,
+    pub sampled: Option<bool>,
+}
+
+impl Trace {
+    /// Returns the trace given with `--trace-id` or `SENTRY_TRACE`, or starts
+    /// a new trace.
+    ///
+    /// A malformed `SENTRY_TRACE` header only causes a warning, since the
+    /// variable is set by the calling system and should not fail the command.
+    pub fn from_matches(matches: &ArgMatches) -> Trace {
+        if let Some(trace_id) = matches.get_one::<TraceId>("trace_id") {
+            return Trace {
+                trace_id: *trace_id,
+                parent_span_id: None,
+                sampled: None,
+            };
+        }
+
+        match env::var(SENTRY_TRACE_ENV) {
+            Ok(header) if !header.trim().is_empty() => {
+                parse_sentry_trace(&header).unwrap_or_else(|err| {
+                    warn!("{err}, starting a new trace");
+                    Trace::new()
+                })
+            }
+            _ => Trace::new(),
+        }
+    }
+
+    /// Starts a new trace.
+    fn new() -> Trace {
+        Trace {
+            trace_id: TraceId::default(),
+            parent_span_id: None,
+            sampled: None,
+        }
+    }
+
+    /// Returns the trace context of a new span in this trace.
+    pub fn new_context(&self) -> TraceContext {
+        TraceContext {
+            trace_id: self.trace_id,
+            span_id: SpanId::default(),
+            parent_span_id: self.parent_span_id,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the `sentry-trace` header continuing this trace from the
+    /// given span.
+    pub fn header(&self, span_id: SpanId) -> String {
+        match self.sampled {
+            Some(sampled) => format!("{}-{span_id}-{}", self.trace_id, u8::from(sampled)),
+            None => format!("{}-{span_id}", self.trace_id),
+        }
+    }
+}
+
+/// Parses a `sentry-trace` header of the form `TRACE_ID-SPAN_ID[-SAMPLED]`.
+pub fn parse_sentry_trace(header: &str) -> Result<Trace> {
+    let invalid = || format_err!("Invalid {SENTRY_TRACE_ENV} header: {header}");
+    let mut parts = header.trim().split('-');
+    let trace_id = trace_id_parser(parts.next().unwrap_or_default()).map_err(|_| invalid())?;
+    let parent_span_id = parts
+        .next()
+        .filter(|span_id| span_id.len() == 16)
+        .and_then(|span_id| span_id.parse().ok())
+        .ok_or_else(invalid)?;
+    let sampled = match parts.next() {
+        None => None,
+        Some("1") => Some(true),
+        Some("0") => Some(false),
+        Some(_) => return Err(invalid()),
+    };
+    if parts.next().is_some() {
+        return Err(invalid());
+    }
+
+    Ok(Trace {
+        trace_id,
+        parent_span_id: Some(parent_span_id),
+        sampled,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sentry_trace() {
+        let trace =
+            parse_sentry_trace("771a43a4192642f0b136d5159a501700-cb7c4c1d4d7f4ac0-1").unwrap();
+        assert_eq!(
+            trace.trace_id.to_string(),
+            "771a43a4192642f0b136d5159a501700"
+        );
+        assert_eq!(
+            trace.parent_span_id.unwrap().to_string(),
+            "cb7c4c1d4d7f4ac0"
+        );
+        assert_eq!(trace.sampled, Some(true));
+        assert_eq!(
+            trace.header("0123456789abcdef".parse().unwrap()),
+            "771a43a4192642f0b136d5159a501700-0123456789abcdef-1"
+        );
+
+        let trace =
+            parse_sentry_trace("771a43a4192642f0b136d5159a501700-cb7c4c1d4d7f4ac0").unwrap();
+        assert_eq!(trace.sampled, None);
+
+        assert!(parse_sentry_trace("771a43a4192642f0b136d5159a501700").is_err());
+        assert!(parse_sentry_trace("771a43a4-cb7c4c1d4d7f4ac0").is_err());
+        assert!(parse_sentry_trace("771a43a4192642f0b136d5159a501700-cb7c4c1d4d7f4ac0-2").is_err());
+    }
+}
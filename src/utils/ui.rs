@@ -1,6 +1,11 @@
 use std::io;
-use std::io::{Read, Write};
+use std::io::{IsTerminal, Read, Write};
 
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+use console::user_attended;
+
+use crate::config::Config;
 use crate::utils::progress::{ProgressBar, ProgressStyle};
 
 /// Prints a message and loops until yes or no is entered.
@@ -36,6 +41,56 @@ pub fn prompt(message: &str) -> io::Result<String> {
     }
 }
 
+/// Returns whether the user can be asked for input.
+fn is_interactive() -> bool {
+    user_attended() && io::stdin().is_terminal()
+}
+
+/// Asks the user to confirm a destructive action, such as `delete release 1.0`.
+///
+/// The action is confirmed without asking if `--yes` was passed, or if the
+/// CLI is in read-only mode and nothing is going to be changed.  When not
+/// running interactively, `--yes` is required.
+pub fn confirm_destructive(matches: &ArgMatches, action: &str) -> Result<bool> {
+    if matches.get_flag("yes") || Config::current().is_read_only() {
+        return Ok(true);
+    }
+    if !is_interactive() {
+        bail!(
+            "Refusing to {action} without confirmation. Pass --yes when not running interactively."
+        );
+    }
+    let question = format!("Do you really want to {action}?");
+    Ok(prompt_to_continue(&question)?)
+}
+
+/// Like [`confirm_destructive`], but asks the user to type `expected`, e.g.
+/// the version of a release, instead of `y`.
+///
+/// `--confirm-release` can be passed instead of typing it.
+pub fn confirm_destructive_typed(
+    matches: &ArgMatches,
+    action: &str,
+    expected: &str,
+) -> Result<bool> {
+    if let Some(confirmation) = matches.get_one::<String>("confirm_release") {
+        if confirmation != expected {
+            bail!("--confirm-release {confirmation} does not match {expected}.");
+        }
+        return Ok(true);
+    }
+    if matches.get_flag("yes") || Config::current().is_read_only() {
+        return Ok(true);
+    }
+    if !is_interactive() {
+        bail!(
+            "Refusing to {action} without confirmation. Pass --yes or \
+            --confirm-release {expected} when not running interactively."
+        );
+    }
+    Ok(prompt(&format!("Type {expected} to {action}"))? == expected)
+}
+
 /// Capitalizes a string and returns it.
 pub fn capitalize_string(s: &str) -> String {
     let mut bytes = s.as_bytes().to_vec();
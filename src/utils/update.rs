@@ -5,10 +5,9 @@ use std::io;
 use std::io::Write;
 #[cfg(not(feature = "managed"))]
 use std::path::Path;
+use std::str::FromStr;
 
-#[cfg(not(feature = "managed"))]
-use anyhow::bail;
-use anyhow::{format_err, Result};
+use anyhow::{bail, format_err, Error, Result};
 use chrono::{DateTime, Duration, Utc};
 use console::{style, user_attended};
 use if_chain::if_chain;
@@ -18,12 +17,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::api::{Api, SentryCliRelease};
 use crate::config::Config;
-use crate::constants::{APP_NAME, VERSION};
+use crate::constants::{APP_NAME, ARCH, LIBC, PLATFORM, VERSION};
 #[cfg(not(feature = "managed"))]
 use crate::utils::fs::{is_writable, set_executable_mode};
-#[cfg(not(feature = "managed"))]
-use crate::utils::system::QuietExit;
-use crate::utils::system::{is_homebrew_install, is_npm_install};
+use crate::utils::installer::Installer;
+use crate::utils::lockfile::AtomicFile;
+use crate::utils::ui::capitalize_string;
 
 #[cfg(windows)]
 fn rename_exe(exe: &Path, downloaded_path: &Path, elevate: bool) -> Result<()> {
@@ -115,6 +114,90 @@ impl LastUpdateCheck {
     }
 }
 
+/// The platform, architecture and C library that select the release file of
+/// sentry-cli to download, e.g. `linux-x86_64-musl` or `windows-aarch64`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleaseTarget {
+    platform: String,
+    arch: String,
+    libc: Option<String>,
+}
+
+impl ReleaseTarget {
+    /// Returns the target this executable was built for.
+    pub fn current() -> Self {
+        ReleaseTarget {
+            platform: PLATFORM.to_owned(),
+            arch: ARCH.to_owned(),
+            libc: (!LIBC.is_empty()).then(|| LIBC.to_owned()),
+        }
+    }
+
+    /// Returns the names the architecture is known by, its own name first.
+    fn arch_names(&self) -> Vec<&str> {
+        let aliases: &[&str] = match self.arch.as_str() {
+            "aarch64" | "arm64" => &["aarch64", "arm64"],
+            "x86_64" | "amd64" => &["x86_64", "amd64"],
+            "i686" | "i586" | "x86" => &["i686", "x86"],
+            "armv7" | "armv7l" => &["armv7", "armv7l"],
+            _ => &[],
+        };
+        let mut names = vec![self.arch.as_str()];
+        names.extend(aliases.iter().filter(|&&alias| alias != self.arch));
+        names
+    }
+
+    /// Returns the possible names of the release file, best match first.
+    ///
+    /// Files built for the C library of the target are preferred over
+    /// generic ones, and the universal binary is preferred on macOS.
+    pub fn file_names(&self) -> Vec<String> {
+        let platform = capitalize_string(&self.platform);
+        let ext = if self.platform == "windows" {
+            ".exe"
+        } else {
+            ""
+        };
+        let mut arch_names = self.arch_names();
+        if self.platform == "darwin" && self.arch != "universal" {
+            arch_names.insert(0, "universal");
+        }
+
+        let mut file_names = vec![];
+        for arch in arch_names {
+            if let Some(ref libc) = self.libc {
+                file_names.push(format!("sentry-cli-{platform}-{arch}-{libc}{ext}"));
+            }
+            file_names.push(format!("sentry-cli-{platform}-{arch}{ext}"));
+        }
+        file_names
+    }
+}
+
+impl FromStr for ReleaseTarget {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.splitn(3, '-');
+        let platform = match parts.next().unwrap().to_lowercase().as_str() {
+            "macos" | "mac" => "darwin".to_owned(),
+            platform => platform.to_owned(),
+        };
+        let arch = parts
+            .next()
+            .filter(|arch| !arch.is_empty())
+            .ok_or_else(|| format_err!("Expected <platform>-<arch>[-<libc>], got {s}"))?;
+        if platform.is_empty() {
+            bail!("Expected <platform>-<arch>[-<libc>], got {s}");
+        }
+        Ok(ReleaseTarget {
+            platform,
+            arch: arch.to_lowercase(),
+            libc: parts.next().map(str::to_lowercase),
+        })
+    }
+}
+
 pub struct SentryCliUpdateInfo {
     latest_release: Option<SentryCliRelease>,
 }
@@ -176,36 +259,18 @@ impl SentryCliUpdateInfo {
     }
 }
 
-pub fn get_latest_sentrycli_release() -> Result<SentryCliUpdateInfo> {
+pub fn get_latest_sentrycli_release(target: &ReleaseTarget) -> Result<SentryCliUpdateInfo> {
     let api = Api::current();
     Ok(SentryCliUpdateInfo {
-        latest_release: api.get_latest_sentrycli_release().unwrap_or_default(),
+        latest_release: api
+            .get_latest_sentrycli_release(&target.file_names())
+            .unwrap_or_default(),
     })
 }
 
 #[cfg(not(feature = "managed"))]
 pub fn can_update_sentrycli() -> bool {
-    !is_homebrew_install() && !is_npm_install()
-}
-
-#[cfg(not(feature = "managed"))]
-pub fn assert_updatable() -> Result<()> {
-    if is_homebrew_install() {
-        println!("This installation of sentry-cli is managed through homebrew");
-        println!("Please use homebrew to update sentry-cli:");
-        println!();
-        println!("{} brew upgrade sentry-cli", style("$").dim());
-        return Err(QuietExit(1).into());
-    } else if is_npm_install() {
-        println!("This installation of sentry-cli is managed through npm/yarn");
-        println!("Please use npm/yarn to update sentry-cli, using one of the following commands:");
-        println!("  yarn upgrade @sentry/cli");
-        println!("  yarn global upgrade @sentry/cli");
-        println!("  npm update @sentry/cli");
-        println!("  npm update -g @sentry/cli");
-        return Err(QuietExit(1).into());
-    }
-    Ok(())
+    Installer::detect().is_none()
 }
 
 fn update_nagger_impl() -> Result<()> {
@@ -223,12 +288,13 @@ fn update_nagger_impl() -> Result<()> {
 
     if check.should_run_check() {
         info!("Running update nagger update check");
-        let ui = get_latest_sentrycli_release()?;
+        let ui = get_latest_sentrycli_release(&ReleaseTarget::current())?;
         if ui.have_version_info() {
             check.update_for_info(&ui);
-            let mut f = fs::File::create(&path)?;
+            let mut f = AtomicFile::create(&path)?;
             serde_json::to_writer_pretty(&mut f, &check)?;
             f.write_all(b"\n")?;
+            f.commit()?;
         }
     } else {
         info!("Skipping update nagger update check");
@@ -245,15 +311,16 @@ fn update_nagger_impl() -> Result<()> {
             ))
             .yellow()
         );
-        if is_homebrew_install() {
-            eprintln!("{}", style("run brew upgrade sentry-cli to update").dim());
-        } else if is_npm_install() {
-            eprintln!(
+        match Installer::detect() {
+            Some(Installer::Npm) => eprintln!(
                 "{}",
                 style("Please use npm/yarn to update sentry-cli").dim()
-            )
-        } else {
-            eprintln!("{}", style("run sentry-cli update to update").dim());
+            ),
+            Some(installer) => eprintln!(
+                "{}",
+                style(format!("run {} to update", installer.update_commands()[0])).dim()
+            ),
+            None => eprintln!("{}", style("run sentry-cli update to update").dim()),
         }
     }
 
@@ -279,7 +346,7 @@ pub fn run_sentrycli_update_nagger() {
 
     // npm installs do not get an update check.  We might want to relax this later
     // to support update checks for global npm installs but not dependency installs.
-    if is_npm_install() {
+    if Installer::detect() == Some(Installer::Npm) {
         return;
     }
 
@@ -291,3 +358,111 @@ pub fn run_sentrycli_update_nagger() {
 
     update_nagger_impl().ok();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_target_file_names() {
+        let target: ReleaseTarget = "windows-arm64".parse().unwrap();
+        assert_eq!(
+            target.file_names(),
+            [
+                "sentry-cli-Windows-arm64.exe",
+                "sentry-cli-Windows-aarch64.exe"
+            ]
+        );
+
+        let target: ReleaseTarget = "linux-x86_64-musl".parse().unwrap();
+        assert_eq!(
+            target.file_names(),
+            [
+                "sentry-cli-Linux-x86_64-musl",
+                "sentry-cli-Linux-x86_64",
+                "sentry-cli-Linux-amd64-musl",
+                "sentry-cli-Linux-amd64"
+            ]
+        );
+
+        let target: ReleaseTarget = "macos-arm64".parse().unwrap();
+        assert_eq!(
+            target.file_names(),
+            [
+                "sentry-cli-Darwin-universal",
+                "sentry-cli-Darwin-arm64",
+                "sentry-cli-Darwin-aarch64"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_release_target_from_str() {
+        let target: ReleaseTarget = "Linux-AARCH64-GNU".parse().unwrap();
+        assert_eq!(
+            target,
+            ReleaseTarget {
+                platform: "linux".into(),
+                arch: "aarch64".into(),
+                libc: Some("gnu".into()),
+            }
+        );
+
+        let target: ReleaseTarget = "mac-x86_64".parse().unwrap();
+        assert_eq!(
+            target,
+            ReleaseTarget {
+                platform: "darwin".into(),
+                arch: "x86_64".into(),
+                libc: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_release_target_file_names_libc() {
+        let target: ReleaseTarget = "linux-aarch64-gnu".parse().unwrap();
+        assert_eq!(
+            target.file_names(),
+            [
+                "sentry-cli-Linux-aarch64-gnu",
+                "sentry-cli-Linux-aarch64",
+                "sentry-cli-Linux-arm64-gnu",
+                "sentry-cli-Linux-arm64"
+            ]
+        );
+
+        let target: ReleaseTarget = "linux-i586".parse().unwrap();
+        assert_eq!(
+            target.file_names(),
+            [
+                "sentry-cli-Linux-i586",
+                "sentry-cli-Linux-i686",
+                "sentry-cli-Linux-x86"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_release_target_file_names_darwin() {
+        let target: ReleaseTarget = "darwin-x86_64".parse().unwrap();
+        assert_eq!(
+            target.file_names(),
+            [
+                "sentry-cli-Darwin-universal",
+                "sentry-cli-Darwin-x86_64",
+                "sentry-cli-Darwin-amd64"
+            ]
+        );
+
+        let target: ReleaseTarget = "darwin-universal".parse().unwrap();
+        assert_eq!(target.file_names(), ["sentry-cli-Darwin-universal"]);
+    }
+
+    #[test]
+    fn test_release_target_invalid() {
+        assert!("linux".parse::<ReleaseTarget>().is_err());
+        assert!("-x86_64".parse::<ReleaseTarget>().is_err());
+        assert!("linux-".parse::<ReleaseTarget>().is_err());
+    }
+}
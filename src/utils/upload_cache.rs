@@ -0,0 +1,260 @@
+//! A cache of debug information files known to exist on the server.
+//!
+//! Files are recorded per server, organization and project once the server
+//! has processed them, so that repeated uploads of unchanged files neither
+//! need to hash them nor ask the server which files are missing.  Like in the
+//! [scan journal](crate::utils::scan_journal), files are identified by their
+//! canonical path, size and modification time.  Entries expire after
+//! [`UPLOAD_CACHE_TTL`], since files can be deleted on the server.
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{format_err, Result};
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{is_stateless, Config};
+use crate::constants::APP_NAME;
+use crate::utils::lockfile::{AtomicFile, FileLock};
+use crate::utils::scan_journal::JournalKey;
+
+const CACHE_FILE_NAME: &str = "uploaded-difs";
+
+/// How long an uploaded file is assumed to exist on the server.
+const UPLOAD_CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    size: u64,
+    mtime: u128,
+    /// Seconds since the UNIX epoch at which the file was recorded.
+    uploaded: u64,
+}
+
+type Entries = BTreeMap<String, BTreeMap<String, CacheEntry>>;
+
+/// Files uploaded to the server, persisted in the cache folder.
+#[derive(Debug, Default)]
+pub struct UploadCache {
+    key: String,
+    entries: Entries,
+}
+
+impl UploadCache {
+    /// Loads the files uploaded to the given organization and project of the
+    /// configured server.
+    ///
+    /// A missing or unreadable cache results in an empty cache.
+    pub fn load(org: &str, project: &str) -> Self {
+        let key = format!(
+            "{}#{org}/{project}",
+            Config::current().get_base_url().unwrap_or_default()
+        );
+
+        // Do not share the cache between integration tests, which all use the
+        // same organization and project.
+        if env::var("SENTRY_INTEGRATION_TEST").is_ok() || is_stateless() {
+            return UploadCache {
+                key,
+                entries: Default::default(),
+            };
+        }
+
+        match cache_path().and_then(|path| read_entries(&path)) {
+            Ok(entries) => UploadCache { key, entries },
+            Err(err) => {
+                debug!("Not using upload cache: {err}");
+                UploadCache {
+                    key,
+                    entries: Default::default(),
+                }
+            }
+        }
+    }
+
+    /// Returns whether the file was uploaded before and has not changed since.
+    pub fn contains(&self, file: &JournalKey) -> bool {
+        let Some(entry) = self
+            .entries
+            .get(&self.key)
+            .and_then(|files| files.get(file.id()))
+        else {
+            return false;
+        };
+
+        if entry.size != file.size() || entry.mtime != file.mtime() || is_expired(entry) {
+            return false;
+        }
+
+        info!(
+            "Skipping previously uploaded file {}",
+            file.path().display()
+        );
+        true
+    }
+
+    /// Records the given files as uploaded.
+    pub fn insert<'a, I>(&mut self, files: I)
+    where
+        I: IntoIterator<Item = &'a JournalKey>,
+    {
+        let uploaded = now();
+        let entries = self.entries.entry(self.key.clone()).or_default();
+        for file in files {
+            entries.insert(
+                file.id().to_owned(),
+                CacheEntry {
+                    size: file.size(),
+                    mtime: file.mtime(),
+                    uploaded,
+                },
+            );
+        }
+    }
+
+    /// Writes the cache to the cache folder.
+    ///
+    /// Files that other processes saved in the meantime are kept, unless this
+    /// cache has newer entries for the same files.  Expired entries are
+    /// dropped.
+    pub fn save(&self) -> Result<()> {
+        if env::var("SENTRY_INTEGRATION_TEST").is_ok() || is_stateless() {
+            return Ok(());
+        }
+
+        self.save_to(&cache_path()?)
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let _lock = FileLock::acquire(path)?;
+        let mut entries = read_entries(path).unwrap_or_default();
+        for (key, files) in &self.entries {
+            entries
+                .entry(key.clone())
+                .or_default()
+                .extend(files.iter().map(|(id, entry)| (id.clone(), entry.clone())));
+        }
+        for files in entries.values_mut() {
+            files.retain(|_, entry| !is_expired(entry));
+        }
+        entries.retain(|_, files| !files.is_empty());
+
+        let mut file = io::BufWriter::new(AtomicFile::create(path)?);
+        serde_json::to_writer(&mut file, &entries)?;
+        file.write_all(b"\n")?;
+        file.into_inner()?.commit()
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn is_expired(entry: &CacheEntry) -> bool {
+    now().saturating_sub(entry.uploaded) >= UPLOAD_CACHE_TTL.as_secs()
+}
+
+fn read_entries(path: &Path) -> Result<Entries> {
+    let file = fs::File::open(path)?;
+    Ok(serde_json::from_reader(io::BufReader::new(file))?)
+}
+
+fn cache_path() -> Result<PathBuf> {
+    let mut path = dirs::cache_dir().ok_or_else(|| format_err!("Could not get cache folder"))?;
+    path.push(APP_NAME);
+    path.push(CACHE_FILE_NAME);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::utils::fs::TempDir;
+
+    fn empty_cache() -> UploadCache {
+        UploadCache {
+            key: "https://sentry.io/#org/project".into(),
+            entries: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_contains_unchanged_files() {
+        let dir = TempDir::create().unwrap();
+        let path = dir.path().join("app.debug");
+        fs::write(&path, b"debug").unwrap();
+
+        let mut cache = empty_cache();
+        let file = JournalKey::new(&path, "app").unwrap();
+        assert!(!cache.contains(&file));
+
+        cache.insert([&file]);
+        assert!(cache.contains(&file));
+        assert!(!cache.contains(&JournalKey::new(&path, "other").unwrap()));
+
+        fs::write(&path, b"changed debug").unwrap();
+        assert!(!cache.contains(&JournalKey::new(&path, "app").unwrap()));
+    }
+
+    #[test]
+    fn test_expired_entries() {
+        let dir = TempDir::create().unwrap();
+        let path = dir.path().join("app.debug");
+        fs::write(&path, b"debug").unwrap();
+
+        let mut cache = empty_cache();
+        let file = JournalKey::new(&path, "app").unwrap();
+        cache.insert([&file]);
+        for entry in cache
+            .entries
+            .values_mut()
+            .flat_map(|files| files.values_mut())
+        {
+            entry.uploaded -= UPLOAD_CACHE_TTL.as_secs();
+        }
+        assert!(!cache.contains(&file));
+
+        let cache_path = dir.path().join("cache");
+        cache.save_to(&cache_path).unwrap();
+        assert!(read_entries(&cache_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_save_keeps_other_entries() {
+        let dir = TempDir::create().unwrap();
+        let cache_path = dir.path().join("cache");
+        let first_path = dir.path().join("first.debug");
+        let second_path = dir.path().join("second.debug");
+        fs::write(&first_path, b"first").unwrap();
+        fs::write(&second_path, b"second").unwrap();
+        let first = JournalKey::new(&first_path, "first").unwrap();
+        let second = JournalKey::new(&second_path, "second").unwrap();
+
+        let mut cache = empty_cache();
+        cache.insert([&first]);
+        cache.save_to(&cache_path).unwrap();
+
+        let mut cache = empty_cache();
+        cache.insert([&second]);
+        cache.save_to(&cache_path).unwrap();
+
+        let cache = UploadCache {
+            entries: read_entries(&cache_path).unwrap(),
+            ..empty_cache()
+        };
+        assert!(cache.contains(&first));
+        assert!(cache.contains(&second));
+    }
+}
@@ -1,5 +1,9 @@
 use crate::utils::auth_token::AuthToken;
+use crate::utils::http::is_absolute_url;
 use anyhow::{anyhow, Result};
+use chrono::Duration;
+use chrono_tz::Tz;
+use sentry::protocol::{MonitorSchedule, TraceId};
 use std::convert::Infallible;
 
 /// Parse key:value pair from string, used as a value_parser for Clap arguments
@@ -18,3 +22,83 @@ pub fn auth_token_parser(s: &str) -> Result<AuthToken, Infallible> {
 
     Ok(token)
 }
+
+/// Parse an age such as `90d`, used as a value_parser for Clap arguments.
+///
+/// Supported units are `s`, `m`, `h`, `d` and `w`.
+pub fn age_parser(s: &str) -> Result<Duration> {
+    let unit_start = s
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| anyhow!("`{s}` is missing a unit (s, m, h, d or w)"))?;
+    let (amount, unit) = s.split_at(unit_start);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| anyhow!("`{s}` does not start with a number"))?;
+
+    let age = match unit {
+        "s" => Duration::try_seconds(amount),
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => return Err(anyhow!("`{unit}` is not a valid unit (s, m, h, d or w)")),
+    };
+
+    age.ok_or_else(|| anyhow!("`{s}` is out of range"))
+}
+
+/// Parse a percentage such as `0.5%`, used as a value_parser for Clap arguments.
+///
+/// The percent sign is optional.
+pub fn percentage_parser(s: &str) -> Result<f64> {
+    let percentage: f64 = s
+        .strip_suffix('%')
+        .unwrap_or(s)
+        .parse()
+        .map_err(|_| anyhow!("`{s}` is not a percentage"))?;
+    if !(0.0..=100.0).contains(&percentage) {
+        return Err(anyhow!("`{s}` is not between 0% and 100%"));
+    }
+    Ok(percentage)
+}
+
+/// Parse a tz database timezone such as `Europe/Vienna`, used as a value_parser for Clap
+/// arguments
+pub fn timezone_parser(s: &str) -> Result<Tz, String> {
+    s.parse::<Tz>().map_err(|err| {
+        err + "\n\tSee here for a list of valid timezone strings: \
+            https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List"
+    })
+}
+
+/// Parse a schedule in crontab format, used as a value_parser for Clap arguments
+pub fn crontab_parser(s: &str) -> Result<String> {
+    MonitorSchedule::from_crontab(s)?;
+    Ok(s.to_owned())
+}
+
+/// Parse an http:// or https:// URL, used as a value_parser for Clap arguments
+pub fn absolute_url_parser(s: &str) -> Result<String> {
+    if !is_absolute_url(s) {
+        return Err(anyhow!("`{s}` is not an http:// or https:// URL"));
+    }
+    Ok(s.to_owned())
+}
+
+/// Parse a trace ID of 32 hex digits, used as a value_parser for Clap arguments
+pub fn trace_id_parser(s: &str) -> Result<TraceId> {
+    s.parse()
+        .ok()
+        .filter(|_| s.len() == 32)
+        .ok_or_else(|| anyhow!("`{s}` is not a trace ID of 32 hex digits"))
+}
+
+/// Parse a non-empty search query, used as a value_parser for Clap arguments.
+///
+/// An empty query matches everything, which is never intended for a filter.
+pub fn search_query_parser(s: &str) -> Result<String> {
+    if s.trim().is_empty() {
+        return Err(anyhow!("the search query must not be empty"));
+    }
+    Ok(s.to_owned())
+}
@@ -0,0 +1,185 @@
+//! Commit detection for Mercurial checkouts.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Result};
+use chrono::DateTime;
+
+use super::{run_command, VcsBackend};
+use crate::api::{GitCommit, PatchSet};
+
+/// Separates the description of a commit from the other fields in the output
+/// of `LOG_TEMPLATE`.
+const FIELD_SEPARATOR: char = '\x1f';
+
+/// Terminates a commit in the output of `LOG_TEMPLATE`.
+const RECORD_SEPARATOR: char = '\x1e';
+
+/// Prints the ID, author and date of a commit on separate lines, followed by
+/// the changed files in `name-status` format and finally the description.
+const LOG_TEMPLATE: &str = "{node}\n{author|person}\n{author|email}\n{date|rfc3339date}\n\
+    {file_adds % \"A\t{file}\n\"}{file_mods % \"M\t{file}\n\"}{file_dels % \"D\t{file}\n\"}\
+    \x1f{desc}\x1e";
+
+pub struct Mercurial {
+    root: PathBuf,
+}
+
+impl Mercurial {
+    pub fn new(root: &Path) -> Mercurial {
+        Mercurial {
+            root: root.to_path_buf(),
+        }
+    }
+
+    fn hg(&self) -> Command {
+        let mut command = Command::new("hg");
+        // Disable user configuration that could change the output format.
+        command.current_dir(&self.root).env("HGPLAIN", "1");
+        command
+    }
+}
+
+impl VcsBackend for Mercurial {
+    fn head(&self) -> Result<String> {
+        self.resolve(".")
+    }
+
+    fn resolve(&self, rev: &str) -> Result<String> {
+        let rev = if rev == "HEAD" { "." } else { rev };
+        let node = run_command(
+            self.hg()
+                .args(["log", "--limit", "1", "--template", "{node}"])
+                .args(["--rev", rev]),
+        )?;
+        if node.is_empty() {
+            bail!("Could not find revision {rev}");
+        }
+        Ok(node)
+    }
+
+    fn remote_url(&self, remote_name: &str) -> Result<Option<String>> {
+        for name in [remote_name, "default"] {
+            if let Ok(url) = run_command(self.hg().args(["paths", name])) {
+                let url = url.trim();
+                if !url.is_empty() {
+                    return Ok(Some(url.to_owned()));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn commits(
+        &self,
+        since: Option<&str>,
+        limit: Option<usize>,
+        repository: &str,
+    ) -> Result<Option<Vec<GitCommit>>> {
+        let revset = match since {
+            Some(since) => {
+                // Only full node IDs can be safely embedded in a revset.
+                if since.len() != 40 || !since.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Ok(None);
+                }
+                let ancestor = run_command(self.hg().args([
+                    "log",
+                    "--template",
+                    "{node}",
+                    "--rev",
+                    &format!("ancestors(.) and id({since})"),
+                ]))?;
+                if ancestor.is_empty() {
+                    return Ok(None);
+                }
+                format!("reverse(only(., {since}))")
+            }
+            None => "reverse(::.)".to_owned(),
+        };
+
+        let mut command = self.hg();
+        command.args(["log", "--template", LOG_TEMPLATE, "--rev", &revset]);
+        if let Some(limit) = limit {
+            command.args(["--limit", &limit.to_string()]);
+        }
+        parse_log(&run_command(&mut command)?, repository).map(Some)
+    }
+}
+
+/// Parses the output of `hg log` with `LOG_TEMPLATE`.
+fn parse_log(output: &str, repository: &str) -> Result<Vec<GitCommit>> {
+    output
+        .split_terminator(RECORD_SEPARATOR)
+        .map(|record| {
+            let Some((header, message)) = record.split_once(FIELD_SEPARATOR) else {
+                bail!("Unexpected output from hg log: {record}");
+            };
+            let mut lines = header.lines();
+            let (Some(id), Some(name), Some(email), Some(date)) =
+                (lines.next(), lines.next(), lines.next(), lines.next())
+            else {
+                bail!("Unexpected output from hg log: {record}");
+            };
+
+            Ok(GitCommit {
+                id: id.to_owned(),
+                author_name: Some(name.to_owned()).filter(|name| !name.is_empty()),
+                author_email: Some(email.to_owned()).filter(|email| !email.is_empty()),
+                message: Some(message.to_owned()),
+                repository: repository.to_owned(),
+                timestamp: DateTime::parse_from_rfc3339(date)?,
+                patch_set: lines
+                    .filter_map(|line| line.split_once('\t'))
+                    .map(|(ty, path)| PatchSet {
+                        ty: ty.to_owned(),
+                        path: path.to_owned(),
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log() {
+        let output = "\
+            2b1c3e2a1f0d5c4b3a29181716151413121110ff\nJane Doe\njane@example.com\n\
+            2024-03-02T10:00:00+01:00\nA\tsrc/new.rs\nM\tsrc/main.rs\n\x1fSecond commit\n\n\
+            Details\x1e\
+            0a1b2c3d4e5f60718293a4b5c6d7e8f901234567\n\n\n2024-03-01T09:30:00+00:00\n\
+            D\told.txt\n\x1fInitial commit\x1e";
+
+        let commits = parse_log(output, "acme/app").unwrap();
+        assert_eq!(commits.len(), 2);
+
+        assert_eq!(commits[0].id, "2b1c3e2a1f0d5c4b3a29181716151413121110ff");
+        assert_eq!(commits[0].author_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(commits[0].author_email.as_deref(), Some("jane@example.com"));
+        assert_eq!(
+            commits[0].message.as_deref(),
+            Some("Second commit\n\nDetails")
+        );
+        assert_eq!(commits[0].repository, "acme/app");
+        assert_eq!(
+            commits[0].timestamp.to_rfc3339(),
+            "2024-03-02T10:00:00+01:00"
+        );
+        assert_eq!(
+            commits[0]
+                .patch_set
+                .iter()
+                .map(|p| (p.ty.as_str(), p.path.as_str()))
+                .collect::<Vec<_>>(),
+            [("A", "src/new.rs"), ("M", "src/main.rs")]
+        );
+
+        assert_eq!(commits[1].author_name, None);
+        assert_eq!(commits[1].author_email, None);
+        assert_eq!(commits[1].patch_set.len(), 1);
+        assert_eq!(commits[1].patch_set[0].ty, "D");
+    }
+}
@@ -1,5 +1,8 @@
+use std::env;
 use std::fmt;
+use std::io;
 use std::path::PathBuf;
+use std::process;
 
 use anyhow::{bail, format_err, Error, Result};
 use chrono::{DateTime, FixedOffset, TimeZone};
@@ -11,6 +14,9 @@ use regex::Regex;
 
 use crate::api::{GitCommit, PatchSet, Ref, Repo};
 
+mod hg;
+mod svn;
+
 #[derive(Copy, Clone)]
 pub enum GitReference<'a> {
     Commit(git2::Oid),
@@ -40,6 +46,71 @@ impl fmt::Display for CommitSpec {
     }
 }
 
+/// A local checkout of a repository in a version control system other than
+/// git.  These are accessed through their command line clients.
+trait VcsBackend {
+    /// Returns the ID of the checked out revision.
+    fn head(&self) -> Result<String>;
+
+    /// Resolves a revision like `HEAD` or a tag to its ID.
+    fn resolve(&self, rev: &str) -> Result<String>;
+
+    /// Returns the URL of the remote with the given name, or of the default
+    /// remote if there is no such remote.
+    fn remote_url(&self, remote_name: &str) -> Result<Option<String>>;
+
+    /// Returns the commits leading up to the checked out revision including
+    /// their patch sets, newest first.
+    ///
+    /// If `since` is given, only commits after it are returned, or `None` if
+    /// it is not in the history of the checked out revision.  At most `limit`
+    /// commits are returned.
+    fn commits(
+        &self,
+        since: Option<&str>,
+        limit: Option<usize>,
+        repository: &str,
+    ) -> Result<Option<Vec<GitCommit>>>;
+}
+
+/// Opens the Mercurial or Subversion checkout containing the current
+/// directory, if there is one.
+fn find_backend() -> Result<Option<Box<dyn VcsBackend>>> {
+    let cwd = env::current_dir()?;
+    for dir in cwd.ancestors() {
+        if dir.join(".hg").is_dir() {
+            debug!("Found Mercurial checkout at {}", dir.display());
+            return Ok(Some(Box::new(hg::Mercurial::new(dir))));
+        }
+        if dir.join(".svn").is_dir() {
+            debug!("Found Subversion checkout at {}", dir.display());
+            return Ok(Some(Box::new(svn::Subversion::new(dir))));
+        }
+    }
+    Ok(None)
+}
+
+/// Runs a version control command and returns its standard output.
+fn run_command(command: &mut process::Command) -> Result<String> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    debug!("Running {:?}", command);
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            bail!("Could not run {program}. Make sure that it is installed and on your PATH.")
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    if !output.status.success() {
+        bail!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
 #[derive(Debug, PartialEq, Eq)]
 struct VcsUrl {
     pub provider: String,
@@ -107,9 +178,10 @@ fn strip_git_suffix(s: &str) -> &str {
 impl VcsUrl {
     pub fn parse(url: &str) -> VcsUrl {
         lazy_static! {
-            static ref GIT_URL_RE: Regex =
-                Regex::new(r"^(?:(?:git\+)?(?:git|ssh|https?))://(?:[^@]+@)?([^/]+)/(.+)$")
-                    .unwrap();
+            static ref GIT_URL_RE: Regex = Regex::new(
+                r"^(?:(?:git\+)?(?:git|ssh|https?)|svn(?:\+ssh)?)://(?:[^@]+@)?([^/]+)/(.+)$"
+            )
+            .unwrap();
             static ref GIT_SSH_RE: Regex = Regex::new(r"^(?:[^@]+@)?([^/]+):(.+)$").unwrap();
         }
 
@@ -271,7 +343,20 @@ fn find_matching_rev(
     let (repo, discovery) = if let Some(ref path) = spec.path {
         (git2::Repository::open(path)?, false)
     } else {
-        (git2::Repository::open_from_env()?, !disable_discovery)
+        match git2::Repository::open_from_env() {
+            Ok(repo) => (repo, !disable_discovery),
+            Err(err) => {
+                let backend = find_backend()?.ok_or(err)?;
+                return find_matching_backend_rev(
+                    backend.as_ref(),
+                    r,
+                    spec,
+                    repos,
+                    !disable_discovery,
+                    remote_name,
+                );
+            }
+        }
     };
 
     match find_reference_url(&spec.repo, repos)? {
@@ -308,6 +393,32 @@ fn find_matching_rev(
     }
 }
 
+/// Like `find_matching_rev`, but for a Mercurial or Subversion checkout.
+fn find_matching_backend_rev(
+    backend: &dyn VcsBackend,
+    r: &str,
+    spec: &CommitSpec,
+    repos: &[Repo],
+    discovery: bool,
+    remote_name: Option<String>,
+) -> Result<Option<String>> {
+    let Some(reference_url) = find_reference_url(&spec.repo, repos)? else {
+        return Ok(None);
+    };
+    debug!("  Looking for reference URL {}", &reference_url);
+
+    let remote_name = remote_name.unwrap_or_else(|| "origin".to_string());
+    if let Some(url) = backend.remote_url(&remote_name)? {
+        if !discovery || is_matching_url(&url, &reference_url) {
+            debug!("  found match: {} == {}, {:?}", url, &reference_url, r);
+            return Ok(Some(log_match!(backend.resolve(r)?)));
+        }
+        debug!("  not a match: {} != {}", url, &reference_url);
+    }
+    info!("  -> no matching revision found");
+    Ok(None)
+}
+
 fn find_matching_submodule(
     r: &str,
     reference_url: String,
@@ -348,6 +459,97 @@ fn find_matching_submodule(
     Ok(None)
 }
 
+/// Resolves a submodule URL relative to the URL of its superproject, like git
+/// does for URLs starting with `./` or `../`.  Other URLs are returned as is.
+fn resolve_submodule_url(parent_url: &str, url: &str) -> String {
+    if !url.starts_with("./") && !url.starts_with("../") {
+        return url.to_owned();
+    }
+
+    let mut rv = parent_url.trim_end_matches('/').to_owned();
+    // scp-like URLs separate the host and the path with a colon, which has to
+    // be kept when going up to the root of the path.
+    let mut separator = '/';
+    for component in url.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                if let Some(idx) = rv.rfind(['/', ':']) {
+                    if rv[idx..].starts_with(':') {
+                        separator = ':';
+                    }
+                    rv.truncate(idx);
+                }
+            }
+            component => {
+                rv.push(separator);
+                rv.push_str(component);
+                separator = '/';
+            }
+        }
+    }
+    rv
+}
+
+fn collect_submodule_heads(
+    repo: &Repository,
+    parent_url: Option<&str>,
+    repos: &[Repo],
+    rv: &mut Vec<Ref>,
+) -> Result<()> {
+    for submodule in repo.submodules()? {
+        let Some(url) = submodule.url() else {
+            continue;
+        };
+        let url = match parent_url {
+            Some(parent_url) => resolve_submodule_url(parent_url, url),
+            None => url.to_owned(),
+        };
+        debug!("  found submodule with URL {}", url);
+
+        let configured_repo = repos.iter().find(|configured_repo| {
+            configured_repo
+                .url
+                .as_deref()
+                .is_some_and(|reference_url| is_matching_url(&url, reference_url))
+        });
+        // prefer the commit recorded in the superproject over the one that is
+        // checked out, as that is what gets deployed.
+        let head = submodule.head_id().or_else(|| submodule.workdir_id());
+        if let (Some(configured_repo), Some(head)) = (configured_repo, head) {
+            if !rv.iter().any(|r| r.repo == configured_repo.name) {
+                info!("Resolving submodule {} ({})", url, configured_repo.name);
+                rv.push(Ref {
+                    repo: configured_repo.name.clone(),
+                    rev: log_match!(head.to_string()),
+                    prev_rev: None,
+                });
+            }
+        }
+
+        // nested submodules can only be found if the submodule is checked out.
+        if let Ok(subrepo) = submodule.open() {
+            collect_submodule_heads(&subrepo, Some(&url), repos, rv)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns head commits for all submodules of the local git repository,
+/// including nested ones, whose URL matches one of the given repos.
+pub fn find_submodule_heads(repos: &[Repo], remote_name: Option<String>) -> Result<Vec<Ref>> {
+    let repo = git2::Repository::open_from_env()?;
+    let remote_name = remote_name.unwrap_or_else(|| "origin".to_string());
+    let url = repo
+        .find_remote(&remote_name)
+        .ok()
+        .and_then(|remote| remote.url().map(str::to_owned));
+
+    let mut rv = vec![];
+    collect_submodule_heads(&repo, url.as_deref(), repos, &mut rv)?;
+    Ok(rv)
+}
+
 fn find_matching_revs(
     spec: &CommitSpec,
     repos: &[Repo],
@@ -391,9 +593,35 @@ fn find_matching_revs(
 }
 
 pub fn find_head() -> Result<String> {
+    match git2::Repository::open_from_env() {
+        Ok(repo) => {
+            let head = repo.revparse_single("HEAD")?;
+            Ok(head.id().to_string())
+        }
+        Err(err) => find_backend()?.ok_or(err)?.head(),
+    }
+}
+
+/// Returns the IDs and messages of the commits in a `FROM..TO` range of the
+/// local git repository, newest first.  `TO` defaults to `HEAD`.
+pub fn get_commit_range_messages(range: &str) -> Result<Vec<(String, String)>> {
+    let Some((from, to)) = range.split_once("..") else {
+        bail!("Invalid commit range {range}, expected FROM..TO");
+    };
+    let to = if to.is_empty() { "HEAD" } else { to };
+
     let repo = git2::Repository::open_from_env()?;
-    let head = repo.revparse_single("HEAD")?;
-    Ok(head.id().to_string())
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_range(&format!("{from}..{to}"))?;
+    revwalk
+        .map(|id| -> Result<_> {
+            let commit = repo.find_commit(id?)?;
+            Ok((
+                commit.id().to_string(),
+                commit.message().unwrap_or_default().to_owned(),
+            ))
+        })
+        .collect()
 }
 
 /// Given commit specs, repos and remote_name this returns a list of head
@@ -475,20 +703,8 @@ pub fn get_commits_from_git<'a>(
 
             // If there is a previous commit but cannot find it in git history
             if !found {
-                // Create a new release with default count if `--ignore-missing` is present
-                if ignore_missing {
-                    println!(
-                        "Could not find the SHA of the previous release in the git history. Skipping previous release and creating a new one with {default_count} commits."
-                    );
-                    return get_default_commits_from_git(repo, default_count);
-                // Or throw an error and point to the right solution otherwise.
-                } else {
-                    return Err(format_err!(
-                        "Could not find the SHA of the previous release in the git history. If you limit the clone depth, try to increase it. \
-                        Otherwise, it means that the commit we are looking for was amended or squashed and cannot be retrieved. \
-                        Use --ignore-missing flag to skip it and create a new release with the default commits count.",
-                    ));
-                }
+                handle_missing_prev_commit(default_count, ignore_missing)?;
+                return get_default_commits_from_git(repo, default_count);
             }
             let prev = result.pop();
             Ok((result, prev))
@@ -503,10 +719,30 @@ pub fn get_commits_from_git<'a>(
     }
 }
 
+/// Reports that the commit of the previous release is not in the local
+/// history.  This is an error unless `ignore_missing` is set, in which case the
+/// caller falls back to the default commits count.
+fn handle_missing_prev_commit(default_count: usize, ignore_missing: bool) -> Result<()> {
+    // Create a new release with default count if `--ignore-missing` is present
+    if ignore_missing {
+        println!(
+            "Could not find the SHA of the previous release in the git history. Skipping previous release and creating a new one with {default_count} commits."
+        );
+        Ok(())
+    // Or throw an error and point to the right solution otherwise.
+    } else {
+        Err(format_err!(
+            "Could not find the SHA of the previous release in the git history. If you limit the clone depth, try to increase it. \
+            Otherwise, it means that the commit we are looking for was amended or squashed and cannot be retrieved. \
+            Use --ignore-missing flag to skip it and create a new release with the default commits count.",
+        ))
+    }
+}
+
 pub fn get_default_commits_from_git(
     repo: &Repository,
     default_count: usize,
-) -> Result<(Vec<Commit>, Option<Commit>)> {
+) -> Result<(Vec<Commit<'_>>, Option<Commit<'_>>)> {
     let mut revwalk = repo.revwalk()?;
     revwalk.push_head()?;
     let mut result: Vec<Commit> = revwalk
@@ -568,6 +804,40 @@ pub fn generate_patch_set(
     Ok(result)
 }
 
+/// Returns the commits since `prev_commit` including their patch sets from the
+/// repository containing the current directory, newest first.
+///
+/// Besides git repositories, this supports Mercurial and Subversion checkouts.
+pub fn get_commits_with_patch_set(
+    prev_commit: &str,
+    default_count: usize,
+    ignore_missing: bool,
+    repository: &str,
+) -> Result<Vec<GitCommit>> {
+    let backend = match git2::Repository::open_from_env() {
+        Ok(repo) => {
+            let (commits, prev_commit) =
+                get_commits_from_git(&repo, prev_commit, default_count, ignore_missing)?;
+            return generate_patch_set(&repo, commits, prev_commit, repository);
+        }
+        Err(err) => find_backend()?.ok_or(err)?,
+    };
+
+    if prev_commit.is_empty() {
+        println!(
+            "Could not find the previous commit. Creating a release with {default_count} commits."
+        );
+    } else if let Some(commits) = backend.commits(Some(prev_commit), None, repository)? {
+        return Ok(commits);
+    } else {
+        handle_missing_prev_commit(default_count, ignore_missing)?;
+    }
+
+    Ok(backend
+        .commits(None, Some(default_count), repository)?
+        .unwrap_or_default())
+}
+
 pub fn get_commit_time(time: Time) -> DateTime<FixedOffset> {
     FixedOffset::east_opt(time.offset_minutes() * 60)
         .unwrap()
@@ -677,6 +947,13 @@ fn test_url_parsing() {
             id: "mitsuhiko/flask".into(),
         }
     );
+    assert_eq!(
+        VcsUrl::parse("svn+ssh://svn.example.com/repos/flask"),
+        VcsUrl {
+            provider: "svn.example.com".into(),
+            id: "repos/flask".into(),
+        }
+    );
     assert_eq!(
         VcsUrl::parse("http://bitbucket.org/mitsuhiko/flask"),
         VcsUrl {
@@ -795,6 +1072,30 @@ fn test_url_parsing() {
     );
 }
 
+#[test]
+fn test_resolve_submodule_url() {
+    assert_eq!(
+        resolve_submodule_url("https://github.com/acme/app.git", "../lib.git"),
+        "https://github.com/acme/lib.git"
+    );
+    assert_eq!(
+        resolve_submodule_url("https://github.com/acme/app/", "./vendor/lib"),
+        "https://github.com/acme/app/vendor/lib"
+    );
+    assert_eq!(
+        resolve_submodule_url("git@github.com:acme/app.git", "../../other/lib.git"),
+        "git@github.com:other/lib.git"
+    );
+    assert_eq!(
+        resolve_submodule_url("ssh://git@example.com:2222/acme/app", "../lib"),
+        "ssh://git@example.com:2222/acme/lib"
+    );
+    assert_eq!(
+        resolve_submodule_url("https://github.com/acme/app", "git@github.com:acme/lib.git"),
+        "git@github.com:acme/lib.git"
+    );
+}
+
 #[test]
 fn test_url_normalization() {
     assert!(!is_matching_url(
@@ -0,0 +1,202 @@
+//! Commit detection for Subversion checkouts.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Result};
+use chrono::DateTime;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::{run_command, VcsBackend};
+use crate::api::{GitCommit, PatchSet};
+
+pub struct Subversion {
+    root: PathBuf,
+}
+
+impl Subversion {
+    pub fn new(root: &Path) -> Subversion {
+        Subversion {
+            root: root.to_path_buf(),
+        }
+    }
+
+    fn svn(&self) -> Command {
+        let mut command = Command::new("svn");
+        // The log is parsed, so make sure that it is not localized.
+        command
+            .current_dir(&self.root)
+            .env("LC_ALL", "C")
+            .arg("--non-interactive");
+        command
+    }
+
+    fn info(&self, item: &str, rev: Option<&str>) -> Result<String> {
+        let mut command = self.svn();
+        command.args(["info", "--show-item", item]);
+        if let Some(rev) = rev {
+            command.args(["--revision", rev]);
+        }
+        Ok(run_command(&mut command)?.trim().to_owned())
+    }
+}
+
+impl VcsBackend for Subversion {
+    fn head(&self) -> Result<String> {
+        self.info("revision", None)
+    }
+
+    fn resolve(&self, rev: &str) -> Result<String> {
+        // In Subversion `HEAD` is the latest revision on the server rather
+        // than the checked out one.
+        if rev == "HEAD" {
+            self.head()
+        } else if rev.bytes().all(|b| b.is_ascii_digit()) {
+            Ok(rev.to_owned())
+        } else {
+            self.info("revision", Some(rev))
+        }
+    }
+
+    fn remote_url(&self, _remote_name: &str) -> Result<Option<String>> {
+        let url = self.info("repos-root-url", None)?;
+        Ok(Some(url).filter(|url| !url.is_empty()))
+    }
+
+    fn commits(
+        &self,
+        since: Option<&str>,
+        limit: Option<usize>,
+        repository: &str,
+    ) -> Result<Option<Vec<GitCommit>>> {
+        let head = self.head()?.parse::<u64>()?;
+        let first = match since {
+            Some(since) => match since.parse::<u64>() {
+                Ok(since) if since <= head => since + 1,
+                _ => return Ok(None),
+            },
+            None => 1,
+        };
+        if first > head {
+            return Ok(Some(vec![]));
+        }
+
+        let mut command = self.svn();
+        command.args(["log", "--verbose", "--revision", &format!("{head}:{first}")]);
+        if let Some(limit) = limit {
+            command.args(["--limit", &limit.to_string()]);
+        }
+        parse_log(&run_command(&mut command)?, repository).map(Some)
+    }
+}
+
+/// Parses the output of `svn log --verbose`.
+fn parse_log(output: &str, repository: &str) -> Result<Vec<GitCommit>> {
+    lazy_static! {
+        static ref HEADER_RE: Regex = Regex::new(
+            r"^r(\d+) \| (.*) \| (\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2} [+-]\d{4}) \(.*\) \| (\d+) lines?$"
+        )
+        .unwrap();
+    }
+
+    let mut commits = vec![];
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        let Some(caps) = HEADER_RE.captures(line) else {
+            continue;
+        };
+
+        let mut patch_set = vec![];
+        if lines.next() == Some("Changed paths:") {
+            for line in lines.by_ref().take_while(|line| !line.is_empty()) {
+                let Some((action, path)) = line.trim_start().split_once(' ') else {
+                    bail!("Unexpected output from svn log: {line}");
+                };
+                // Strip the source of copies, e.g. `/trunk/a.c (from /trunk/b.c:12)`.
+                let path = path.split(" (from ").next().unwrap_or(path);
+                patch_set.push(PatchSet {
+                    // Sentry does not know about replaced files.
+                    ty: (if action == "R" { "M" } else { action }).to_owned(),
+                    path: path.trim_start_matches('/').to_owned(),
+                });
+            }
+        }
+
+        let message_lines = caps[4].parse::<usize>()?;
+        let message = lines
+            .by_ref()
+            .take(message_lines)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        commits.push(GitCommit {
+            id: caps[1].to_owned(),
+            author_name: Some(caps[2].to_owned()).filter(|name| name != "(no author)"),
+            author_email: None,
+            message: Some(message),
+            repository: repository.to_owned(),
+            timestamp: DateTime::parse_from_str(&caps[3], "%Y-%m-%d %H:%M:%S %z")?,
+            patch_set,
+        });
+    }
+
+    Ok(commits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log() {
+        let output = "\
+------------------------------------------------------------------------
+r12 | jane | 2024-03-02 10:00:00 +0100 (Sat, 02 Mar 2024) | 3 lines
+Changed paths:
+   A /trunk/src/new.c (from /trunk/src/old.c:11)
+   D /trunk/src/old.c
+   R /trunk/README
+
+Second commit
+
+Details
+------------------------------------------------------------------------
+r11 | (no author) | 2024-03-01 09:30:00 +0000 (Fri, 01 Mar 2024) | 1 line
+Changed paths:
+   M /trunk/src/old.c
+
+Initial commit
+------------------------------------------------------------------------
+";
+
+        let commits = parse_log(output, "acme/app").unwrap();
+        assert_eq!(commits.len(), 2);
+
+        assert_eq!(commits[0].id, "12");
+        assert_eq!(commits[0].author_name.as_deref(), Some("jane"));
+        assert_eq!(
+            commits[0].message.as_deref(),
+            Some("Second commit\n\nDetails")
+        );
+        assert_eq!(
+            commits[0].timestamp.to_rfc3339(),
+            "2024-03-02T10:00:00+01:00"
+        );
+        assert_eq!(
+            commits[0]
+                .patch_set
+                .iter()
+                .map(|p| (p.ty.as_str(), p.path.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                ("A", "trunk/src/new.c"),
+                ("D", "trunk/src/old.c"),
+                ("M", "trunk/README")
+            ]
+        );
+
+        assert_eq!(commits[1].id, "11");
+        assert_eq!(commits[1].author_name, None);
+        assert_eq!(commits[1].message.as_deref(), Some("Initial commit"));
+    }
+}
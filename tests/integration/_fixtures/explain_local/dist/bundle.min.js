@@ -0,0 +1,3 @@
+(()=>{var e={};function bar(t){throw new Error(t)}bar("whoops")})();
+//# debugId=3a6b5a3f-5d8f-4c5e-9a2b-6f1c2d3e4f5a
+//# sourceMappingURL=bundle.min.js.map
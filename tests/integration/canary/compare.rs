@@ -0,0 +1,53 @@
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+fn mock_environments(mut manager: TestManager) -> TestManager {
+    for (env, sessions, issues) in [
+        (
+            "prod",
+            "/api/0/organizations/wat-org/sessions/?project=-1&field=sum(session)&groupBy=session.status&query=release:%22wat-release%22&environment=prod&statsPeriod=1800s",
+            "/api/0/projects/wat-org/wat-project/issues/?query=first-release:%22wat-release%22%20environment:%22prod%22&cursor=",
+        ),
+        (
+            "canary",
+            "/api/0/organizations/wat-org/sessions/?project=-1&field=sum(session)&groupBy=session.status&query=release:%22wat-release%22&environment=canary&statsPeriod=1800s",
+            "/api/0/projects/wat-org/wat-project/issues/?query=first-release:%22wat-release%22%20environment:%22canary%22&cursor=",
+        ),
+    ] {
+        manager = manager
+            .mock_endpoint(
+                MockEndpointBuilder::new("GET", sessions)
+                    .with_response_file(&format!("canary/get-sessions-{env}.json")),
+            )
+            .mock_endpoint(
+                MockEndpointBuilder::new("GET", issues)
+                    .with_response_file(&format!("canary/get-issues-{env}.json")),
+            );
+    }
+    manager
+}
+
+#[test]
+fn command_canary_compare_help() {
+    TestManager::new().register_trycmd_test("canary/canary-compare-help.trycmd");
+}
+
+#[test]
+fn command_canary_compare_pass() {
+    mock_environments(TestManager::new())
+        .register_trycmd_test("canary/canary-compare-pass.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn command_canary_compare_fail() {
+    mock_environments(TestManager::new())
+        .register_trycmd_test("canary/canary-compare-fail.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn command_canary_compare_json() {
+    mock_environments(TestManager::new())
+        .register_trycmd_test("canary/canary-compare-json.trycmd")
+        .with_default_token();
+}
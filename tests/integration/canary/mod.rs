@@ -0,0 +1,8 @@
+use crate::integration::TestManager;
+
+mod compare;
+
+#[test]
+fn command_canary_help() {
+    TestManager::new().register_trycmd_test("canary/canary-help.trycmd");
+}
@@ -96,3 +96,35 @@ fn command_bundle_jvm() {
         .register_trycmd_test("debug_files/bundle_jvm/debug_files-bundle-jvm.trycmd")
         .with_default_token();
 }
+
+#[test]
+fn command_bundle_jvm_incremental() {
+    let testcase_cwd_path =
+        "tests/integration/_cases/debug_files/bundle_jvm/debug_files-bundle-jvm-incremental.in/";
+    if std::path::Path::new(testcase_cwd_path).exists() {
+        remove_dir_all(testcase_cwd_path).unwrap();
+    }
+    copy_recursively("tests/integration/_fixtures/jvm/", testcase_cwd_path).unwrap();
+    TestManager::new()
+        .mock_common_upload_endpoints(ServerBehavior::Legacy, Default::default())
+        .register_trycmd_test("debug_files/bundle_jvm/debug_files-bundle-jvm-incremental.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn command_bundle_jvm_sources_jar() {
+    let testcase_cwd_path =
+        "tests/integration/_cases/debug_files/bundle_jvm/debug_files-bundle-jvm-sources-jar.in/";
+    if std::path::Path::new(testcase_cwd_path).exists() {
+        remove_dir_all(testcase_cwd_path).unwrap();
+    }
+    copy_recursively(
+        "tests/integration/_fixtures/jvm_sources/",
+        testcase_cwd_path,
+    )
+    .unwrap();
+    TestManager::new()
+        .mock_common_upload_endpoints(ServerBehavior::Legacy, Default::default())
+        .register_trycmd_test("debug_files/bundle_jvm/debug_files-bundle-jvm-sources-jar.trycmd")
+        .with_default_token();
+}
@@ -0,0 +1,21 @@
+use crate::integration::TestManager;
+
+#[test]
+fn command_debug_files_convert() {
+    TestManager::new().register_trycmd_test("debug_files/convert/debug_files-convert.trycmd");
+
+    let symbols = std::fs::read_to_string("debug-files-convert/elf-Linux-ARMv7-ls.sym").unwrap();
+    std::fs::remove_dir_all("debug-files-convert").unwrap();
+
+    let mut lines = symbols.lines();
+    assert_eq!(
+        lines.next(),
+        Some("MODULE Linux arm 307A540294808EC225F1A4ADC744A9910 elf-Linux-ARMv7-ls")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("INFO CODE_ID 02547A308094C28E25F1A4ADC744A9917194DB0A elf-Linux-ARMv7-ls")
+    );
+    // The binary is stripped, so only public symbols remain.
+    assert!(lines.all(|line| line.starts_with("PUBLIC ")));
+}
@@ -0,0 +1,38 @@
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_debug_files_download() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/files/dsyms/?debug_id=307a5402-9480-8ec2-25f1-a4adc744a991",
+            )
+            .with_response_file("debug_files/get-dsyms-by-debug-id.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/projects/wat-org/wat-project/files/dsyms/?id=42")
+                .with_response_body("debug"),
+        )
+        .register_trycmd_test("debug_files/download/debug_files-download.trycmd")
+        .with_default_token();
+
+    let path = "debug-files-download/307a5402-9480-8ec2-25f1-a4adc744a991/ls";
+    let contents = std::fs::read_to_string(path).unwrap();
+    std::fs::remove_dir_all("debug-files-download").unwrap();
+    assert_eq!(contents, "debug");
+}
+
+#[test]
+fn command_debug_files_download_missing() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/files/dsyms/?debug_id=307a5402-9480-8ec2-25f1-a4adc744a991",
+            )
+            .with_response_body("[]"),
+        )
+        .register_trycmd_test("debug_files/download/debug_files-download-missing.trycmd")
+        .with_default_token();
+}
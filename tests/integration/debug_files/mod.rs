@@ -1,6 +1,8 @@
 use crate::integration::TestManager;
 
 mod bundle_jvm;
+mod convert;
+mod download;
 mod upload;
 
 #[test]
@@ -62,6 +62,133 @@ fn command_debug_files_upload_pdb() {
         .with_default_token();
 }
 
+#[test]
+/// This test verifies that il2cpp line mappings that do not belong to any of the uploaded
+/// objects are skipped with a warning.
+fn command_debug_files_upload_il2cpp_not_covered() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/chunk-upload/")
+                .with_response_body(
+                    r#"{
+                    "url": "organizations/wat-org/chunk-upload/",
+                    "chunkSize": 8388608,
+                    "chunksPerRequest": 64,
+                    "maxFileSize": 2147483648,
+                    "maxRequestSize": 33554432,
+                    "concurrency": 8,
+                    "hashAlgorithm": "sha1",
+                    "compression": ["gzip"],
+                    "accept": ["debug_files", "il2cpp"]
+                }"#,
+                ),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "POST",
+                "/api/0/projects/wat-org/wat-project/files/difs/assemble/",
+            )
+            .with_response_body(
+                r#"{
+                "397e8e2fa1b60e5f46c4f4903f73ab1895daf939": {
+                    "state": "ok",
+                    "missingChunks": []
+                }
+            }"#,
+            ),
+        )
+        .register_trycmd_test("debug_files/upload/debug_files-upload-il2cpp-not-covered.trycmd")
+        .with_default_token();
+}
+
+#[test]
+/// This test verifies that debug files are uploaded in stateless mode.
+fn command_debug_files_upload_no_state() {
+    let is_first_assemble_call = AtomicBool::new(true);
+
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/chunk-upload/")
+                .with_response_file("debug_files/get-chunk-upload.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("POST", "/api/0/organizations/wat-org/chunk-upload/")
+                .expect(1),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "POST",
+                "/api/0/projects/wat-org/wat-project/files/difs/assemble/",
+            )
+            .with_response_fn(move |_| {
+                if is_first_assemble_call.swap(false, Ordering::Relaxed) {
+                    r#"{
+                        "5f81d6becc51980870acc9f6636ab53d26160763": {
+                            "state": "not_found",
+                            "missingChunks": ["5f81d6becc51980870acc9f6636ab53d26160763"]
+                        }
+                    }"#
+                } else {
+                    r#"{
+                        "5f81d6becc51980870acc9f6636ab53d26160763": {
+                            "state": "ok",
+                            "missingChunks": []
+                        }
+                    }"#
+                }
+                .into()
+            })
+            .expect(2),
+        )
+        .register_trycmd_test("debug_files/upload/debug_files-upload-no-state.trycmd")
+        .with_default_token();
+}
+
+#[test]
+/// This test verifies that sources are fetched from the archive given with
+/// `--include-sources=URL`, instead of being read from the file system.
+fn command_debug_files_upload_remote_sources() {
+    // An empty ZIP archive, consisting only of the end of central directory record.
+    let mut empty_zip = b"PK\x05\x06".to_vec();
+    empty_zip.resize(22, 0);
+
+    let manager = TestManager::new();
+    let sources_url = format!("--include-sources={}/sources.zip", manager.server_url());
+
+    manager
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/sources.zip")
+                .with_response_body(empty_zip)
+                .expect(1),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/chunk-upload/")
+                .with_response_file("debug_files/get-chunk-upload.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "POST",
+                "/api/0/projects/wat-org/wat-project/files/difs/assemble/",
+            )
+            .with_response_body(
+                r#"{
+                "5f81d6becc51980870acc9f6636ab53d26160763": {
+                    "state": "ok",
+                    "missingChunks": []
+                }
+            }"#,
+            ),
+        )
+        .assert_cmd([
+            "debug-files",
+            "upload",
+            &sources_url,
+            "tests/integration/_fixtures/Sentry.Samples.Console.Basic.pdb",
+        ])
+        .with_default_token()
+        .run_and_assert(AssertCommand::Success);
+}
+
 #[test]
 fn command_debug_files_upload_pdb_embedded_sources() {
     TestManager::new()
@@ -160,6 +287,13 @@ fn command_debug_files_upload_no_upload() {
         .register_trycmd_test("debug_files/upload/debug_files-upload-no-upload.trycmd");
 }
 
+#[test]
+fn command_debug_files_upload_provenance_without_sources() {
+    TestManager::new().register_trycmd_test(
+        "debug_files/upload/debug_files-upload-provenance-without-sources.trycmd",
+    );
+}
+
 #[test]
 /// This test ensures that the correct initial call to the debug files assemble endpoint is made.
 /// The mock assemble endpoint returns a 200 response simulating the case where all chunks
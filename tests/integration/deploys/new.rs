@@ -15,7 +15,13 @@ fn command_deploys_new() {
             .with_matcher(Matcher::PartialJson(json!({
                 "environment": "production",
                 "name": "custom-deploy",
-            }))),
+            })))
+            .with_header_matcher(
+                "idempotency-key",
+                Matcher::Regex(
+                    "^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$".into(),
+                ),
+            ),
         )
         .register_trycmd_test("deploys/deploys-new.trycmd")
         .with_default_token();
@@ -38,3 +44,40 @@ fn command_releases_deploys_new() {
         .register_trycmd_test("releases/releases-deploys-new.trycmd")
         .with_default_token();
 }
+
+#[test]
+fn command_deploys_new_multiple_projects() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "POST",
+                "/api/0/organizations/wat-org/releases/wat-release/deploys/",
+            )
+            .with_response_file("deploys/post-deploys.json")
+            .with_matcher(Matcher::PartialJson(json!({
+                "environment": "production",
+                "projects": ["wat-project", "other-project"],
+            }))),
+        )
+        .register_trycmd_test("deploys/deploys-new-multiple-projects.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn command_deploys_new_retry() {
+    // Creating a deploy is retried while Sentry is unavailable, and every
+    // attempt carries an idempotency key.
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "POST",
+                "/api/0/organizations/wat-org/releases/wat-release/deploys/",
+            )
+            .with_status(503)
+            .with_header_matcher("idempotency-key", Matcher::Any)
+            .expect(2),
+        )
+        .register_trycmd_test("deploys/deploys-new-retry.trycmd")
+        .env("SENTRY_HTTP_MAX_RETRIES", "1")
+        .with_default_token();
+}
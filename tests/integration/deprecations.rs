@@ -0,0 +1,6 @@
+use crate::integration::TestManager;
+
+#[test]
+fn command_deprecations() {
+    TestManager::new().register_trycmd_test("deprecations/*.trycmd");
+}
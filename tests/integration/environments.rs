@@ -0,0 +1,33 @@
+use mockito::Matcher;
+use serde_json::json;
+
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_environments() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/environments/?visibility=all",
+            )
+            .with_response_file("environments/get-environments.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "PUT",
+                "/api/0/projects/wat-org/wat-project/environments/feature-login/",
+            )
+            .with_response_file("environments/put-environment.json")
+            .with_matcher(Matcher::Json(json!({ "isHidden": true }))),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "PUT",
+                "/api/0/projects/wat-org/wat-project/environments/missing-environment/",
+            )
+            .with_status(404),
+        )
+        .register_trycmd_test("environments/*.trycmd")
+        .with_default_token();
+}
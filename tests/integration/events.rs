@@ -12,3 +12,35 @@ fn command_events() {
         .register_trycmd_test("events/*.trycmd")
         .with_default_token();
 }
+
+#[test]
+fn command_events_export() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/projects/wat-org/wat-project/events/?cursor=")
+                .with_response_file("events/get-events.json"),
+        )
+        .register_trycmd_test("events/export/*.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn command_events_delete() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/projects/wat-org/wat-project/issues/?id=1&id=2",
+            )
+            .with_status(204),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/projects/wat-org/wat-project/issues/?query=is:unresolved%20release:1.0",
+            )
+            .with_status(204),
+        )
+        .register_trycmd_test("events/delete/*.trycmd")
+        .with_default_token();
+}
@@ -0,0 +1,36 @@
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_files_list_json() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/files/?cursor=",
+            )
+            .with_response_file("sourcemaps/get-artifacts.json"),
+        )
+        .register_trycmd_test("files/files-list-json.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn command_files_info() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/files/?cursor=",
+            )
+            .with_response_file("sourcemaps/get-artifacts.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/files/6796495645/",
+            )
+            .with_response_file("sourcemaps/get-file-metadata-sourcemap-header.json"),
+        )
+        .register_trycmd_test("files/files-info.trycmd")
+        .with_default_token();
+}
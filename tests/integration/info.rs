@@ -73,3 +73,72 @@ fn command_info_json_without_defaults() {
         .with_server_var()
         .expect("Failed to set server variable");
 }
+
+#[test]
+fn command_info_check_scopes() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/").with_response_file("info/get-info.json"),
+        )
+        .register_trycmd_test("info/info-json-scopes.trycmd")
+        .register_trycmd_test("info/info-missing-scope.trycmd")
+        .with_default_token()
+        .with_server_var()
+        .expect("Failed to set server variable");
+}
+
+#[test]
+fn command_info_token_command() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/")
+                .with_header_matcher(
+                    "authorization",
+                    "Bearer 0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+                )
+                .with_response_file("info/get-info.json"),
+        )
+        .register_trycmd_test("info/info-token-command.trycmd")
+        .env(
+            "SENTRY_PROPERTIES",
+            "tests/integration/_fixtures/token-command.properties",
+        )
+        .with_server_var()
+        .expect("Failed to set server variable");
+}
+
+#[test]
+fn command_info_signing_command() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/")
+                .with_header_matcher("x-signature", "signed")
+                .with_response_file("info/get-info.json"),
+        )
+        .register_trycmd_test("info/info-signing-command.trycmd")
+        .env("SENTRY_SIGNING_COMMAND", "echo X-Signature: signed")
+        .with_default_token()
+        .with_server_var()
+        .expect("Failed to set server variable");
+}
+
+#[test]
+fn command_info_auth_token_file() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/")
+                .with_header_matcher(
+                    "authorization",
+                    "Bearer 0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+                )
+                .with_response_file("info/get-info.json"),
+        )
+        .register_trycmd_test("info/info-auth-token-file.trycmd")
+        .with_server_var()
+        .expect("Failed to set server variable");
+}
+
+#[test]
+fn command_info_auth_token_file_missing() {
+    TestManager::new().register_trycmd_test("info/info-auth-token-file-missing.trycmd");
+}
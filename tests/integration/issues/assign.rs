@@ -0,0 +1,33 @@
+use mockito::Matcher;
+use serde_json::json;
+
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_issues_assign_help() {
+    TestManager::new().register_trycmd_test("issues/issues-assign-help.trycmd");
+}
+
+#[test]
+fn assigns_issue() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("PUT", "/api/0/organizations/wat-org/issues/4242424243/")
+                .with_matcher(Matcher::Json(json!({ "assignedTo": "jane@example.com" })))
+                .with_response_body(r#"{"assignedTo":{"type":"user","id":"1"}}"#),
+        )
+        .register_trycmd_test("issues/issues-assign.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn fails_to_assign_missing_issue() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("PUT", "/api/0/organizations/wat-org/issues/4242424243/")
+                .with_status(404)
+                .with_response_body(r#"{"detail":"The requested resource does not exist"}"#),
+        )
+        .register_trycmd_test("issues/issues-assign-not-found.trycmd")
+        .with_default_token();
+}
@@ -0,0 +1,27 @@
+use mockito::Matcher;
+use serde_json::json;
+
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_issues_comment_help() {
+    TestManager::new().register_trycmd_test("issues/issues-comment-help.trycmd");
+}
+
+#[test]
+fn comments_on_issue() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "POST",
+                "/api/0/organizations/wat-org/issues/SEN-CLI-L/comments/",
+            )
+            .with_matcher(Matcher::Json(json!({ "text": "Rolled back the deploy." })))
+            .with_status(201)
+            .with_response_body(
+                r#"{"id":"1","type":"note","data":{"text":"Rolled back the deploy."}}"#,
+            ),
+        )
+        .register_trycmd_test("issues/issues-comment.trycmd")
+        .with_default_token();
+}
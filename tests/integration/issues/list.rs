@@ -46,3 +46,17 @@ fn display_resolved_issues() {
         .register_trycmd_test("issues/issues-display-with-query.trycmd")
         .with_default_token();
 }
+
+#[test]
+fn display_issues_with_status() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/issues/?query=is:resolved&cursor=",
+            )
+            .with_response_file("issues/get-resolved-issues.json"),
+        )
+        .register_trycmd_test("issues/issues-display-with-status.trycmd")
+        .with_default_token();
+}
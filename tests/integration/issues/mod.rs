@@ -1,8 +1,19 @@
 use crate::integration::TestManager;
 
+mod assign;
+mod comment;
 mod list;
+mod summary;
+mod watch;
 
 #[test]
 fn command_issues_help() {
     TestManager::new().register_trycmd_test("issues/issues-help.trycmd");
 }
+
+#[test]
+fn command_issues_invalid_selection() {
+    TestManager::new()
+        .register_trycmd_test("issues/issues-invalid-selection.trycmd")
+        .with_default_token();
+}
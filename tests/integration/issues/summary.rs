@@ -0,0 +1,34 @@
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn summarizes_release_issues() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/issues/?query=first-release:%22wat-release%22",
+            )
+            .with_header("x-hits", "2")
+            .with_response_file("issues/get-issues.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/issues/\
+                ?query=is:regressed%20release:%22wat-release%22",
+            )
+            .with_header("x-hits", "1")
+            .with_response_file("issues/get-issues.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/issues/\
+                ?query=is:unresolved%20release:%22wat-release%22",
+            )
+            .with_header("x-hits", "5")
+            .with_response_file("issues/get-issues.json"),
+        )
+        .register_trycmd_test("issues/issues-summary*.trycmd")
+        .with_default_token();
+}
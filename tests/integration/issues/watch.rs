@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_issues_watch_help() {
+    TestManager::new().register_trycmd_test("issues/issues-watch-help.trycmd");
+}
+
+#[test]
+fn stops_after_window() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/issues/?query=is:unresolved&cursor=",
+            )
+            .with_response_file("issues/get-issues.json"),
+        )
+        .register_trycmd_test("issues/issues-watch-window.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn fails_on_new_issue() {
+    let polls = AtomicUsize::new(0);
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/issues/?query=is:unresolved&cursor=",
+            )
+            .with_response_fn(move |_| {
+                // The first poll only returns an existing issue.
+                if polls.fetch_add(1, Ordering::Relaxed) == 0 {
+                    br#"[{"id":"4242424241","shortId":"SEN-CLI-1","title":"NameError: name 'jobs' is not defined","lastSeen":"2023-07-18T00:00:01.222387Z","status":"unresolved","level":"error"}]"#.to_vec()
+                } else {
+                    std::fs::read("tests/integration/_responses/issues/get-issues.json").unwrap()
+                }
+            }),
+        )
+        .register_trycmd_test("issues/issues-watch-fail-on-new.trycmd")
+        .with_default_token();
+}
@@ -0,0 +1,28 @@
+use mockito::Matcher;
+
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_logs_send() {
+    TestManager::new()
+        .mock_endpoint(MockEndpointBuilder::new("POST", "/api/1337/envelope/"))
+        .register_trycmd_test("logs/*.trycmd");
+}
+
+#[test]
+fn command_logs_send_scrubbed() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("POST", "/api/1337/envelope/")
+                .with_matcher(Matcher::Regex(
+                    r#""body":"login with password=\[Filtered\]""#.into(),
+                ))
+                .expect(1),
+        )
+        .register_trycmd_test("logs/scrubbing/logs-send-scrubbed.trycmd")
+        .env(
+            "SENTRY_PROPERTIES",
+            "tests/integration/_fixtures/scrub.properties",
+        )
+        .assert_mock_endpoints();
+}
@@ -1,30 +1,43 @@
 mod bash_hook;
+mod canary;
 mod debug_files;
 mod deploys;
+mod deprecations;
+mod environments;
 mod events;
+mod files;
 mod help;
 mod info;
 mod invalid_env;
 mod issues;
 mod login;
+mod logs;
 mod monitors;
+mod notify;
 mod org_tokens;
 mod organizations;
 mod projects;
-#[cfg(target_os = "macos")]
+mod queue;
 mod react_native;
 mod releases;
+mod repos;
+mod run;
 mod send_envelope;
 mod send_event;
 mod send_metric;
 mod sourcemaps;
+mod support_bundle;
+mod tail;
 mod test_utils;
 mod token_validation;
+mod tokens;
 mod uninstall;
 mod update;
 mod upload_dif;
 mod upload_dsym;
 mod upload_proguard;
+#[cfg(unix)]
+mod worker;
 
 use std::fs;
 use std::io;
@@ -60,3 +73,13 @@ pub fn token_redacted() {
 pub fn token_redacted_2() {
     TestManager::new().register_trycmd_test("token-redacted-2.trycmd");
 }
+
+#[test]
+pub fn token_redacted_file() {
+    TestManager::new().register_trycmd_test("token-redacted-file.trycmd");
+}
+
+#[test]
+pub fn auth_token_fd_standard_stream() {
+    TestManager::new().register_trycmd_test("auth-token-fd-standard-stream.trycmd");
+}
@@ -1,3 +1,6 @@
+use mockito::Matcher;
+use serde_json::json;
+
 use crate::integration::{MockEndpointBuilder, TestManager};
 
 #[test]
@@ -12,6 +15,50 @@ fn command_monitors() {
             MockEndpointBuilder::new("POST", "/api/0/monitors/foo-monitor/checkins/")
                 .with_response_file("monitors/post-monitors.json"),
         )
+        .mock_endpoint(
+            MockEndpointBuilder::new("POST", "/api/0/organizations/wat-org/monitors/")
+                .with_response_file("monitors/get-monitor.json")
+                .with_matcher(Matcher::Json(json!({
+                    "name": "Nightly Backup",
+                    "project": "wat-project",
+                    "type": "cron_job",
+                    "config": {
+                        "schedule_type": "crontab",
+                        "schedule": "0 2 * * *",
+                        "max_runtime": 30,
+                        "timezone": "Europe/Vienna",
+                    },
+                }))),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/organizations/wat-org/monitors/nightly-backup/",
+            )
+            .with_response_file("monitors/get-monitor.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "PUT",
+                "/api/0/organizations/wat-org/monitors/nightly-backup/",
+            )
+            .with_response_file("monitors/get-monitor.json")
+            .with_matcher(Matcher::Json(json!({ "config": { "checkin_margin": 10 } }))),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/organizations/wat-org/monitors/nightly-backup/",
+            )
+            .with_status(204),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/organizations/wat-org/monitors/missing-monitor/",
+            )
+            .with_status(404),
+        )
         .register_trycmd_test("monitors/*.trycmd")
         .with_default_token();
 
@@ -0,0 +1,6 @@
+use crate::integration::TestManager;
+
+#[test]
+fn command_notify_invalid_webhook() {
+    TestManager::new().register_trycmd_test("notify/notify-invalid-webhook.trycmd");
+}
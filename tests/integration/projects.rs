@@ -1,3 +1,6 @@
+use mockito::Matcher;
+use serde_json::json;
+
 use crate::integration::{MockEndpointBuilder, TestManager};
 
 #[test]
@@ -8,6 +11,27 @@ fn command_projects_list() {
             MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/projects/?cursor=")
                 .with_response_file("projects/get-projects.json"),
         )
+        .mock_endpoint(
+            MockEndpointBuilder::new("POST", "/api/0/teams/wat-org/wat-team/projects/")
+                .with_response_file("projects/post-project.json")
+                .with_matcher(Matcher::PartialJson(json!({
+                    "name": "New Project",
+                    "platform": "python",
+                }))),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("PUT", "/api/0/projects/wat-org/new-project/")
+                .with_response_file("projects/post-project.json")
+                .with_matcher(Matcher::Json(json!({ "platform": "python" }))),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("DELETE", "/api/0/projects/wat-org/new-project/")
+                .with_status(204),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("DELETE", "/api/0/projects/wat-org/missing-project/")
+                .with_status(404),
+        )
         .register_trycmd_test("projects/*.trycmd")
         .with_default_token();
 }
@@ -0,0 +1,6 @@
+use crate::integration::TestManager;
+
+#[test]
+fn command_queue_flush() {
+    TestManager::new().register_trycmd_test("queue/*.trycmd");
+}
@@ -0,0 +1,46 @@
+use sourcemap::SourceMap;
+
+use crate::integration::TestManager;
+
+fn read_composed_sourcemap(path: &str) -> SourceMap {
+    let sourcemap = SourceMap::from_slice(&std::fs::read(path).unwrap()).unwrap();
+    std::fs::remove_file(path).unwrap();
+    sourcemap
+}
+
+fn assert_original_position(sourcemap: &SourceMap, col: u32, src_line: u32, src_col: u32) {
+    let token = sourcemap.lookup_token(0, col).unwrap();
+    assert_eq!(token.get_source(), Some("src/add.js"));
+    assert_eq!(
+        (token.get_src_line(), token.get_src_col()),
+        (src_line, src_col)
+    );
+}
+
+#[test]
+fn command_react_native_compose_sourcemaps() {
+    TestManager::new().register_trycmd_test("react_native/compose-sourcemaps.trycmd");
+
+    let sourcemap = read_composed_sourcemap("rn-composed-sourcemap.map");
+    assert_eq!(sourcemap.get_file(), Some("index.android.bundle.hbc"));
+    assert_eq!(
+        sourcemap.get_debug_id().unwrap().to_string(),
+        "5d8e1c2a-9b3f-4e6d-8a7c-1f2e3d4c5b6a"
+    );
+    assert_original_position(&sourcemap, 0, 0, 0);
+    assert_original_position(&sourcemap, 10, 1, 2);
+}
+
+#[test]
+fn command_react_native_compose_sourcemaps_no_debug_id() {
+    TestManager::new().register_trycmd_test("react_native/compose-sourcemaps-no-debug-id.trycmd");
+
+    let sourcemap = read_composed_sourcemap("rn-composed-sourcemap-no-debug-id.map");
+    assert!(sourcemap.get_debug_id().is_some());
+    assert_original_position(&sourcemap, 10, 1, 2);
+}
+
+#[test]
+fn command_react_native_compose_sourcemaps_invalid() {
+    TestManager::new().register_trycmd_test("react_native/compose-sourcemaps-invalid.trycmd");
+}
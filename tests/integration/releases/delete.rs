@@ -56,3 +56,46 @@ fn doesnt_allow_to_delete_active_releases() {
         .register_trycmd_test("releases/releases-delete-active.trycmd")
         .with_default_token();
 }
+
+#[test]
+fn doesnt_allow_to_delete_when_denied_by_policy() {
+    TestManager::new()
+        .register_trycmd_test("releases/releases-delete-policy.trycmd")
+        .env(
+            "SENTRY_PROPERTIES",
+            "tests/integration/_fixtures/policy.properties",
+        )
+        .with_default_token();
+}
+
+#[test]
+fn ignores_config_files_in_stateless_mode() {
+    TestManager::new()
+        .register_trycmd_test("releases/releases-delete-no-state.trycmd")
+        .env(
+            "SENTRY_PROPERTIES",
+            "tests/integration/_fixtures/policy.properties",
+        )
+        .with_default_token();
+}
+
+#[test]
+fn doesnt_delete_in_read_only_mode() {
+    TestManager::new()
+        .register_trycmd_test("releases/releases-delete-read-only.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn doesnt_delete_without_confirmation() {
+    TestManager::new()
+        .register_trycmd_test("releases/releases-delete-unconfirmed.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn doesnt_delete_when_confirmation_does_not_match() {
+    TestManager::new()
+        .register_trycmd_test("releases/releases-delete-confirm-mismatch.trycmd")
+        .with_default_token();
+}
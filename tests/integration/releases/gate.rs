@@ -0,0 +1,37 @@
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+const NEW_ISSUES_PATH: &str =
+    "/api/0/projects/wat-org/wat-project/issues/?query=first-release:%22wat-release%22&cursor=";
+
+#[test]
+fn passes_healthy_release() {
+    TestManager::new()
+        .mock_endpoint(MockEndpointBuilder::new("GET", NEW_ISSUES_PATH).with_response_body("[]"))
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/organizations/wat-org/sessions/?project=-1&field=crash_free_rate(session)\
+                &query=release:%22wat-release%22&statsPeriod=1s",
+            )
+            .with_response_file("releases/get-sessions-crash-free-rate.json"),
+        )
+        .register_trycmd_test("releases/releases-gate.trycmd")
+        .with_default_token()
+        .assert_mock_endpoints();
+}
+
+#[test]
+fn fails_on_new_issues() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", NEW_ISSUES_PATH)
+                .with_response_file("issues/get-issues.json"),
+        )
+        .register_trycmd_test("releases/releases-gate-new-issues.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn requires_threshold() {
+    TestManager::new().register_trycmd_test("releases/releases-gate-no-thresholds.trycmd");
+}
@@ -0,0 +1,122 @@
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn lists_releases_in_dry_run() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/?cursor=",
+            )
+            .with_response_file("releases/get-releases.json"),
+        )
+        .register_trycmd_test("releases/releases-gc-dry-run.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn deletes_old_releases() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/?cursor=",
+            )
+            .with_response_file("releases/get-releases.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/projects/wat-org/wat-project/releases/sentry@test/",
+            )
+            .with_status(204),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/projects/wat-org/wat-project/releases/sentry@dev/",
+            )
+            .with_status(204),
+        )
+        .register_trycmd_test("releases/releases-gc.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn lists_releases_across_pages() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/?cursor=",
+            )
+            .with_response_file("releases/get-releases.json")
+            .with_header(
+                "Link",
+                "<https://sentry.io/api/0/projects/wat-org/wat-project/releases/?cursor=100:1:0>; \
+                    rel=\"next\"; results=\"true\"; cursor=\"100:1:0\"",
+            ),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/?cursor=100:1:0",
+            )
+            .with_response_file("releases/get-releases-page-2.json"),
+        )
+        .register_trycmd_test("releases/releases-gc-paginated.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn skips_releases_that_cannot_be_deleted() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/?cursor=",
+            )
+            .with_response_file("releases/get-releases.json")
+            .with_header(
+                "Link",
+                "<https://sentry.io/api/0/projects/wat-org/wat-project/releases/?cursor=100:1:0>; \
+                    rel=\"next\"; results=\"true\"; cursor=\"100:1:0\"",
+            ),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/?cursor=100:1:0",
+            )
+            .with_response_file("releases/get-releases-page-2.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/projects/wat-org/wat-project/releases/sentry@test/",
+            )
+            .with_status(204),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/projects/wat-org/wat-project/releases/sentry@dev/",
+            )
+            .with_status(400)
+            .with_response_file("releases/delete-active-release.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/projects/wat-org/wat-project/releases/sentry@old/",
+            )
+            .with_status(204),
+        )
+        .register_trycmd_test("releases/releases-gc-active.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn requires_retention_option() {
+    TestManager::new().register_trycmd_test("releases/releases-gc-no-options.trycmd");
+}
@@ -14,6 +14,34 @@ fn shows_release_details() {
         .with_default_token();
 }
 
+#[test]
+fn shows_release_details_with_template() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/",
+            )
+            .with_response_file("releases/get-release.json"),
+        )
+        .register_trycmd_test("releases/releases-info-template.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn fails_with_unknown_template_variable() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/",
+            )
+            .with_response_file("releases/get-release.json"),
+        )
+        .register_trycmd_test("releases/releases-info-template-invalid.trycmd")
+        .with_default_token();
+}
+
 #[test]
 fn shows_release_details_with_projects_and_commits() {
     TestManager::new()
@@ -27,7 +55,7 @@ fn shows_release_details_with_projects_and_commits() {
         .mock_endpoint(
             MockEndpointBuilder::new(
                 "GET",
-                "/api/0/projects/wat-org/wat-project/releases/wat-release/commits/",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/commits/?cursor=",
             )
             .with_response_file("releases/get-release-commits.json"),
         )
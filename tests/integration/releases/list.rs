@@ -65,3 +65,14 @@ fn displays_releases_in_raw_mode_with_delimiter() {
         .register_trycmd_test("releases/releases-list-raw-delimiter.trycmd")
         .with_default_token();
 }
+
+#[test]
+fn reports_field_of_invalid_response() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/projects/wat-org/wat-project/releases/")
+                .with_response_file("releases/get-releases-invalid.json"),
+        )
+        .register_trycmd_test("releases/releases-list-invalid-response.trycmd")
+        .with_default_token();
+}
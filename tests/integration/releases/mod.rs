@@ -2,9 +2,15 @@ use crate::integration::TestManager;
 
 mod delete;
 mod finalize;
+mod gate;
+mod gc;
 mod info;
 mod list;
 mod new;
+mod notes;
+#[cfg(not(windows))]
+mod propose_version;
+mod undelete;
 
 #[test]
 fn command_releases_help() {
@@ -24,6 +24,13 @@ fn creates_release() {
         .with_default_token();
 }
 
+#[test]
+fn doesnt_create_release_in_read_only_mode() {
+    TestManager::new()
+        .register_trycmd_test("releases/releases-new-read-only.trycmd")
+        .with_default_token();
+}
+
 #[test]
 fn allows_for_release_to_start_with_hyphen() {
     TestManager::new()
@@ -0,0 +1,56 @@
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn generates_release_notes() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/commits/?cursor=",
+            )
+            .with_response_file("releases/get-release-commits-with-messages.json"),
+        )
+        .register_trycmd_test("releases/releases-notes.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn generates_release_notes_across_pages() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/commits/?cursor=",
+            )
+            .with_response_file("releases/get-release-commits-with-messages.json")
+            .with_header(
+                "Link",
+                "<https://sentry.io/api/0/projects/wat-org/wat-project/releases/wat-release/commits/?cursor=100:1:0>; \
+                rel=\"next\"; results=\"true\"; cursor=\"100:1:0\"",
+            ),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/commits/?cursor=100:1:0",
+            )
+            .with_response_file("releases/get-release-commits-page-2.json"),
+        )
+        .register_trycmd_test("releases/releases-notes-paginated.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn fails_for_missing_release() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/commits/?cursor=",
+            )
+            .with_status(404)
+            .with_response_body(r#"{"detail":"The requested resource does not exist"}"#),
+        )
+        .register_trycmd_test("releases/releases-notes-not-found.trycmd")
+        .with_default_token();
+}
@@ -0,0 +1,17 @@
+use crate::integration::TestManager;
+
+#[test]
+fn command_releases_propose_version_from_image_labels() {
+    let fake_docker = std::env::current_dir()
+        .unwrap()
+        .join("tests/integration/_fixtures/fake_docker");
+    let path = format!(
+        "{}:{}",
+        fake_docker.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    TestManager::new()
+        .register_trycmd_test("releases/not_windows/releases-propose-version-image-labels.trycmd")
+        .env("PATH", path);
+}
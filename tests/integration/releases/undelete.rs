@@ -0,0 +1,8 @@
+use crate::integration::TestManager;
+
+#[test]
+fn command_releases_undelete_not_in_trash() {
+    TestManager::new()
+        .register_trycmd_test("releases/releases-undelete-not-in-trash.trycmd")
+        .with_default_token();
+}
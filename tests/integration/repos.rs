@@ -0,0 +1,54 @@
+use mockito::Matcher;
+use serde_json::json;
+
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_repos() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/repos/?cursor=")
+                .with_response_file("repos/get-repos.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("POST", "/api/0/organizations/wat-org/repos/")
+                .with_response_file("repos/post-repo.json")
+                .with_matcher(Matcher::Json(json!({
+                    "name": "getsentry/sentry-javascript",
+                    "provider": "integrations:github",
+                    "installation": "7",
+                    "identifier": "getsentry/sentry-javascript",
+                }))),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("DELETE", "/api/0/organizations/wat-org/repos/42/")
+                .with_status(202),
+        )
+        .register_trycmd_test("repos/*.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn command_repos_rate_limited() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/repos/?cursor=")
+                .with_status(429)
+                .with_header("Retry-After", "30"),
+        )
+        .register_trycmd_test("repos/rate_limited/*.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn command_repos_rate_limited_retries() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/repos/?cursor=")
+                .with_status(429)
+                .with_header("Retry-After", "0")
+                .expect(2),
+        )
+        .register_trycmd_test("repos/rate_limited_retries/*.trycmd")
+        .with_default_token();
+}
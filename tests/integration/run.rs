@@ -0,0 +1,34 @@
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_run() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/projects/wat-org/wat-project/releases/")
+                .with_response_file("releases/get-releases.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "DELETE",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/",
+            )
+            .with_status(204),
+        )
+        .register_trycmd_test("run/run.trycmd")
+        .with_default_token();
+}
+
+#[test]
+fn command_run_nested() {
+    TestManager::new().register_trycmd_test("run/run-nested.trycmd");
+}
+
+#[test]
+fn command_run_send_metric() {
+    TestManager::new().register_trycmd_test("run/run-send-metric.trycmd");
+}
+
+#[test]
+fn command_run_startup_arg() {
+    TestManager::new().register_trycmd_test("run/run-startup-arg.trycmd");
+}
@@ -10,6 +10,16 @@ fn command_sourcemaps_explain() {
     TestManager::new().register_trycmd_test("sourcemaps/sourcemaps-explain.trycmd");
 }
 
+#[test]
+fn command_sourcemaps_explain_local() {
+    TestManager::new().register_trycmd_test("sourcemaps/sourcemaps-explain-local.trycmd");
+}
+
+#[test]
+fn command_sourcemaps_explain_local_no_match() {
+    TestManager::new().register_trycmd_test("sourcemaps/sourcemaps-explain-local-no-match.trycmd");
+}
+
 #[test]
 fn command_sourcemaps_explain_missing_event() {
     TestManager::new()
@@ -61,6 +61,47 @@ fn command_sourcemaps_inject_output_embedded() {
     TestManager::new().register_trycmd_test("sourcemaps/sourcemaps-inject-embedded.trycmd");
 }
 
+#[test]
+fn command_sourcemaps_inject_json() {
+    let testcase_cwd_path =
+        std::path::Path::new("tests/integration/_cases/sourcemaps/sourcemaps-inject-json.in/");
+    if testcase_cwd_path.exists() {
+        remove_dir_all(testcase_cwd_path).unwrap();
+    }
+    fs::create_dir_all(testcase_cwd_path).unwrap();
+    fs::copy(
+        "tests/integration/_fixtures/inject/server/dummy_embedded.js",
+        testcase_cwd_path.join("dummy_embedded.js"),
+    )
+    .unwrap();
+
+    TestManager::new().register_trycmd_test("sourcemaps/sourcemaps-inject-json.trycmd");
+}
+
+#[test]
+fn command_sourcemaps_inject_vcs_ignore() {
+    let testcase_cwd_path = std::path::Path::new(
+        "tests/integration/_cases/sourcemaps/sourcemaps-inject-vcs-ignore.in/",
+    );
+    if testcase_cwd_path.exists() {
+        remove_dir_all(testcase_cwd_path).unwrap();
+    }
+    fs::create_dir_all(testcase_cwd_path.join("ignored")).unwrap();
+    fs::copy(
+        "tests/integration/_fixtures/inject/server/dummy_embedded.js",
+        testcase_cwd_path.join("dummy_embedded.js"),
+    )
+    .unwrap();
+    fs::copy(
+        "tests/integration/_fixtures/inject/server/dummy_embedded.js",
+        testcase_cwd_path.join("ignored/dummy_embedded.js"),
+    )
+    .unwrap();
+    fs::write(testcase_cwd_path.join(".ignore"), "ignored/\n").unwrap();
+
+    TestManager::new().register_trycmd_test("sourcemaps/sourcemaps-inject-vcs-ignore.trycmd");
+}
+
 #[test]
 fn command_sourcemaps_inject_output_split() {
     let testcase_cwd_path = "tests/integration/_cases/sourcemaps/sourcemaps-inject-split.in/";
@@ -171,3 +212,26 @@ fn command_sourcemaps_inject_complex_extension() {
     TestManager::new()
         .register_trycmd_test("sourcemaps/sourcemaps-inject-complex-extension.trycmd");
 }
+
+#[test]
+fn command_sourcemaps_inject_css() {
+    let testcase_cwd_path = "tests/integration/_cases/sourcemaps/sourcemaps-inject-css.in/";
+    if std::path::Path::new(testcase_cwd_path).exists() {
+        remove_dir_all(testcase_cwd_path).unwrap();
+    }
+
+    copy_recursively("tests/integration/_fixtures/inject_css", testcase_cwd_path).unwrap();
+
+    TestManager::new().register_trycmd_test("sourcemaps/sourcemaps-inject-css.trycmd");
+
+    let css = fs::read_to_string(format!("{testcase_cwd_path}main.css")).unwrap();
+    let map = fs::read_to_string(format!("{testcase_cwd_path}main.css.map")).unwrap();
+    let debug_id = css
+        .lines()
+        .find_map(|line| line.strip_prefix("/*# debugId="))
+        .and_then(|rest| rest.strip_suffix(" */"))
+        .expect("CSS file should have a debug id comment");
+
+    assert!(css.starts_with(".button{color:red}.button:hover{color:blue}\n"));
+    assert!(map.contains(&format!(r#""debug_id":"{debug_id}""#)));
+}
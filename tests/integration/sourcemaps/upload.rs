@@ -1,3 +1,6 @@
+use mockito::Matcher;
+use serde_json::json;
+
 use crate::integration::{ChunkOptions, MockEndpointBuilder, ServerBehavior, TestManager};
 
 #[test]
@@ -51,6 +54,60 @@ fn command_sourcemaps_upload_skip_already_uploaded() {
         .assert_mock_endpoints();
 }
 
+#[test]
+fn command_sourcemaps_upload_verify_other_release() {
+    TestManager::new()
+        .mock_common_upload_endpoints(ServerBehavior::Legacy, Default::default())
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/files/?cursor=&checksum=38ed853073df85147960ea3a5bced6170ec389b0",
+            )
+            .with_response_body("[]"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/projects/wat-org/wat-project/events/?cursor=")
+                .with_response_body(
+                    r#"[{
+                        "eventID": "43a57a55cd5a4207ac520c03e1dee1b4",
+                        "dateCreated": "2022-05-12T11:08:01.496220Z",
+                        "title": "Error: whoops"
+                    }]"#,
+                ),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/events/43a57a55cd5a4207ac520c03e1dee1b4/json/",
+            )
+            .with_response_file("sourcemaps/get-event.json"),
+        )
+        .register_trycmd_test("sourcemaps/sourcemaps-upload-verify-other-release.trycmd")
+        .with_default_token()
+        .assert_mock_endpoints();
+}
+
+#[test]
+fn command_sourcemaps_upload_verify_error() {
+    TestManager::new()
+        .mock_common_upload_endpoints(ServerBehavior::Legacy, Default::default())
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "GET",
+                "/api/0/projects/wat-org/wat-project/releases/wat-release/files/?cursor=&checksum=38ed853073df85147960ea3a5bced6170ec389b0",
+            )
+            .with_response_body("[]"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/projects/wat-org/wat-project/events/?cursor=")
+                .with_status(403)
+                .with_response_body(r#"{"detail":"You do not have permission to perform this action."}"#),
+        )
+        .register_trycmd_test("sourcemaps/sourcemaps-upload-verify-error.trycmd")
+        .with_default_token()
+        .assert_mock_endpoints();
+}
+
 #[test]
 fn command_sourcemaps_upload_no_dedupe() {
     TestManager::new()
@@ -85,6 +142,41 @@ fn command_sourcemaps_upload_modern_v2() {
         .assert_mock_endpoints();
 }
 
+#[test]
+fn command_sourcemaps_upload_skip_existing_bundle() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/chunk-upload/")
+                .with_response_body(
+                    r#"{
+                        "url": "organizations/wat-org/chunk-upload/",
+                        "chunkSize": 8388608,
+                        "chunksPerRequest": 64,
+                        "maxRequestSize": 33554432,
+                        "concurrency": 8,
+                        "hashAlgorithm": "sha1",
+                        "accept": ["release_files", "artifact_bundles_v2"]
+                    }"#,
+                ),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("POST", "/api/0/organizations/wat-org/chunk-upload/")
+                .expect(0),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "POST",
+                "/api/0/organizations/wat-org/artifactbundle/assemble/",
+            )
+            .with_matcher(Matcher::PartialJson(json!({ "chunks": [] })))
+            .with_response_body(r#"{"state":"ok","missingChunks":[]}"#)
+            .expect(1),
+        )
+        .register_trycmd_test("sourcemaps/sourcemaps-upload-skip-existing-bundle.trycmd")
+        .with_default_token()
+        .assert_mock_endpoints();
+}
+
 #[test]
 fn command_sourcemaps_upload_empty() {
     TestManager::new()
@@ -142,6 +234,14 @@ fn command_sourcemaps_upload_no_debugids() {
         .with_default_token();
 }
 
+#[test]
+fn command_sourcemaps_upload_strict_validation_invalid() {
+    TestManager::new()
+        .mock_common_upload_endpoints(ServerBehavior::Modern, Default::default())
+        .register_trycmd_test("sourcemaps/sourcemaps-upload-strict-validation-invalid.trycmd")
+        .with_default_token();
+}
+
 #[test]
 fn command_sourcemaps_upload_file_ram_bundle() {
     TestManager::new()
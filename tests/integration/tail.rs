@@ -0,0 +1,16 @@
+use crate::integration::TestManager;
+
+#[test]
+fn command_tail_help() {
+    TestManager::new().register_trycmd_test("tail/tail-help.trycmd");
+}
+
+#[test]
+fn command_tail_missing_file() {
+    TestManager::new().register_trycmd_test("tail/tail-missing-file.trycmd");
+}
+
+#[test]
+fn command_tail_invalid_source() {
+    TestManager::new().register_trycmd_test("tail/tail-invalid-source.trycmd");
+}
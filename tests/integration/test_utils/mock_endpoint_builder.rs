@@ -32,6 +32,12 @@ impl MockEndpointBuilder {
         self
     }
 
+    /// Set a header of the response of the mock endpoint.
+    pub fn with_header(mut self, key: &'static str, value: &'static str) -> Self {
+        self.builder = Box::new(move |server| (self.builder)(server).with_header(key, value));
+        self
+    }
+
     /// Set the response body of the mock endpoint.
     pub fn with_response_body<T>(mut self, body: T) -> Self
     where
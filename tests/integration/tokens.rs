@@ -0,0 +1,57 @@
+use crate::integration::{MockEndpointBuilder, TestManager};
+
+#[test]
+fn command_tokens_rotate_help() {
+    TestManager::new().register_trycmd_test("tokens/tokens-rotate-help.trycmd");
+}
+
+#[test]
+fn command_tokens_rotate() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/org-auth-tokens/")
+                .with_response_file("tokens/get-org-auth-tokens.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("POST", "/api/0/organizations/wat-org/org-auth-tokens/")
+                .with_response_file("tokens/post-org-auth-token.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/")
+                .with_header_matcher(
+                    "Authorization",
+                    "Bearer fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210",
+                )
+                .with_response_body(r#"{"auth": null, "user": null}"#),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("DELETE", "/api/0/organizations/wat-org/org-auth-tokens/1/")
+                .with_status(204)
+                .expect(1),
+        )
+        .register_trycmd_test("tokens/tokens-rotate.trycmd")
+        .with_default_token()
+        .assert_mock_endpoints();
+}
+
+#[test]
+fn command_tokens_rotate_invalid() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/org-auth-tokens/")
+                .with_response_file("tokens/get-org-auth-tokens.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new("POST", "/api/0/organizations/wat-org/org-auth-tokens/")
+                .with_response_file("tokens/post-org-auth-token.json"),
+        )
+        .mock_endpoint(MockEndpointBuilder::new("GET", "/api/0/").with_status(401))
+        .mock_endpoint(
+            MockEndpointBuilder::new("DELETE", "/api/0/organizations/wat-org/org-auth-tokens/3/")
+                .with_status(204)
+                .expect(1),
+        )
+        .register_trycmd_test("tokens/tokens-rotate-invalid.trycmd")
+        .with_default_token()
+        .assert_mock_endpoints();
+}
@@ -1,5 +1,5 @@
 #[cfg(not(windows))]
-use crate::integration::TestManager;
+use crate::integration::{MockEndpointBuilder, TestManager};
 
 // I have no idea why this is timing out on Windows.
 // I verified it manually, and this command works just fine. — Kamil
@@ -9,3 +9,30 @@ use crate::integration::TestManager;
 fn command_upload_dif_help() {
     TestManager::new().register_trycmd_test("upload_dif/upload_dif-help.trycmd");
 }
+
+// The legacy command must behave exactly like `debug-files upload`. These cases
+// mirror `debug_files-upload.trycmd` and `debug_files-upload-no-upload.trycmd`.
+#[cfg(not(windows))]
+#[test]
+fn command_upload_dif() {
+    TestManager::new()
+        .mock_endpoint(
+            MockEndpointBuilder::new("GET", "/api/0/organizations/wat-org/chunk-upload/")
+                .with_response_file("debug_files/get-chunk-upload.json"),
+        )
+        .mock_endpoint(
+            MockEndpointBuilder::new(
+                "POST",
+                "/api/0/projects/wat-org/wat-project/files/difs/assemble/",
+            )
+            .with_response_file("debug_files/post-difs-assemble.json"),
+        )
+        .register_trycmd_test("upload_dif/upload_dif.trycmd")
+        .with_default_token();
+}
+
+#[cfg(not(windows))]
+#[test]
+fn command_upload_dif_no_upload() {
+    TestManager::new().register_trycmd_test("upload_dif/upload_dif-no-upload.trycmd");
+}
@@ -0,0 +1,21 @@
+use crate::integration::TestManager;
+
+#[test]
+fn command_worker_help() {
+    TestManager::new().register_trycmd_test("worker/worker-help.trycmd");
+}
+
+#[test]
+fn command_worker_arg_file() {
+    TestManager::new().register_trycmd_test("worker/worker-arg-file.trycmd");
+}
+
+#[test]
+fn command_worker_startup_args() {
+    TestManager::new().register_trycmd_test("worker/worker-startup-args.trycmd");
+}
+
+#[test]
+fn command_worker_no_command() {
+    TestManager::new().register_trycmd_test("worker/worker-no-command.trycmd");
+}